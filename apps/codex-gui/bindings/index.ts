@@ -75,6 +75,7 @@ export type { LedgerCreateCompanyResponse } from "./LedgerCreateCompanyResponse"
 export type { LedgerCurrency } from "./LedgerCurrency";
 export type { LedgerCurrencyMode } from "./LedgerCurrencyMode";
 export type { LedgerCurrencyRate } from "./LedgerCurrencyRate";
+export type { LedgerDocumentRef } from "./LedgerDocumentRef";
 export type { LedgerEntryOrigin } from "./LedgerEntryOrigin";
 export type { LedgerEntryStatus } from "./LedgerEntryStatus";
 export type { LedgerFiscalCalendar } from "./LedgerFiscalCalendar";
@@ -405,6 +405,16 @@ pub struct LedgerJournalEntry {
     pub reverses_entry_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reversed_by_entry_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<LedgerDocumentRef>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerDocumentRef {
+    pub document_id: String,
+    pub firm_id: String,
+    pub version: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
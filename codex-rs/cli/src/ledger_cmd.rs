@@ -19,6 +19,7 @@ use codex_accounting_api::demo::seed_demo_ledger;
 use codex_accounting_api::demo::seed_demo_reconciliation;
 use codex_accounting_api::duplicate_set_labels;
 use codex_accounting_api::preview_copy::duplicate_guidance_message;
+use codex_accounting_api::reconciliation_audit_log_path;
 use codex_app_server_protocol::LedgerCompany;
 use codex_app_server_protocol::LedgerLockPeriodParams;
 use codex_app_server_protocol::LedgerPeriodAction;
@@ -29,6 +30,9 @@ use codex_ledger::EnsurePeriodRequest;
 use codex_ledger::InMemoryLedgerService;
 use codex_ledger::LedgerService;
 use codex_ledger::PeriodRef;
+use codex_reconcile::Coverage;
+use codex_reconcile::JsonlReconciliationAuditHook;
+use codex_reconcile::ReconciliationAuditRecord;
 use serde::Serialize;
 
 #[derive(Debug, Parser)]
@@ -138,6 +142,47 @@ enum LedgerReconciliationSubcommand {
         #[arg(long = "format", value_enum, default_value_t = OutputFormat::Text)]
         format: OutputFormat,
     },
+
+    /// Export the persisted reconciliation audit trail for a session; same as
+    /// `codex reconcile audit-export`.
+    AuditExport {
+        /// Session whose audit events should be exported.
+        #[arg(long = "session-id", value_name = "SESSION_ID")]
+        session_id: String,
+        /// Export format (defaults to CSV).
+        #[arg(long = "format", value_enum, default_value_t = AuditExportFormat::Csv)]
+        format: AuditExportFormat,
+    },
+}
+
+/// `codex reconcile …`: reconciliation workflows outside the demo ledger.
+#[derive(Debug, Parser)]
+pub struct ReconcileCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    command: ReconcileSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum ReconcileSubcommand {
+    /// Export the persisted reconciliation audit trail for a session.
+    AuditExport {
+        /// Session whose audit events should be exported.
+        #[arg(long = "session-id", value_name = "SESSION_ID")]
+        session_id: String,
+        /// Export format (defaults to CSV).
+        #[arg(long = "format", value_enum, default_value_t = AuditExportFormat::Csv)]
+        format: AuditExportFormat,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq, Default)]
+enum AuditExportFormat {
+    #[default]
+    Csv,
+    Json,
 }
 
 struct DemoLedgerContext {
@@ -201,11 +246,27 @@ pub async fn run(cli: LedgerCli) -> Result<()> {
             LedgerReconciliationSubcommand::Summary { format } => {
                 run_reconciliation_summary(format).await
             }
+            LedgerReconciliationSubcommand::AuditExport { session_id, format } => {
+                run_reconciliation_audit_export(&session_id, format)
+            }
         },
         LedgerSubcommand::GoLiveChecklist => run_go_live_checklist().await,
     }
 }
 
+pub async fn run_reconcile(cli: ReconcileCli) -> Result<()> {
+    let _ = cli
+        .config_overrides
+        .parse_overrides()
+        .map_err(|err| anyhow!(err))?;
+
+    match cli.command {
+        ReconcileSubcommand::AuditExport { session_id, format } => {
+            run_reconciliation_audit_export(&session_id, format)
+        }
+    }
+}
+
 async fn run_demo() -> Result<()> {
     let DemoLedgerContext { data, .. } = build_demo_context().await?;
     let company = data
@@ -529,6 +590,10 @@ async fn run_reconciliation_summary(format: OutputFormat) -> Result<()> {
         .facade
         .list_candidates(&context.session_id)
         .map_err(|err| anyhow!(err))?;
+    let coverage = context
+        .facade
+        .coverage(&context.session_id)
+        .map_err(|err| anyhow!(err))?;
     let counters = context.telemetry.snapshot();
 
     if format.is_json() {
@@ -541,7 +606,8 @@ async fn run_reconciliation_summary(format: OutputFormat) -> Result<()> {
             company_name,
             matched: summary.matched,
             pending: summary.pending,
-            coverage_ratio: summary.coverage_ratio(),
+            coverage_ratio: coverage.ratio(),
+            session_coverage: coverage,
             ingest: IngestSnapshotOutput {
                 ingested_total: context.ingest_snapshot.ingested_total,
                 deduped_total: context.ingest_snapshot.deduped_total,
@@ -592,10 +658,15 @@ async fn run_reconciliation_summary(format: OutputFormat) -> Result<()> {
         company_name, context.company_id
     );
     println!(
-        "- Coverage {:.0}% (matched {} • pending {})",
-        (summary.coverage_ratio() * 100.0).clamp(0.0, 100.0),
-        summary.matched,
-        summary.pending
+        "- Coverage {:.0}% of session {} (matched {} • pending {})",
+        (coverage.ratio() * 100.0).clamp(0.0, 100.0),
+        context.session_id,
+        coverage.matched(),
+        coverage.pending
+    );
+    println!(
+        "- Company totals: matched {} • pending {}",
+        summary.matched, summary.pending
     );
     if !duplicate_sets.is_empty() {
         print_duplicate_guidance(&duplicate_sets);
@@ -639,10 +710,12 @@ async fn run_reconciliation_summary(format: OutputFormat) -> Result<()> {
     }
 
     println!(
-        "- Telemetry counters: {} transactions • {} candidates • {} write-offs",
+        "- Telemetry counters: {} transactions • {} candidates • {} write-offs • {} rejections • {} reopens",
         counters.reconciliation_transactions,
         counters.reconciliation_candidates,
-        counters.reconciliation_write_offs
+        counters.reconciliation_write_offs,
+        counters.reconciliation_rejections,
+        counters.reconciliation_reopens
     );
     println!(
         "  Period locks: {} total (close {} / soft {} / reopen {})",
@@ -667,6 +740,46 @@ async fn run_reconciliation_summary(format: OutputFormat) -> Result<()> {
     Ok(())
 }
 
+fn run_reconciliation_audit_export(session_id: &str, format: AuditExportFormat) -> Result<()> {
+    let path = reconciliation_audit_log_path().ok_or_else(|| {
+        anyhow!("Set CODEX_HOME to persist and export the reconciliation audit trail")
+    })?;
+    let hook = JsonlReconciliationAuditHook::new(path).map_err(|err| anyhow!(err))?;
+    let records = hook
+        .records(Some(&session_id.to_string()))
+        .map_err(|err| anyhow!(err))?;
+    match format {
+        AuditExportFormat::Csv => print!("{}", audit_records_csv(&records)),
+        AuditExportFormat::Json => println!("{}", serde_json::to_string_pretty(&records)?),
+    }
+    Ok(())
+}
+
+fn audit_records_csv(records: &[ReconciliationAuditRecord]) -> String {
+    let mut csv = String::from("timestamp,session_id,candidate_id,action,note\n");
+    for record in records {
+        let row = [
+            record.recorded_at.to_rfc3339(),
+            record.session_id.clone(),
+            record.candidate_id.clone().unwrap_or_default(),
+            format!("{:?}", record.action),
+            record.note.clone().unwrap_or_default(),
+        ];
+        let fields: Vec<String> = row.iter().map(String::as_str).map(csv_field).collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 fn warn_if_corrupt_telemetry() {
     if let Ok(mut home) = find_codex_home() {
         home.push("accounting");
@@ -712,9 +825,9 @@ async fn run_go_live_checklist() -> Result<()> {
     let reconciliation = seed_demo_reconciliation()
         .await
         .map_err(|err| anyhow!(err))?;
-    let summary = reconciliation
+    let coverage = reconciliation
         .facade
-        .summary(&reconciliation.company_id)
+        .coverage(&reconciliation.session_id)
         .map_err(|err| anyhow!(err))?;
     let approvals_overdue = reconciliation.approvals_view.overdue.len();
     let approvals_total = reconciliation.approvals_view.tasks.len();
@@ -729,9 +842,9 @@ async fn run_go_live_checklist() -> Result<()> {
     );
     println!(
         "- Reconciliation coverage: {:.0}% ({} matched / {} pending).",
-        (summary.coverage_ratio() * 100.0).clamp(0.0, 100.0),
-        summary.matched,
-        summary.pending
+        (coverage.ratio() * 100.0).clamp(0.0, 100.0),
+        coverage.matched(),
+        coverage.pending
     );
     if approvals_total == 0 {
         println!("- Approvals backlog: queue empty ✔");
@@ -893,7 +1006,10 @@ struct ReconciliationSummaryOutput {
     company_name: String,
     matched: usize,
     pending: usize,
+    /// Share of the demo session's candidates that are matched, from
+    /// `Coverage::ratio`; `matched` and `pending` are company-wide totals.
     coverage_ratio: f32,
+    session_coverage: Coverage,
     ingest: IngestSnapshotOutput,
     approvals: ApprovalsBacklogOutput,
     telemetry_path: Option<String>,
@@ -982,6 +1098,24 @@ mod tests {
     use super::*;
     use tokio::runtime::Runtime;
 
+    #[test]
+    fn audit_csv_escapes_fields() {
+        let records = vec![ReconciliationAuditRecord {
+            recorded_at: DateTime::parse_from_rfc3339("2025-03-01T12:00:00Z")
+                .expect("timestamp")
+                .with_timezone(&Utc),
+            session_id: "sess-1".into(),
+            candidate_id: Some("cand-1".into()),
+            action: codex_reconcile::ReconciliationAuditAction::CandidateRejected,
+            note: Some("payee \"ACME\", wrong".into()),
+        }];
+        assert_eq!(
+            audit_records_csv(&records),
+            "timestamp,session_id,candidate_id,action,note\n\
+             2025-03-01T12:00:00+00:00,sess-1,cand-1,CandidateRejected,\"payee \"\"ACME\"\", wrong\"\n"
+        );
+    }
+
     #[test]
     fn currency_formatting_is_stable() {
         assert_eq!(format_currency(12_500), "$125.00");
@@ -44,6 +44,7 @@ fn init_logging() {
 }
 
 use crate::ledger_cmd::LedgerCli;
+use crate::ledger_cmd::ReconcileCli;
 use crate::mcp_cmd::McpCli;
 use crate::tenancy_cmd::TenancyCli;
 use codex_core::config::Config;
@@ -119,6 +120,9 @@ enum Subcommand {
     /// Prototype Codex Accounting workflows.
     Ledger(LedgerCli),
 
+    /// Export and review reconciliation records.
+    Reconcile(ReconcileCli),
+
     /// Manage accounting firm tenants and companies.
     Tenancy(TenancyCli),
 
@@ -432,6 +436,13 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             );
             ledger_cmd::run(ledger_cli).await?;
         }
+        Some(Subcommand::Reconcile(mut reconcile_cli)) => {
+            prepend_config_flags(
+                &mut reconcile_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            ledger_cmd::run_reconcile(reconcile_cli).await?;
+        }
         Some(Subcommand::Tenancy(mut tenancy_cli)) => {
             prepend_config_flags(
                 &mut tenancy_cli.config_overrides,
@@ -18,6 +18,8 @@ pub struct ReconciliationTelemetryOutput {
     pub policy_reject: usize,
     pub approvals_total: usize,
     pub approvals_overdue: usize,
+    pub reconciliation_rejections: usize,
+    pub reconciliation_reopens: usize,
 }
 
 impl From<&TelemetryCounters> for ReconciliationTelemetryOutput {
@@ -36,6 +38,8 @@ impl From<&TelemetryCounters> for ReconciliationTelemetryOutput {
             policy_reject: counters.policy_reject,
             approvals_total: counters.approvals_total,
             approvals_overdue: counters.approvals_overdue,
+            reconciliation_rejections: counters.reconciliation_rejections,
+            reconciliation_reopens: counters.reconciliation_reopens,
         }
     }
 }
@@ -2,6 +2,9 @@ use std::cmp::max;
 use std::collections::HashSet;
 use std::fmt::Write;
 use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write as _;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -18,17 +21,25 @@ use clap::Parser;
 use codex_accounting_api::AccountingTelemetry;
 use codex_accounting_api::ApprovalsQueueView;
 use codex_accounting_api::ControlsFacade;
+use codex_accounting_api::LedgerFacade;
 use codex_accounting_api::TelemetryCounters;
 use codex_accounting_api::TenancyFacade;
+use codex_accounting_api::demo::advance_demo_reconciliation;
 use codex_accounting_api::demo::seed_demo_reconciliation;
+use codex_accounting_api::demo::seed_demo_reconciliation_with;
 use codex_approvals::ApprovalStatus;
 use codex_approvals::ApprovalTask;
 use codex_approvals::InMemoryApprovalsService;
 use codex_approvals::QueueFilter;
+use codex_audit_log::AuditLog;
+use codex_audit_log::AuditLogFilter;
+use codex_audit_log::AuditRecord;
+use codex_audit_log::InMemoryAuditLog;
 use codex_common::CliConfigOverrides;
 use codex_core::config::find_codex_home;
 use codex_ledger::AccountType as LedgerAccountType;
 use codex_ledger::ChartAccount;
+use codex_ledger::ChartTypeWarning;
 use codex_ledger::CreateCompanyRequest as LedgerCreateCompanyRequest;
 use codex_ledger::Currency as LedgerCurrency;
 use codex_ledger::CurrencyMode as LedgerCurrencyMode;
@@ -56,11 +67,13 @@ use codex_tenancy::InMemoryTenancyService;
 use codex_tenancy::Role;
 use codex_tenancy::RoleAssignment;
 use codex_tenancy::RoleScope;
+use codex_tenancy::TenancyActor;
 use codex_tenancy::TenancyError;
 use codex_tenancy::TenancyService;
 use codex_tenancy::TenancySnapshot;
 use codex_tenancy::UserAccount;
 use codex_tenancy::UserStatus;
+use codex_tenancy::default_currency_precision;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::to_string;
@@ -72,6 +85,26 @@ pub struct TenancyCli {
     #[clap(flatten)]
     pub config_overrides: CliConfigOverrides,
 
+    /// User recorded as the actor on tenancy mutations.
+    #[arg(
+        long = "actor",
+        value_name = "USER_ID",
+        default_value = "cli",
+        global = true
+    )]
+    actor: String,
+
+    /// Role the actor acts with (partner, senior, staff, auditor). Repeat the
+    /// flag for multiple roles. Once the firm has any users, the actor must be
+    /// an active member and only roles their account holds count.
+    #[arg(
+        long = "actor-role",
+        value_name = "ROLE",
+        default_value = "partner",
+        global = true
+    )]
+    actor_roles: Vec<String>,
+
     #[command(subcommand)]
     command: TenancyCommand,
 }
@@ -105,6 +138,10 @@ enum TenancyCommand {
         /// Base currency in ISO-4217 format.
         #[arg(long = "currency", value_name = "CURRENCY")]
         currency: String,
+        /// Minor-unit digits of the base currency; defaults to its ISO-4217
+        /// precision (e.g. 0 for JPY).
+        #[arg(long = "currency-precision", value_name = "DIGITS")]
+        currency_precision: Option<u8>,
         /// Optional company tags. Repeat the flag to add multiple tags.
         #[arg(long = "tag", value_name = "TAG")]
         tags: Vec<String>,
@@ -112,6 +149,17 @@ enum TenancyCommand {
         #[arg(long = "metadata", value_name = "METADATA")]
         metadata: Option<String>,
     },
+    /// Create a firm's demo company and seed its ledger, policy, and demo
+    /// reconciliation data from a fixed seed. Re-running with the same firm
+    /// and seed leaves existing data untouched.
+    Bootstrap {
+        /// Firm identifier to bootstrap; created when missing.
+        #[arg(long = "firm-id", value_name = "FIRM_ID")]
+        firm_id: String,
+        /// Seed for the demo reconciliation ids.
+        #[arg(long = "seed", value_name = "SEED", default_value_t = DEMO_STREAM_SEED)]
+        seed: u64,
+    },
     /// Archive a company.
     Archive {
         #[arg(long = "firm-id", value_name = "FIRM_ID")]
@@ -180,6 +228,12 @@ pub async fn run(cli: TenancyCli) -> Result<()> {
     let mut bootstrap = AccountingBootstrap::load()?;
     let tenancy_service: Arc<dyn TenancyService> = service.clone();
     let facade = TenancyFacade::new(tenancy_service);
+    let actor_roles = cli
+        .actor_roles
+        .iter()
+        .map(|role| parse_role(role))
+        .collect::<Result<Vec<_>>>()?;
+    let actor = TenancyActor::new(cli.actor, actor_roles);
     let mut dirty = false;
     let mut bootstrap_dirty = false;
 
@@ -207,17 +261,22 @@ pub async fn run(cli: TenancyCli) -> Result<()> {
             firm_id,
             name,
             currency,
+            currency_precision,
             tags,
             metadata,
         } => {
             let outcome = create_company(
                 &facade,
+                &actor,
                 &mut bootstrap,
-                firm_id,
-                name,
-                currency,
-                tags,
-                metadata,
+                CreateCompanyRequest {
+                    firm_id,
+                    name,
+                    base_currency: currency,
+                    currency_precision,
+                    tags,
+                    metadata,
+                },
             )
             .await?;
             if outcome {
@@ -225,18 +284,24 @@ pub async fn run(cli: TenancyCli) -> Result<()> {
             }
             dirty = true;
         }
+        TenancyCommand::Bootstrap { firm_id, seed } => {
+            if bootstrap_firm(&facade, &actor, &mut bootstrap, &firm_id, seed).await? {
+                dirty = true;
+                bootstrap_dirty = true;
+            }
+        }
         TenancyCommand::Archive {
             firm_id,
             company_id,
         } => {
-            archive_company(&facade, firm_id, company_id).await?;
+            archive_company(&facade, &actor, firm_id, company_id).await?;
             dirty = true;
         }
         TenancyCommand::Reactivate {
             firm_id,
             company_id,
         } => {
-            reactivate_company(&facade, firm_id, company_id).await?;
+            reactivate_company(&facade, &actor, firm_id, company_id).await?;
             dirty = true;
         }
         TenancyCommand::Policy { command } => {
@@ -312,6 +377,10 @@ async fn list_companies(
     Ok(())
 }
 
+/// Seed for the `--json` reconciliation stream's demo data and the default
+/// `bootstrap` seed.
+const DEMO_STREAM_SEED: u64 = 0x5eed;
+
 async fn stream_reconciliation_metrics(format: StreamFormat) -> Result<()> {
     if !format.is_json() {
         println!("Reconciliation metrics (demo feed):");
@@ -323,26 +392,29 @@ async fn stream_reconciliation_metrics(format: StreamFormat) -> Result<()> {
     } else {
         Some(TokioDuration::from_millis(500))
     };
+    // JSON consumers diff consecutive ticks, so they get stable seeded ids;
+    // the text feed keeps the random demo. Both build the context once and
+    // resolve one pending candidate per tick so the metrics move.
+    let context = if format.is_json() {
+        seed_demo_reconciliation_with(DEMO_STREAM_SEED).await
+    } else {
+        seed_demo_reconciliation().await
+    }
+    .map_err(|err| anyhow!(err))?;
     for tick in 0..ticks {
-        let context = seed_demo_reconciliation()
-            .await
-            .map_err(|err| anyhow!(err))?;
+        if tick > 0 {
+            advance_demo_reconciliation(&context)?;
+        }
         if telemetry_path.is_none() {
             telemetry_path = context
                 .telemetry
                 .store_path()
                 .map(|path| path.display().to_string());
         }
-        let summary = context
+        let coverage = context
             .facade
-            .summary(&context.company_id)
+            .coverage(&context.session_id)
             .map_err(|err| anyhow!(err))?;
-        let total = summary.matched + summary.pending;
-        let coverage = if total == 0 {
-            0.0
-        } else {
-            summary.matched as f32 / total as f32
-        };
         let counters = context.telemetry.snapshot();
         if format.is_json() {
             let approvals = ApprovalsBacklogOutput {
@@ -358,10 +430,10 @@ async fn stream_reconciliation_metrics(format: StreamFormat) -> Result<()> {
             };
             let payload = ReconciliationStreamTickOutput {
                 tick: tick + 1,
-                matched: summary.matched,
-                pending: summary.pending,
-                coverage_ratio: coverage,
-                coverage_percent: (coverage * 100.0).clamp(0.0, 100.0),
+                matched: coverage.matched(),
+                pending: coverage.pending,
+                coverage_ratio: coverage.ratio(),
+                coverage_percent: (coverage.ratio() * 100.0).clamp(0.0, 100.0),
                 approvals,
                 ingest,
                 telemetry: ReconciliationTelemetryOutput::from(&counters),
@@ -373,9 +445,9 @@ async fn stream_reconciliation_metrics(format: StreamFormat) -> Result<()> {
             println!(
                 "  tick {}: matched {} | pending {} | coverage {:.0}% | backlog {} overdue / {} open",
                 tick + 1,
-                summary.matched,
-                summary.pending,
-                (coverage * 100.0).clamp(0.0, 100.0),
+                coverage.matched(),
+                coverage.pending,
+                (coverage.ratio() * 100.0).clamp(0.0, 100.0),
                 context.approvals_view.overdue.len(),
                 context.approvals_view.tasks.len()
             );
@@ -428,24 +500,16 @@ fn short_since(timestamp: DateTime<Utc>) -> String {
 
 async fn create_company(
     facade: &TenancyFacade,
+    actor: &TenancyActor,
     bootstrap: &mut AccountingBootstrap,
-    firm_id: String,
-    name: String,
-    currency: String,
-    tags: Vec<String>,
-    metadata: Option<String>,
+    request: CreateCompanyRequest,
 ) -> Result<bool> {
-    ensure_firm_exists(facade, &firm_id)
+    let firm_id = request.firm_id.clone();
+    ensure_firm_exists(facade, actor, &firm_id)
         .await
         .with_context(|| format!("failed to ensure firm {firm_id} exists"))?;
     let company = facade
-        .create_company(CreateCompanyRequest {
-            firm_id,
-            name,
-            base_currency: currency,
-            tags,
-            metadata,
-        })
+        .create_company(request, actor)
         .await
         .context("failed to create company")?;
 
@@ -463,6 +527,12 @@ async fn create_company(
             "Seeded ledger company {} with {} starter accounts.",
             bootstrap_summary.ledger_company_id, bootstrap_summary.accounts_seeded
         );
+        for warning in &bootstrap_summary.chart_warnings {
+            println!(
+                "Warning: account {} is typed {:?} but its code suggests {:?}.",
+                warning.code, warning.supplied, warning.inferred
+            );
+        }
     } else {
         println!(
             "Ledger bootstrap already exists for company {} ({} accounts).",
@@ -483,13 +553,82 @@ async fn create_company(
     Ok(bootstrap_summary.newly_seeded)
 }
 
+/// Creates the firm's demo company for `seed` unless one already carries its
+/// tag, then reports the seeded demo reconciliation. Returns whether anything
+/// was created.
+async fn bootstrap_firm(
+    facade: &TenancyFacade,
+    actor: &TenancyActor,
+    bootstrap: &mut AccountingBootstrap,
+    firm_id: &str,
+    seed: u64,
+) -> Result<bool> {
+    ensure_firm_exists(facade, actor, firm_id)
+        .await
+        .with_context(|| format!("failed to ensure firm {firm_id} exists"))?;
+    let tag = format!("demo-seed-{seed:x}");
+    let existing = facade
+        .list_companies(&firm_id.to_string())
+        .await
+        .context("failed to list companies")?
+        .into_iter()
+        .find(|company| company.tags.contains(&tag));
+    let created = match existing {
+        Some(company) => {
+            println!(
+                "Demo company {} ({}) already exists for firm {firm_id}.",
+                company.name, company.id
+            );
+            bootstrap
+                .ensure_bootstrapped(&company)
+                .await
+                .context("failed to bootstrap ledger state")?
+                .newly_seeded
+        }
+        None => {
+            create_company(
+                facade,
+                actor,
+                bootstrap,
+                CreateCompanyRequest {
+                    firm_id: firm_id.to_string(),
+                    name: format!("Demo Company {seed:x}"),
+                    base_currency: "USD".into(),
+                    currency_precision: None,
+                    tags: vec![tag],
+                    metadata: None,
+                },
+            )
+            .await?;
+            true
+        }
+    };
+
+    let context = seed_demo_reconciliation_with(seed)
+        .await
+        .map_err(|err| anyhow!(err))?;
+    let coverage = context
+        .facade
+        .coverage(&context.session_id)
+        .map_err(|err| anyhow!(err))?;
+    println!(
+        "Demo reconciliation session {}: matched {} | pending {} | {} approvals queued",
+        context.session_id,
+        coverage.matched(),
+        coverage.pending,
+        context.approvals_view.tasks.len()
+    );
+    Ok(created)
+}
+
 async fn archive_company(
     facade: &TenancyFacade,
+    actor: &TenancyActor,
     firm_id: String,
     company_id: String,
 ) -> Result<()> {
     let company = facade
-        .archive_company(&firm_id, &company_id)
+        .archive_company(&firm_id, &company_id, actor)
         .await
         .context("failed to archive company")?;
     println!("Archived company {} ({})", company.name, company.id);
@@ -498,11 +637,12 @@ async fn archive_company(
 
 async fn reactivate_company(
     facade: &TenancyFacade,
+    actor: &TenancyActor,
     firm_id: String,
     company_id: String,
 ) -> Result<()> {
     let company = facade
-        .reactivate_company(&firm_id, &company_id)
+        .reactivate_company(&firm_id, &company_id, actor)
         .await
         .context("failed to reactivate company")?;
     println!("Reactivated company {} ({})", company.name, company.id);
@@ -902,6 +1042,7 @@ fn format_approval_status(status: ApprovalStatus) -> &'static str {
 
 struct TenancyStore {
     path: PathBuf,
+    audit_log: Arc<InMemoryAuditLog>,
 }
 
 impl TenancyStore {
@@ -914,8 +1055,15 @@ impl TenancyStore {
         } else {
             TenancySnapshot::default()
         };
-        let service = Arc::new(InMemoryTenancyService::from_snapshot(snapshot));
-        Ok((Self { path }, service))
+        let mut audit_log = InMemoryAuditLog::default();
+        if let Some(hash) = Self::last_audit_hash(&Self::audit_path_for(&path))? {
+            audit_log = audit_log.with_previous_hash(hash);
+        }
+        let audit_log = Arc::new(audit_log);
+        let service = Arc::new(
+            InMemoryTenancyService::from_snapshot(snapshot).with_audit_log(audit_log.clone()),
+        );
+        Ok((Self { path, audit_log }, service))
     }
 
     async fn persist(&self, service: Arc<InMemoryTenancyService>) -> Result<()> {
@@ -929,6 +1077,52 @@ impl TenancyStore {
             serde_json::to_vec_pretty(&stored).context("failed to encode tenancy snapshot")?;
         fs::write(&self.path, data)
             .with_context(|| format!("failed to write {}", self.path.display()))?;
+        self.append_audit_records().await
+    }
+
+    fn audit_path_for(path: &Path) -> PathBuf {
+        path.with_file_name("tenancy-audit.jsonl")
+    }
+
+    /// Hash of the last record in the audit file, which the next run's
+    /// records chain off.
+    fn last_audit_hash(path: &Path) -> Result<Option<String>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let Some(line) = data.lines().rev().find(|line| !line.trim().is_empty()) else {
+            return Ok(None);
+        };
+        let record: AuditRecord = serde_json::from_str(line)
+            .with_context(|| format!("failed to decode the last record of {}", path.display()))?;
+        Ok(Some(record.hash))
+    }
+
+    /// Appends this run's audit records to `tenancy-audit.jsonl` beside the
+    /// snapshot, continuing the hash chain of the records already there.
+    async fn append_audit_records(&self) -> Result<()> {
+        let records = self
+            .audit_log
+            .records(AuditLogFilter::default())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        if records.is_empty() {
+            return Ok(());
+        }
+        let path = Self::audit_path_for(&self.path);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+        for record in records {
+            let line =
+                serde_json::to_string(&record).context("failed to encode tenancy audit record")?;
+            writeln!(file, "{line}")
+                .with_context(|| format!("failed to write {}", path.display()))?;
+        }
         Ok(())
     }
 
@@ -972,6 +1166,7 @@ impl AccountingBootstrap {
                 newly_seeded: false,
                 ledger_company_id: existing.ledger_company_id,
                 accounts_seeded: existing.accounts_seeded,
+                chart_warnings: Vec::new(),
                 policy_preview: preview,
             });
         }
@@ -993,6 +1188,7 @@ impl AccountingBootstrap {
             newly_seeded: true,
             ledger_company_id: ledger.ledger_company_id,
             accounts_seeded: ledger.accounts_seeded,
+            chart_warnings: ledger.chart_warnings,
             policy_preview: preview,
         })
     }
@@ -1140,12 +1336,14 @@ struct LedgerSeedSummary {
     ledger_company_id: String,
     accounts_seeded: usize,
     seeded_at: DateTime<Utc>,
+    chart_warnings: Vec<ChartTypeWarning>,
 }
 
 struct BootstrapOutcome {
     newly_seeded: bool,
     ledger_company_id: String,
     accounts_seeded: usize,
+    chart_warnings: Vec<ChartTypeWarning>,
     policy_preview: PolicyPreview,
 }
 
@@ -1187,7 +1385,7 @@ async fn seed_ledger(company: &Company) -> Result<LedgerSeedSummary> {
             name: company.name.clone(),
             base_currency: LedgerCurrency {
                 code: company.base_currency.clone(),
-                precision: 2,
+                precision: company.currency_precision,
             },
             fiscal_calendar: LedgerFiscalCalendar {
                 periods_per_year: 12,
@@ -1198,7 +1396,7 @@ async fn seed_ledger(company: &Company) -> Result<LedgerSeedSummary> {
         .await
         .map_err(anyhow::Error::from)?;
 
-    let accounts = service
+    let seeded = LedgerFacade::new(service)
         .seed_chart(SeedChartRequest {
             company_id: ledger_company.id.clone(),
             accounts: default_chart_accounts(),
@@ -1209,8 +1407,9 @@ async fn seed_ledger(company: &Company) -> Result<LedgerSeedSummary> {
 
     Ok(LedgerSeedSummary {
         ledger_company_id: ledger_company.id,
-        accounts_seeded: accounts.len(),
+        accounts_seeded: seeded.accounts.len(),
         seeded_at: Utc::now(),
+        chart_warnings: seeded.warnings,
     })
 }
 
@@ -1360,17 +1559,33 @@ fn describe_trigger(trigger: &PolicyTrigger) -> String {
         PolicyTrigger::AccountBlocked { account_code } => {
             format!("account {account_code} blocked")
         }
+        PolicyTrigger::OutsideBusinessHours { submitted_at } => {
+            format!(
+                "submitted outside business hours at {}",
+                submitted_at.to_rfc3339()
+            )
+        }
+        PolicyTrigger::SelfApprovalBlocked { submitted_by } => {
+            format!("{submitted_by} cannot approve their own proposal")
+        }
     }
 }
 
-async fn ensure_firm_exists(facade: &TenancyFacade, firm_id: &str) -> Result<Firm> {
+async fn ensure_firm_exists(
+    facade: &TenancyFacade,
+    actor: &TenancyActor,
+    firm_id: &str,
+) -> Result<Firm> {
     match facade.get_firm(&firm_id.to_string()).await {
         Ok(firm) => Ok(firm),
         Err(TenancyError::NotFound(_)) => match facade
-            .create_firm(CreateFirmRequest {
-                name: firm_id.to_string(),
-                metadata: None,
-            })
+            .create_firm(
+                CreateFirmRequest {
+                    name: firm_id.to_string(),
+                    metadata: None,
+                },
+                actor,
+            )
             .await
         {
             Ok(firm) => Ok(firm),
@@ -1473,6 +1688,8 @@ struct StoredCompany {
     name: String,
     status: String,
     base_currency: String,
+    #[serde(default)]
+    currency_precision: Option<u8>,
     tags: Vec<String>,
     created_at: String,
     archived_at: Option<String>,
@@ -1510,6 +1727,9 @@ impl StoredCompany {
             firm_id: self.firm_id,
             name: self.name,
             status,
+            currency_precision: self
+                .currency_precision
+                .unwrap_or_else(|| default_currency_precision(&self.base_currency)),
             base_currency: self.base_currency,
             tags: self.tags,
             created_at,
@@ -1533,6 +1753,7 @@ impl From<Company> for StoredCompany {
             name: company.name,
             status,
             base_currency: company.base_currency,
+            currency_precision: Some(company.currency_precision),
             tags: company.tags,
             created_at: company.created_at.to_rfc3339(),
             archived_at: company.archived_at.map(|value| value.to_rfc3339()),
@@ -1698,6 +1919,56 @@ mod tests {
         assert_eq!(TenancyStore::storage_path_from_home(base), expected);
     }
 
+    #[tokio::test]
+    async fn audit_records_continue_the_chain_in_the_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("tenancy.json");
+        let audit_path = TenancyStore::audit_path_for(&path);
+        assert_eq!(
+            TenancyStore::last_audit_hash(&audit_path).expect("missing file"),
+            None
+        );
+
+        let mut hashes = Vec::new();
+        for run in 0..2 {
+            let previous = TenancyStore::last_audit_hash(&audit_path).expect("last hash");
+            let mut audit_log = InMemoryAuditLog::default();
+            if let Some(hash) = previous.clone() {
+                audit_log = audit_log.with_previous_hash(hash);
+            }
+            let store = TenancyStore {
+                path: path.clone(),
+                audit_log: Arc::new(audit_log),
+            };
+            let record = store
+                .audit_log
+                .append(codex_audit_log::AppendRequest {
+                    entity_id: format!("entity-{run}"),
+                    actor: "user-admin".into(),
+                    action: "firm.created".into(),
+                    metadata: serde_json::json!({}),
+                    request_id: None,
+                })
+                .await
+                .expect("append");
+            assert_eq!(
+                record.previous_hash,
+                previous.unwrap_or_else(|| "genesis".into())
+            );
+            store.append_audit_records().await.expect("write records");
+            hashes.push(record.hash);
+        }
+
+        let lines: Vec<AuditRecord> = fs::read_to_string(&audit_path)
+            .expect("read audit file")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("decode record"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].previous_hash, hashes[0]);
+        assert_eq!(lines[1].hash, hashes[1]);
+    }
+
     #[test]
     fn storage_path_handles_windows_style_home() {
         let base = PathBuf::from(r"C:\Users\demo\.codex");
@@ -396,6 +396,13 @@ fn ledger_reconciliation_summary_json_exposes_metrics() -> Result<()> {
             .expect("write-off telemetry"),
         0
     );
+    assert_eq!(
+        telemetry
+            .get("reconciliation_rejections")
+            .and_then(Value::as_u64)
+            .expect("rejection telemetry"),
+        0
+    );
     let transactions = value
         .get("transactions")
         .and_then(Value::as_array)
@@ -565,6 +572,45 @@ fn ledger_go_live_checklist_surfaces_counters() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn reconcile_audit_export_writes_csv_rows_for_a_session() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let output = codex_command(codex_home.path())?
+        .args(["ledger", "reconciliation", "summary", "--format", "json"])
+        .output()?;
+    assert!(output.status.success(), "summary should seed the audit log");
+    let audit_log = fs::read_to_string(
+        codex_home
+            .path()
+            .join("accounting")
+            .join("reconciliation-audit.jsonl"),
+    )?;
+    let first: Value = serde_json::from_str(audit_log.lines().next().expect("audit record"))?;
+    let session_id = first
+        .get("session_id")
+        .and_then(Value::as_str)
+        .expect("session id")
+        .to_string();
+
+    let output = codex_command(codex_home.path())?
+        .args(["reconcile", "audit-export", "--session-id", &session_id])
+        .output()?;
+    assert!(output.status.success(), "audit export should succeed");
+    let stdout = String::from_utf8(output.stdout)?;
+    let mut lines = stdout.lines();
+    assert_eq!(
+        lines.next(),
+        Some("timestamp,session_id,candidate_id,action,note")
+    );
+    let rows: Vec<&str> = lines.collect();
+    assert!(!rows.is_empty(), "audit export has no rows: {stdout}");
+    assert!(
+        rows.iter().all(|row| row.contains(&session_id)),
+        "rows from another session: {stdout}"
+    );
+    Ok(())
+}
+
 #[test]
 fn tenancy_list_streams_reconciliation_metrics() -> Result<()> {
     let codex_home = TempDir::new()?;
@@ -650,6 +696,17 @@ fn tenancy_list_streams_reconciliation_json() -> Result<()> {
         .and_then(Value::as_u64)
         .expect("tick number");
     assert_eq!(second, 2, "second tick index mismatch: {ticks:?}");
+    let counts = |tick: &Value, key: &str| tick.get(key).and_then(Value::as_u64);
+    assert_eq!(
+        counts(&ticks[1], "pending").map(|pending| pending + 1),
+        counts(&ticks[0], "pending"),
+        "second tick should resolve one pending candidate: {ticks:?}"
+    );
+    assert_eq!(
+        counts(&ticks[1], "matched"),
+        counts(&ticks[0], "matched").map(|matched| matched + 1),
+        "second tick should add one match: {ticks:?}"
+    );
     let telemetry_path = ticks.iter().find_map(|tick| {
         tick.get("telemetry_path")
             .and_then(Value::as_str)
@@ -707,3 +764,68 @@ fn tenancy_list_streams_reconciliation_json() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn tenancy_bootstrap_is_idempotent() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let bootstrap = || -> Result<String> {
+        let output = codex_command(codex_home.path())?
+            .args([
+                "tenancy",
+                "bootstrap",
+                "--firm-id",
+                "demo-firm",
+                "--seed",
+                "7",
+            ])
+            .output()?;
+        assert!(output.status.success(), "tenancy bootstrap should succeed");
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    let first = bootstrap()?;
+    assert!(
+        first.contains("Created company Demo Company 7"),
+        "first run should create the demo company: {first}"
+    );
+    assert!(
+        first.contains("Demo reconciliation session demo-session-7-1"),
+        "first run should report the seeded session: {first}"
+    );
+    let second = bootstrap()?;
+    assert!(
+        second.contains("already exists for firm demo-firm"),
+        "second run should reuse the demo company: {second}"
+    );
+    assert!(
+        second.contains("Demo reconciliation session demo-session-7-1"),
+        "second run should report the same session: {second}"
+    );
+
+    let output = codex_command(codex_home.path())?
+        .args(["tenancy", "list", "--firm-id", "demo-firm"])
+        .output()?;
+    let listing = String::from_utf8(output.stdout)?;
+    assert_eq!(
+        listing.matches("Demo Company 7").count(),
+        1,
+        "bootstrap should create one company: {listing}"
+    );
+
+    let audit = fs::read_to_string(
+        codex_home
+            .path()
+            .join("accounting")
+            .join("tenancy-audit.jsonl"),
+    )?;
+    let actions: Vec<String> = audit
+        .lines()
+        .map(|line| {
+            let record: Value = serde_json::from_str(line).expect("audit record json");
+            assert_eq!(record["actor"], "cli", "unexpected actor: {record}");
+            record["action"].as_str().unwrap_or_default().to_string()
+        })
+        .collect();
+    assert_eq!(actions, vec!["firm.created", "company.created"]);
+    Ok(())
+}
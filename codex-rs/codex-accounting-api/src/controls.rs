@@ -1,25 +1,49 @@
 use std::sync::Arc;
 
+use anyhow::anyhow;
+use anyhow::bail;
 use chrono::DateTime;
 use chrono::Utc;
+use codex_app_server_protocol::LedgerJournalEntry;
+use codex_app_server_protocol::LedgerPostEntryParams;
+use codex_app_server_protocol::LedgerPostingSide;
+use codex_approvals::ApprovalId;
+use codex_approvals::ApprovalRequest;
+use codex_approvals::ApprovalStage;
+use codex_approvals::ApprovalStatus;
 use codex_approvals::ApprovalTask;
+use codex_approvals::ApprovalsError;
 use codex_approvals::ApprovalsResult;
 use codex_approvals::ApprovalsService;
 use codex_approvals::QueueFilter;
+use codex_approvals::StageAccess;
+use codex_approvals::UserId;
+use codex_ledger::TenantContext as LedgerTenantContext;
 use codex_policy::CompanyId;
+use codex_policy::EvaluationOutcome;
+use codex_policy::PolicyContext;
+use codex_policy::PolicyDecision;
+use codex_policy::PolicyEngine;
 use codex_policy::PolicyResult;
 use codex_policy::PolicyRuleSet;
 use codex_policy::PolicyStore;
+use codex_policy::PolicyTrigger;
+use codex_policy::PostingProposal;
 use serde::Deserialize;
 use serde::Serialize;
+use serde_json::json;
 
 use crate::AccountingTelemetry;
+use crate::LedgerFacade;
 
 #[derive(Clone)]
 pub struct ControlsFacade {
     policy_store: Arc<dyn PolicyStore>,
     approvals: Arc<dyn ApprovalsService>,
     telemetry: Option<Arc<AccountingTelemetry>>,
+    policy_engine: Arc<PolicyEngine>,
+    ledger: Option<LedgerFacade>,
+    approval_stages: Vec<ApprovalStage>,
 }
 
 impl ControlsFacade {
@@ -33,12 +57,39 @@ impl ControlsFacade {
         telemetry: Option<Arc<AccountingTelemetry>>,
     ) -> Self {
         Self {
+            policy_engine: Arc::new(PolicyEngine::new(Arc::clone(&policy_store))),
             policy_store,
             approvals,
             telemetry,
+            ledger: None,
+            approval_stages: Vec::new(),
         }
     }
 
+    /// Replaces the engine `submit_proposal` evaluates with, e.g. one wired to
+    /// an event sink. It should read from the facade's policy store.
+    #[must_use]
+    pub fn with_policy_engine(mut self, policy_engine: Arc<PolicyEngine>) -> Self {
+        self.policy_engine = policy_engine;
+        self
+    }
+
+    /// Ledger that `submit_proposal` posts `AutoPost` decisions to.
+    #[must_use]
+    pub fn with_ledger(mut self, ledger: LedgerFacade) -> Self {
+        self.ledger = Some(ledger);
+        self
+    }
+
+    /// Stages given to every policy approval. `submit_proposal` also derives
+    /// the proposal's `eligible_approvers` from them, so
+    /// `prevent_self_approval` sees who could approve.
+    #[must_use]
+    pub fn with_approval_stages(mut self, stages: Vec<ApprovalStage>) -> Self {
+        self.approval_stages = stages;
+        self
+    }
+
     pub async fn list_policy_rule_sets(&self) -> PolicyResult<Vec<PolicyRuleSetView>> {
         let rule_sets = self.policy_store.list_rule_sets().await?;
         let mut views = rule_sets
@@ -71,6 +122,237 @@ impl ControlsFacade {
         }
         Ok(view)
     }
+
+    /// Evaluates `proposal` and acts on the decision: `AutoPost` posts
+    /// `entry` through the ledger set by `with_ledger`, `NeedsApproval`
+    /// enqueues it via `enqueue_policy_approval` with `entry` kept in the
+    /// request metadata for `post_approved`, and `Reject` is counted in the
+    /// facade's telemetry. Auto-posting without a ledger is an error, as is
+    /// an `entry` or `tenant` that does not match the proposal's company,
+    /// currency, and total. The proposal is evaluated as submitted by the
+    /// context's actor, with `eligible_approvers` taken from the facade's
+    /// approval stages when any are configured.
+    pub async fn submit_proposal(
+        &self,
+        context: PolicyContext,
+        mut proposal: PostingProposal,
+        entry: LedgerPostEntryParams,
+        tenant: LedgerTenantContext,
+    ) -> anyhow::Result<SubmitOutcome> {
+        ensure_entry_matches_proposal(&proposal, &entry, &tenant)?;
+        let submitted_by = context.actor.clone();
+        proposal.submitted_by = Some(submitted_by.clone());
+        if !self.approval_stages.is_empty() {
+            proposal.eligible_approvers = eligible_approvers(&self.approval_stages, &submitted_by);
+        }
+        let outcome = self
+            .policy_engine
+            .evaluate(context, proposal.clone())
+            .await?;
+        let mut submitted = SubmitOutcome {
+            decision: outcome.decision.clone(),
+            triggers: outcome.triggers.clone(),
+            approval_id: None,
+            posted_entry: None,
+        };
+        match &outcome.decision {
+            PolicyDecision::AutoPost => {
+                let ledger = self.ledger.as_ref().ok_or_else(|| {
+                    anyhow!(
+                        "proposal {} was auto-posted but no ledger is configured",
+                        proposal.id
+                    )
+                })?;
+                let posted = ledger.post_entry(entry, tenant).await?;
+                submitted.posted_entry = Some(posted.entry);
+            }
+            PolicyDecision::NeedsApproval => {
+                let mut request = policy_approval_request(
+                    &proposal,
+                    &outcome,
+                    submitted_by,
+                    &self.approval_stages,
+                )?;
+                request.currency_precision = entry
+                    .entry
+                    .lines
+                    .first()
+                    .map(|line| line.currency.precision);
+                if let Some(metadata) = request.metadata.as_object_mut() {
+                    metadata.insert("entry".into(), serde_json::to_value(&entry)?);
+                }
+                let task = self.approvals.enqueue(request).await?;
+                submitted.approval_id = Some(task.request.id);
+            }
+            PolicyDecision::Reject => {
+                if let Some(telemetry) = &self.telemetry {
+                    telemetry.record_policy_decision(PolicyDecision::Reject);
+                }
+            }
+        }
+        Ok(submitted)
+    }
+
+    /// Enqueues the approval a `NeedsApproval` outcome calls for, linked to
+    /// the proposal by `originating_proposal_id`, routed through the facade's
+    /// approval stages, and carrying the proposal id and triggers as
+    /// metadata. Other decisions are a validation error.
+    pub async fn enqueue_policy_approval(
+        &self,
+        proposal: &PostingProposal,
+        outcome: &EvaluationOutcome,
+        submitted_by: UserId,
+    ) -> ApprovalsResult<ApprovalTask> {
+        let request =
+            policy_approval_request(proposal, outcome, submitted_by, &self.approval_stages)?;
+        self.approvals.enqueue(request).await
+    }
+
+    /// Posts the entry `submit_proposal` stored with a `NeedsApproval`
+    /// request once that request is `Approved`. `tenant` must belong to the
+    /// request's company.
+    pub async fn post_approved(
+        &self,
+        approval_id: &ApprovalId,
+        tenant: LedgerTenantContext,
+    ) -> anyhow::Result<LedgerJournalEntry> {
+        let task = self.approvals.get(approval_id).await?;
+        if task.status != ApprovalStatus::Approved {
+            bail!("approval {approval_id} is {:?}, not approved", task.status);
+        }
+        if tenant.tenant_id != task.request.company_id {
+            bail!(
+                "tenant {} cannot post approval {approval_id} for company {}",
+                tenant.tenant_id,
+                task.request.company_id
+            );
+        }
+        let entry = task
+            .request
+            .metadata_field("entry")
+            .cloned()
+            .ok_or_else(|| anyhow!("approval {approval_id} carries no ledger entry"))?;
+        let entry: LedgerPostEntryParams = serde_json::from_value(entry)?;
+        let ledger = self.ledger.as_ref().ok_or_else(|| {
+            anyhow!("approval {approval_id} is approved but no ledger is configured")
+        })?;
+        Ok(ledger.post_entry(entry, tenant).await?.entry)
+    }
+}
+
+/// Who could approve a proposal routed through `stages`, for
+/// `PostingProposal::eligible_approvers`. A named stage only `submitted_by`
+/// can action forces self-approval on its own, so it decides the list;
+/// otherwise the named approvers of all stages count, and any `Anyone` or
+/// `Role` stage leaves the list empty, i.e. unrestricted.
+fn eligible_approvers(stages: &[ApprovalStage], submitted_by: &str) -> Vec<UserId> {
+    let submitter_only = stages.iter().find(|stage| {
+        let approvers = stage.access.named_approvers();
+        !approvers.is_empty() && approvers.iter().all(|approver| approver == submitted_by)
+    });
+    if let Some(stage) = submitter_only {
+        return stage.access.named_approvers().to_vec();
+    }
+    if !stages
+        .iter()
+        .all(|stage| matches!(stage.access, StageAccess::Named(_)))
+    {
+        return Vec::new();
+    }
+    let mut approvers: Vec<UserId> = Vec::new();
+    for approver in stages
+        .iter()
+        .flat_map(|stage| stage.access.named_approvers())
+    {
+        if !approvers.contains(approver) {
+            approvers.push(approver.clone());
+        }
+    }
+    approvers
+}
+
+fn policy_approval_request(
+    proposal: &PostingProposal,
+    outcome: &EvaluationOutcome,
+    submitted_by: UserId,
+    stages: &[ApprovalStage],
+) -> ApprovalsResult<ApprovalRequest> {
+    if outcome.decision != PolicyDecision::NeedsApproval {
+        return Err(ApprovalsError::Validation(format!(
+            "proposal {} does not need approval (decision {:?})",
+            proposal.id, outcome.decision
+        )));
+    }
+    let mut request = ApprovalRequest::new(
+        proposal.company_id.clone(),
+        submitted_by,
+        format!("Approve posting proposal {}", proposal.id),
+    );
+    request.amount_minor = proposal.total_minor;
+    request.currency = proposal.currency.clone();
+    request.originating_proposal_id = Some(proposal.id.clone());
+    request.stages = stages.to_vec();
+    request.metadata = json!({
+        "proposal_id": proposal.id,
+        "triggers": outcome.triggers,
+    });
+    Ok(request)
+}
+
+/// Policy only sees `proposal`, so the entry that gets posted must be the one
+/// it describes: same company as the tenant, every line in the proposal's
+/// currency, and debits totalling `proposal.total_minor`.
+fn ensure_entry_matches_proposal(
+    proposal: &PostingProposal,
+    entry: &LedgerPostEntryParams,
+    tenant: &LedgerTenantContext,
+) -> anyhow::Result<()> {
+    if tenant.tenant_id != proposal.company_id {
+        bail!(
+            "tenant {} does not match proposal {} company {}",
+            tenant.tenant_id,
+            proposal.id,
+            proposal.company_id
+        );
+    }
+    let mut debits_minor: i64 = 0;
+    for line in &entry.entry.lines {
+        if !line.currency.code.eq_ignore_ascii_case(&proposal.currency) {
+            bail!(
+                "entry {} line {} is in {}, but proposal {} is in {}",
+                entry.entry.id,
+                line.id,
+                line.currency.code,
+                proposal.id,
+                proposal.currency
+            );
+        }
+        if line.side == LedgerPostingSide::Debit {
+            debits_minor = debits_minor
+                .checked_add(line.amount_minor)
+                .ok_or_else(|| anyhow!("entry {} debits overflow", entry.entry.id))?;
+        }
+    }
+    if debits_minor != proposal.total_minor {
+        bail!(
+            "entry {} totals {debits_minor}, but proposal {} totals {}",
+            entry.entry.id,
+            proposal.id,
+            proposal.total_minor
+        );
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubmitOutcome {
+    pub decision: PolicyDecision,
+    pub triggers: Vec<PolicyTrigger>,
+    /// Set when the decision is `NeedsApproval`; pass it to `post_approved`
+    /// once the request is approved.
+    pub approval_id: Option<ApprovalId>,
+    /// The ledger entry posted for an `AutoPost` decision.
+    pub posted_entry: Option<LedgerJournalEntry>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -89,15 +371,22 @@ pub struct ApprovalsQueueView {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::demo::DemoLedgerData;
+    use crate::demo::demo_company_tenant;
+    use crate::demo::seed_demo_ledger;
+    use codex_app_server_protocol::LedgerEntryStatus;
+    use codex_app_server_protocol::LedgerPostingMode;
     use codex_approvals::ApprovalDecision;
     use codex_approvals::ApprovalRequest;
     use codex_approvals::ApprovalStage;
     use codex_approvals::DecisionInput;
     use codex_approvals::InMemoryApprovalsService;
     use codex_approvals::QueueFilter;
+    use codex_ledger::InMemoryLedgerService;
     use codex_policy::InMemoryPolicyStore;
     use codex_policy::PolicyRuleSet;
     use pretty_assertions::assert_eq;
+    use std::collections::HashSet;
 
     #[tokio::test]
     async fn facade_lists_policies_and_queue() {
@@ -122,12 +411,8 @@ mod tests {
         let mut request =
             ApprovalRequest::new("comp-1".into(), "user-1".into(), "Multi stage".into());
         request.stages = vec![
-            ApprovalStage {
-                approvers: vec!["approver-1".into()],
-            },
-            ApprovalStage {
-                approvers: vec!["approver-2".into()],
-            },
+            ApprovalStage::named(vec!["approver-1".into()]),
+            ApprovalStage::named(vec!["approver-2".into()]),
         ];
         request.sla_at = Some(Utc::now() - chrono::Duration::minutes(10));
         approvals
@@ -168,6 +453,361 @@ mod tests {
         assert_eq!(queue_after.tasks[0].current_stage_index, 1);
     }
 
+    #[tokio::test]
+    async fn policy_approval_links_back_to_proposal() {
+        let policy_store: Arc<dyn PolicyStore> = Arc::new(InMemoryPolicyStore::new());
+        let approvals: Arc<dyn ApprovalsService> = Arc::new(InMemoryApprovalsService::new());
+        let facade = ControlsFacade::new(policy_store, approvals);
+
+        let mut proposal = PostingProposal::new("comp-1".into(), 250_000);
+        proposal.currency = "EUR".into();
+        let outcome = EvaluationOutcome {
+            decision: PolicyDecision::NeedsApproval,
+            triggers: vec![PolicyTrigger::AmountExceedsLimit {
+                limit_minor: 100_000,
+                actual_minor: 250_000,
+            }],
+        };
+        let task = facade
+            .enqueue_policy_approval(&proposal, &outcome, "controller".into())
+            .await
+            .expect("approval enqueued");
+        assert_eq!(
+            task.request.originating_proposal_id.as_deref(),
+            Some(proposal.id.as_str())
+        );
+        assert_eq!(task.request.amount_minor, 250_000);
+        assert_eq!(task.request.currency, "EUR");
+        assert_eq!(
+            task.request.metadata_field("proposal_id"),
+            Some(&serde_json::Value::String(proposal.id.clone()))
+        );
+        assert_eq!(
+            task.request.metadata_field("triggers"),
+            Some(&json!([{
+                "kind": "amount_exceeds_limit",
+                "limit_minor": 100_000,
+                "actual_minor": 250_000,
+            }]))
+        );
+
+        let err = facade
+            .enqueue_policy_approval(
+                &proposal,
+                &EvaluationOutcome {
+                    decision: PolicyDecision::AutoPost,
+                    triggers: Vec::new(),
+                },
+                "controller".into(),
+            )
+            .await
+            .expect_err("auto-post outcomes need no approval");
+        assert!(matches!(err, ApprovalsError::Validation(_)));
+    }
+
+    async fn submit_fixture(
+        rules: PolicyRuleSet,
+    ) -> (
+        ControlsFacade,
+        Arc<dyn ApprovalsService>,
+        Arc<AccountingTelemetry>,
+        DemoLedgerData,
+    ) {
+        let ledger = LedgerFacade::new(Arc::new(InMemoryLedgerService::new()));
+        let data = seed_demo_ledger(&ledger).await.expect("ledger seeded");
+        let store = Arc::new(InMemoryPolicyStore::new());
+        store
+            .put_rule_set(data.companies[0].id.clone(), rules)
+            .await
+            .expect("policy insert");
+        let policy_store: Arc<dyn PolicyStore> = store;
+        let approvals: Arc<dyn ApprovalsService> = Arc::new(InMemoryApprovalsService::new());
+        let telemetry = Arc::new(AccountingTelemetry::new());
+        let facade = ControlsFacade::with_telemetry(
+            policy_store,
+            approvals.clone(),
+            Some(telemetry.clone()),
+        )
+        .with_ledger(ledger);
+        (facade, approvals, telemetry, data)
+    }
+
+    fn draft_entry(data: &DemoLedgerData, id: &str, amount_minor: i64) -> LedgerPostEntryParams {
+        let mut entry = data.entries[0].entry.clone();
+        entry.id = id.into();
+        entry.status = LedgerEntryStatus::Draft;
+        for (index, line) in entry.lines.iter_mut().enumerate() {
+            line.id = format!("{id}-{index}");
+            line.amount_minor = amount_minor;
+            line.functional_amount_minor = amount_minor;
+        }
+        LedgerPostEntryParams {
+            entry,
+            mode: LedgerPostingMode::Commit,
+        }
+    }
+
+    #[tokio::test]
+    async fn submit_proposal_posts_or_enqueues_by_decision() {
+        let (facade, approvals, _, data) = submit_fixture(PolicyRuleSet {
+            auto_post_enabled: true,
+            auto_post_limit_minor: 100_000,
+            confidence_floor: None,
+            ..PolicyRuleSet::default()
+        })
+        .await;
+        let company_id = data.companies[0].id.clone();
+        let context = PolicyContext {
+            company_id: company_id.clone(),
+            actor: "bookkeeper".into(),
+        };
+
+        let small = facade
+            .submit_proposal(
+                context.clone(),
+                PostingProposal::new(company_id.clone(), 5_000),
+                draft_entry(&data, "je-small", 5_000),
+                demo_company_tenant(&company_id),
+            )
+            .await
+            .expect("small proposal evaluates");
+        assert_eq!(small.decision, PolicyDecision::AutoPost);
+        assert_eq!(small.approval_id, None);
+        let posted = small.posted_entry.expect("entry posted");
+        assert_eq!(posted.id, "je-small");
+        assert_eq!(posted.status, LedgerEntryStatus::Posted);
+
+        let large = PostingProposal::new(company_id.clone(), 250_000);
+        let submitted = facade
+            .submit_proposal(
+                context,
+                large.clone(),
+                draft_entry(&data, "je-large", 250_000),
+                demo_company_tenant(&company_id),
+            )
+            .await
+            .expect("large proposal evaluates");
+        assert_eq!(submitted.decision, PolicyDecision::NeedsApproval);
+        assert_eq!(submitted.posted_entry, None);
+        let approval_id = submitted.approval_id.expect("approval enqueued");
+        let task = approvals.get(&approval_id).await.expect("task stored");
+        assert_eq!(task.request.submitted_by, "bookkeeper");
+        assert_eq!(
+            task.request.originating_proposal_id.as_deref(),
+            Some(large.id.as_str())
+        );
+
+        let err = facade
+            .post_approved(&approval_id, demo_company_tenant(&company_id))
+            .await
+            .expect_err("pending approvals do not post");
+        assert!(err.to_string().contains("not approved"), "{err}");
+        approvals
+            .decide(
+                &approval_id,
+                DecisionInput {
+                    decided_by: "controller".into(),
+                    decision: ApprovalDecision::Approved,
+                    reason: None,
+                },
+            )
+            .await
+            .expect("approved");
+        let posted = facade
+            .post_approved(&approval_id, demo_company_tenant(&company_id))
+            .await
+            .expect("approved entry posts");
+        assert_eq!(posted.id, "je-large");
+        assert_eq!(posted.status, LedgerEntryStatus::Posted);
+    }
+
+    #[tokio::test]
+    async fn submit_proposal_blocks_self_approval_through_the_approval_stages() {
+        let (facade, approvals, _, data) = submit_fixture(PolicyRuleSet {
+            auto_post_enabled: true,
+            auto_post_limit_minor: 100_000,
+            confidence_floor: None,
+            prevent_self_approval: true,
+            ..PolicyRuleSet::default()
+        })
+        .await;
+        let company_id = data.companies[0].id.clone();
+        let context = PolicyContext {
+            company_id: company_id.clone(),
+            actor: "bookkeeper".into(),
+        };
+        let submit = |facade: ControlsFacade, id: &'static str| {
+            let context = context.clone();
+            let company_id = company_id.clone();
+            let entry = draft_entry(&data, id, 250_000);
+            async move {
+                facade
+                    .submit_proposal(
+                        context,
+                        PostingProposal::new(company_id.clone(), 250_000),
+                        entry,
+                        demo_company_tenant(&company_id),
+                    )
+                    .await
+                    .expect("proposal evaluates")
+            }
+        };
+
+        let self_only = facade
+            .clone()
+            .with_approval_stages(vec![ApprovalStage::named(vec!["bookkeeper".into()])]);
+        let blocked = submit(self_only, "je-self").await;
+        assert_eq!(blocked.decision, PolicyDecision::NeedsApproval);
+        assert!(
+            blocked
+                .triggers
+                .contains(&PolicyTrigger::SelfApprovalBlocked {
+                    submitted_by: "bookkeeper".into(),
+                }),
+            "{:?}",
+            blocked.triggers
+        );
+        let task = approvals
+            .get(&blocked.approval_id.expect("approval enqueued"))
+            .await
+            .expect("task stored");
+        assert_eq!(
+            task.request.stages,
+            vec![ApprovalStage::named(vec!["bookkeeper".into()])]
+        );
+
+        let with_controller = facade.with_approval_stages(vec![ApprovalStage::named(vec![
+            "bookkeeper".into(),
+            "controller".into(),
+        ])]);
+        let routed = submit(with_controller, "je-routed").await;
+        assert_eq!(routed.decision, PolicyDecision::NeedsApproval);
+        assert!(
+            !routed
+                .triggers
+                .iter()
+                .any(|trigger| matches!(trigger, PolicyTrigger::SelfApprovalBlocked { .. })),
+            "{:?}",
+            routed.triggers
+        );
+    }
+
+    #[test]
+    fn eligible_approvers_follow_the_stages() {
+        let named = |approvers: &[&str]| {
+            ApprovalStage::named(approvers.iter().map(ToString::to_string).collect())
+        };
+        assert_eq!(
+            eligible_approvers(&[named(&["a", "b"]), named(&["b", "c"])], "a"),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(
+            eligible_approvers(&[named(&["b"]), named(&["a"])], "a"),
+            vec!["a"]
+        );
+        assert!(eligible_approvers(&[named(&["a"]), ApprovalStage::anyone()], "b").is_empty());
+    }
+
+    #[tokio::test]
+    async fn submit_proposal_rejects_entries_that_differ_from_the_proposal() {
+        let (facade, approvals, _, data) = submit_fixture(PolicyRuleSet {
+            auto_post_enabled: true,
+            auto_post_limit_minor: 100_000,
+            confidence_floor: None,
+            ..PolicyRuleSet::default()
+        })
+        .await;
+        let company_id = data.companies[0].id.clone();
+        let context = PolicyContext {
+            company_id: company_id.clone(),
+            actor: "bookkeeper".into(),
+        };
+        let proposal = PostingProposal::new(company_id.clone(), 5_000);
+
+        let larger = facade
+            .submit_proposal(
+                context.clone(),
+                proposal.clone(),
+                draft_entry(&data, "je-larger", 12_500),
+                demo_company_tenant(&company_id),
+            )
+            .await
+            .expect_err("entry larger than the proposal");
+        assert!(larger.to_string().contains("totals 12500"), "{larger}");
+
+        let mut euro = draft_entry(&data, "je-euro", 5_000);
+        for line in &mut euro.entry.lines {
+            line.currency.code = "EUR".into();
+        }
+        let currency = facade
+            .submit_proposal(
+                context.clone(),
+                proposal.clone(),
+                euro,
+                demo_company_tenant(&company_id),
+            )
+            .await
+            .expect_err("entry in another currency");
+        assert!(currency.to_string().contains("EUR"), "{currency}");
+
+        let tenant = facade
+            .submit_proposal(
+                context,
+                proposal,
+                draft_entry(&data, "je-other", 5_000),
+                demo_company_tenant("other-company"),
+            )
+            .await
+            .expect_err("tenant of another company");
+        assert!(tenant.to_string().contains("other-company"), "{tenant}");
+
+        assert!(
+            approvals
+                .list(QueueFilter::default())
+                .await
+                .expect("queue lists")
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn submit_proposal_records_rejections_without_posting() {
+        let (facade, approvals, telemetry, data) = submit_fixture(PolicyRuleSet {
+            auto_post_enabled: true,
+            confidence_floor: None,
+            blocked_vendors: HashSet::from(["shell co".to_string()]),
+            ..PolicyRuleSet::default()
+        })
+        .await;
+        let company_id = data.companies[0].id.clone();
+        let mut proposal = PostingProposal::new(company_id.clone(), 5_000);
+        proposal.vendor_id = Some("Shell Co".into());
+
+        let rejected = facade
+            .submit_proposal(
+                PolicyContext {
+                    company_id: company_id.clone(),
+                    actor: "bookkeeper".into(),
+                },
+                proposal,
+                draft_entry(&data, "je-blocked", 5_000),
+                demo_company_tenant(&company_id),
+            )
+            .await
+            .expect("blocked proposal evaluates");
+        assert_eq!(rejected.decision, PolicyDecision::Reject);
+        assert_eq!(rejected.approval_id, None);
+        assert_eq!(rejected.posted_entry, None);
+        assert_eq!(telemetry.snapshot().policy_reject, 1);
+        assert!(
+            approvals
+                .list(QueueFilter::default())
+                .await
+                .expect("queue lists")
+                .is_empty()
+        );
+    }
+
     #[tokio::test]
     async fn approvals_queue_records_telemetry_snapshot() {
         let store = Arc::new(InMemoryPolicyStore::new());
@@ -188,9 +828,7 @@ mod tests {
 
         let mut overdue_request =
             ApprovalRequest::new("comp-telemetry".into(), "user-1".into(), "Overdue".into());
-        overdue_request.stages = vec![ApprovalStage {
-            approvers: vec!["approver-a".into()],
-        }];
+        overdue_request.stages = vec![ApprovalStage::named(vec!["approver-a".into()])];
         overdue_request.sla_at = Some(Utc::now() - chrono::Duration::minutes(5));
         approvals
             .enqueue(overdue_request)
@@ -199,9 +837,7 @@ mod tests {
 
         let mut fresh_request =
             ApprovalRequest::new("comp-telemetry".into(), "user-2".into(), "Fresh".into());
-        fresh_request.stages = vec![ApprovalStage {
-            approvers: vec!["approver-b".into()],
-        }];
+        fresh_request.stages = vec![ApprovalStage::named(vec!["approver-b".into()])];
         fresh_request.sla_at = Some(Utc::now() + chrono::Duration::minutes(30));
         approvals
             .enqueue(fresh_request)
@@ -1,4 +1,8 @@
+use std::collections::HashSet;
+
 use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
 use chrono::Utc;
 use codex_app_server_protocol::LedgerAccount;
 use codex_app_server_protocol::LedgerAccountType;
@@ -8,6 +12,7 @@ use codex_app_server_protocol::LedgerCreateCompanyParams;
 use codex_app_server_protocol::LedgerCurrency;
 use codex_app_server_protocol::LedgerCurrencyMode;
 use codex_app_server_protocol::LedgerCurrencyRate;
+use codex_app_server_protocol::LedgerDocumentRef;
 use codex_app_server_protocol::LedgerEntryOrigin;
 use codex_app_server_protocol::LedgerEntryStatus;
 use codex_app_server_protocol::LedgerFiscalCalendar;
@@ -31,6 +36,7 @@ use codex_app_server_protocol::LedgerRevalueCurrencyResponse;
 use codex_app_server_protocol::LedgerReverseEntryParams;
 use codex_app_server_protocol::LedgerTaxCode;
 use codex_app_server_protocol::LedgerUpsertAccountParams;
+use codex_bank_ingest::NormalizedBankTransaction;
 use codex_ledger::Account as LedgerAccountModel;
 use codex_ledger::AccountType as LedgerAccountTypeModel;
 use codex_ledger::AuditEvent as LedgerAuditEventModel;
@@ -41,6 +47,7 @@ use codex_ledger::Currency as LedgerCurrencyModel;
 use codex_ledger::CurrencyMode as LedgerCurrencyModeModel;
 use codex_ledger::CurrencyRate as LedgerCurrencyRateModel;
 use codex_ledger::CurrencyRevaluationRequest;
+use codex_ledger::DocumentRef as LedgerDocumentRefModel;
 use codex_ledger::FiscalCalendar as LedgerFiscalCalendarModel;
 use codex_ledger::Journal as LedgerJournalModel;
 use codex_ledger::JournalEntry as LedgerJournalEntryModel;
@@ -54,10 +61,14 @@ use codex_ledger::PeriodState as LedgerPeriodStateModel;
 use codex_ledger::PostEntryRequest;
 use codex_ledger::PostingMode as LedgerPostingModeModel;
 use codex_ledger::PostingSide as LedgerPostingSideModel;
+use codex_ledger::RateProvider;
 use codex_ledger::ReconciliationStatus as LedgerReconciliationStatusModel;
 use codex_ledger::ReverseEntryRequest;
 use codex_ledger::TenantContext as LedgerTenantContext;
 use codex_ledger::UpsertAccountRequest;
+use codex_reconcile::FxRate;
+use codex_reconcile::MatchProposal;
+use codex_reconcile::ScoringStrategy;
 
 pub fn to_ledger_currency(currency: LedgerCurrency) -> LedgerCurrencyModel {
     LedgerCurrencyModel {
@@ -214,6 +225,16 @@ pub fn from_ledger_currency_rate(rate: LedgerCurrencyRateModel) -> LedgerCurrenc
     }
 }
 
+pub fn to_fx_rate(rate: &LedgerCurrencyRateModel) -> FxRate {
+    FxRate {
+        base: rate.base.code.clone(),
+        base_precision: rate.base.precision,
+        quote: rate.quote.code.clone(),
+        quote_precision: rate.quote.precision,
+        rate: rate.rate,
+    }
+}
+
 pub fn to_ledger_journal_line(line: LedgerJournalLine) -> LedgerJournalLineModel {
     LedgerJournalLineModel {
         id: line.id,
@@ -312,6 +333,12 @@ pub fn to_ledger_journal_entry(entry: LedgerJournalEntry) -> LedgerJournalEntryM
         memo: entry.memo,
         reverses_entry_id: entry.reverses_entry_id,
         reversed_by_entry_id: entry.reversed_by_entry_id,
+        reconciled_amount_minor: 0,
+        attachments: entry
+            .attachments
+            .into_iter()
+            .map(to_ledger_document_ref)
+            .collect(),
     }
 }
 
@@ -330,6 +357,27 @@ pub fn from_ledger_journal_entry(entry: LedgerJournalEntryModel) -> LedgerJourna
         memo: entry.memo,
         reverses_entry_id: entry.reverses_entry_id,
         reversed_by_entry_id: entry.reversed_by_entry_id,
+        attachments: entry
+            .attachments
+            .into_iter()
+            .map(from_ledger_document_ref)
+            .collect(),
+    }
+}
+
+pub fn to_ledger_document_ref(document: LedgerDocumentRef) -> LedgerDocumentRefModel {
+    LedgerDocumentRefModel {
+        document_id: document.document_id,
+        firm_id: document.firm_id,
+        version: document.version,
+    }
+}
+
+pub fn from_ledger_document_ref(document: LedgerDocumentRefModel) -> LedgerDocumentRef {
+    LedgerDocumentRef {
+        document_id: document.document_id,
+        firm_id: document.firm_id,
+        version: document.version,
     }
 }
 
@@ -458,6 +506,7 @@ pub fn build_post_entry_request(
     PostEntryRequest {
         entry: to_ledger_journal_entry(params.entry),
         mode: to_ledger_posting_mode(params.mode),
+        idempotency_key: None,
         tenant,
     }
 }
@@ -482,6 +531,7 @@ pub fn build_lock_period_request(
         period: to_ledger_period_ref(params.period),
         action: to_ledger_period_action(params.action),
         approval_reference: params.approval_reference,
+        require_reconciled: false,
         tenant,
     }
 }
@@ -546,6 +596,160 @@ pub fn build_audit_trail_response(
     }
 }
 
+/// A ledger entry paired with the date bank lines are matched against;
+/// ledger entries do not carry a posting date of their own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatedJournalEntry {
+    pub entry: LedgerJournalEntryModel,
+    pub posted_date: NaiveDate,
+    /// The ledger account the bank statement is reconciled against; its net
+    /// debit on the entry is the cash movement bank lines are compared with.
+    pub bank_account_id: String,
+}
+
+impl DatedJournalEntry {
+    /// Net functional debit to `bank_account_id`: positive for money in,
+    /// negative for money out, `None` when the entry does not touch it.
+    fn signed_amount_minor(&self) -> Option<i64> {
+        self.entry
+            .lines
+            .iter()
+            .filter(|line| line.account_id == self.bank_account_id)
+            .map(|line| match line.side {
+                LedgerPostingSideModel::Debit => line.functional_amount_minor,
+                LedgerPostingSideModel::Credit => -line.functional_amount_minor,
+            })
+            .reduce(i64::saturating_add)
+    }
+}
+
+/// Pairs non-void bank transactions with the entries that `strategy` scores
+/// highest, best pairs first, so each transaction and each entry appears in
+/// at most one proposal. Reconciled and written-off entries are skipped.
+/// Deltas are bank minus ledger: the amount delta compares the signed bank
+/// amount with the entry's signed cash movement, and the date delta is in
+/// days. Cross-currency pairs are converted with the latest rate `rates`
+/// holds for the bank line's date; without one they are left unconverted and
+/// flagged by [`MatchProposal::needs_fx_review`]. Proposals are returned in
+/// transaction order.
+pub fn proposals_from(
+    transactions: &[NormalizedBankTransaction],
+    entries: &[DatedJournalEntry],
+    strategy: &dyn ScoringStrategy,
+    rates: Option<&dyn RateProvider>,
+) -> Vec<MatchProposal> {
+    let open_entries: Vec<(usize, &DatedJournalEntry)> = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, dated)| {
+            !matches!(
+                dated.entry.reconciliation_status,
+                LedgerReconciliationStatusModel::Reconciled { .. }
+                    | LedgerReconciliationStatusModel::WriteOff { .. }
+            )
+        })
+        .collect();
+    let mut scored: Vec<(f32, usize, usize, MatchProposal)> = transactions
+        .iter()
+        .enumerate()
+        .filter(|(_, transaction)| !transaction.is_void)
+        .flat_map(|(transaction_index, transaction)| {
+            open_entries.iter().filter_map(move |(entry_index, dated)| {
+                proposal_for(transaction, dated, rates)
+                    .map(|proposal| (transaction_index, *entry_index, proposal))
+            })
+        })
+        .map(|(transaction_index, entry_index, proposal)| {
+            (
+                strategy.score(&proposal),
+                transaction_index,
+                entry_index,
+                proposal,
+            )
+        })
+        .collect();
+    scored.sort_by(|left, right| right.0.total_cmp(&left.0));
+
+    let mut matched_transactions = HashSet::new();
+    let mut matched_entries = HashSet::new();
+    let mut chosen: Vec<(usize, MatchProposal)> = Vec::new();
+    for (_, transaction_index, entry_index, proposal) in scored {
+        if matched_transactions.contains(&transaction_index)
+            || matched_entries.contains(&entry_index)
+        {
+            continue;
+        }
+        matched_transactions.insert(transaction_index);
+        matched_entries.insert(entry_index);
+        chosen.push((transaction_index, proposal));
+    }
+    chosen.sort_by_key(|(transaction_index, _)| *transaction_index);
+    chosen.into_iter().map(|(_, proposal)| proposal).collect()
+}
+
+fn proposal_for(
+    transaction: &NormalizedBankTransaction,
+    dated: &DatedJournalEntry,
+    rates: Option<&dyn RateProvider>,
+) -> Option<MatchProposal> {
+    let currency = &dated.entry.lines.first()?.functional_currency;
+    let journal_amount_minor = dated.signed_amount_minor()?;
+    let journal_description = dated
+        .entry
+        .memo
+        .clone()
+        .or_else(|| dated.entry.lines.iter().find_map(|line| line.memo.clone()))
+        .unwrap_or_default();
+    let proposal = MatchProposal {
+        transaction_id: transaction.transaction_id.clone(),
+        journal_entry_id: dated.entry.id.clone(),
+        amount_delta_minor: transaction
+            .amount_minor
+            .saturating_sub(journal_amount_minor),
+        date_delta_days: (transaction.posted_date - dated.posted_date).num_days(),
+        transaction_description: transaction.description.clone(),
+        journal_description,
+        group_id: None,
+        currency: Some(currency.code.clone()),
+        currency_precision: Some(currency.precision),
+        transaction_currency: Some(transaction.currency.clone()),
+        journal_currency: Some(currency.code.clone()),
+        transaction_amount_minor: Some(transaction.amount_minor),
+        journal_amount_minor: Some(journal_amount_minor),
+        proposed_candidate_id: None,
+        ..MatchProposal::default()
+    };
+    if !proposal.is_cross_currency() {
+        return Some(proposal);
+    }
+    // Rates are keyed by currency code; the bank line's precision is unknown
+    // here and comes from the stored rate instead.
+    let bank_currency = LedgerCurrencyModel {
+        code: transaction.currency.clone(),
+        precision: currency.precision,
+    };
+    let as_of = transaction
+        .posted_date
+        .succ_opt()
+        .unwrap_or(transaction.posted_date)
+        .and_time(NaiveTime::MIN)
+        .and_utc();
+    let rate = rates.and_then(|rates| {
+        rates
+            .rate(&bank_currency, currency, as_of.into())
+            .or_else(|| rates.rate(currency, &bank_currency, as_of.into()))
+    });
+    match rate {
+        Some(rate) => Some(
+            proposal
+                .clone()
+                .with_fx_rate(&to_fx_rate(&rate))
+                .unwrap_or(proposal),
+        ),
+        None => Some(proposal),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -574,6 +778,31 @@ mod tests {
         assert_eq!(account, roundtrip);
     }
 
+    #[test]
+    fn converts_journal_entry_attachments_roundtrip() {
+        let entry = LedgerJournalEntry {
+            id: "je-1".into(),
+            journal_id: "jnl-gl".into(),
+            status: LedgerEntryStatus::Draft,
+            reconciliation_status: LedgerReconciliationStatus::Unreconciled,
+            lines: Vec::new(),
+            origin: LedgerEntryOrigin::Ingestion,
+            memo: None,
+            reverses_entry_id: None,
+            reversed_by_entry_id: None,
+            attachments: vec![LedgerDocumentRef {
+                document_id: "doc-invoice".into(),
+                firm_id: "firm-1".into(),
+                version: 2,
+            }],
+        };
+
+        let converted = to_ledger_journal_entry(entry.clone());
+        assert_eq!(converted.attachments[0].document_id, "doc-invoice");
+        assert_eq!(converted.attachments[0].version, 2);
+        assert_eq!(from_ledger_journal_entry(converted), entry);
+    }
+
     #[test]
     fn builds_lock_period_request() {
         let params = LedgerLockPeriodParams {
@@ -694,6 +923,8 @@ mod tests {
             memo: None,
             reverses_entry_id: None,
             reversed_by_entry_id: None,
+            reconciled_amount_minor: 0,
+            attachments: Vec::new(),
         };
 
         let response = build_revalue_currency_response(vec![entry], Some("je-1".into()));
@@ -718,4 +949,173 @@ mod tests {
         assert_eq!(response.events[0].description, "Updated period state");
         assert_eq!(response.next_cursor, None);
     }
+
+    fn bank_line(
+        id: &str,
+        amount_minor: i64,
+        day: u32,
+        description: &str,
+    ) -> NormalizedBankTransaction {
+        NormalizedBankTransaction {
+            transaction_id: id.into(),
+            account_id: "operating-cash".into(),
+            posted_date: NaiveDate::from_ymd_opt(2025, 3, day).expect("valid date"),
+            amount_minor,
+            currency: "USD".into(),
+            description: description.into(),
+            source_reference: None,
+            source_checksum: None,
+            is_void: false,
+            checksum_computed: false,
+            duplicate_metadata: Default::default(),
+            currency_validation: Default::default(),
+            institution: None,
+        }
+    }
+
+    /// An entry moving `amount_minor` through `cash`: positive amounts debit
+    /// it (money in), negative amounts credit it (money out).
+    fn dated_entry(id: &str, amount_minor: i64, day: u32, memo: &str) -> DatedJournalEntry {
+        let usd = LedgerCurrencyModel {
+            code: "USD".into(),
+            precision: 2,
+        };
+        let (cash_side, other_side) = if amount_minor < 0 {
+            (
+                LedgerPostingSideModel::Credit,
+                LedgerPostingSideModel::Debit,
+            )
+        } else {
+            (
+                LedgerPostingSideModel::Debit,
+                LedgerPostingSideModel::Credit,
+            )
+        };
+        let line = |side, account_id: &str| LedgerJournalLineModel {
+            id: format!("{id}-{account_id}"),
+            account_id: account_id.into(),
+            side,
+            amount_minor: amount_minor.abs(),
+            currency: usd.clone(),
+            functional_amount_minor: amount_minor.abs(),
+            functional_currency: usd.clone(),
+            exchange_rate: None,
+            tax_code: None,
+            memo: None,
+        };
+        DatedJournalEntry {
+            entry: LedgerJournalEntryModel {
+                id: id.into(),
+                journal_id: "jnl-gl".into(),
+                status: codex_ledger::EntryStatus::Posted,
+                reconciliation_status: codex_ledger::ReconciliationStatus::Unreconciled,
+                lines: vec![line(cash_side, "cash"), line(other_side, "offset")],
+                origin: codex_ledger::EntryOrigin::Manual,
+                memo: Some(memo.into()),
+                reverses_entry_id: None,
+                reversed_by_entry_id: None,
+                reconciled_amount_minor: 0,
+                attachments: Vec::new(),
+            },
+            posted_date: NaiveDate::from_ymd_opt(2025, 3, day).expect("valid date"),
+            bank_account_id: "cash".into(),
+        }
+    }
+
+    #[test]
+    fn proposals_from_pairs_exact_and_near_matches() {
+        let transactions = vec![
+            bank_line("txn-exact", 12_500, 10, "Stripe payout"),
+            bank_line("txn-near", -4_150, 14, "Utility payment March"),
+        ];
+        let entries = vec![
+            dated_entry("je-stripe", 12_500, 10, "Stripe payout"),
+            dated_entry("je-utility", -4_200, 12, "Utility payment"),
+        ];
+
+        let proposals = proposals_from(
+            &transactions,
+            &entries,
+            &codex_reconcile::LinearScoringStrategy::new(),
+            None,
+        );
+        assert_eq!(proposals.len(), 2);
+
+        let exact = &proposals[0];
+        assert_eq!(exact.transaction_id, "txn-exact");
+        assert_eq!(exact.journal_entry_id, "je-stripe");
+        assert_eq!(exact.amount_delta_minor, 0);
+        assert_eq!(exact.date_delta_days, 0);
+        assert_eq!(exact.currency_precision, Some(2));
+
+        let near = &proposals[1];
+        assert_eq!(near.transaction_id, "txn-near");
+        assert_eq!(near.journal_entry_id, "je-utility");
+        assert_eq!(near.amount_delta_minor, 50);
+        assert_eq!(near.date_delta_days, 2);
+        assert_eq!(near.journal_description, "Utility payment");
+    }
+
+    #[test]
+    fn proposals_from_respects_direction_and_proposes_each_entry_once() {
+        let strategy = codex_reconcile::LinearScoringStrategy::new();
+        let refund = vec![bank_line("txn-refund", 4_200, 12, "Utility refund")];
+        let payment = vec![dated_entry("je-utility", -4_200, 12, "Utility payment")];
+        let proposals = proposals_from(&refund, &payment, &strategy, None);
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].amount_delta_minor, 8_400);
+
+        let transactions = vec![
+            bank_line("txn-early", 12_400, 8, "Stripe payout"),
+            bank_line("txn-exact", 12_500, 10, "Stripe payout"),
+        ];
+        let mut reconciled = dated_entry("je-old", 12_500, 10, "Stripe payout");
+        reconciled.entry.reconciliation_status = codex_ledger::ReconciliationStatus::Reconciled {
+            session_id: "sess-1".into(),
+        };
+        let entries = vec![
+            reconciled,
+            dated_entry("je-stripe", 12_500, 10, "Stripe payout"),
+        ];
+        let proposals = proposals_from(&transactions, &entries, &strategy, None);
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].transaction_id, "txn-exact");
+        assert_eq!(proposals[0].journal_entry_id, "je-stripe");
+    }
+
+    #[test]
+    fn proposals_from_converts_cross_currency_pairs_when_a_rate_exists() {
+        let mut euro_line = bank_line("txn-eur", 10_000, 10, "Supplier refund");
+        euro_line.currency = "EUR".into();
+        let entries = vec![dated_entry("je-refund", 11_000, 10, "Supplier refund")];
+        let strategy = codex_reconcile::LinearScoringStrategy::new();
+
+        let unconverted =
+            proposals_from(std::slice::from_ref(&euro_line), &entries, &strategy, None);
+        assert_eq!(unconverted.len(), 1);
+        assert!(unconverted[0].needs_fx_review());
+
+        let rates = codex_ledger::InMemoryRateStore::new();
+        rates
+            .insert(LedgerCurrencyRateModel {
+                base: LedgerCurrencyModel {
+                    code: "EUR".into(),
+                    precision: 2,
+                },
+                quote: LedgerCurrencyModel {
+                    code: "USD".into(),
+                    precision: 2,
+                },
+                rate: 1.1,
+                source: Some("ecb".into()),
+                observed_at: SystemTime::UNIX_EPOCH,
+            })
+            .expect("rate stored");
+        let converted = proposals_from(&[euro_line], &entries, &strategy, Some(&rates));
+        assert_eq!(converted.len(), 1);
+        assert!(!converted[0].needs_fx_review());
+        assert_eq!(converted[0].fx_rate, Some(1.1));
+        assert_eq!(converted[0].amount_delta_minor, 0);
+        assert_eq!(converted[0].currency.as_deref(), Some("USD"));
+    }
 }
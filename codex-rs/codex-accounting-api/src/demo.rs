@@ -38,10 +38,13 @@ use codex_policy::InMemoryPolicyStore;
 use codex_policy::PolicyRuleSet;
 use codex_policy::PolicyStore;
 use codex_reconcile::InMemoryReconciliationService;
+use codex_reconcile::JsonlReconciliationAuditHook;
 use codex_reconcile::LinearScoringStrategy;
+use codex_reconcile::MatchCandidate;
 use codex_reconcile::MatchProposal;
 use codex_reconcile::ReconciliationService;
 use codex_reconcile::SessionId;
+use tracing::warn;
 
 use crate::AccountingTelemetry;
 use crate::LedgerFacade;
@@ -51,6 +54,7 @@ use crate::controls::ApprovalsQueueView;
 use crate::controls::ControlsFacade;
 use crate::reconciliation::InMemoryBankTransactionSource;
 use crate::reconciliation::InMemoryReconciliationSummaryProvider;
+use crate::reconciliation::reconciliation_audit_log_path;
 
 const DEMO_ADMIN_TENANT: &str = "ledger-admin";
 const DEMO_USER_ID: &str = "codex-ledger-demo";
@@ -214,6 +218,7 @@ pub async fn seed_demo_ledger(facade: &LedgerFacade) -> LedgerResult<DemoLedgerD
         memo: Some("Starter transaction posted by Codex CLI".to_string()),
         reverses_entry_id: None,
         reversed_by_entry_id: None,
+        attachments: Vec::new(),
     };
 
     let company_tenant = demo_company_tenant(&company_id);
@@ -245,7 +250,44 @@ pub async fn seed_demo_ledger_with_service(
     seed_demo_ledger(&facade).await
 }
 
+/// Seeds a fresh demo context. Session, candidate, and approval ids are
+/// random, so every call looks like a new firm.
 pub async fn seed_demo_reconciliation() -> Result<DemoReconciliationContext> {
+    build_demo_reconciliation(None).await
+}
+
+/// Seeds the same demo context with ids derived from `seed`: calls with the
+/// same seed produce identical ids and amounts, so repeated snapshots line up.
+/// Timestamps stay relative to now.
+pub async fn seed_demo_reconciliation_with(seed: u64) -> Result<DemoReconciliationContext> {
+    build_demo_reconciliation(Some(seed)).await
+}
+
+/// Moves a demo context forward one step by writing off the session's best
+/// pending candidate, which resolves it without closing the session, so a feed
+/// built from one context shows reconciliation progressing. Returns the
+/// written-off candidate, or `None` once nothing is pending.
+pub fn advance_demo_reconciliation(
+    context: &DemoReconciliationContext,
+) -> Result<Option<MatchCandidate>> {
+    let session = context
+        .reconciliation_service
+        .session(&context.session_id)
+        .map_err(|err| anyhow!(err))?;
+    let Some(candidate) = session.best_candidate() else {
+        return Ok(None);
+    };
+    context
+        .facade
+        .write_off_candidate(
+            &context.session_id,
+            &candidate.id,
+            &format!("APR-DEMO-{}", candidate.transaction_id),
+        )
+        .map(Some)
+}
+
+async fn build_demo_reconciliation(seed: Option<u64>) -> Result<DemoReconciliationContext> {
     let ledger_service: Arc<dyn LedgerService> = Arc::new(InMemoryLedgerService::new());
     let ledger = seed_demo_ledger_with_service(ledger_service.clone())
         .await
@@ -271,8 +313,10 @@ pub async fn seed_demo_reconciliation() -> Result<DemoReconciliationContext> {
                 source_reference: Some(reference.to_string()),
                 source_checksum: None,
                 is_void: false,
+                checksum_computed: false,
                 duplicate_metadata: Default::default(),
                 currency_validation: Default::default(),
+                institution: None,
             }
         };
     let raw_transactions = vec![
@@ -292,9 +336,23 @@ pub async fn seed_demo_reconciliation() -> Result<DemoReconciliationContext> {
         last_ingest_at: Utc::now() - Duration::hours(3),
     };
 
-    let reconciliation_service = Arc::new(InMemoryReconciliationService::new(Arc::new(
-        LinearScoringStrategy::new(),
-    )));
+    let mut reconciliation_service =
+        InMemoryReconciliationService::new(Arc::new(LinearScoringStrategy::new()));
+    if let Some(seed) = seed {
+        reconciliation_service =
+            reconciliation_service.with_session_id_prefix(format!("demo-session-{seed:x}"));
+    }
+    let reconciliation_service = Arc::new(reconciliation_service);
+    if let Some(path) = reconciliation_audit_log_path() {
+        match JsonlReconciliationAuditHook::new(&path) {
+            Ok(hook) => reconciliation_service.register_audit_hook(Arc::new(hook)),
+            Err(err) => warn!(
+                path = %path.display(),
+                error = %err,
+                "failed to open reconciliation audit log; continuing without it"
+            ),
+        }
+    }
     let summary_provider = Arc::new(InMemoryReconciliationSummaryProvider::new());
     let facade = ReconciliationFacade::with_summary_and_telemetry(
         transactions_source.clone(),
@@ -325,6 +383,10 @@ pub async fn seed_demo_reconciliation() -> Result<DemoReconciliationContext> {
         transaction_description: txn_desc.to_string(),
         journal_description: journal_desc.to_string(),
         group_id: group_id.map(std::string::ToString::to_string),
+        currency: Some("USD".to_string()),
+        currency_precision: Some(2),
+        proposed_candidate_id: seed.map(|seed| format!("demo-candidate-{seed:x}-{txn_id}")),
+        ..MatchProposal::default()
     };
 
     let _primary = reconciliation_service
@@ -380,7 +442,12 @@ pub async fn seed_demo_reconciliation() -> Result<DemoReconciliationContext> {
         )
         .map_err(|err| anyhow!(err))?;
     reconciliation_service
-        .write_off(&session_id, &write_off.id, "APR-UTILITY-ADJ".to_string())
+        .write_off(
+            &session_id,
+            &write_off.id,
+            "APR-UTILITY-ADJ".to_string(),
+            Some("APR-UTILITY-ADJ".to_string()),
+        )
         .map_err(|err| anyhow!(err))?;
     let _pending = reconciliation_service
         .add_candidate(
@@ -415,12 +482,13 @@ pub async fn seed_demo_reconciliation() -> Result<DemoReconciliationContext> {
         "ops-user".into(),
         "Write-off approval".into(),
     );
+    if let Some(seed) = seed {
+        overdue_request.id = format!("demo-approval-{seed:x}-write-off");
+    }
     overdue_request.amount_minor = 8_100;
     overdue_request.priority = ApprovalPriority::High;
     overdue_request.sla_at = Some(Utc::now() - Duration::hours(6));
-    overdue_request.stages = vec![ApprovalStage {
-        approvers: vec!["approver-finance".into()],
-    }];
+    overdue_request.stages = vec![ApprovalStage::named(vec!["approver-finance".into()])];
     approvals_service
         .enqueue(overdue_request)
         .await
@@ -430,12 +498,13 @@ pub async fn seed_demo_reconciliation() -> Result<DemoReconciliationContext> {
         "ops-user".into(),
         "Monthly reconciliation sign-off".into(),
     );
+    if let Some(seed) = seed {
+        upcoming_request.id = format!("demo-approval-{seed:x}-sign-off");
+    }
     upcoming_request.amount_minor = 5_000;
     upcoming_request.priority = ApprovalPriority::Normal;
     upcoming_request.sla_at = Some(Utc::now() + Duration::hours(4));
-    upcoming_request.stages = vec![ApprovalStage {
-        approvers: vec!["approver-controller".into()],
-    }];
+    upcoming_request.stages = vec![ApprovalStage::named(vec!["approver-controller".into()])];
     approvals_service
         .enqueue(upcoming_request)
         .await
@@ -445,7 +514,11 @@ pub async fn seed_demo_reconciliation() -> Result<DemoReconciliationContext> {
         policy_store,
         approvals_service.clone(),
         Some(telemetry.clone()),
-    );
+    )
+    .with_ledger(LedgerFacade::with_telemetry(
+        ledger_service,
+        Some(telemetry.clone()),
+    ));
     let approvals_view = approvals_facade
         .approvals_queue(QueueFilter {
             company_id: Some(company_id.clone()),
@@ -475,6 +548,7 @@ mod tests {
     use super::*;
     use codex_ledger::InMemoryLedgerService;
     use codex_ledger::LedgerError;
+    use codex_reconcile::CandidateStatus;
     use pretty_assertions::assert_eq;
     use tokio::runtime::Runtime;
 
@@ -523,6 +597,15 @@ mod tests {
                 unreachable!("not called")
             }
 
+            async fn deactivate_account(
+                &self,
+                _company_id: &codex_ledger::CompanyId,
+                _account_id: &codex_ledger::AccountId,
+                _tenant: codex_ledger::TenantContext,
+            ) -> LedgerResult<codex_ledger::Account> {
+                unreachable!("not called")
+            }
+
             async fn seed_chart(
                 &self,
                 _request: codex_ledger::SeedChartRequest,
@@ -533,7 +616,7 @@ mod tests {
             async fn post_entry(
                 &self,
                 _request: codex_ledger::PostEntryRequest,
-            ) -> LedgerResult<codex_ledger::JournalEntry> {
+            ) -> LedgerResult<codex_ledger::PostEntryOutcome> {
                 unreachable!("not called")
             }
 
@@ -544,6 +627,13 @@ mod tests {
                 unreachable!("not called")
             }
 
+            async fn mark_entry_reconciled(
+                &self,
+                _request: codex_ledger::MarkReconciledRequest,
+            ) -> LedgerResult<codex_ledger::JournalEntry> {
+                unreachable!("not called")
+            }
+
             async fn lock_period(
                 &self,
                 _request: codex_ledger::LockPeriodRequest,
@@ -571,6 +661,23 @@ mod tests {
             ) -> LedgerResult<Vec<codex_ledger::AuditEvent>> {
                 unreachable!("not called")
             }
+
+            async fn list_entries(
+                &self,
+                _company_id: &codex_ledger::CompanyId,
+                _filter: codex_ledger::EntryQuery,
+            ) -> LedgerResult<Vec<codex_ledger::JournalEntry>> {
+                unreachable!("not called")
+            }
+
+            async fn search_entries(
+                &self,
+                _company_id: &codex_ledger::CompanyId,
+                _query: &str,
+                _limit: usize,
+            ) -> LedgerResult<Vec<codex_ledger::JournalEntry>> {
+                unreachable!("not called")
+            }
         }
 
         let runtime = Runtime::new().expect("runtime");
@@ -606,4 +713,62 @@ mod tests {
         let counters = context.telemetry.snapshot();
         assert!(counters.reconciliation_candidates >= candidates.len());
     }
+
+    #[tokio::test]
+    async fn seeded_demo_reconciliation_is_reproducible() {
+        let fingerprint = |context: &DemoReconciliationContext| {
+            let candidates = context
+                .facade
+                .list_candidates(&context.session_id)
+                .expect("candidates listed")
+                .into_iter()
+                .map(|candidate| candidate.id)
+                .collect::<Vec<_>>();
+            let mut approvals = context
+                .approvals_view
+                .tasks
+                .iter()
+                .map(|task| (task.request.id.clone(), task.request.amount_minor))
+                .collect::<Vec<_>>();
+            approvals.sort();
+            (
+                context.company_id.clone(),
+                context.session_id.clone(),
+                candidates,
+                approvals,
+            )
+        };
+
+        let first = seed_demo_reconciliation_with(7).await.expect("first seed");
+        let second = seed_demo_reconciliation_with(7).await.expect("second seed");
+        assert_eq!(fingerprint(&first), fingerprint(&second));
+        assert_eq!(first.session_id, "demo-session-7-1");
+
+        let other = seed_demo_reconciliation_with(8).await.expect("other seed");
+        assert_ne!(fingerprint(&first).1, fingerprint(&other).1);
+    }
+
+    #[tokio::test]
+    async fn advancing_demo_reconciliation_resolves_pending_candidates() {
+        let context = seed_demo_reconciliation_with(7).await.expect("seeded");
+        let coverage = || {
+            context
+                .facade
+                .coverage(&context.session_id)
+                .expect("coverage")
+        };
+        let start = coverage();
+        assert!(start.pending > 0);
+
+        let mut accepted = Vec::new();
+        while let Some(candidate) = advance_demo_reconciliation(&context).expect("advanced") {
+            assert_eq!(candidate.status, CandidateStatus::WrittenOff);
+            accepted.push(candidate.id);
+            let now = coverage();
+            assert_eq!(now.pending, start.pending - accepted.len());
+            assert_eq!(now.matched(), start.matched() + accepted.len());
+        }
+        assert_eq!(accepted.len(), start.pending);
+        assert_eq!(coverage().pending, 0);
+    }
 }
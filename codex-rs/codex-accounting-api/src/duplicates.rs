@@ -39,12 +39,14 @@ mod tests {
             source_reference: reference.map(std::string::ToString::to_string),
             source_checksum: None,
             is_void: false,
+            checksum_computed: false,
             duplicate_metadata: DuplicateMetadata {
                 group_key: group.map(std::string::ToString::to_string),
                 total_occurrences: total,
                 discarded_ids: Vec::new(),
             },
             currency_validation: Default::default(),
+            institution: None,
         }
     }
 
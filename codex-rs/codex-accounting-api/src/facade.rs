@@ -15,6 +15,7 @@ use codex_app_server_protocol::LedgerReverseEntryResponse;
 use codex_app_server_protocol::LedgerUpsertAccountParams;
 use codex_app_server_protocol::LedgerUpsertAccountResponse;
 use codex_ledger::Account;
+use codex_ledger::ChartTypeWarning;
 use codex_ledger::EnsurePeriodRequest;
 use codex_ledger::Journal;
 use codex_ledger::LedgerError;
@@ -38,6 +39,14 @@ use crate::convert::from_ledger_company;
 use crate::convert::from_ledger_journal;
 use crate::convert::from_ledger_journal_entry;
 
+/// Accounts created by `LedgerFacade::seed_chart`, plus the accounts whose
+/// supplied type contradicts their code range.
+#[derive(Debug, Clone)]
+pub struct SeededChart {
+    pub accounts: Vec<Account>,
+    pub warnings: Vec<ChartTypeWarning>,
+}
+
 #[derive(Clone)]
 pub struct LedgerFacade {
     service: Arc<dyn LedgerService>,
@@ -93,13 +102,17 @@ impl LedgerFacade {
         self.service
             .post_entry(request)
             .await
-            .map(|entry| LedgerPostEntryResponse {
-                entry: from_ledger_journal_entry(entry),
+            .map(|outcome| LedgerPostEntryResponse {
+                entry: from_ledger_journal_entry(outcome.entry),
             })
     }
 
-    pub async fn seed_chart(&self, request: SeedChartRequest) -> LedgerResult<Vec<Account>> {
-        self.service.seed_chart(request).await
+    /// Seeds the chart and reports `SeedChartRequest::validate` warnings;
+    /// mistyped accounts are still created.
+    pub async fn seed_chart(&self, request: SeedChartRequest) -> LedgerResult<SeededChart> {
+        let warnings = request.validate();
+        let accounts = self.service.seed_chart(request).await?;
+        Ok(SeededChart { accounts, warnings })
     }
 
     pub async fn ensure_period(&self, request: EnsurePeriodRequest) -> LedgerResult<Journal> {
@@ -353,6 +366,7 @@ mod tests {
                             memo: Some("Demo sale".into()),
                             reverses_entry_id: None,
                             reversed_by_entry_id: None,
+                            attachments: Vec::new(),
                         },
                         mode: LedgerPostingMode::Commit,
                     },
@@ -525,7 +539,7 @@ mod tests {
             let company_id = company.company.id.clone();
             let tenant = demo_tenant(&company_id);
 
-            let accounts = facade
+            let seeded = facade
                 .seed_chart(SeedChartRequest {
                     company_id: company_id.clone(),
                     tenant: tenant.clone(),
@@ -548,12 +562,29 @@ mod tests {
                             tax_code: None,
                             is_summary: false,
                         },
+                        ChartAccount {
+                            code: "4000".into(),
+                            name: "Sales".into(),
+                            account_type: AccountType::Expense,
+                            parent_code: None,
+                            currency_mode: CoreCurrencyMode::FunctionalOnly,
+                            tax_code: None,
+                            is_summary: false,
+                        },
                     ],
                 })
                 .await
                 .expect("seed chart");
 
-            assert_eq!(accounts.len(), 2);
+            assert_eq!(seeded.accounts.len(), 3);
+            assert_eq!(
+                seeded.warnings,
+                vec![ChartTypeWarning {
+                    code: "4000".into(),
+                    supplied: AccountType::Expense,
+                    inferred: AccountType::Revenue,
+                }]
+            );
 
             let journal = facade
                 .ensure_period(EnsurePeriodRequest {
@@ -13,8 +13,10 @@ mod tenancy;
 pub use controls::ApprovalsQueueView;
 pub use controls::ControlsFacade;
 pub use controls::PolicyRuleSetView;
+pub use controls::SubmitOutcome;
 pub use duplicates::duplicate_set_labels;
 pub use facade::LedgerFacade;
+pub use facade::SeededChart;
 pub use reconciliation::BankTransactionSource;
 pub use reconciliation::InMemoryBankTransactionSource;
 pub use reconciliation::InMemoryReconciliationSummaryProvider;
@@ -22,6 +24,7 @@ pub use reconciliation::NullReconciliationSummaryProvider;
 pub use reconciliation::ReconciliationFacade;
 pub use reconciliation::ReconciliationSummary;
 pub use reconciliation::ReconciliationSummaryProvider;
+pub use reconciliation::reconciliation_audit_log_path;
 pub use telemetry::AccountingTelemetry;
 pub use telemetry::TelemetryCounters;
 pub use telemetry::TelemetryPolicyEventSink;
@@ -1,4 +1,6 @@
 use std::collections::HashMap;
+use std::env;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::RwLock;
 
@@ -6,13 +8,31 @@ use chrono::DateTime;
 use chrono::Utc;
 use codex_bank_ingest::NormalizedBankTransaction;
 use codex_ledger::CompanyId;
+use codex_ledger::LedgerService;
+use codex_ledger::MarkReconciledRequest;
+use codex_ledger::TenantContext;
 use codex_reconcile::CandidateId;
+use codex_reconcile::CandidateStatus;
+use codex_reconcile::Coverage;
 use codex_reconcile::MatchCandidate;
 use codex_reconcile::ReconciliationService;
+use codex_reconcile::ReconciliationSession;
 use codex_reconcile::SessionId;
+use codex_reconcile::SessionStatus;
 
 use crate::AccountingTelemetry;
 
+/// Location of the persisted reconciliation audit trail
+/// (`$CODEX_HOME/accounting/reconciliation-audit.jsonl`), if `CODEX_HOME` is set.
+#[must_use]
+pub fn reconciliation_audit_log_path() -> Option<PathBuf> {
+    let home = env::var_os("CODEX_HOME")?;
+    let mut path = PathBuf::from(home);
+    path.push("accounting");
+    path.push("reconciliation-audit.jsonl");
+    Some(path)
+}
+
 /// Summary response for reconciliation dashboards.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ReconciliationSummary {
@@ -131,6 +151,7 @@ pub struct ReconciliationFacade {
     service: Arc<dyn ReconciliationService>,
     summary: Arc<dyn ReconciliationSummaryProvider>,
     telemetry: Option<Arc<AccountingTelemetry>>,
+    ledger: Option<Arc<dyn LedgerService>>,
 }
 
 impl ReconciliationFacade {
@@ -165,9 +186,17 @@ impl ReconciliationFacade {
             service,
             summary,
             telemetry,
+            ledger: None,
         }
     }
 
+    /// Ledger whose entries `accept_candidate` marks reconciled.
+    #[must_use]
+    pub fn with_ledger(mut self, ledger: Arc<dyn LedgerService>) -> Self {
+        self.ledger = Some(ledger);
+        self
+    }
+
     pub fn list_transactions(
         &self,
         company_id: &CompanyId,
@@ -190,6 +219,64 @@ impl ReconciliationFacade {
         Ok(session.candidates)
     }
 
+    /// Accepts a candidate and marks its journal entry reconciled in the
+    /// configured ledger. The ledger is updated first, after checking that the
+    /// candidate can still be accepted, so a ledger failure leaves the session
+    /// untouched and fires no acceptance notifications. Without a ledger only
+    /// the reconciliation side changes.
+    pub async fn accept_candidate(
+        &self,
+        session_id: &SessionId,
+        candidate_id: &CandidateId,
+        tenant: TenantContext,
+    ) -> anyhow::Result<MatchCandidate> {
+        let Some(ledger) = self.ledger.as_ref() else {
+            return self
+                .service
+                .accept(session_id, candidate_id)
+                .map_err(|err| anyhow::anyhow!(err));
+        };
+        let session = self
+            .service
+            .session(session_id)
+            .map_err(|err| anyhow::anyhow!(err))?;
+        if matches!(session.status, SessionStatus::Closed) {
+            anyhow::bail!("session {session_id} is closed");
+        }
+        let pending = session
+            .candidates
+            .iter()
+            .find(|candidate| &candidate.id == candidate_id)
+            .ok_or_else(|| anyhow::anyhow!("candidate {candidate_id} not found"))?;
+        if !matches!(
+            pending.status,
+            CandidateStatus::Pending | CandidateStatus::PartiallyAccepted
+        ) {
+            anyhow::bail!("candidate {candidate_id} is not pending");
+        }
+        ledger
+            .mark_entry_reconciled(MarkReconciledRequest {
+                entry_id: pending.journal_entry_id.clone(),
+                session_id: session_id.clone(),
+                tenant,
+            })
+            .await
+            .map_err(|err| {
+                anyhow::anyhow!(
+                    "failed to mark entry {} reconciled; candidate left pending: {err}",
+                    pending.journal_entry_id
+                )
+            })?;
+        self.service
+            .accept(session_id, candidate_id)
+            .map_err(|err| {
+                anyhow::anyhow!(
+                    "entry {} was marked reconciled, but accepting candidate {candidate_id} failed: {err}",
+                    pending.journal_entry_id
+                )
+            })
+    }
+
     pub fn write_off_candidate(
         &self,
         session_id: &SessionId,
@@ -201,7 +288,12 @@ impl ReconciliationFacade {
         }
         let candidate = self
             .service
-            .write_off(session_id, candidate_id, approval_reference.to_string())
+            .write_off(
+                session_id,
+                candidate_id,
+                approval_reference.to_string(),
+                Some(approval_reference.to_string()),
+            )
             .map_err(|err| anyhow::anyhow!(err))?;
         if let Some(telemetry) = self.telemetry.as_ref() {
             telemetry.record_write_off();
@@ -209,16 +301,53 @@ impl ReconciliationFacade {
         Ok(candidate)
     }
 
+    pub fn reject_candidate(
+        &self,
+        session_id: &SessionId,
+        candidate_id: &CandidateId,
+        reason: Option<String>,
+    ) -> anyhow::Result<MatchCandidate> {
+        let candidate = match reason {
+            Some(reason) => self
+                .service
+                .reject_with_reason(session_id, candidate_id, reason),
+            None => self.service.reject(session_id, candidate_id),
+        }
+        .map_err(|err| anyhow::anyhow!(err))?;
+        if let Some(telemetry) = self.telemetry.as_ref() {
+            telemetry.record_rejection();
+        }
+        Ok(candidate)
+    }
+
+    pub fn reopen_session(&self, session_id: &SessionId) -> anyhow::Result<ReconciliationSession> {
+        let session = self
+            .service
+            .reopen(session_id)
+            .map_err(|err| anyhow::anyhow!(err))?;
+        if let Some(telemetry) = self.telemetry.as_ref() {
+            telemetry.record_reopen();
+        }
+        Ok(session)
+    }
+
     pub fn summary(&self, company_id: &CompanyId) -> anyhow::Result<ReconciliationSummary> {
         self.summary.summary(company_id)
     }
+
+    /// Candidate counts for one session, as computed by
+    /// `ReconciliationService::coverage`.
+    pub fn coverage(&self, session_id: &SessionId) -> anyhow::Result<Coverage> {
+        self.service
+            .coverage(session_id)
+            .map_err(|err| anyhow::anyhow!(err))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::NaiveDate;
-    use codex_reconcile::CandidateStatus;
     use codex_reconcile::InMemoryReconciliationService;
     use codex_reconcile::LinearScoringStrategy;
     use codex_reconcile::MatchProposal;
@@ -257,8 +386,10 @@ mod tests {
             source_reference: Some(format!("REF-{id}")),
             source_checksum: Some(format!("CHK-{id}")),
             is_void: false,
+            checksum_computed: false,
             duplicate_metadata: Default::default(),
             currency_validation: Default::default(),
+            institution: None,
         }
     }
 
@@ -305,6 +436,10 @@ mod tests {
                     transaction_description: "Utilities invoice".into(),
                     journal_description: "Utilities invoice".into(),
                     group_id: Some("grp-1".into()),
+                    currency: None,
+                    currency_precision: None,
+                    proposed_candidate_id: None,
+                    ..MatchProposal::default()
                 },
             )
             .expect("candidate added");
@@ -320,6 +455,9 @@ mod tests {
             .expect("write off");
         assert_eq!(written_off.status, CandidateStatus::WrittenOff);
         assert_eq!(written_off.write_off_reason.as_deref(), Some("APR-99"));
+        let coverage = facade.coverage(&session.id).expect("coverage");
+        assert_eq!(coverage.written_off, 1);
+        assert_eq!(coverage.ratio(), 1.0);
 
         summaries.insert(ReconciliationSummary {
             company_id: "co-2".into(),
@@ -334,6 +472,125 @@ mod tests {
         assert_eq!(summary.pending, 2);
     }
 
+    #[tokio::test]
+    async fn accept_candidate_marks_ledger_entry_and_rolls_back_on_failure() {
+        let ledger: Arc<dyn LedgerService> = Arc::new(codex_ledger::InMemoryLedgerService::new());
+        let data = crate::demo::seed_demo_ledger_with_service(ledger.clone())
+            .await
+            .expect("demo ledger");
+        let posted = &data.entries[0];
+        let (facade, _, service, _) = reconciliation_facade();
+        let facade = facade.with_ledger(ledger.clone());
+        let session = service
+            .create_session(&posted.company_id)
+            .expect("session created");
+        let proposal = |transaction_id: &str, journal_entry_id: &str| MatchProposal {
+            transaction_id: transaction_id.into(),
+            journal_entry_id: journal_entry_id.into(),
+            amount_delta_minor: 0,
+            date_delta_days: 0,
+            transaction_description: "Demo deposit".into(),
+            journal_description: "Demo deposit".into(),
+            group_id: None,
+            currency: None,
+            currency_precision: None,
+            proposed_candidate_id: None,
+            ..MatchProposal::default()
+        };
+        let tenant = crate::demo::demo_company_tenant(&posted.company_id);
+
+        let missing = service
+            .add_candidate(&session.id, proposal("txn-missing", "je-missing"))
+            .expect("candidate added");
+        let err = facade
+            .accept_candidate(&session.id, &missing.id, tenant.clone())
+            .await
+            .expect_err("unknown ledger entry");
+        assert!(err.to_string().contains("left pending"), "{err}");
+        let candidates = facade.list_candidates(&session.id).expect("candidates");
+        assert_eq!(candidates[0].status, CandidateStatus::Pending);
+
+        let other_tenant = crate::demo::demo_company_tenant("other-company");
+        let foreign = service
+            .add_candidate(&session.id, proposal("txn-foreign", &posted.entry.id))
+            .expect("candidate added");
+        let err = facade
+            .accept_candidate(&session.id, &foreign.id, other_tenant)
+            .await
+            .expect_err("entry belongs to another company");
+        assert!(err.to_string().contains("left pending"), "{err}");
+        let candidates = facade.list_candidates(&session.id).expect("candidates");
+        assert!(
+            candidates
+                .iter()
+                .all(|candidate| candidate.status == CandidateStatus::Pending)
+        );
+
+        let matched = service
+            .add_candidate(&session.id, proposal("txn-1", &posted.entry.id))
+            .expect("candidate added");
+        let accepted = facade
+            .accept_candidate(&session.id, &matched.id, tenant)
+            .await
+            .expect("accept and mark");
+        assert_eq!(accepted.status, CandidateStatus::Accepted);
+        let entries = ledger
+            .list_entries(&posted.company_id, codex_ledger::EntryQuery::default())
+            .await
+            .expect("entries listed");
+        let entry = entries
+            .iter()
+            .find(|entry| entry.id == posted.entry.id)
+            .expect("posted entry");
+        assert_eq!(
+            entry.reconciliation_status,
+            codex_ledger::ReconciliationStatus::Reconciled {
+                session_id: session.id.clone()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_ledger_update_keeps_earlier_actions_undoable() {
+        let ledger: Arc<dyn LedgerService> = Arc::new(codex_ledger::InMemoryLedgerService::new());
+        let (facade, _, service, _) = reconciliation_facade();
+        let facade = facade.with_ledger(ledger);
+        let session = service.create_session("co-3").expect("session created");
+        let proposal = |transaction_id: &str| MatchProposal {
+            transaction_id: transaction_id.into(),
+            journal_entry_id: format!("je-{transaction_id}"),
+            transaction_description: "Deposit".into(),
+            journal_description: "Deposit".into(),
+            ..MatchProposal::default()
+        };
+        let rejected = service
+            .add_candidate(&session.id, proposal("txn-1"))
+            .expect("candidate added");
+        let missing = service
+            .add_candidate(&session.id, proposal("txn-2"))
+            .expect("candidate added");
+        facade
+            .reject_candidate(&session.id, &rejected.id, None)
+            .expect("reject");
+
+        facade
+            .accept_candidate(
+                &session.id,
+                &missing.id,
+                crate::demo::demo_company_tenant("co-3"),
+            )
+            .await
+            .expect_err("unknown ledger entry");
+
+        let undone = service.undo_last(&session.id).expect("undo reject");
+        let restored = undone
+            .candidates
+            .iter()
+            .find(|candidate| candidate.id == rejected.id)
+            .expect("rejected candidate");
+        assert_eq!(restored.status, CandidateStatus::Pending);
+    }
+
     #[test]
     fn list_candidates_requires_existing_session() {
         let (facade, _, _, _) = reconciliation_facade();
@@ -358,6 +615,10 @@ mod tests {
                     transaction_description: "Approval test".into(),
                     journal_description: "Approval test".into(),
                     group_id: None,
+                    currency: None,
+                    currency_precision: None,
+                    proposed_candidate_id: None,
+                    ..MatchProposal::default()
                 },
             )
             .expect("candidate created");
@@ -397,6 +658,10 @@ mod tests {
                     transaction_description: "demo".into(),
                     journal_description: "demo".into(),
                     group_id: None,
+                    currency: None,
+                    currency_precision: None,
+                    proposed_candidate_id: None,
+                    ..MatchProposal::default()
                 },
             )
             .expect("candidate added");
@@ -416,4 +681,51 @@ mod tests {
         assert_eq!(counters.reconciliation_candidates, 1);
         assert_eq!(counters.reconciliation_write_offs, 1);
     }
+
+    #[test]
+    fn telemetry_records_rejections_and_reopens() {
+        let source = Arc::new(InMemoryBankTransactionSource::new());
+        let service = Arc::new(InMemoryReconciliationService::new(Arc::new(
+            LinearScoringStrategy::new(),
+        )));
+        let telemetry = Arc::new(AccountingTelemetry::new());
+        let facade = ReconciliationFacade::with_summary_and_telemetry(
+            source,
+            service.clone(),
+            Arc::new(InMemoryReconciliationSummaryProvider::new()),
+            Some(telemetry.clone()),
+        );
+        let session = service
+            .create_session("co-telemetry")
+            .expect("session created");
+        let candidate = service
+            .add_candidate(
+                &session.id,
+                MatchProposal {
+                    transaction_id: "txn-1".into(),
+                    journal_entry_id: "je-1".into(),
+                    amount_delta_minor: 0,
+                    date_delta_days: 0,
+                    transaction_description: "demo".into(),
+                    journal_description: "other".into(),
+                    group_id: None,
+                    currency: None,
+                    currency_precision: None,
+                    proposed_candidate_id: None,
+                    ..MatchProposal::default()
+                },
+            )
+            .expect("candidate added");
+
+        let rejected = facade
+            .reject_candidate(&session.id, &candidate.id, Some("wrong vendor".into()))
+            .expect("reject");
+        assert_eq!(rejected.status, CandidateStatus::Rejected);
+        assert_eq!(rejected.rejection_reason.as_deref(), Some("wrong vendor"));
+        facade.reopen_session(&session.id).expect("reopen");
+
+        let counters = telemetry.snapshot();
+        assert_eq!(counters.reconciliation_rejections, 1);
+        assert_eq!(counters.reconciliation_reopens, 1);
+    }
 }
@@ -29,6 +29,10 @@ pub struct TelemetryCounters {
     pub policy_reject: usize,
     pub approvals_total: usize,
     pub approvals_overdue: usize,
+    #[serde(default)]
+    pub reconciliation_rejections: usize,
+    #[serde(default)]
+    pub reconciliation_reopens: usize,
 }
 
 #[derive(Debug)]
@@ -178,6 +182,20 @@ impl AccountingTelemetry {
         });
     }
 
+    pub fn record_rejection(&self) {
+        self.update(|counters| {
+            counters.reconciliation_rejections += 1;
+            true
+        });
+    }
+
+    pub fn record_reopen(&self) {
+        self.update(|counters| {
+            counters.reconciliation_reopens += 1;
+            true
+        });
+    }
+
     pub fn record_period_lock(&self, action: LedgerPeriodAction) {
         self.update(|counters| {
             counters.period_lock_events += 1;
@@ -245,7 +263,16 @@ pub struct TelemetryPolicyEventSink {
 #[async_trait]
 impl PolicyEventSink for TelemetryPolicyEventSink {
     async fn record(&self, event: PolicyEvaluationEvent) {
-        self.telemetry.record_policy_decision(event.decision);
+        if event.is_override {
+            return;
+        }
+        // Shadow-mode callers were told `NeedsApproval` in place of `AutoPost`.
+        let decision = if event.shadow && event.decision == PolicyDecision::AutoPost {
+            PolicyDecision::NeedsApproval
+        } else {
+            event.decision
+        };
+        self.telemetry.record_policy_decision(decision);
     }
 }
 
@@ -261,6 +288,8 @@ mod tests {
         telemetry.record_transactions(3);
         telemetry.record_candidates(2);
         telemetry.record_write_off();
+        telemetry.record_rejection();
+        telemetry.record_reopen();
         telemetry.record_period_lock(LedgerPeriodAction::SoftClose);
         telemetry.record_period_lock(LedgerPeriodAction::Close);
         telemetry.record_approvals_snapshot(5, 2);
@@ -270,6 +299,8 @@ mod tests {
         assert_eq!(counters.reconciliation_transactions, 3);
         assert_eq!(counters.reconciliation_candidates, 2);
         assert_eq!(counters.reconciliation_write_offs, 1);
+        assert_eq!(counters.reconciliation_rejections, 1);
+        assert_eq!(counters.reconciliation_reopens, 1);
         assert_eq!(counters.period_lock_events, 2);
         assert_eq!(counters.policy_needs_approval, 1);
         assert_eq!(counters.policy_auto_post, 1);
@@ -311,10 +342,54 @@ mod tests {
             auto_post_limit_minor: 0,
             confidence_floor: None,
             evaluated_at: Utc::now(),
+            is_override: false,
+            overridden_decision: None,
+            justification: None,
+            shadow: false,
         };
-        sink.record(event).await;
+        sink.record(event.clone()).await;
+        sink.record(PolicyEvaluationEvent {
+            is_override: true,
+            overridden_decision: Some(PolicyDecision::NeedsApproval),
+            justification: Some("controller sign-off".into()),
+            ..event.clone()
+        })
+        .await;
+        sink.record(PolicyEvaluationEvent {
+            decision: PolicyDecision::AutoPost,
+            shadow: true,
+            ..event.clone()
+        })
+        .await;
+        sink.record(PolicyEvaluationEvent {
+            shadow: true,
+            ..event
+        })
+        .await;
         let counters = telemetry.snapshot();
-        assert_eq!(counters.policy_reject, 1);
+        assert_eq!(counters.policy_reject, 2);
+        assert_eq!(counters.policy_auto_post, 0);
+        assert_eq!(counters.policy_needs_approval, 1);
+    }
+
+    #[test]
+    fn persisted_counters_without_new_fields_still_load() {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("telemetry.json");
+        let mut legacy = serde_json::to_value(TelemetryCounters {
+            reconciliation_write_offs: 3,
+            ..TelemetryCounters::default()
+        })
+        .expect("encode counters");
+        let fields = legacy.as_object_mut().expect("counters object");
+        fields.remove("reconciliation_rejections");
+        fields.remove("reconciliation_reopens");
+        fs::write(&path, legacy.to_string()).expect("write legacy telemetry");
+
+        let counters = AccountingTelemetry::with_store_path(path).snapshot();
+        assert_eq!(counters.reconciliation_write_offs, 3);
+        assert_eq!(counters.reconciliation_rejections, 0);
+        assert_eq!(counters.reconciliation_reopens, 0);
     }
 
     #[test]
@@ -7,6 +7,7 @@ use codex_tenancy::CreateFirmRequest;
 use codex_tenancy::Firm;
 use codex_tenancy::FirmId;
 use codex_tenancy::InviteUserRequest;
+use codex_tenancy::TenancyActor;
 use codex_tenancy::TenancyResult;
 use codex_tenancy::TenancyService;
 use codex_tenancy::UpdateUserRolesRequest;
@@ -24,8 +25,12 @@ impl TenancyFacade {
         Self { service }
     }
 
-    pub async fn create_firm(&self, request: CreateFirmRequest) -> TenancyResult<Firm> {
-        self.service.create_firm(request).await
+    pub async fn create_firm(
+        &self,
+        request: CreateFirmRequest,
+        actor: &TenancyActor,
+    ) -> TenancyResult<Firm> {
+        self.service.create_firm(request, actor).await
     }
 
     pub async fn list_firms(&self) -> TenancyResult<Vec<Firm>> {
@@ -36,8 +41,12 @@ impl TenancyFacade {
         self.service.get_firm(firm_id).await
     }
 
-    pub async fn create_company(&self, request: CreateCompanyRequest) -> TenancyResult<Company> {
-        self.service.create_company(request).await
+    pub async fn create_company(
+        &self,
+        request: CreateCompanyRequest,
+        actor: &TenancyActor,
+    ) -> TenancyResult<Company> {
+        self.service.create_company(request, actor).await
     }
 
     pub async fn list_companies(&self, firm_id: &FirmId) -> TenancyResult<Vec<Company>> {
@@ -56,20 +65,30 @@ impl TenancyFacade {
         &self,
         firm_id: &FirmId,
         company_id: &CompanyId,
+        actor: &TenancyActor,
     ) -> TenancyResult<Company> {
-        self.service.archive_company(firm_id, company_id).await
+        self.service
+            .archive_company(firm_id, company_id, actor)
+            .await
     }
 
     pub async fn reactivate_company(
         &self,
         firm_id: &FirmId,
         company_id: &CompanyId,
+        actor: &TenancyActor,
     ) -> TenancyResult<Company> {
-        self.service.reactivate_company(firm_id, company_id).await
+        self.service
+            .reactivate_company(firm_id, company_id, actor)
+            .await
     }
 
-    pub async fn invite_user(&self, request: InviteUserRequest) -> TenancyResult<UserAccount> {
-        self.service.invite_user(request).await
+    pub async fn invite_user(
+        &self,
+        request: InviteUserRequest,
+        actor: &TenancyActor,
+    ) -> TenancyResult<UserAccount> {
+        self.service.invite_user(request, actor).await
     }
 
     pub async fn list_users(&self, firm_id: &FirmId) -> TenancyResult<Vec<UserAccount>> {
@@ -83,8 +102,9 @@ impl TenancyFacade {
     pub async fn set_user_roles(
         &self,
         request: UpdateUserRolesRequest,
+        actor: &TenancyActor,
     ) -> TenancyResult<UserAccount> {
-        self.service.set_user_roles(request).await
+        self.service.set_user_roles(request, actor).await
     }
 
     pub async fn update_user_status(
@@ -92,9 +112,10 @@ impl TenancyFacade {
         firm_id: &FirmId,
         user_id: &UserId,
         status: UserStatus,
+        actor: &TenancyActor,
     ) -> TenancyResult<UserAccount> {
         self.service
-            .update_user_status(firm_id, user_id, status)
+            .update_user_status(firm_id, user_id, status, actor)
             .await
     }
 }
@@ -111,12 +132,16 @@ mod tests {
     async fn facade_round_trips() {
         let service: Arc<dyn TenancyService> = Arc::new(InMemoryTenancyService::new());
         let facade = TenancyFacade::new(service);
+        let actor = TenancyActor::new("user-admin", vec![Role::Partner]);
 
         let firm = facade
-            .create_firm(CreateFirmRequest {
-                name: "Demo Firm".into(),
-                metadata: None,
-            })
+            .create_firm(
+                CreateFirmRequest {
+                    name: "Demo Firm".into(),
+                    metadata: None,
+                },
+                &actor,
+            )
             .await
             .expect("firm should be created");
 
@@ -125,13 +150,17 @@ mod tests {
         assert_eq!(listed_firms[0], firm);
 
         let created = facade
-            .create_company(CreateCompanyRequest {
-                firm_id: firm.id.clone(),
-                name: "Demo Co".into(),
-                base_currency: "usd".into(),
-                tags: vec!["pilot".into()],
-                metadata: None,
-            })
+            .create_company(
+                CreateCompanyRequest {
+                    firm_id: firm.id.clone(),
+                    name: "Demo Co".into(),
+                    base_currency: "usd".into(),
+                    currency_precision: None,
+                    tags: vec!["pilot".into()],
+                    metadata: None,
+                },
+                &actor,
+            )
             .await
             .expect("company should be created");
 
@@ -142,13 +171,13 @@ mod tests {
         assert_eq!(fetched, created);
 
         let archived = facade
-            .archive_company(&firm.id, &created.id)
+            .archive_company(&firm.id, &created.id, &actor)
             .await
             .expect("company should be archived");
         assert_eq!(archived.status, codex_tenancy::CompanyStatus::Archived);
 
         let reactivated = facade
-            .reactivate_company(&firm.id, &created.id)
+            .reactivate_company(&firm.id, &created.id, &actor)
             .await
             .expect("company should be reactivated");
         assert_eq!(reactivated.status, codex_tenancy::CompanyStatus::Active);
@@ -160,13 +189,30 @@ mod tests {
         assert_eq!(listed.len(), 1);
         assert_eq!(listed[0], reactivated);
 
+        let admin = facade
+            .invite_user(
+                InviteUserRequest {
+                    firm_id: firm.id.clone(),
+                    email: "admin@example.com".into(),
+                    display_name: "Admin User".into(),
+                    roles: vec![RoleAssignment::firm(Role::Partner)],
+                },
+                &actor,
+            )
+            .await
+            .expect("first user becomes the actor's partner account");
+        assert_eq!(admin.id, actor.user_id);
+
         let user = facade
-            .invite_user(InviteUserRequest {
-                firm_id: firm.id.clone(),
-                email: "user@example.com".into(),
-                display_name: "Example User".into(),
-                roles: vec![RoleAssignment::firm(Role::Partner)],
-            })
+            .invite_user(
+                InviteUserRequest {
+                    firm_id: firm.id.clone(),
+                    email: "user@example.com".into(),
+                    display_name: "Example User".into(),
+                    roles: vec![RoleAssignment::firm(Role::Partner)],
+                },
+                &actor,
+            )
             .await
             .expect("user should be invited");
 
@@ -180,21 +226,23 @@ mod tests {
             .list_users(&firm.id)
             .await
             .expect("users should list");
-        assert_eq!(listed_users.len(), 1);
-        assert_eq!(listed_users[0], user);
+        assert_eq!(listed_users, vec![admin, user.clone()]);
 
         let updated_user = facade
-            .set_user_roles(UpdateUserRolesRequest {
-                firm_id: firm.id.clone(),
-                user_id: user.id.clone(),
-                roles: vec![RoleAssignment::firm(Role::Senior)],
-            })
+            .set_user_roles(
+                UpdateUserRolesRequest {
+                    firm_id: firm.id.clone(),
+                    user_id: user.id.clone(),
+                    roles: vec![RoleAssignment::firm(Role::Senior)],
+                },
+                &actor,
+            )
             .await
             .expect("user roles should update");
         assert!(updated_user.has_role(Role::Senior));
 
         let activated = facade
-            .update_user_status(&firm.id, &user.id, UserStatus::Active)
+            .update_user_status(&firm.id, &user.id, UserStatus::Active, &actor)
             .await
             .expect("user should activate");
         assert!(activated.status.is_active());
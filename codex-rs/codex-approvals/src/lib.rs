@@ -1,19 +1,45 @@
 #![deny(clippy::print_stdout, clippy::print_stderr)]
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 
 use async_trait::async_trait;
 use chrono::DateTime;
+use chrono::Duration;
 use chrono::Utc;
+use codex_tenancy::FirmId;
+use codex_tenancy::Role;
+use codex_tenancy::RoleScope;
+use codex_tenancy::TenancyError;
+use codex_tenancy::TenancyService;
+use codex_tenancy::UserStatus;
+use codex_tenancy::default_currency_precision;
 use serde::Deserialize;
+use serde::Deserializer;
 use serde::Serialize;
+use serde_json::Value;
 use thiserror::Error;
 use tokio::sync::RwLock;
+use tracing::warn;
 use uuid::Uuid;
 
+mod words;
+
+pub use words::format_amount_words;
+
 pub type ApprovalId = String;
 pub type CompanyId = String;
 pub type UserId = String;
+pub type ProposalId = String;
 
 pub type ApprovalsResult<T> = Result<T, ApprovalsError>;
 
@@ -21,6 +47,8 @@ pub type ApprovalsResult<T> = Result<T, ApprovalsError>;
 pub enum ApprovalsError {
     #[error("approval {0} was not found")]
     NotFound(String),
+    #[error("approval {0} already exists")]
+    AlreadyExists(ApprovalId),
     #[error("approval is already assigned to {assignee}")]
     AlreadyAssigned { assignee: UserId },
     #[error("approval is not assigned to {0}")]
@@ -29,25 +57,139 @@ pub enum ApprovalsError {
     Finalized,
     #[error("validation error: {0}")]
     Validation(String),
+    #[error("storage error: {0}")]
+    Storage(String),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+impl ApprovalsError {
+    /// Stable machine-readable code such as `approvals.finalized`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "approvals.not_found",
+            Self::AlreadyExists(_) => "approvals.already_exists",
+            Self::AlreadyAssigned { .. } => "approvals.already_assigned",
+            Self::NotAssigned(_) => "approvals.not_assigned",
+            Self::Finalized => "approvals.finalized",
+            Self::Validation(_) => "approvals.validation",
+            Self::Storage(_) => "approvals.storage",
+        }
+    }
+
+    /// HTTP status for API error bodies; assignment and lifecycle conflicts map to 409.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            Self::NotFound(_) => 404,
+            Self::AlreadyExists(_) => 409,
+            Self::AlreadyAssigned { .. } => 409,
+            Self::NotAssigned(_) => 409,
+            Self::Finalized => 409,
+            Self::Validation(_) => 400,
+            Self::Storage(_) => 500,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ApprovalPriority {
     Low,
     Normal,
     High,
 }
 
+/// Who may act on an approval stage.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageAccess {
+    /// Any user may assign or decide the stage.
+    #[default]
+    Anyone,
+    /// Only the listed users. An empty list admits nobody, so `enqueue`
+    /// rejects it.
+    Named(Vec<UserId>),
+    /// Users holding the role, firm-wide or for the request's company, as
+    /// reported by the service's `UserResolver`.
+    Role(Role),
+}
+
+impl StageAccess {
+    /// The listed users of a `Named` stage; empty otherwise.
+    #[must_use]
+    pub fn named_approvers(&self) -> &[UserId] {
+        match self {
+            Self::Named(approvers) => approvers,
+            Self::Anyone | Self::Role(_) => &[],
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "StageRecord")]
 pub struct ApprovalStage {
+    pub access: StageAccess,
+}
+
+/// Wire form of `ApprovalStage`. Stages saved before `StageAccess` carried
+/// only an `approvers` list, where an empty list meant anyone; those load as
+/// `Anyone` or `Named`.
+#[derive(Deserialize)]
+struct StageRecord {
     #[serde(default)]
-    pub approvers: Vec<UserId>,
+    access: Option<StageAccess>,
+    #[serde(default)]
+    approvers: Vec<UserId>,
+}
+
+impl From<StageRecord> for ApprovalStage {
+    fn from(record: StageRecord) -> Self {
+        let access = match record.access {
+            Some(access) => access,
+            None if record.approvers.is_empty() => StageAccess::Anyone,
+            None => StageAccess::Named(record.approvers),
+        };
+        Self { access }
+    }
 }
 
 impl ApprovalStage {
     #[must_use]
-    pub fn allows(&self, user_id: &UserId) -> bool {
-        self.approvers.is_empty() || self.approvers.iter().any(|candidate| candidate == user_id)
+    pub fn anyone() -> Self {
+        Self {
+            access: StageAccess::Anyone,
+        }
+    }
+
+    #[must_use]
+    pub fn named(approvers: Vec<UserId>) -> Self {
+        Self {
+            access: StageAccess::Named(approvers),
+        }
+    }
+
+    #[must_use]
+    pub fn role(role: Role) -> Self {
+        Self {
+            access: StageAccess::Role(role),
+        }
+    }
+
+    /// Whether `user_id` may act on this stage of a `company_id` request.
+    /// `Role` stages ask `resolver` and admit nobody without one.
+    pub async fn allows(
+        &self,
+        company_id: &CompanyId,
+        user_id: &UserId,
+        resolver: Option<&dyn UserResolver>,
+    ) -> ApprovalsResult<bool> {
+        match &self.access {
+            StageAccess::Anyone => Ok(true),
+            StageAccess::Named(approvers) => {
+                Ok(approvers.iter().any(|candidate| candidate == user_id))
+            }
+            StageAccess::Role(role) => match resolver {
+                Some(resolver) => resolver.has_role(company_id, user_id, *role).await,
+                None => Ok(false),
+            },
+        }
     }
 }
 
@@ -60,9 +202,24 @@ pub struct ApprovalRequest {
     pub summary: String,
     pub amount_minor: i64,
     pub currency: String,
+    /// Minor-unit digits of `currency`, e.g. the company's configured
+    /// precision; `None` falls back to the ISO-4217 default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency_precision: Option<u8>,
     pub priority: ApprovalPriority,
     pub sla_at: Option<DateTime<Utc>>,
-    pub metadata: Option<String>,
+    /// Due date of the bill behind this approval, if any. See
+    /// `InMemoryApprovalsService::with_due_date_window`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<DateTime<Utc>>,
+    /// Structured context such as proposal ids, amounts, and links. Payloads
+    /// written when this was an opaque string still load; strings holding a
+    /// JSON object or array are parsed, anything else stays a string.
+    #[serde(default, deserialize_with = "deserialize_metadata")]
+    pub metadata: Value,
+    /// Posting proposal whose policy evaluation required this approval.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub originating_proposal_id: Option<ProposalId>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub stages: Vec<ApprovalStage>,
 }
@@ -77,13 +234,32 @@ impl ApprovalRequest {
             summary,
             amount_minor: 0,
             currency: "USD".into(),
+            currency_precision: None,
             priority: ApprovalPriority::Normal,
             sla_at: None,
-            metadata: None,
+            due_date: None,
+            metadata: Value::Null,
+            originating_proposal_id: None,
             stages: Vec::new(),
         }
     }
 
+    /// The amount spelled out for cheque-signing review; see
+    /// [`format_amount_words`].
+    #[must_use]
+    pub fn amount_in_words(&self) -> String {
+        let precision = self
+            .currency_precision
+            .unwrap_or_else(|| default_currency_precision(&self.currency));
+        format_amount_words(self.amount_minor, &self.currency, precision)
+    }
+
+    /// Looks up `key` when the metadata is a JSON object.
+    #[must_use]
+    pub fn metadata_field(&self, key: &str) -> Option<&Value> {
+        self.metadata.get(key)
+    }
+
     pub fn validate(&self) -> ApprovalsResult<()> {
         if self.summary.trim().is_empty() {
             return Err(ApprovalsError::Validation(
@@ -99,6 +275,27 @@ impl ApprovalRequest {
     }
 }
 
+/// Renders `summary: <amount in words>`, e.g.
+/// `Vendor payout: One thousand two hundred and 00/100 USD`.
+impl fmt::Display for ApprovalRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.summary, self.amount_in_words())
+    }
+}
+
+fn deserialize_metadata<'de, D>(deserializer: D) -> Result<Value, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Value::deserialize(deserializer)? {
+        Value::String(raw) => match serde_json::from_str(&raw) {
+            Ok(parsed @ (Value::Object(_) | Value::Array(_))) => parsed,
+            _ => Value::String(raw),
+        },
+        value => value,
+    })
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ApprovalStatus {
     Pending,
@@ -129,14 +326,15 @@ pub struct ApprovalTask {
     pub decision: Option<DecisionRecord>,
     pub current_stage_index: usize,
     pub stage_decisions: Vec<Option<DecisionRecord>>,
+    /// When the overdue hook last fired for this task's SLA breach.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_escalated_at: Option<DateTime<Utc>>,
 }
 
 impl ApprovalTask {
     pub fn new(mut request: ApprovalRequest) -> Self {
         if request.stages.is_empty() {
-            request.stages.push(ApprovalStage {
-                approvers: Vec::new(),
-            });
+            request.stages.push(ApprovalStage::anyone());
         }
         let stage_count = request.stages.len();
         Self {
@@ -146,6 +344,7 @@ impl ApprovalTask {
             decision: None,
             current_stage_index: 0,
             stage_decisions: vec![None; stage_count],
+            last_escalated_at: None,
         }
     }
 
@@ -164,6 +363,126 @@ impl ApprovalTask {
                 .map(|deadline| deadline < now)
                 .unwrap_or(false)
     }
+
+    /// Overdue and not yet escalated for the current SLA deadline; moving
+    /// `sla_at` past the last escalation re-arms the hook.
+    fn needs_escalation(&self, now: DateTime<Utc>) -> bool {
+        self.is_overdue(now)
+            && match (self.last_escalated_at, self.request.sla_at) {
+                (Some(escalated_at), Some(deadline)) => escalated_at < deadline,
+                _ => true,
+            }
+    }
+}
+
+/// Push notification for SLA breaches, fired by
+/// `ApprovalsService::scan_overdue_and_notify`.
+#[async_trait]
+pub trait OverdueHook: Send + Sync {
+    async fn on_overdue(&self, task: &ApprovalTask);
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopOverdueHook;
+
+#[async_trait]
+impl OverdueHook for NoopOverdueHook {
+    async fn on_overdue(&self, _task: &ApprovalTask) {}
+}
+
+/// Logs each breach as a `tracing` warning.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LoggingOverdueHook;
+
+#[async_trait]
+impl OverdueHook for LoggingOverdueHook {
+    async fn on_overdue(&self, task: &ApprovalTask) {
+        warn!(
+            approval_id = %task.request.id,
+            company_id = %task.request.company_id,
+            sla_at = ?task.request.sla_at,
+            assigned_to = ?task.assigned_to,
+            "approval task breached its SLA"
+        );
+    }
+}
+
+/// Looks up stage approvers so `enqueue` can refuse stages nobody can action.
+#[async_trait]
+pub trait UserResolver: Send + Sync {
+    /// Status of `user_id` as seen from `company_id`'s firm; `None` when the
+    /// user is unknown there.
+    async fn user_status(
+        &self,
+        company_id: &CompanyId,
+        user_id: &UserId,
+    ) -> ApprovalsResult<Option<UserStatus>>;
+
+    /// Whether `user_id` holds `role` for `company_id`, deciding `Role`
+    /// stages. Resolvers without role data admit nobody.
+    async fn has_role(
+        &self,
+        _company_id: &CompanyId,
+        _user_id: &UserId,
+        _role: Role,
+    ) -> ApprovalsResult<bool> {
+        Ok(false)
+    }
+}
+
+/// Resolves approvers against one firm's users in a `TenancyService`.
+pub struct TenancyUserResolver {
+    tenancy: Arc<dyn TenancyService>,
+    firm_id: FirmId,
+}
+
+impl TenancyUserResolver {
+    pub fn new(tenancy: Arc<dyn TenancyService>, firm_id: impl Into<FirmId>) -> Self {
+        Self {
+            tenancy,
+            firm_id: firm_id.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl UserResolver for TenancyUserResolver {
+    async fn user_status(
+        &self,
+        _company_id: &CompanyId,
+        user_id: &UserId,
+    ) -> ApprovalsResult<Option<UserStatus>> {
+        match self.tenancy.get_user(&self.firm_id, user_id).await {
+            Ok(user) => Ok(Some(user.status)),
+            Err(TenancyError::NotFound(_) | TenancyError::Rejected(_)) => Ok(None),
+            Err(err) => Err(ApprovalsError::Storage(format!(
+                "failed to resolve approver {user_id}: {err}"
+            ))),
+        }
+    }
+
+    /// Active users holding `role` firm-wide or scoped to `company_id`.
+    async fn has_role(
+        &self,
+        company_id: &CompanyId,
+        user_id: &UserId,
+        role: Role,
+    ) -> ApprovalsResult<bool> {
+        match self.tenancy.get_user(&self.firm_id, user_id).await {
+            Ok(user) => Ok(user.status.is_active()
+                && user.roles.iter().any(|assignment| {
+                    assignment.role == role
+                        && match &assignment.scope {
+                            RoleScope::FirmWide => true,
+                            RoleScope::Company(scoped) => scoped == company_id,
+                        }
+                })),
+            Err(TenancyError::NotFound(_) | TenancyError::Rejected(_)) => Ok(false),
+            Err(err) => Err(ApprovalsError::Storage(format!(
+                "failed to resolve roles for {user_id}: {err}"
+            ))),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -194,6 +513,69 @@ impl QueueFilter {
     }
 }
 
+/// Aggregate KPIs over the tasks matching a `QueueFilter`. Rates are
+/// fractions of finalized tasks (0.0 when none are finalized); pending tasks
+/// count toward `total` but not toward `average_decision_time`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApprovalsMetrics {
+    pub total: usize,
+    pub approved: usize,
+    pub declined: usize,
+    pub pending: usize,
+    pub overdue: usize,
+    pub approval_rate: f64,
+    pub decline_rate: f64,
+    /// Mean of `decision.decided_at - request.submitted_at` over finalized
+    /// tasks.
+    pub average_decision_time: Option<Duration>,
+}
+
+impl ApprovalsMetrics {
+    pub fn from_tasks<'a>(
+        tasks: impl IntoIterator<Item = &'a ApprovalTask>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let mut metrics = Self {
+            total: 0,
+            approved: 0,
+            declined: 0,
+            pending: 0,
+            overdue: 0,
+            approval_rate: 0.0,
+            decline_rate: 0.0,
+            average_decision_time: None,
+        };
+        let mut decision_time_total = Duration::zero();
+        let mut decision_time_count = 0_i32;
+        for task in tasks {
+            metrics.total += 1;
+            if task.is_overdue(now) {
+                metrics.overdue += 1;
+            }
+            match task.status {
+                ApprovalStatus::Approved => metrics.approved += 1,
+                ApprovalStatus::Declined => metrics.declined += 1,
+                ApprovalStatus::Pending | ApprovalStatus::Assigned => metrics.pending += 1,
+            }
+            if task.is_finalized()
+                && let Some(record) = &task.decision
+            {
+                decision_time_total += record.decided_at - task.request.submitted_at;
+                decision_time_count += 1;
+            }
+        }
+        let decided = metrics.approved + metrics.declined;
+        if decided > 0 {
+            metrics.approval_rate = metrics.approved as f64 / decided as f64;
+            metrics.decline_rate = metrics.declined as f64 / decided as f64;
+        }
+        if decision_time_count > 0 {
+            metrics.average_decision_time = Some(decision_time_total / decision_time_count);
+        }
+        metrics
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DecisionInput {
     pub decided_by: UserId,
@@ -222,18 +604,177 @@ pub trait ApprovalsService: Send + Sync {
         decision: DecisionInput,
     ) -> ApprovalsResult<ApprovalTask>;
     async fn overdue(&self, now: DateTime<Utc>) -> ApprovalsResult<Vec<ApprovalTask>>;
+    /// Fires the overdue hook once per SLA breach, stamping
+    /// `last_escalated_at`, and returns the tasks notified by this scan.
+    async fn scan_overdue_and_notify(
+        &self,
+        now: DateTime<Utc>,
+    ) -> ApprovalsResult<Vec<ApprovalTask>>;
     async fn export_queue(&self) -> ApprovalsResult<QueueExport>;
+    /// Approval/decline rates, average decision time, and current overdue
+    /// count for the tasks matching `filter`.
+    async fn metrics(&self, filter: QueueFilter) -> ApprovalsResult<ApprovalsMetrics>;
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct QueueExport {
     pub generated_at: DateTime<Utc>,
     pub tasks: Vec<ApprovalTask>,
+    /// Each task's amount spelled out, keyed by approval id.
+    #[serde(default)]
+    pub amounts_in_words: BTreeMap<ApprovalId, String>,
+}
+
+/// Durable backing for approval tasks, keyed by approval id.
+pub trait ApprovalsStore: Send + Sync {
+    fn create_task(&self, task: &ApprovalTask) -> ApprovalsResult<()>;
+    fn save_task(&self, task: &ApprovalTask) -> ApprovalsResult<()>;
+    fn list_tasks(&self) -> ApprovalsResult<Vec<ApprovalTask>>;
+}
+
+/// Stores each approval task as `<approval id>.json` under a root directory.
+pub struct JsonFileApprovalsStore {
+    root: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl JsonFileApprovalsStore {
+    pub fn new(root: impl Into<PathBuf>) -> ApprovalsResult<Self> {
+        let root = root.into();
+        fs::create_dir_all(&root).map_err(|err| {
+            ApprovalsError::Storage(format!(
+                "failed to create approvals directory {}: {err}",
+                root.display()
+            ))
+        })?;
+        Ok(Self {
+            root,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    fn task_path(&self, approval_id: &ApprovalId) -> ApprovalsResult<PathBuf> {
+        if approval_id.is_empty()
+            || !approval_id
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        {
+            return Err(ApprovalsError::Storage(format!(
+                "approval id {approval_id} cannot be used as a file name"
+            )));
+        }
+        Ok(self.root.join(format!("{approval_id}.json")))
+    }
+
+    fn with_write<F, T>(&self, f: F) -> ApprovalsResult<T>
+    where
+        F: FnOnce() -> ApprovalsResult<T>,
+    {
+        let _guard = self
+            .write_lock
+            .lock()
+            .map_err(|_| ApprovalsError::Storage("approvals store poisoned".into()))?;
+        f()
+    }
+
+    fn write_task(&self, path: &Path, task: &ApprovalTask) -> ApprovalsResult<()> {
+        let data = serde_json::to_vec_pretty(task).map_err(|err| {
+            ApprovalsError::Storage(format!(
+                "failed to encode approval {}: {err}",
+                task.request.id
+            ))
+        })?;
+        let staging = path.with_extension("json.tmp");
+        fs::write(&staging, data)
+            .and_then(|()| fs::rename(&staging, path))
+            .map_err(|err| {
+                ApprovalsError::Storage(format!("failed to write {}: {err}", path.display()))
+            })
+    }
+}
+
+impl ApprovalsStore for JsonFileApprovalsStore {
+    fn create_task(&self, task: &ApprovalTask) -> ApprovalsResult<()> {
+        let path = self.task_path(&task.request.id)?;
+        self.with_write(|| {
+            if path.exists() {
+                return Err(ApprovalsError::AlreadyExists(task.request.id.clone()));
+            }
+            self.write_task(&path, task)
+        })
+    }
+
+    fn save_task(&self, task: &ApprovalTask) -> ApprovalsResult<()> {
+        let path = self.task_path(&task.request.id)?;
+        self.with_write(|| {
+            if !path.exists() {
+                return Err(ApprovalsError::NotFound(task.request.id.clone()));
+            }
+            self.write_task(&path, task)
+        })
+    }
+
+    fn list_tasks(&self) -> ApprovalsResult<Vec<ApprovalTask>> {
+        let entries = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => {
+                return Err(ApprovalsError::Storage(format!(
+                    "failed to read {}: {err}",
+                    self.root.display()
+                )));
+            }
+        };
+        let mut tasks = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|err| {
+                    ApprovalsError::Storage(format!(
+                        "failed to read {}: {err}",
+                        self.root.display()
+                    ))
+                })?
+                .path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let data = fs::read(&path).map_err(|err| {
+                ApprovalsError::Storage(format!("failed to read {}: {err}", path.display()))
+            })?;
+            let task: ApprovalTask = serde_json::from_slice(&data).map_err(|err| {
+                ApprovalsError::Storage(format!("failed to parse {}: {err}", path.display()))
+            })?;
+            tasks.push(task);
+        }
+        tasks.sort_by(|a, b| a.request.id.cmp(&b.request.id));
+        Ok(tasks)
+    }
+}
+
+/// How `enqueue` picks an assignee from the first stage's approvers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutoAssignPolicy {
+    /// Leave new tasks unassigned.
+    #[default]
+    None,
+    /// Rotate through the stage's approvers in listed order.
+    RoundRobin,
+    /// Pick the approver holding the fewest open assignments; ties go to the
+    /// earliest listed approver.
+    LeastLoaded,
 }
 
 #[derive(Default)]
 pub struct InMemoryApprovalsService {
     tasks: RwLock<HashMap<ApprovalId, ApprovalTask>>,
+    store: Option<Arc<dyn ApprovalsStore>>,
+    auto_assign: AutoAssignPolicy,
+    round_robin_cursor: AtomicUsize,
+    escalation_stages_for_priority: HashMap<ApprovalPriority, Vec<ApprovalStage>>,
+    sla_defaults: HashMap<ApprovalPriority, Duration>,
+    due_date_window: Option<Duration>,
+    overdue_hook: Option<Arc<dyn OverdueHook>>,
+    user_resolver: Option<Arc<dyn UserResolver>>,
 }
 
 impl InMemoryApprovalsService {
@@ -241,14 +782,224 @@ impl InMemoryApprovalsService {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Loads existing tasks from `store` and writes every transition through it.
+    pub fn with_store(store: Arc<dyn ApprovalsStore>) -> ApprovalsResult<Self> {
+        let tasks = store
+            .list_tasks()?
+            .into_iter()
+            .map(|task| (task.request.id.clone(), task))
+            .collect();
+        Ok(Self {
+            tasks: RwLock::new(tasks),
+            store: Some(store),
+            ..Self::default()
+        })
+    }
+
+    #[must_use]
+    pub fn with_auto_assign(mut self, policy: AutoAssignPolicy) -> Self {
+        self.auto_assign = policy;
+        self
+    }
+
+    /// Appends `stages` to the chain of every request enqueued with
+    /// `priority`, e.g. routing `High` requests to senior approvers.
+    #[must_use]
+    pub fn with_escalation_stages(
+        mut self,
+        priority: ApprovalPriority,
+        stages: Vec<ApprovalStage>,
+    ) -> Self {
+        self.escalation_stages_for_priority.insert(priority, stages);
+        self
+    }
+
+    /// Requests of `priority` enqueued without `sla_at` get
+    /// `submitted_at + sla` as their deadline. An explicit `sla_at` wins.
+    #[must_use]
+    pub fn with_sla_default(mut self, priority: ApprovalPriority, sla: Duration) -> Self {
+        self.sla_defaults.insert(priority, sla);
+        self
+    }
+
+    /// Requests whose `due_date` falls within `window` of `submitted_at` (or
+    /// has already passed) are raised to `High` priority at enqueue time,
+    /// before escalation stages and SLA defaults are applied. Priorities are
+    /// only ever raised, never lowered.
+    #[must_use]
+    pub fn with_due_date_window(mut self, window: Duration) -> Self {
+        self.due_date_window = Some(window);
+        self
+    }
+
+    /// Hook for `scan_overdue_and_notify`; defaults to `NoopOverdueHook`.
+    #[must_use]
+    pub fn with_overdue_hook(mut self, hook: Arc<dyn OverdueHook>) -> Self {
+        self.overdue_hook = Some(hook);
+        self
+    }
+
+    /// Every named stage approver must resolve to an `Active` user, and
+    /// `Role` stages are decided by `UserResolver::has_role`. Without a
+    /// resolver, requests with `Role` stages are rejected at enqueue.
+    #[must_use]
+    pub fn with_user_resolver(mut self, resolver: Arc<dyn UserResolver>) -> Self {
+        self.user_resolver = Some(resolver);
+        self
+    }
+
+    async fn validate_approvers(&self, request: &ApprovalRequest) -> ApprovalsResult<()> {
+        for (index, stage) in request.stages.iter().enumerate() {
+            match &stage.access {
+                StageAccess::Named(approvers) if approvers.is_empty() => {
+                    return Err(ApprovalsError::Validation(format!(
+                        "stage {} names no approvers",
+                        index + 1
+                    )));
+                }
+                StageAccess::Role(role) if self.user_resolver.is_none() => {
+                    return Err(ApprovalsError::Validation(format!(
+                        "stage {} requires role {role:?} but no user resolver is configured",
+                        index + 1
+                    )));
+                }
+                _ => {}
+            }
+        }
+        let Some(resolver) = &self.user_resolver else {
+            return Ok(());
+        };
+        for (index, stage) in request.stages.iter().enumerate() {
+            for approver in stage.access.named_approvers() {
+                match resolver.user_status(&request.company_id, approver).await? {
+                    Some(status) if status.is_active() => {}
+                    Some(status) => {
+                        return Err(ApprovalsError::Validation(format!(
+                            "approver {approver} in stage {} is not active ({status:?})",
+                            index + 1
+                        )));
+                    }
+                    None => {
+                        return Err(ApprovalsError::Validation(format!(
+                            "approver {approver} in stage {} is not a known user",
+                            index + 1
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn prioritize_by_due_date(&self, request: &mut ApprovalRequest) {
+        if let (Some(window), Some(due_date)) = (self.due_date_window, request.due_date)
+            && due_date - request.submitted_at <= window
+        {
+            request.priority = ApprovalPriority::High;
+        }
+    }
+
+    /// Without an explicit `sla_at`, the deadline is the earlier of the
+    /// priority's SLA default and the bill's due date.
+    fn apply_sla(&self, request: &mut ApprovalRequest) {
+        if request.sla_at.is_some() {
+            return;
+        }
+        let default = self
+            .sla_defaults
+            .get(&request.priority)
+            .map(|sla| request.submitted_at + *sla);
+        request.sla_at = match (default, request.due_date) {
+            (Some(default), Some(due_date)) => Some(default.min(due_date)),
+            (default, due_date) => default.or(due_date),
+        };
+    }
+
+    fn escalate(&self, request: &mut ApprovalRequest) -> ApprovalsResult<()> {
+        let Some(extra) = self.escalation_stages_for_priority.get(&request.priority) else {
+            return Ok(());
+        };
+        if extra.is_empty() {
+            return Ok(());
+        }
+        if let Some(position) = extra
+            .iter()
+            .position(|stage| stage.access == StageAccess::Anyone)
+        {
+            return Err(ApprovalsError::Validation(format!(
+                "escalation stage {} for {:?} priority is open to anyone",
+                position + 1,
+                request.priority
+            )));
+        }
+        if request.stages.is_empty() {
+            request.stages.push(ApprovalStage::anyone());
+        }
+        // No appended stage is open to anyone, so the final (deciding) stage
+        // of the combined chain is always an explicit sign-off.
+        request.stages.extend(extra.iter().cloned());
+        Ok(())
+    }
+
+    fn pick_assignee(
+        &self,
+        tasks: &HashMap<ApprovalId, ApprovalTask>,
+        approvers: &[UserId],
+    ) -> Option<UserId> {
+        if approvers.is_empty() {
+            return None;
+        }
+        match self.auto_assign {
+            AutoAssignPolicy::None => None,
+            AutoAssignPolicy::RoundRobin => {
+                let cursor = self.round_robin_cursor.fetch_add(1, Ordering::Relaxed);
+                approvers.get(cursor % approvers.len()).cloned()
+            }
+            AutoAssignPolicy::LeastLoaded => approvers
+                .iter()
+                .min_by_key(|approver| {
+                    tasks
+                        .values()
+                        .filter(|task| {
+                            !task.is_finalized() && task.assigned_to.as_ref() == Some(*approver)
+                        })
+                        .count()
+                })
+                .cloned(),
+        }
+    }
+
+    fn persist(&self, task: &ApprovalTask) -> ApprovalsResult<()> {
+        match &self.store {
+            Some(store) => store.save_task(task),
+            None => Ok(()),
+        }
+    }
 }
 
 #[async_trait]
 impl ApprovalsService for InMemoryApprovalsService {
-    async fn enqueue(&self, request: ApprovalRequest) -> ApprovalsResult<ApprovalTask> {
+    async fn enqueue(&self, mut request: ApprovalRequest) -> ApprovalsResult<ApprovalTask> {
         request.validate()?;
+        self.prioritize_by_due_date(&mut request);
+        self.escalate(&mut request)?;
+        self.validate_approvers(&request).await?;
+        self.apply_sla(&mut request);
         let mut guard = self.tasks.write().await;
-        let task = ApprovalTask::new(request);
+        if guard.contains_key(&request.id) {
+            return Err(ApprovalsError::AlreadyExists(request.id));
+        }
+        let mut task = ApprovalTask::new(request);
+        if let Some(assignee) =
+            self.pick_assignee(&guard, task.request.stages[0].access.named_approvers())
+        {
+            task.assigned_to = Some(assignee);
+            task.status = ApprovalStatus::Assigned;
+        }
+        if let Some(store) = &self.store {
+            store.create_task(&task)?;
+        }
         guard.insert(task.request.id.clone(), task.clone());
         Ok(task)
     }
@@ -276,8 +1027,9 @@ impl ApprovalsService for InMemoryApprovalsService {
         assignee: UserId,
     ) -> ApprovalsResult<ApprovalTask> {
         let mut guard = self.tasks.write().await;
-        let task = guard
-            .get_mut(approval_id)
+        let mut task = guard
+            .get(approval_id)
+            .cloned()
             .ok_or_else(|| ApprovalsError::NotFound(approval_id.clone()))?;
         if task.is_finalized() {
             return Err(ApprovalsError::Finalized);
@@ -287,7 +1039,14 @@ impl ApprovalsService for InMemoryApprovalsService {
             .stages
             .get(task.current_stage_index)
             .ok_or_else(|| ApprovalsError::Validation("missing approval stage".into()))?;
-        if !stage.allows(&assignee) {
+        if !stage
+            .allows(
+                &task.request.company_id,
+                &assignee,
+                self.user_resolver.as_deref(),
+            )
+            .await?
+        {
             return Err(ApprovalsError::Validation(format!(
                 "{assignee} is not an approver for stage {}",
                 task.current_stage_index + 1
@@ -303,7 +1062,9 @@ impl ApprovalsService for InMemoryApprovalsService {
 
         task.assigned_to = Some(assignee);
         task.status = ApprovalStatus::Assigned;
-        Ok(task.clone())
+        self.persist(&task)?;
+        guard.insert(approval_id.clone(), task.clone());
+        Ok(task)
     }
 
     async fn unassign(
@@ -312,21 +1073,23 @@ impl ApprovalsService for InMemoryApprovalsService {
         actor: &UserId,
     ) -> ApprovalsResult<ApprovalTask> {
         let mut guard = self.tasks.write().await;
-        let task = guard
-            .get_mut(approval_id)
+        let mut task = guard
+            .get(approval_id)
+            .cloned()
             .ok_or_else(|| ApprovalsError::NotFound(approval_id.clone()))?;
         if task.is_finalized() {
             return Err(ApprovalsError::Finalized);
         }
         match &task.assigned_to {
-            Some(current) if current == actor => {
-                task.assigned_to = None;
-                task.status = ApprovalStatus::Pending;
-                Ok(task.clone())
-            }
-            Some(current) => Err(ApprovalsError::NotAssigned(current.clone())),
-            None => Err(ApprovalsError::NotAssigned(actor.clone())),
+            Some(current) if current == actor => {}
+            Some(current) => return Err(ApprovalsError::NotAssigned(current.clone())),
+            None => return Err(ApprovalsError::NotAssigned(actor.clone())),
         }
+        task.assigned_to = None;
+        task.status = ApprovalStatus::Pending;
+        self.persist(&task)?;
+        guard.insert(approval_id.clone(), task.clone());
+        Ok(task)
     }
 
     async fn decide(
@@ -335,8 +1098,9 @@ impl ApprovalsService for InMemoryApprovalsService {
         decision: DecisionInput,
     ) -> ApprovalsResult<ApprovalTask> {
         let mut guard = self.tasks.write().await;
-        let task = guard
-            .get_mut(approval_id)
+        let mut task = guard
+            .get(approval_id)
+            .cloned()
             .ok_or_else(|| ApprovalsError::NotFound(approval_id.clone()))?;
         if task.is_finalized() {
             return Err(ApprovalsError::Finalized);
@@ -346,7 +1110,14 @@ impl ApprovalsService for InMemoryApprovalsService {
             .stages
             .get(task.current_stage_index)
             .ok_or_else(|| ApprovalsError::Validation("missing approval stage".into()))?;
-        if !stage.allows(&decision.decided_by) {
+        if !stage
+            .allows(
+                &task.request.company_id,
+                &decision.decided_by,
+                self.user_resolver.as_deref(),
+            )
+            .await?
+        {
             return Err(ApprovalsError::Validation(format!(
                 "{} is not an approver for stage {}",
                 decision.decided_by,
@@ -384,7 +1155,9 @@ impl ApprovalsService for InMemoryApprovalsService {
                 task.decision = Some(record);
             }
         }
-        Ok(task.clone())
+        self.persist(&task)?;
+        guard.insert(approval_id.clone(), task.clone());
+        Ok(task)
     }
 
     async fn overdue(&self, now: DateTime<Utc>) -> ApprovalsResult<Vec<ApprovalTask>> {
@@ -398,30 +1171,78 @@ impl ApprovalsService for InMemoryApprovalsService {
         Ok(tasks)
     }
 
+    async fn scan_overdue_and_notify(
+        &self,
+        now: DateTime<Utc>,
+    ) -> ApprovalsResult<Vec<ApprovalTask>> {
+        let mut guard = self.tasks.write().await;
+        let mut ids = guard
+            .values()
+            .filter(|task| task.needs_escalation(now))
+            .map(|task| task.request.id.clone())
+            .collect::<Vec<_>>();
+        ids.sort();
+        let mut notified = Vec::with_capacity(ids.len());
+        for id in ids {
+            let Some(existing) = guard.get(&id) else {
+                continue;
+            };
+            let mut task = existing.clone();
+            task.last_escalated_at = Some(now);
+            self.persist(&task)?;
+            guard.insert(id, task.clone());
+            notified.push(task);
+        }
+        drop(guard);
+
+        let hook = self
+            .overdue_hook
+            .clone()
+            .unwrap_or_else(|| Arc::new(NoopOverdueHook));
+        for task in &notified {
+            hook.on_overdue(task).await;
+        }
+        Ok(notified)
+    }
+
     async fn export_queue(&self) -> ApprovalsResult<QueueExport> {
         let guard = self.tasks.read().await;
         let mut tasks = guard.values().cloned().collect::<Vec<_>>();
         tasks.sort_by(|a, b| a.request.id.cmp(&b.request.id));
+        let amounts_in_words = tasks
+            .iter()
+            .map(|task| (task.request.id.clone(), task.request.amount_in_words()))
+            .collect();
         Ok(QueueExport {
             generated_at: Utc::now(),
             tasks,
+            amounts_in_words,
         })
     }
+
+    async fn metrics(&self, filter: QueueFilter) -> ApprovalsResult<ApprovalsMetrics> {
+        let guard = self.tasks.read().await;
+        Ok(ApprovalsMetrics::from_tasks(
+            guard.values().filter(|task| filter.matches(task)),
+            Utc::now(),
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
-    use std::sync::Arc;
+    use tempfile::TempDir;
 
     fn make_request(company: &str, summary: &str) -> ApprovalRequest {
         let mut request = ApprovalRequest::new(company.into(), "user-1".into(), summary.into());
         request.amount_minor = 75_00;
         request.currency = "USD".into();
-        request.stages = vec![ApprovalStage {
-            approvers: vec!["approver-1".into(), "approver-2".into()],
-        }];
+        request.stages = vec![ApprovalStage::named(vec![
+            "approver-1".into(),
+            "approver-2".into(),
+        ])];
         request
     }
 
@@ -431,6 +1252,56 @@ mod tests {
         request
     }
 
+    #[test]
+    fn metadata_accepts_legacy_strings_and_objects() {
+        let mut encoded =
+            serde_json::to_value(make_request("comp-1", "Post invoice INV-1001")).expect("encode");
+        let fields = encoded.as_object_mut().expect("request object");
+
+        fields.insert(
+            "metadata".into(),
+            Value::String(r#"{"proposal_id":"pp-1","amount_minor":7500}"#.into()),
+        );
+        let legacy: ApprovalRequest =
+            serde_json::from_value(encoded.clone()).expect("legacy json string");
+        assert_eq!(
+            legacy.metadata_field("proposal_id"),
+            Some(&Value::String("pp-1".into()))
+        );
+
+        let fields = encoded.as_object_mut().expect("request object");
+        fields.insert("metadata".into(), Value::String("see INV-1001".into()));
+        let opaque: ApprovalRequest =
+            serde_json::from_value(encoded.clone()).expect("legacy opaque string");
+        assert_eq!(opaque.metadata, Value::String("see INV-1001".into()));
+        assert_eq!(opaque.metadata_field("proposal_id"), None);
+
+        for scalar in ["42", "true", "null", r#""quoted""#] {
+            let fields = encoded.as_object_mut().expect("request object");
+            fields.insert("metadata".into(), Value::String(scalar.into()));
+            let kept: ApprovalRequest =
+                serde_json::from_value(encoded.clone()).expect("legacy scalar string");
+            assert_eq!(kept.metadata, Value::String(scalar.into()));
+        }
+
+        let fields = encoded.as_object_mut().expect("request object");
+        fields.insert(
+            "metadata".into(),
+            serde_json::json!({ "link": "https://example.test/inv-1001" }),
+        );
+        let structured: ApprovalRequest =
+            serde_json::from_value(encoded.clone()).expect("object metadata");
+        assert_eq!(
+            structured.metadata_field("link"),
+            Some(&Value::String("https://example.test/inv-1001".into()))
+        );
+
+        let fields = encoded.as_object_mut().expect("request object");
+        fields.remove("metadata");
+        let missing: ApprovalRequest = serde_json::from_value(encoded).expect("missing metadata");
+        assert_eq!(missing.metadata, Value::Null);
+    }
+
     #[tokio::test]
     async fn enqueue_and_assign_flow() {
         let service: Arc<dyn ApprovalsService> = Arc::new(InMemoryApprovalsService::new());
@@ -553,12 +1424,8 @@ mod tests {
         let service: Arc<dyn ApprovalsService> = Arc::new(InMemoryApprovalsService::new());
         let mut request = make_request("comp-1", "Two stage approval");
         request.stages = vec![
-            ApprovalStage {
-                approvers: vec!["approver-1".into()],
-            },
-            ApprovalStage {
-                approvers: vec!["approver-2".into()],
-            },
+            ApprovalStage::named(vec!["approver-1".into()]),
+            ApprovalStage::named(vec!["approver-2".into()]),
         ];
         let task = service
             .enqueue(request)
@@ -616,12 +1483,8 @@ mod tests {
         let service: Arc<dyn ApprovalsService> = Arc::new(InMemoryApprovalsService::new());
         let mut request = make_request("comp-1", "Decline early");
         request.stages = vec![
-            ApprovalStage {
-                approvers: vec!["approver-1".into()],
-            },
-            ApprovalStage {
-                approvers: vec!["approver-2".into()],
-            },
+            ApprovalStage::named(vec!["approver-1".into()]),
+            ApprovalStage::named(vec!["approver-2".into()]),
         ];
         let task = service.enqueue(request).await.expect("enqueue");
         let declined = service
@@ -653,6 +1516,161 @@ mod tests {
         assert!(matches!(follow_up, ApprovalsError::Finalized));
     }
 
+    #[tokio::test]
+    async fn enqueue_applies_priority_sla_defaults() {
+        let service = InMemoryApprovalsService::new()
+            .with_sla_default(ApprovalPriority::High, Duration::hours(4))
+            .with_sla_default(ApprovalPriority::Normal, Duration::days(1));
+        let mut high = make_request_with_id("comp-1", "Wire", "high");
+        high.priority = ApprovalPriority::High;
+        let mut explicit = make_request_with_id("comp-1", "Bill", "explicit");
+        explicit.priority = ApprovalPriority::High;
+        let explicit_sla = explicit.submitted_at + Duration::minutes(10);
+        explicit.sla_at = Some(explicit_sla);
+        let mut low = make_request_with_id("comp-1", "Memo", "low");
+        low.priority = ApprovalPriority::Low;
+
+        let high_task = service.enqueue(high.clone()).await.expect("enqueue high");
+        assert_eq!(
+            high_task.request.sla_at,
+            Some(high.submitted_at + Duration::hours(4))
+        );
+        let explicit_task = service.enqueue(explicit).await.expect("enqueue explicit");
+        assert_eq!(explicit_task.request.sla_at, Some(explicit_sla));
+        let low_task = service.enqueue(low).await.expect("enqueue low");
+        assert_eq!(low_task.request.sla_at, None);
+    }
+
+    #[tokio::test]
+    async fn due_date_within_window_raises_priority_and_caps_sla() {
+        let service = InMemoryApprovalsService::new()
+            .with_due_date_window(Duration::days(3))
+            .with_sla_default(ApprovalPriority::High, Duration::days(2))
+            .with_escalation_stages(
+                ApprovalPriority::High,
+                vec![ApprovalStage::named(vec!["controller".into()])],
+            );
+
+        let mut imminent = make_request_with_id("comp-1", "Rent bill", "imminent");
+        imminent.priority = ApprovalPriority::Low;
+        let imminent_due = imminent.submitted_at + Duration::days(1);
+        imminent.due_date = Some(imminent_due);
+        let mut distant = make_request_with_id("comp-1", "Annual licence", "distant");
+        distant.due_date = Some(distant.submitted_at + Duration::days(30));
+        let mut explicit = make_request_with_id("comp-1", "Payroll", "explicit");
+        explicit.priority = ApprovalPriority::High;
+        explicit.due_date = Some(explicit.submitted_at + Duration::days(30));
+        let mut undated = make_request_with_id("comp-1", "Expense", "undated");
+        undated.priority = ApprovalPriority::Low;
+
+        let imminent_task = service.enqueue(imminent).await.expect("enqueue imminent");
+        assert_eq!(imminent_task.request.priority, ApprovalPriority::High);
+        assert_eq!(imminent_task.request.stages.len(), 2);
+        assert_eq!(imminent_task.request.sla_at, Some(imminent_due));
+
+        let distant_task = service
+            .enqueue(distant.clone())
+            .await
+            .expect("enqueue distant");
+        assert_eq!(distant_task.request.priority, ApprovalPriority::Normal);
+        assert_eq!(distant_task.request.sla_at, distant.due_date);
+
+        let explicit_task = service
+            .enqueue(explicit.clone())
+            .await
+            .expect("enqueue explicit");
+        assert_eq!(explicit_task.request.priority, ApprovalPriority::High);
+        assert_eq!(
+            explicit_task.request.sla_at,
+            Some(explicit.submitted_at + Duration::days(2))
+        );
+
+        let undated_task = service.enqueue(undated).await.expect("enqueue undated");
+        assert_eq!(undated_task.request.priority, ApprovalPriority::Low);
+        assert_eq!(undated_task.request.sla_at, None);
+    }
+
+    #[tokio::test]
+    async fn enqueue_rejects_unknown_or_inactive_approvers() {
+        use codex_tenancy::CreateFirmRequest;
+        use codex_tenancy::InMemoryTenancyService;
+        use codex_tenancy::InviteUserRequest;
+        use codex_tenancy::Role;
+        use codex_tenancy::RoleAssignment;
+        use codex_tenancy::TenancyActor;
+
+        let tenancy = Arc::new(InMemoryTenancyService::new());
+        let actor = TenancyActor::new("user-admin", vec![Role::Partner]);
+        let firm = tenancy
+            .create_firm(
+                CreateFirmRequest {
+                    name: "Demo Firm".into(),
+                    metadata: None,
+                },
+                &actor,
+            )
+            .await
+            .expect("create firm");
+        tenancy
+            .invite_user(
+                InviteUserRequest {
+                    firm_id: firm.id.clone(),
+                    email: "admin@example.com".into(),
+                    display_name: "admin@example.com".into(),
+                    roles: vec![RoleAssignment::firm(Role::Partner)],
+                },
+                &actor,
+            )
+            .await
+            .expect("first user becomes the actor's partner account");
+        let invite = |email: &str| InviteUserRequest {
+            firm_id: firm.id.clone(),
+            email: email.into(),
+            display_name: email.into(),
+            roles: vec![RoleAssignment::firm(Role::Senior)],
+        };
+        let active = tenancy
+            .invite_user(invite("active@example.com"), &actor)
+            .await
+            .expect("invite active");
+        tenancy
+            .update_user_status(&firm.id, &active.id, UserStatus::Active, &actor)
+            .await
+            .expect("activate user");
+        let invited = tenancy
+            .invite_user(invite("invited@example.com"), &actor)
+            .await
+            .expect("invite pending");
+
+        let service = InMemoryApprovalsService::new()
+            .with_user_resolver(Arc::new(TenancyUserResolver::new(tenancy, firm.id.clone())));
+        let with_approvers = |id: &str, approvers: Vec<UserId>| {
+            let mut request = make_request_with_id("comp-1", "Vendor bill", id);
+            request.stages = vec![ApprovalStage::anyone(), ApprovalStage::named(approvers)];
+            request
+        };
+
+        service
+            .enqueue(with_approvers("ok", vec![active.id.clone()]))
+            .await
+            .expect("active approver accepted");
+        let unknown = service
+            .enqueue(with_approvers("typo", vec!["aprover-1".into()]))
+            .await
+            .expect_err("unknown approver rejected");
+        assert!(
+            matches!(&unknown, ApprovalsError::Validation(message) if message.contains("aprover-1")),
+            "unexpected error: {unknown:?}"
+        );
+        let inactive = service
+            .enqueue(with_approvers("inactive", vec![invited.id.clone()]))
+            .await
+            .expect_err("invited approver rejected");
+        assert!(
+            matches!(&inactive, ApprovalsError::Validation(message) if message.contains(&invited.id))
+        );
+    }
+
     #[tokio::test]
     async fn overdue_reports_tasks_past_sla() {
         let service: Arc<dyn ApprovalsService> = Arc::new(InMemoryApprovalsService::new());
@@ -680,11 +1698,146 @@ mod tests {
         assert_eq!(overdue[0].request.id, "overdue");
     }
 
+    #[test]
+    fn metrics_summarize_decisions_and_overdue_tasks() {
+        let submitted_at = Utc::now() - Duration::hours(10);
+        let task = |id: &str, status: ApprovalStatus, decided_after: Option<i64>| {
+            let mut request = make_request("comp-1", id);
+            request.id = id.into();
+            request.submitted_at = submitted_at;
+            request.sla_at = Some(submitted_at + Duration::hours(1));
+            let mut task = ApprovalTask::new(request);
+            task.status = status;
+            task.decision = decided_after.map(|hours| DecisionRecord {
+                decision: if status == ApprovalStatus::Declined {
+                    ApprovalDecision::Declined
+                } else {
+                    ApprovalDecision::Approved
+                },
+                decided_by: "approver-1".into(),
+                decided_at: submitted_at + Duration::hours(hours),
+                reason: None,
+            });
+            task
+        };
+        let tasks = vec![
+            task("a", ApprovalStatus::Approved, Some(2)),
+            task("b", ApprovalStatus::Approved, Some(4)),
+            task("c", ApprovalStatus::Declined, Some(6)),
+            task("d", ApprovalStatus::Pending, None),
+        ];
+
+        let metrics = ApprovalsMetrics::from_tasks(&tasks, Utc::now());
+        assert_eq!(
+            metrics,
+            ApprovalsMetrics {
+                total: 4,
+                approved: 2,
+                declined: 1,
+                pending: 1,
+                overdue: 1,
+                approval_rate: 2.0 / 3.0,
+                decline_rate: 1.0 / 3.0,
+                average_decision_time: Some(Duration::hours(4)),
+            }
+        );
+
+        let no_tasks: Vec<ApprovalTask> = Vec::new();
+        let empty = ApprovalsMetrics::from_tasks(&no_tasks, Utc::now());
+        assert_eq!(empty.total, 0);
+        assert_eq!(empty.approval_rate, 0.0);
+        assert_eq!(empty.average_decision_time, None);
+    }
+
+    #[tokio::test]
+    async fn metrics_respect_queue_filter() {
+        let service = InMemoryApprovalsService::new();
+        let approved = service
+            .enqueue(make_request("comp-1", "Approve me"))
+            .await
+            .expect("enqueue");
+        service
+            .enqueue(make_request("comp-2", "Other company"))
+            .await
+            .expect("enqueue");
+        service
+            .decide(
+                &approved.request.id,
+                DecisionInput {
+                    decided_by: "approver-1".into(),
+                    decision: ApprovalDecision::Approved,
+                    reason: None,
+                },
+            )
+            .await
+            .expect("decide");
+
+        let metrics = service
+            .metrics(QueueFilter {
+                company_id: Some("comp-1".into()),
+                ..QueueFilter::default()
+            })
+            .await
+            .expect("metrics");
+        assert_eq!(metrics.total, 1);
+        assert_eq!(metrics.approved, 1);
+        assert_eq!(metrics.approval_rate, 1.0);
+        assert!(metrics.average_decision_time.is_some());
+    }
+
+    #[derive(Default)]
+    struct RecordingOverdueHook {
+        seen: tokio::sync::Mutex<Vec<ApprovalId>>,
+    }
+
+    #[async_trait]
+    impl OverdueHook for RecordingOverdueHook {
+        async fn on_overdue(&self, task: &ApprovalTask) {
+            self.seen.lock().await.push(task.request.id.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn scan_overdue_notifies_each_breach_once() {
+        let hook = Arc::new(RecordingOverdueHook::default());
+        let service = InMemoryApprovalsService::new().with_overdue_hook(hook.clone());
+        let mut overdue_request = make_request_with_id("comp-1", "Late", "late");
+        overdue_request.sla_at = Some(Utc::now() - chrono::Duration::minutes(5));
+        let mut upcoming_request = make_request_with_id("comp-1", "Soon", "soon");
+        upcoming_request.sla_at = Some(Utc::now() + chrono::Duration::minutes(5));
+        service
+            .enqueue(overdue_request)
+            .await
+            .expect("enqueue overdue");
+        service
+            .enqueue(upcoming_request)
+            .await
+            .expect("enqueue upcoming");
+
+        let now = Utc::now();
+        let notified = service
+            .scan_overdue_and_notify(now)
+            .await
+            .expect("first scan");
+        assert_eq!(notified.len(), 1);
+        assert_eq!(notified[0].last_escalated_at, Some(now));
+        let again = service
+            .scan_overdue_and_notify(now + chrono::Duration::minutes(1))
+            .await
+            .expect("second scan");
+        assert!(again.is_empty());
+        assert_eq!(*hook.seen.lock().await, vec!["late".to_string()]);
+
+        let stored = service.get(&"late".to_string()).await.expect("get late");
+        assert_eq!(stored.last_escalated_at, Some(now));
+    }
+
     #[tokio::test]
     async fn export_queue_serializes_current_state() {
         let service: Arc<dyn ApprovalsService> = Arc::new(InMemoryApprovalsService::new());
         let request_a = make_request_with_id("comp-1", "A", "task-a");
-        let request_b = make_request_with_id("comp-1", "B", "task-b");
+        let mut request_b = make_request_with_id("comp-1", "B", "task-b");
+        request_b.currency_precision = Some(3);
         service
             .enqueue(request_a)
             .await
@@ -699,5 +1852,315 @@ mod tests {
         assert!(export.generated_at <= Utc::now());
         assert_eq!(export.tasks[0].request.id, "task-a");
         assert_eq!(export.tasks[1].request.id, "task-b");
+        assert_eq!(
+            export.amounts_in_words.get("task-a").map(String::as_str),
+            Some("Seventy-five and 00/100 USD")
+        );
+        assert_eq!(
+            export.tasks[0].request.to_string(),
+            "A: Seventy-five and 00/100 USD"
+        );
+        assert_eq!(
+            export.amounts_in_words.get("task-b").map(String::as_str),
+            Some("Seven and 500/1000 USD")
+        );
+    }
+
+    #[tokio::test]
+    async fn enqueue_rejects_duplicate_ids() {
+        let service = InMemoryApprovalsService::new();
+        service
+            .enqueue(make_request_with_id("comp-1", "First", "dup"))
+            .await
+            .expect("first enqueue succeeds");
+        let err = service
+            .enqueue(make_request_with_id("comp-1", "Second", "dup"))
+            .await
+            .expect_err("duplicate enqueue should fail");
+        assert!(matches!(err, ApprovalsError::AlreadyExists(id) if id == "dup"));
+    }
+
+    #[tokio::test]
+    async fn json_file_store_survives_restart() {
+        let dir = TempDir::new().expect("temp dir");
+        {
+            let store = Arc::new(JsonFileApprovalsStore::new(dir.path()).expect("store"));
+            let service = InMemoryApprovalsService::with_store(store).expect("service");
+            service
+                .enqueue(make_request_with_id("comp-1", "Persisted", "task-p"))
+                .await
+                .expect("enqueue");
+            service
+                .assign(&"task-p".to_string(), "approver-1".into())
+                .await
+                .expect("assign");
+        }
+
+        let store = Arc::new(JsonFileApprovalsStore::new(dir.path()).expect("store"));
+        let service = InMemoryApprovalsService::with_store(store).expect("reload");
+        let task = service.get(&"task-p".to_string()).await.expect("task");
+        assert_eq!(task.status, ApprovalStatus::Assigned);
+        assert_eq!(task.assigned_to, Some("approver-1".into()));
+
+        let decided = service
+            .decide(
+                &"task-p".to_string(),
+                DecisionInput {
+                    decided_by: "approver-1".into(),
+                    decision: ApprovalDecision::Approved,
+                    reason: None,
+                },
+            )
+            .await
+            .expect("decide after restart");
+        assert_eq!(decided.status, ApprovalStatus::Approved);
+
+        let err = service
+            .enqueue(make_request_with_id("comp-1", "Again", "task-p"))
+            .await
+            .expect_err("persisted id conflicts");
+        assert!(matches!(err, ApprovalsError::AlreadyExists(_)));
+    }
+
+    #[test]
+    fn json_file_store_reports_conflicts_and_missing_tasks() {
+        let dir = TempDir::new().expect("temp dir");
+        let store = JsonFileApprovalsStore::new(dir.path()).expect("store");
+        let task = ApprovalTask::new(make_request_with_id("comp-1", "Store", "task-s"));
+
+        let err = store.save_task(&task).expect_err("save before create");
+        assert!(matches!(err, ApprovalsError::NotFound(_)));
+
+        store.create_task(&task).expect("create");
+        let err = store.create_task(&task).expect_err("duplicate create");
+        assert!(matches!(err, ApprovalsError::AlreadyExists(_)));
+
+        let bad = ApprovalTask::new(make_request_with_id("comp-1", "Bad", "../escape"));
+        let err = store.create_task(&bad).expect_err("unsafe id");
+        assert!(matches!(err, ApprovalsError::Storage(_)));
+
+        assert_eq!(store.list_tasks().expect("list"), vec![task]);
+    }
+
+    #[tokio::test]
+    async fn round_robin_distributes_new_tasks() {
+        let service =
+            InMemoryApprovalsService::new().with_auto_assign(AutoAssignPolicy::RoundRobin);
+        let mut assignees = Vec::new();
+        for summary in ["First", "Second", "Third"] {
+            let task = service
+                .enqueue(make_request("comp-1", summary))
+                .await
+                .expect("enqueue");
+            assert_eq!(task.status, ApprovalStatus::Assigned);
+            assignees.push(task.assigned_to.expect("auto-assigned"));
+        }
+        assert_eq!(assignees, vec!["approver-1", "approver-2", "approver-1"]);
+    }
+
+    #[tokio::test]
+    async fn least_loaded_prefers_free_approver() {
+        let service =
+            InMemoryApprovalsService::new().with_auto_assign(AutoAssignPolicy::LeastLoaded);
+        let first = service
+            .enqueue(make_request("comp-1", "First"))
+            .await
+            .expect("enqueue first");
+        assert_eq!(first.assigned_to, Some("approver-1".into()));
+
+        let second = service
+            .enqueue(make_request("comp-1", "Second"))
+            .await
+            .expect("enqueue second");
+        assert_eq!(second.assigned_to, Some("approver-2".into()));
+
+        service
+            .decide(
+                &first.request.id,
+                DecisionInput {
+                    decided_by: "approver-1".into(),
+                    decision: ApprovalDecision::Approved,
+                    reason: None,
+                },
+            )
+            .await
+            .expect("decide first");
+        let third = service
+            .enqueue(make_request("comp-1", "Third"))
+            .await
+            .expect("enqueue third");
+        assert_eq!(third.assigned_to, Some("approver-1".into()));
+    }
+
+    #[tokio::test]
+    async fn auto_assign_skips_open_stages() {
+        let service =
+            InMemoryApprovalsService::new().with_auto_assign(AutoAssignPolicy::RoundRobin);
+        let mut request = make_request("comp-1", "Anyone may approve");
+        request.stages.clear();
+        let task = service.enqueue(request).await.expect("enqueue");
+        assert_eq!(task.status, ApprovalStatus::Pending);
+        assert_eq!(task.assigned_to, None);
+    }
+
+    #[tokio::test]
+    async fn high_priority_requests_get_escalation_stages() {
+        let senior = ApprovalStage::named(vec!["controller".into()]);
+        let service = InMemoryApprovalsService::new()
+            .with_escalation_stages(ApprovalPriority::High, vec![senior.clone()]);
+
+        let mut urgent = make_request("comp-1", "Large wire");
+        urgent.priority = ApprovalPriority::High;
+        let task = service.enqueue(urgent).await.expect("enqueue high");
+        assert_eq!(task.request.stages.len(), 2);
+        assert_eq!(task.request.stages[1], senior);
+        assert_eq!(task.stage_decisions.len(), 2);
+
+        let routine = make_request("comp-1", "Office supplies");
+        let task = service.enqueue(routine).await.expect("enqueue normal");
+        assert_eq!(task.request.stages.len(), 1);
+
+        let mut open = make_request("comp-1", "Open first stage");
+        open.priority = ApprovalPriority::High;
+        open.stages.clear();
+        let task = service.enqueue(open).await.expect("enqueue open");
+        assert_eq!(task.request.stages[0], ApprovalStage::anyone());
+        assert_eq!(task.request.stages[1], senior);
+    }
+
+    #[test]
+    fn legacy_approver_lists_load_as_stage_access() {
+        let open: ApprovalStage =
+            serde_json::from_str(r#"{"approvers":[]}"#).expect("legacy open stage");
+        assert_eq!(open, ApprovalStage::anyone());
+        let missing: ApprovalStage = serde_json::from_str("{}").expect("stage without approvers");
+        assert_eq!(missing, ApprovalStage::anyone());
+        let named: ApprovalStage =
+            serde_json::from_str(r#"{"approvers":["approver-1"]}"#).expect("legacy named stage");
+        assert_eq!(named, ApprovalStage::named(vec!["approver-1".into()]));
+
+        let role = ApprovalStage::role(Role::Senior);
+        let encoded = serde_json::to_string(&role).expect("encode stage");
+        assert_eq!(encoded, r#"{"access":{"role":"senior"}}"#);
+        let decoded: ApprovalStage = serde_json::from_str(&encoded).expect("decode stage");
+        assert_eq!(decoded, role);
+    }
+
+    #[tokio::test]
+    async fn role_stages_check_membership_through_resolver() {
+        use codex_tenancy::CreateFirmRequest;
+        use codex_tenancy::InMemoryTenancyService;
+        use codex_tenancy::InviteUserRequest;
+        use codex_tenancy::RoleAssignment;
+        use codex_tenancy::TenancyActor;
+
+        let tenancy = Arc::new(InMemoryTenancyService::new());
+        let actor = TenancyActor::new("user-admin", vec![Role::Partner]);
+        let firm = tenancy
+            .create_firm(
+                CreateFirmRequest {
+                    name: "Demo Firm".into(),
+                    metadata: None,
+                },
+                &actor,
+            )
+            .await
+            .expect("create firm");
+        tenancy
+            .invite_user(
+                InviteUserRequest {
+                    firm_id: firm.id.clone(),
+                    email: "admin@example.com".into(),
+                    display_name: "admin@example.com".into(),
+                    roles: vec![RoleAssignment::firm(Role::Partner)],
+                },
+                &actor,
+            )
+            .await
+            .expect("first user becomes the actor's partner account");
+        let mut users = Vec::new();
+        for (email, role) in [
+            ("senior@example.com", RoleAssignment::firm(Role::Senior)),
+            ("staff@example.com", RoleAssignment::firm(Role::Staff)),
+        ] {
+            let user = tenancy
+                .invite_user(
+                    InviteUserRequest {
+                        firm_id: firm.id.clone(),
+                        email: email.into(),
+                        display_name: email.into(),
+                        roles: vec![role],
+                    },
+                    &actor,
+                )
+                .await
+                .expect("invite user");
+            tenancy
+                .update_user_status(&firm.id, &user.id, UserStatus::Active, &actor)
+                .await
+                .expect("activate user");
+            users.push(user.id);
+        }
+        let (senior, staff) = (users[0].clone(), users[1].clone());
+
+        let role_request = |id: &str| {
+            let mut request = make_request_with_id("comp-1", "Senior sign-off", id);
+            request.stages = vec![ApprovalStage::role(Role::Senior)];
+            request
+        };
+        let unresolved = InMemoryApprovalsService::new()
+            .enqueue(role_request("no-resolver"))
+            .await
+            .expect_err("role stage needs a resolver");
+        assert!(matches!(unresolved, ApprovalsError::Validation(_)));
+
+        let service = InMemoryApprovalsService::new()
+            .with_user_resolver(Arc::new(TenancyUserResolver::new(tenancy, firm.id.clone())));
+        service
+            .enqueue(role_request("senior"))
+            .await
+            .expect("enqueue role stage");
+        let err = service
+            .assign(&"senior".to_string(), staff)
+            .await
+            .expect_err("staff lacks the role");
+        assert!(matches!(err, ApprovalsError::Validation(_)));
+        let task = service
+            .decide(
+                &"senior".to_string(),
+                DecisionInput {
+                    decision: ApprovalDecision::Approved,
+                    decided_by: senior,
+                    reason: None,
+                },
+            )
+            .await
+            .expect("senior approves");
+        assert_eq!(task.status, ApprovalStatus::Approved);
+
+        let mut nobody = make_request_with_id("comp-1", "Empty list", "nobody");
+        nobody.stages = vec![ApprovalStage::named(Vec::new())];
+        let err = service
+            .enqueue(nobody)
+            .await
+            .expect_err("named stage without approvers");
+        assert!(matches!(err, ApprovalsError::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn escalation_stages_require_approvers() {
+        let service = InMemoryApprovalsService::new()
+            .with_escalation_stages(ApprovalPriority::High, vec![ApprovalStage::anyone()]);
+        let mut request = make_request("comp-1", "Large wire");
+        request.priority = ApprovalPriority::High;
+        let err = service.enqueue(request).await.expect_err("empty stage");
+        assert!(matches!(err, ApprovalsError::Validation(_)));
+        assert!(
+            service
+                .list(QueueFilter::default())
+                .await
+                .expect("list")
+                .is_empty()
+        );
     }
 }
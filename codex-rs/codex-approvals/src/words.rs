@@ -0,0 +1,153 @@
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: [&str; 7] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+];
+
+/// The most minor-unit digits an `i64` amount can need.
+const MAX_PRECISION: u8 = 19;
+
+/// Spells out an amount the way it is written on a cheque, e.g.
+/// `format_amount_words(120_000, "USD", 2)` is
+/// `"One thousand two hundred and 00/100 USD"`. `precision` is the number of
+/// minor-unit digits; the fraction is omitted when it is zero, and
+/// precisions above 19 are treated as 19. Negative amounts start with
+/// `Minus`.
+#[must_use]
+pub fn format_amount_words(amount_minor: i64, currency: &str, precision: u8) -> String {
+    let precision = u32::from(precision.min(MAX_PRECISION));
+    let divisor = 10_u64.pow(precision);
+    let magnitude = amount_minor.unsigned_abs();
+    let mut words = integer_words(magnitude / divisor);
+    if amount_minor < 0 {
+        words = format!("minus {words}");
+    }
+    let mut spelled = capitalize(&words);
+    if precision > 0 {
+        let width = precision as usize;
+        let fraction = magnitude % divisor;
+        spelled.push_str(&format!(" and {fraction:0width$}/{divisor}"));
+    }
+    format!("{spelled} {currency}")
+}
+
+fn integer_words(value: u64) -> String {
+    if value == 0 {
+        return ONES[0].to_string();
+    }
+    let mut groups = Vec::new();
+    let mut remaining = value;
+    let mut scale = 0;
+    while remaining > 0 {
+        let group = remaining % 1_000;
+        if group > 0 {
+            let words = group_words(group);
+            groups.push(match SCALES[scale] {
+                "" => words,
+                name => format!("{words} {name}"),
+            });
+        }
+        remaining /= 1_000;
+        scale += 1;
+    }
+    groups.reverse();
+    groups.join(" ")
+}
+
+fn group_words(group: u64) -> String {
+    let hundreds = (group / 100) as usize;
+    let rest = (group % 100) as usize;
+    let mut parts = Vec::new();
+    if hundreds > 0 {
+        parts.push(format!("{} hundred", ONES[hundreds]));
+    }
+    if rest >= 20 {
+        parts.push(match rest % 10 {
+            0 => TENS[rest / 10].to_string(),
+            ones => format!("{}-{}", TENS[rest / 10], ONES[ones]),
+        });
+    } else if rest > 0 {
+        parts.push(ONES[rest].to_string());
+    }
+    parts.join(" ")
+}
+
+fn capitalize(words: &str) -> String {
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn spells_out_amounts_cheque_style() {
+        assert_eq!(
+            format_amount_words(120_000, "USD", 2),
+            "One thousand two hundred and 00/100 USD"
+        );
+        assert_eq!(format_amount_words(0, "USD", 2), "Zero and 00/100 USD");
+        assert_eq!(
+            format_amount_words(-4_205, "EUR", 2),
+            "Minus forty-two and 05/100 EUR"
+        );
+        assert_eq!(
+            format_amount_words(1_000_019, "JPY", 0),
+            "One million nineteen JPY"
+        );
+        assert_eq!(
+            format_amount_words(12_345, "KWD", 3),
+            "Twelve and 345/1000 KWD"
+        );
+        assert_eq!(
+            format_amount_words(12_345, "USD", 3),
+            "Twelve and 345/1000 USD"
+        );
+        assert_eq!(
+            format_amount_words(7, "XTS", u8::MAX),
+            "Zero and 0000000000000000007/10000000000000000000 XTS"
+        );
+        assert_eq!(
+            format_amount_words(i64::MIN, "USD", 2),
+            "Minus ninety-two quadrillion two hundred thirty-three trillion seven hundred \
+             twenty billion three hundred sixty-eight million five hundred forty-seven \
+             thousand seven hundred fifty-eight and 08/100 USD"
+        );
+    }
+}
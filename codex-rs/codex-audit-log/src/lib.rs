@@ -1,5 +1,6 @@
 #![deny(clippy::print_stdout, clippy::print_stderr)]
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -10,6 +11,7 @@ use serde::Serialize;
 use sha2::Digest;
 use sha2::Sha256;
 use thiserror::Error;
+use tokio::sync::Mutex;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
@@ -32,6 +34,10 @@ pub struct AppendRequest {
     pub action: String,
     #[serde(default)]
     pub metadata: serde_json::Value,
+    /// Client-chosen id for retry safety: appending a request id the log has
+    /// already seen returns the original record instead of a new one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +50,10 @@ pub struct AuditRecord {
     pub metadata: serde_json::Value,
     pub previous_hash: String,
     pub hash: String,
+    /// The `AppendRequest::request_id` this record was created for; part of
+    /// the hashed payload so a dedupe key cannot be rewritten unnoticed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -59,9 +69,40 @@ pub trait AuditLog: Send + Sync {
     async fn records(&self, filter: AuditLogFilter) -> AuditLogResult<Vec<AuditRecord>>;
 }
 
-#[derive(Default)]
+/// Serialized metadata size accepted by `InMemoryAuditLog::shared()`.
+pub const DEFAULT_MAX_METADATA_BYTES: usize = 16 * 1024;
+
+const GENESIS_HASH: &str = "genesis";
+
 pub struct InMemoryAuditLog {
     records: RwLock<Vec<AuditRecord>>,
+    /// Prefix of `records` whose chain has already been checked. Locked only
+    /// while `records` is held.
+    verified: Mutex<VerifiedHead>,
+    /// Index into `records` of the record created for each request id.
+    /// Locked only while `records` is held for writing.
+    request_ids: Mutex<HashMap<String, usize>>,
+    max_metadata_bytes: Option<usize>,
+}
+
+struct VerifiedHead {
+    len: usize,
+    hash: String,
+}
+
+impl Default for VerifiedHead {
+    fn default() -> Self {
+        Self {
+            len: 0,
+            hash: GENESIS_HASH.into(),
+        }
+    }
+}
+
+impl Default for InMemoryAuditLog {
+    fn default() -> Self {
+        Self::new_with_limits(Some(DEFAULT_MAX_METADATA_BYTES))
+    }
 }
 
 impl InMemoryAuditLog {
@@ -70,12 +111,33 @@ impl InMemoryAuditLog {
         Arc::new(Self::default())
     }
 
+    /// Creates a log that rejects metadata whose serialized JSON exceeds
+    /// `max_metadata_bytes`; `None` disables the size check.
+    #[must_use]
+    pub fn new_with_limits(max_metadata_bytes: Option<usize>) -> Self {
+        Self {
+            records: RwLock::new(Vec::new()),
+            verified: Mutex::new(VerifiedHead::default()),
+            request_ids: Mutex::new(HashMap::new()),
+            max_metadata_bytes,
+        }
+    }
+
+    /// Chains the first record off `hash` instead of the genesis hash, so a
+    /// log that resumes a persisted trail stays one chain across runs.
+    #[must_use]
+    pub fn with_previous_hash(mut self, hash: impl Into<String>) -> Self {
+        self.verified.get_mut().hash = hash.into();
+        self
+    }
+
     fn compute_hash(
         previous: &str,
         entity_id: &str,
         action: &str,
         occurred_at: DateTime<Utc>,
         metadata: &serde_json::Value,
+        request_id: Option<&str>,
     ) -> String {
         let mut hasher = Sha256::new();
         hasher.update(previous.as_bytes());
@@ -86,10 +148,13 @@ impl InMemoryAuditLog {
             .unwrap_or_else(|| occurred_at.timestamp_micros() * 1_000);
         hasher.update(nanos.to_be_bytes());
         hasher.update(metadata.to_string().as_bytes());
+        if let Some(request_id) = request_id {
+            hasher.update(request_id.as_bytes());
+        }
         format!("{:x}", hasher.finalize())
     }
 
-    fn validate_request(request: &AppendRequest) -> AuditLogResult<()> {
+    fn validate_request(&self, request: &AppendRequest) -> AuditLogResult<()> {
         if request.entity_id.trim().is_empty() {
             return Err(AuditLogError::Validation(
                 "entity_id must be provided".into(),
@@ -101,11 +166,68 @@ impl InMemoryAuditLog {
         if request.actor.trim().is_empty() {
             return Err(AuditLogError::Validation("actor must be provided".into()));
         }
+        if request
+            .request_id
+            .as_deref()
+            .is_some_and(|request_id| request_id.trim().is_empty())
+        {
+            return Err(AuditLogError::Validation(
+                "request_id must not be blank".into(),
+            ));
+        }
+        self.validate_metadata(&request.metadata)
+    }
+
+    /// Metadata must be a JSON object, or null when omitted.
+    fn validate_metadata(&self, metadata: &serde_json::Value) -> AuditLogResult<()> {
+        if !(metadata.is_object() || metadata.is_null()) {
+            return Err(AuditLogError::Validation(
+                "metadata must be a JSON object".into(),
+            ));
+        }
+        if let Some(limit) = self.max_metadata_bytes {
+            let size = metadata.to_string().len();
+            if size > limit {
+                return Err(AuditLogError::Validation(format!(
+                    "metadata is {size} bytes; limit is {limit}"
+                )));
+            }
+        }
         Ok(())
     }
 
-    async fn verify_chain(records: &[AuditRecord]) -> AuditLogResult<()> {
-        let mut previous = String::from("genesis");
+    /// Verifies records appended since the last check and advances the
+    /// cached head, so repeated reads only hash new records. Tampering with an
+    /// already-verified record is not re-detected.
+    async fn verify_new_records(&self, records: &[AuditRecord]) -> AuditLogResult<String> {
+        let mut verified = self.verified.lock().await;
+        let Some(unverified) = records.get(verified.len..) else {
+            return Err(AuditLogError::Corrupted(format!(
+                "log has {} records but {} were verified",
+                records.len(),
+                verified.len
+            )));
+        };
+        if let Some(last_verified) = verified
+            .len
+            .checked_sub(1)
+            .and_then(|index| records.get(index))
+            && last_verified.hash != verified.hash
+        {
+            return Err(AuditLogError::Corrupted(format!(
+                "verified head {} was modified",
+                last_verified.id
+            )));
+        }
+        let head = Self::verify_chain(&verified.hash, unverified)?;
+        verified.len = records.len();
+        verified.hash = head.clone();
+        Ok(head)
+    }
+
+    /// Checks that `records` chain off `previous` and returns the last hash.
+    fn verify_chain(previous: &str, records: &[AuditRecord]) -> AuditLogResult<String> {
+        let mut previous = previous.to_string();
         for record in records {
             if record.previous_hash != previous {
                 return Err(AuditLogError::Corrupted(format!(
@@ -119,6 +241,7 @@ impl InMemoryAuditLog {
                 &record.action,
                 record.occurred_at,
                 &record.metadata,
+                record.request_id.as_deref(),
             );
             if expected != record.hash {
                 return Err(AuditLogError::Corrupted(format!(
@@ -128,20 +251,27 @@ impl InMemoryAuditLog {
             }
             previous = record.hash.clone();
         }
-        Ok(())
+        Ok(previous)
     }
 }
 
 #[async_trait]
 impl AuditLog for InMemoryAuditLog {
     async fn append(&self, request: AppendRequest) -> AuditLogResult<AuditRecord> {
-        Self::validate_request(&request)?;
+        self.validate_request(&request)?;
 
         let mut guard = self.records.write().await;
-        let previous_hash = guard
-            .last()
-            .map(|record| record.hash.clone())
-            .unwrap_or_else(|| "genesis".into());
+        let mut request_ids = self.request_ids.lock().await;
+        if let Some(request_id) = &request.request_id
+            && let Some(existing) = request_ids.get(request_id)
+        {
+            return guard.get(*existing).cloned().ok_or_else(|| {
+                AuditLogError::Corrupted(format!(
+                    "request {request_id} points past the end of the log"
+                ))
+            });
+        }
+        let previous_hash = self.verify_new_records(&guard).await?;
 
         let occurred_at = Utc::now();
         let hash = Self::compute_hash(
@@ -150,6 +280,7 @@ impl AuditLog for InMemoryAuditLog {
             &request.action,
             occurred_at,
             &request.metadata,
+            request.request_id.as_deref(),
         );
 
         let record = AuditRecord {
@@ -161,15 +292,19 @@ impl AuditLog for InMemoryAuditLog {
             metadata: request.metadata,
             previous_hash,
             hash,
+            request_id: request.request_id,
         };
 
+        if let Some(request_id) = &record.request_id {
+            request_ids.insert(request_id.clone(), guard.len());
+        }
         guard.push(record.clone());
         Ok(record)
     }
 
     async fn records(&self, filter: AuditLogFilter) -> AuditLogResult<Vec<AuditRecord>> {
         let guard = self.records.read().await;
-        Self::verify_chain(&guard).await?;
+        self.verify_new_records(&guard).await?;
         let mut filtered = guard.clone();
 
         if let Some(entity_id) = filter.entity_id {
@@ -201,6 +336,7 @@ mod tests {
                 actor: "system".into(),
                 action: "created".into(),
                 metadata: serde_json::json!({"source": "test"}),
+                request_id: None,
             })
             .await
             .expect("append record");
@@ -214,6 +350,7 @@ mod tests {
                 actor: "user".into(),
                 action: "updated".into(),
                 metadata: serde_json::json!({"field": "status"}),
+                request_id: None,
             })
             .await
             .expect("append record");
@@ -227,6 +364,85 @@ mod tests {
         assert_eq!(records.len(), 2);
     }
 
+    #[tokio::test]
+    async fn resumed_logs_chain_off_the_previous_hash() {
+        let log = InMemoryAuditLog::default().with_previous_hash("abc123");
+        let request = || AppendRequest {
+            entity_id: "entity".into(),
+            actor: "user".into(),
+            action: "updated".into(),
+            metadata: serde_json::json!({}),
+            request_id: None,
+        };
+
+        let first = log.append(request()).await.expect("append");
+        assert_eq!(first.previous_hash, "abc123");
+        let second = log.append(request()).await.expect("append");
+        assert_eq!(second.previous_hash, first.hash);
+
+        let records = log
+            .records(AuditLogFilter::default())
+            .await
+            .expect("records");
+        assert_eq!(records.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn retried_request_ids_return_the_original_record() {
+        let log = InMemoryAuditLog::shared();
+        let request = |action: &str, request_id: Option<&str>| AppendRequest {
+            entity_id: "entity".into(),
+            actor: "user".into(),
+            action: action.into(),
+            metadata: serde_json::json!({}),
+            request_id: request_id.map(str::to_string),
+        };
+
+        let first = log
+            .append(request("created", Some("req-1")))
+            .await
+            .expect("append");
+        let retried = log
+            .append(request("created", Some("req-1")))
+            .await
+            .expect("retry");
+        assert_eq!(retried.id, first.id);
+        assert_eq!(retried.hash, first.hash);
+        assert_eq!(retried.request_id.as_deref(), Some("req-1"));
+
+        let next = log
+            .append(request("updated", Some("req-2")))
+            .await
+            .expect("append");
+        assert_eq!(next.previous_hash, first.hash);
+        log.append(request("updated", None))
+            .await
+            .expect("append without request id");
+
+        let records = log
+            .records(AuditLogFilter::default())
+            .await
+            .expect("records");
+        assert_eq!(records.len(), 3);
+
+        let err = log
+            .append(request("updated", Some("  ")))
+            .await
+            .expect_err("blank request id");
+        assert!(matches!(err, AuditLogError::Validation(_)));
+
+        {
+            let mut guard = log.records.write().await;
+            guard[1].request_id = Some("req-forged".into());
+        }
+        *log.verified.lock().await = VerifiedHead::default();
+        let err = log
+            .records(AuditLogFilter::default())
+            .await
+            .expect_err("rewritten request id");
+        assert!(matches!(err, AuditLogError::Corrupted(_)));
+    }
+
     #[tokio::test]
     async fn detects_tampering() {
         let log = InMemoryAuditLog::shared();
@@ -236,6 +452,7 @@ mod tests {
             actor: "user".into(),
             action: "created".into(),
             metadata: serde_json::json!({}),
+            request_id: None,
         })
         .await
         .expect("append");
@@ -245,6 +462,7 @@ mod tests {
             actor: "user".into(),
             action: "updated".into(),
             metadata: serde_json::json!({}),
+            request_id: None,
         })
         .await
         .expect("append");
@@ -257,4 +475,113 @@ mod tests {
         let err = log.records(AuditLogFilter::default()).await.unwrap_err();
         assert!(matches!(err, AuditLogError::Corrupted(_)));
     }
+
+    #[tokio::test]
+    async fn reads_only_verify_records_appended_since_last_check() {
+        let log = InMemoryAuditLog::new_with_limits(None);
+        let append = |index: usize| AppendRequest {
+            entity_id: format!("entity-{}", index % 10),
+            actor: "system".into(),
+            action: "updated".into(),
+            metadata: serde_json::json!({ "index": index }),
+            request_id: None,
+        };
+        for index in 0..10_000 {
+            log.append(append(index)).await.expect("append");
+        }
+        // Each append verifies the record before it, leaving only the tail.
+        assert_eq!(log.verified.lock().await.len, 9_999);
+
+        let records = log
+            .records(AuditLogFilter::default())
+            .await
+            .expect("records");
+        assert_eq!(records.len(), 10_000);
+        {
+            let verified = log.verified.lock().await;
+            assert_eq!(verified.len, 10_000);
+            assert_eq!(verified.hash, records[9_999].hash);
+        }
+
+        let latest = log.append(append(10_000)).await.expect("append");
+        assert_eq!(latest.previous_hash, records[9_999].hash);
+        let filtered = log
+            .records(AuditLogFilter {
+                entity_id: Some("entity-0".into()),
+                limit: Some(5),
+            })
+            .await
+            .expect("filtered records");
+        assert_eq!(filtered.len(), 5);
+        assert_eq!(log.verified.lock().await.len, 10_001);
+
+        {
+            let mut guard = log.records.write().await;
+            guard[10_000].hash = "tampered".into();
+        }
+        let err = log
+            .append(append(10_001))
+            .await
+            .expect_err("append refuses a broken chain");
+        assert!(matches!(err, AuditLogError::Corrupted(_)));
+    }
+
+    #[tokio::test]
+    async fn rejects_non_object_metadata() {
+        let log = InMemoryAuditLog::shared();
+
+        let err = log
+            .append(AppendRequest {
+                entity_id: "entity".into(),
+                actor: "user".into(),
+                action: "created".into(),
+                metadata: serde_json::json!(["not", "an", "object"]),
+                request_id: None,
+            })
+            .await
+            .expect_err("array metadata rejected");
+        assert!(matches!(err, AuditLogError::Validation(_)));
+
+        log.append(AppendRequest {
+            entity_id: "entity".into(),
+            actor: "user".into(),
+            action: "created".into(),
+            metadata: serde_json::Value::Null,
+            request_id: None,
+        })
+        .await
+        .expect("omitted metadata accepted");
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_metadata() {
+        let log = InMemoryAuditLog::new_with_limits(Some(32));
+
+        let err = log
+            .append(AppendRequest {
+                entity_id: "entity".into(),
+                actor: "user".into(),
+                action: "created".into(),
+                metadata: serde_json::json!({"blob": "x".repeat(64)}),
+                request_id: None,
+            })
+            .await
+            .expect_err("oversized metadata rejected");
+        match err {
+            AuditLogError::Validation(message) => assert!(message.contains("limit is 32")),
+            other => panic!("unexpected error {other:?}"),
+        }
+
+        let unlimited = InMemoryAuditLog::new_with_limits(None);
+        unlimited
+            .append(AppendRequest {
+                entity_id: "entity".into(),
+                actor: "user".into(),
+                action: "created".into(),
+                metadata: serde_json::json!({"blob": "x".repeat(64)}),
+                request_id: None,
+            })
+            .await
+            .expect("no limit configured");
+    }
 }
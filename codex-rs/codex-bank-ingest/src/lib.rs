@@ -1,8 +1,18 @@
 #![deny(clippy::print_stdout, clippy::print_stderr)]
 
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Lines;
+use std::io::Read;
 
+use chrono::DateTime;
+use chrono::Duration;
 use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::Utc;
 use csv::StringRecord;
 use serde::Deserialize;
 use serde::Serialize;
@@ -18,6 +28,35 @@ fn default_amount_factor() -> i64 {
     100
 }
 
+fn default_checksum_fields() -> Vec<ChecksumField> {
+    DEFAULT_CHECKSUM_FIELDS.to_vec()
+}
+
+fn default_has_headers() -> bool {
+    true
+}
+
+/// Transaction fields that can feed a computed checksum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumField {
+    TransactionId,
+    AccountId,
+    PostedDate,
+    Amount,
+    Currency,
+    Description,
+    SourceReference,
+}
+
+/// Fields hashed when a profile does not choose its own.
+pub const DEFAULT_CHECKSUM_FIELDS: [ChecksumField; 4] = [
+    ChecksumField::TransactionId,
+    ChecksumField::AccountId,
+    ChecksumField::PostedDate,
+    ChecksumField::Amount,
+];
+
 #[derive(Debug, Error)]
 pub enum BankIngestError {
     #[error("parser not implemented: {0}")]
@@ -82,10 +121,18 @@ pub struct NormalizedBankTransaction {
     pub source_checksum: Option<String>,
     #[serde(default)]
     pub is_void: bool,
+    /// Set when `source_checksum` was computed from `checksum_fields` because
+    /// the source did not supply one.
+    #[serde(default)]
+    pub checksum_computed: bool,
     #[serde(default)]
     pub duplicate_metadata: DuplicateMetadata,
     #[serde(default)]
     pub currency_validation: CurrencyValidation,
+    /// Issuing institution, stamped from the statement header when the parser
+    /// profile asks for it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub institution: Option<String>,
 }
 
 impl NormalizedBankTransaction {
@@ -99,25 +146,39 @@ impl NormalizedBankTransaction {
         )
     }
 
-    fn build_checksum_fields(&self) -> [String; 4] {
-        [
-            self.transaction_id.clone(),
-            self.account_id.clone(),
-            self.posted_date.to_string(),
-            self.amount_minor.to_string(),
-        ]
+    fn build_checksum_fields(&self, fields: &[ChecksumField]) -> Vec<String> {
+        let fields = if fields.is_empty() {
+            &DEFAULT_CHECKSUM_FIELDS[..]
+        } else {
+            fields
+        };
+        fields
+            .iter()
+            .map(|field| match field {
+                ChecksumField::TransactionId => self.transaction_id.clone(),
+                ChecksumField::AccountId => self.account_id.clone(),
+                ChecksumField::PostedDate => self.posted_date.to_string(),
+                ChecksumField::Amount => self.amount_minor.to_string(),
+                ChecksumField::Currency => self.currency.clone(),
+                ChecksumField::Description => self.description.clone(),
+                ChecksumField::SourceReference => self.source_reference.clone().unwrap_or_default(),
+            })
+            .collect()
     }
 
-    fn ensure_checksum(&mut self) {
+    /// Computes `source_checksum` from `fields` when the source did not supply
+    /// one. An empty field list falls back to `DEFAULT_CHECKSUM_FIELDS`.
+    fn ensure_checksum(&mut self, fields: &[ChecksumField]) {
         if self.source_checksum.is_some() {
             return;
         }
-        let joined = self.build_checksum_fields();
+        let joined = self.build_checksum_fields(fields);
         self.source_checksum = Some(compute_checksum(&joined));
+        self.checksum_computed = true;
     }
 }
 
-fn compute_checksum(fields: &[String; 4]) -> String {
+fn compute_checksum(fields: &[String]) -> String {
     let mut hasher = Sha256::new();
     for field in fields {
         hasher.update(field.as_bytes());
@@ -126,6 +187,163 @@ fn compute_checksum(fields: &[String; 4]) -> String {
     format!("{:x}", hasher.finalize())
 }
 
+/// Transactions plus the statement-level closing balance and sign-on
+/// header, when the format carries them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParsedStatement {
+    pub transactions: Vec<NormalizedBankTransaction>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closing_balance_minor: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<NaiveDate>,
+    /// Institution name (OFX `<FI><ORG>`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub institution: Option<String>,
+    /// Institution identifier (OFX `<FI><FID>`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub institution_id: Option<String>,
+    /// When the institution generated the file (OFX `<DTSERVER>`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_time: Option<DateTime<Utc>>,
+}
+
+impl ParsedStatement {
+    /// Sum of `amount_minor` across non-void transactions.
+    #[must_use]
+    pub fn ingested_total_minor(&self) -> i64 {
+        self.transactions
+            .iter()
+            .filter(|transaction| !transaction.is_void)
+            .map(|transaction| transaction.amount_minor)
+            .sum()
+    }
+}
+
+/// Debit and credit totals for one currency of an import batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CurrencyTotals {
+    /// Sum of outflows (negative amounts) as a positive number.
+    pub debits_minor: i64,
+    pub credits_minor: i64,
+}
+
+impl CurrencyTotals {
+    /// Credits minus debits. Both totals are non-negative, so this cannot
+    /// overflow.
+    #[must_use]
+    pub fn net_minor(&self) -> i64 {
+        self.credits_minor - self.debits_minor
+    }
+}
+
+/// Post-ingest sanity totals for an import batch. Void rows are counted in
+/// `row_count` and `void_count` but excluded from the per-currency totals.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub row_count: usize,
+    /// Debit and credit totals keyed by currency code; amounts in different
+    /// currencies are never added together.
+    pub totals: BTreeMap<String, CurrencyTotals>,
+    pub void_count: usize,
+    pub currencies: BTreeSet<String>,
+    /// Earliest and latest posted date across every row.
+    pub date_range: Option<(NaiveDate, NaiveDate)>,
+}
+
+impl ImportSummary {
+    /// Summarizes `transactions`, failing with `Invalid` when a currency's
+    /// debit or credit total overflows `i64`.
+    pub fn from_transactions(
+        transactions: &[NormalizedBankTransaction],
+    ) -> Result<Self, BankIngestError> {
+        let overflow = |transaction: &NormalizedBankTransaction| {
+            BankIngestError::Invalid(format!(
+                "{} totals overflow at transaction {}",
+                transaction.currency, transaction.transaction_id
+            ))
+        };
+        let mut summary = Self::default();
+        for transaction in transactions {
+            summary.row_count += 1;
+            summary.currencies.insert(transaction.currency.clone());
+            let date = transaction.posted_date;
+            summary.date_range = Some(match summary.date_range {
+                Some((first, last)) => (first.min(date), last.max(date)),
+                None => (date, date),
+            });
+            if transaction.is_void {
+                summary.void_count += 1;
+                continue;
+            }
+            let totals = summary
+                .totals
+                .entry(transaction.currency.clone())
+                .or_default();
+            let (total, amount) = if transaction.amount_minor < 0 {
+                (
+                    &mut totals.debits_minor,
+                    transaction.amount_minor.checked_neg(),
+                )
+            } else {
+                (&mut totals.credits_minor, Some(transaction.amount_minor))
+            };
+            *total = amount
+                .and_then(|amount| total.checked_add(amount))
+                .ok_or_else(|| overflow(transaction))?;
+        }
+        Ok(summary)
+    }
+
+    /// Credits minus debits in `currency`, or `None` when the batch has no
+    /// non-void rows in it.
+    #[must_use]
+    pub fn net_minor(&self, currency: &str) -> Option<i64> {
+        self.totals.get(currency).map(CurrencyTotals::net_minor)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceReconciliation {
+    pub expected: i64,
+    pub actual: i64,
+    pub difference_minor: i64,
+}
+
+/// Confirms that `opening_balance_minor` plus the ingested transaction total
+/// ties out to the statement closing balance within `tolerance_minor`.
+pub fn reconcile_statement_balance(
+    statement: &ParsedStatement,
+    opening_balance_minor: i64,
+    ingested_total_minor: i64,
+    tolerance_minor: i64,
+) -> Result<BalanceReconciliation, BankIngestError> {
+    let expected = statement.closing_balance_minor.ok_or_else(|| {
+        BankIngestError::Invalid("statement does not report a closing balance".into())
+    })?;
+    let actual = opening_balance_minor
+        .checked_add(ingested_total_minor)
+        .ok_or_else(|| BankIngestError::Parse("balance overflow".into()))?;
+    let difference_minor = actual
+        .checked_sub(expected)
+        .ok_or_else(|| BankIngestError::Parse("balance difference overflow".into()))?;
+    let distance = difference_minor
+        .checked_abs()
+        .ok_or_else(|| BankIngestError::Parse("balance difference overflow".into()))?;
+    let tolerance = tolerance_minor
+        .checked_abs()
+        .ok_or_else(|| BankIngestError::Invalid("tolerance is out of range".into()))?;
+    if distance > tolerance {
+        return Err(BankIngestError::Invalid(format!(
+            "statement balance does not tie out: expected {expected}, actual {actual}, difference {difference_minor}"
+        )));
+    }
+    Ok(BalanceReconciliation {
+        expected,
+        actual,
+        difference_minor,
+    })
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct DedupeMetrics {
     pub kept: usize,
@@ -138,8 +356,51 @@ pub struct DedupeOutcome {
     pub metrics: DedupeMetrics,
 }
 
+/// Which transaction of a duplicate group `dedupe_transactions_with` keeps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupePrimarySelector {
+    /// The first transaction in input order.
+    #[default]
+    FirstSeen,
+    /// The first non-void transaction, preferring ones whose `source_checksum`
+    /// was supplied by the source rather than computed on ingest; falls back
+    /// to input order among equals.
+    PreferComplete,
+}
+
+impl DedupePrimarySelector {
+    /// Position of the primary within `entries`, which are sorted by index.
+    fn select(self, entries: &[(usize, NormalizedBankTransaction)]) -> usize {
+        match self {
+            Self::FirstSeen => 0,
+            Self::PreferComplete => entries
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (index, tx))| {
+                    (
+                        tx.is_void,
+                        tx.source_checksum.is_none() || tx.checksum_computed,
+                        *index,
+                    )
+                })
+                .map_or(0, |(position, _)| position),
+        }
+    }
+}
+
 #[must_use]
 pub fn dedupe_transactions(transactions: Vec<NormalizedBankTransaction>) -> DedupeOutcome {
+    dedupe_transactions_with(transactions, DedupePrimarySelector::default())
+}
+
+/// Like `dedupe_transactions`, keeping the primary `selector` picks from
+/// each duplicate group. Output is ordered by each primary's input index.
+#[must_use]
+pub fn dedupe_transactions_with(
+    transactions: Vec<NormalizedBankTransaction>,
+    selector: DedupePrimarySelector,
+) -> DedupeOutcome {
     let mut grouped: HashMap<String, Vec<(usize, NormalizedBankTransaction)>> = HashMap::new();
     for (index, mut tx) in transactions.into_iter().enumerate() {
         let key = tx.dedupe_key();
@@ -150,20 +411,21 @@ pub fn dedupe_transactions(transactions: Vec<NormalizedBankTransaction>) -> Dedu
     let mut metrics = DedupeMetrics::default();
     let mut ordered: Vec<(usize, NormalizedBankTransaction)> = Vec::new();
     for (_, mut entries) in grouped {
-        entries.sort_by_key(|(idx, _)| *idx);
-        let mut iter = entries.into_iter();
-        if let Some((first_index, mut primary)) = iter.next() {
-            let mut duplicates = Vec::new();
-            for (_, tx) in iter {
-                duplicates.push(tx.transaction_id);
-            }
-            metrics.kept += 1;
-            metrics.dropped += duplicates.len();
-            primary.duplicate_metadata.total_occurrences = duplicates.len() + 1;
-            primary.duplicate_metadata.discarded_ids = duplicates;
-            primary.ensure_checksum();
-            ordered.push((first_index, primary));
+        if entries.is_empty() {
+            continue;
         }
+        entries.sort_by_key(|(idx, _)| *idx);
+        let (primary_index, mut primary) = entries.remove(selector.select(&entries));
+        let duplicates = entries
+            .into_iter()
+            .map(|(_, tx)| tx.transaction_id)
+            .collect::<Vec<_>>();
+        metrics.kept += 1;
+        metrics.dropped += duplicates.len();
+        primary.duplicate_metadata.total_occurrences = duplicates.len() + 1;
+        primary.duplicate_metadata.discarded_ids = duplicates;
+        primary.ensure_checksum(&DEFAULT_CHECKSUM_FIELDS);
+        ordered.push((primary_index, primary));
     }
 
     ordered.sort_by_key(|(idx, _)| *idx);
@@ -178,12 +440,131 @@ pub fn dedupe_transactions(transactions: Vec<NormalizedBankTransaction>) -> Dedu
     }
 }
 
+/// Remembers dedupe keys and checksums across `ingest` calls so a statement
+/// imported twice is caught even when the batches arrive days apart.
+#[derive(Debug, Clone, Default)]
+pub struct StatefulDeduper {
+    seen_keys: HashMap<String, String>,
+    seen_checksums: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatefulDedupeOutcome {
+    /// Transactions not seen before, deduplicated within the batch.
+    pub transactions: Vec<NormalizedBankTransaction>,
+    /// Transactions matching an earlier batch; `discarded_ids` holds the id of
+    /// the previously ingested transaction.
+    pub previously_ingested: Vec<NormalizedBankTransaction>,
+    pub metrics: DedupeMetrics,
+}
+
+impl StatefulDeduper {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ingest(&mut self, batch: Vec<NormalizedBankTransaction>) -> StatefulDedupeOutcome {
+        let DedupeOutcome {
+            transactions: deduped,
+            mut metrics,
+        } = dedupe_transactions(batch);
+        let mut transactions = Vec::new();
+        let mut previously_ingested = Vec::new();
+        for mut tx in deduped {
+            let key = tx.dedupe_key();
+            let prior = self.seen_keys.get(&key).or_else(|| {
+                tx.source_checksum
+                    .as_ref()
+                    .and_then(|checksum| self.seen_checksums.get(checksum))
+            });
+            if let Some(prior_id) = prior {
+                tx.duplicate_metadata.discarded_ids = vec![prior_id.clone()];
+                metrics.kept -= 1;
+                metrics.dropped += 1;
+                previously_ingested.push(tx);
+                continue;
+            }
+            self.seen_keys.insert(key, tx.transaction_id.clone());
+            if let Some(checksum) = &tx.source_checksum {
+                self.seen_checksums
+                    .insert(checksum.clone(), tx.transaction_id.clone());
+            }
+            transactions.push(tx);
+        }
+        StatefulDedupeOutcome {
+            transactions,
+            previously_ingested,
+            metrics,
+        }
+    }
+
+    /// Number of distinct transactions ingested so far.
+    #[must_use]
+    pub fn seen_count(&self) -> usize {
+        self.seen_keys.len()
+    }
+}
+
+/// How split debit/credit columns combine into a signed amount.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitAmountSign {
+    /// `credit - debit`: deposits are positive.
+    #[default]
+    CreditPositive,
+    /// `debit - credit`: withdrawals are positive.
+    DebitPositive,
+}
+
+/// How digits below the minor unit (e.g. the third decimal of a USD amount)
+/// are resolved. Applied to the magnitude, so `-1.005` rounds like `1.005`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Drop the sub-minor remainder.
+    #[default]
+    Truncate,
+    /// Round halves away from zero.
+    HalfUp,
+    /// Round halves to the nearest even minor unit.
+    HalfEven,
+}
+
+impl RoundingMode {
+    /// Rounds `quotient + remainder / scale`, where `quotient` is the whole
+    /// minor amount so that `HalfEven` checks the parity of the final value.
+    /// `None` when rounding up overflows.
+    fn round(self, quotient: i64, remainder: i64, scale: i64) -> Option<i64> {
+        let doubled = remainder * 2;
+        let bump = match self {
+            Self::Truncate => false,
+            Self::HalfUp => doubled >= scale,
+            Self::HalfEven => doubled > scale || (doubled == scale && quotient % 2 == 1),
+        };
+        if bump {
+            quotient.checked_add(1)
+        } else {
+            Some(quotient)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct CsvParserProfile {
     pub transaction_id: String,
     pub account_id: String,
     pub posted_date: String,
-    pub amount: String,
+    /// Single signed amount column. Mutually exclusive with
+    /// `debit_column`/`credit_column`.
+    #[serde(default)]
+    pub amount: Option<String>,
+    #[serde(default)]
+    pub debit_column: Option<String>,
+    #[serde(default)]
+    pub credit_column: Option<String>,
+    #[serde(default)]
+    pub split_sign: SplitAmountSign,
     pub currency: String,
     pub description: String,
     #[serde(default)]
@@ -196,6 +577,72 @@ pub struct CsvParserProfile {
     pub date_format: String,
     #[serde(default = "default_amount_factor")]
     pub amount_minor_factor: i64,
+    #[serde(default)]
+    pub rounding: RoundingMode,
+    /// Fields hashed into computed checksums. Changing the set changes which
+    /// transactions checksum-based dedupe treats as identical.
+    #[serde(default = "default_checksum_fields")]
+    pub checksum_fields: Vec<ChecksumField>,
+    /// When false the first row is data, not a header, and columns are
+    /// mapped through `positions` instead of the names above.
+    #[serde(default = "default_has_headers")]
+    pub has_headers: bool,
+    #[serde(default)]
+    pub positions: Option<CsvColumnPositions>,
+}
+
+/// Zero-based column positions for header-less files. Mirrors the named
+/// columns of `CsvParserProfile`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CsvColumnPositions {
+    pub transaction_id: usize,
+    pub account_id: usize,
+    pub posted_date: usize,
+    #[serde(default)]
+    pub amount: Option<usize>,
+    #[serde(default)]
+    pub debit_column: Option<usize>,
+    #[serde(default)]
+    pub credit_column: Option<usize>,
+    pub currency: usize,
+    pub description: usize,
+    #[serde(default)]
+    pub source_reference: Option<usize>,
+    #[serde(default)]
+    pub source_checksum: Option<usize>,
+    #[serde(default)]
+    pub voided: Option<usize>,
+}
+
+impl CsvColumnPositions {
+    fn amount_columns(&self) -> Result<AmountColumns, BankIngestError> {
+        let has_split = self.debit_column.is_some() || self.credit_column.is_some();
+        match (self.amount, has_split) {
+            (Some(_), true) => Err(BankIngestError::Invalid(
+                "profile cannot configure both amount and debit/credit positions".into(),
+            )),
+            (Some(amount), false) => Ok(AmountColumns::Signed(amount)),
+            (None, true) => Ok(AmountColumns::Split {
+                debit: self.debit_column,
+                credit: self.credit_column,
+            }),
+            (None, false) => Err(BankIngestError::MissingColumn("amount".into())),
+        }
+    }
+
+    fn indexes(&self) -> Result<CsvIndexes, BankIngestError> {
+        Ok(CsvIndexes {
+            transaction_id: self.transaction_id,
+            account_id: self.account_id,
+            posted_date: self.posted_date,
+            amount: self.amount_columns()?,
+            currency: self.currency,
+            description: self.description,
+            source_reference: self.source_reference,
+            source_checksum: self.source_checksum,
+            voided: self.voided,
+        })
+    }
 }
 
 impl Default for CsvParserProfile {
@@ -204,7 +651,10 @@ impl Default for CsvParserProfile {
             transaction_id: "transaction_id".into(),
             account_id: "account_id".into(),
             posted_date: "posted_date".into(),
-            amount: "amount".into(),
+            amount: Some("amount".into()),
+            debit_column: None,
+            credit_column: None,
+            split_sign: SplitAmountSign::default(),
             currency: "currency".into(),
             description: "description".into(),
             source_reference: Some("source_reference".into()),
@@ -212,15 +662,42 @@ impl Default for CsvParserProfile {
             voided: Some("voided".into()),
             date_format: default_date_format(),
             amount_minor_factor: default_amount_factor(),
+            rounding: RoundingMode::default(),
+            checksum_fields: default_checksum_fields(),
+            has_headers: default_has_headers(),
+            positions: None,
         }
     }
 }
 
+/// Decimal places `infer_amount_factor` reports at most, keeping the
+/// suggested factor well inside `i64`.
+const MAX_INFERRED_DECIMAL_PLACES: u32 = 6;
+
+/// What a sample of amounts implies about `amount_minor_factor`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmountFactorInference {
+    /// Most digits seen after a decimal point, capped at six.
+    pub max_decimal_places: u32,
+    /// `10^max_decimal_places`; 1 when no amount carries decimals.
+    pub suggested_factor: i64,
+    /// Set when the configured factor contradicts the sample.
+    pub warning: Option<String>,
+}
+
+enum AmountColumns {
+    Signed(usize),
+    Split {
+        debit: Option<usize>,
+        credit: Option<usize>,
+    },
+}
+
 struct CsvIndexes {
     transaction_id: usize,
     account_id: usize,
     posted_date: usize,
-    amount: usize,
+    amount: AmountColumns,
     currency: usize,
     description: usize,
     source_reference: Option<usize>,
@@ -228,13 +705,157 @@ struct CsvIndexes {
     voided: Option<usize>,
 }
 
+impl AmountColumns {
+    fn indexes(&self) -> Vec<usize> {
+        match self {
+            AmountColumns::Signed(idx) => vec![*idx],
+            AmountColumns::Split { debit, credit } => debit.iter().chain(credit).copied().collect(),
+        }
+    }
+}
+
+impl CsvIndexes {
+    /// Number of fields a record needs for every configured column to exist.
+    fn width(&self) -> usize {
+        [
+            self.transaction_id,
+            self.account_id,
+            self.posted_date,
+            self.currency,
+            self.description,
+        ]
+        .into_iter()
+        .chain(self.amount.indexes())
+        .chain(self.source_reference)
+        .chain(self.source_checksum)
+        .chain(self.voided)
+        .max()
+        .map_or(0, |max| max + 1)
+    }
+}
+
+/// Rejects records too narrow for the configured columns, which only
+/// happens when header-less positions point past the end of the file's rows.
+fn ensure_width(record: &StringRecord, width: usize) -> Result<(), BankIngestError> {
+    if width > record.len() {
+        return Err(BankIngestError::Invalid(format!(
+            "column position {} exceeds record width {}",
+            width - 1,
+            record.len()
+        )));
+    }
+    Ok(())
+}
+
 impl CsvParserProfile {
+    fn reader<'a>(&self, input: &'a str) -> csv::Reader<&'a [u8]> {
+        csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .has_headers(self.has_headers)
+            .from_reader(input.as_bytes())
+    }
+
+    fn positions(&self) -> Result<&CsvColumnPositions, BankIngestError> {
+        self.positions.as_ref().ok_or_else(|| {
+            BankIngestError::Invalid("header-less profiles must configure positions".into())
+        })
+    }
+
+    fn headers(reader: &mut csv::Reader<&[u8]>) -> Result<StringRecord, BankIngestError> {
+        reader
+            .headers()
+            .cloned()
+            .map_err(|err| BankIngestError::Csv(err.to_string()))
+    }
+
+    fn amount_columns(&self, headers: &StringRecord) -> Result<AmountColumns, BankIngestError> {
+        let has_split = self.debit_column.is_some() || self.credit_column.is_some();
+        match (&self.amount, has_split) {
+            (Some(_), true) => Err(BankIngestError::Invalid(
+                "profile cannot configure both amount and debit/credit columns".into(),
+            )),
+            (Some(amount), false) => Ok(AmountColumns::Signed(find_index(headers, amount)?)),
+            (None, true) => Ok(AmountColumns::Split {
+                debit: optional_index(headers, self.debit_column.as_deref())?,
+                credit: optional_index(headers, self.credit_column.as_deref())?,
+            }),
+            (None, false) => Err(BankIngestError::MissingColumn("amount".into())),
+        }
+    }
+
+    /// Inspects the amount columns of a CSV sample (header row included unless
+    /// `has_headers` is false) and suggests a factor. Warns only when amounts
+    /// carry more decimals than the exponent of `amount_minor_factor` (2 for
+    /// 100), since those digits are lost; whole amounts never warn. Parsing is
+    /// unaffected. Returns `None` when the sample has no amounts.
+    pub fn infer_amount_factor(
+        &self,
+        sample: &str,
+    ) -> Result<Option<AmountFactorInference>, BankIngestError> {
+        let mut reader = self.reader(sample);
+        let columns = if self.has_headers {
+            self.amount_columns(&Self::headers(&mut reader)?)?
+        } else {
+            self.positions()?.amount_columns()?
+        }
+        .indexes();
+        let width = columns.iter().max().map_or(0, |max| max + 1);
+        let mut max_decimal_places = None;
+        for record in reader.records() {
+            let record = record.map_err(|err| BankIngestError::Csv(err.to_string()))?;
+            ensure_width(&record, width)?;
+            for value in columns.iter().filter_map(|idx| record.get(*idx)) {
+                let value = value.trim();
+                if value.is_empty() {
+                    continue;
+                }
+                let places = value
+                    .split_once('.')
+                    .map(|(_, fraction)| fraction.chars().filter(char::is_ascii_digit).count())
+                    .unwrap_or(0) as u32;
+                max_decimal_places = max_decimal_places.max(Some(places));
+            }
+        }
+        let Some(max_decimal_places) = max_decimal_places else {
+            return Ok(None);
+        };
+        let max_decimal_places = max_decimal_places.min(MAX_INFERRED_DECIMAL_PLACES);
+        let suggested_factor = 10_i64.pow(max_decimal_places);
+        let configured = self.amount_minor_factor;
+        let configured_exponent = configured.max(1).ilog10();
+        let warning = (max_decimal_places > configured_exponent).then(|| {
+            let resolved = match self.rounding {
+                RoundingMode::Truncate => "truncated",
+                RoundingMode::HalfUp | RoundingMode::HalfEven => "rounded",
+            };
+            format!(
+                "amounts carry {max_decimal_places} decimal place(s) but amount_minor_factor {configured} keeps {configured_exponent}; extra digits will be {resolved} (suggested {suggested_factor})"
+            )
+        });
+        Ok(Some(AmountFactorInference {
+            max_decimal_places,
+            suggested_factor,
+            warning,
+        }))
+    }
+
+    fn resolve_indexes(
+        &self,
+        reader: &mut csv::Reader<&[u8]>,
+    ) -> Result<CsvIndexes, BankIngestError> {
+        if self.has_headers {
+            self.indexes(&Self::headers(reader)?)
+        } else {
+            self.positions()?.indexes()
+        }
+    }
+
     fn indexes(&self, headers: &StringRecord) -> Result<CsvIndexes, BankIngestError> {
         Ok(CsvIndexes {
             transaction_id: find_index(headers, &self.transaction_id)?,
             account_id: find_index(headers, &self.account_id)?,
             posted_date: find_index(headers, &self.posted_date)?,
-            amount: find_index(headers, &self.amount)?,
+            amount: self.amount_columns(headers)?,
             currency: find_index(headers, &self.currency)?,
             description: find_index(headers, &self.description)?,
             source_reference: optional_index(headers, self.source_reference.as_deref())?,
@@ -268,6 +889,16 @@ impl CsvBankParser {
         Self { profile }
     }
 
+    /// Parses like `parse` and also returns an `ImportSummary` of the batch.
+    pub fn parse_with_summary(
+        &self,
+        input: &str,
+    ) -> Result<(Vec<NormalizedBankTransaction>, ImportSummary), BankIngestError> {
+        let transactions = self.parse(input)?;
+        let summary = ImportSummary::from_transactions(&transactions)?;
+        Ok((transactions, summary))
+    }
+
     fn build_transaction(
         &self,
         record: &StringRecord,
@@ -294,11 +925,7 @@ impl CsvBankParser {
                 BankIngestError::Parse(format!("invalid date {posted_date_raw}: {err}"))
             })?;
 
-        let amount_raw = record
-            .get(indexes.amount)
-            .ok_or_else(|| BankIngestError::Invalid("amount missing".into()))?
-            .trim();
-        let amount_minor = parse_amount(amount_raw, self.profile.amount_minor_factor)?;
+        let amount_minor = self.amount_minor(record, &indexes.amount)?;
 
         let currency = record
             .get(indexes.currency)
@@ -342,12 +969,53 @@ impl CsvBankParser {
             source_reference,
             source_checksum: checksum_from_source,
             is_void,
+            checksum_computed: false,
             duplicate_metadata: DuplicateMetadata::default(),
             currency_validation,
+            institution: None,
         };
-        transaction.ensure_checksum();
+        transaction.ensure_checksum(&self.profile.checksum_fields);
         Ok(transaction)
     }
+
+    fn amount_minor(
+        &self,
+        record: &StringRecord,
+        columns: &AmountColumns,
+    ) -> Result<i64, BankIngestError> {
+        let factor = self.profile.amount_minor_factor;
+        let rounding = self.profile.rounding;
+        match columns {
+            AmountColumns::Signed(idx) => {
+                let amount_raw = record
+                    .get(*idx)
+                    .ok_or_else(|| BankIngestError::Invalid("amount missing".into()))?
+                    .trim();
+                parse_amount(amount_raw, factor, rounding)
+            }
+            AmountColumns::Split { debit, credit } => {
+                let side = |idx: &Option<usize>| -> Result<i64, BankIngestError> {
+                    match idx.and_then(|idx| record.get(idx)).map(str::trim) {
+                        Some(value) if !value.is_empty() => {
+                            parse_amount(value, factor, rounding).map(i64::abs)
+                        }
+                        _ => Ok(0),
+                    }
+                };
+                let debit = side(debit)?;
+                let credit = side(credit)?;
+                if debit == 0 && credit == 0 {
+                    return Err(BankIngestError::Invalid(
+                        "debit or credit amount must be provided".into(),
+                    ));
+                }
+                Ok(match self.profile.split_sign {
+                    SplitAmountSign::CreditPositive => credit - debit,
+                    SplitAmountSign::DebitPositive => debit - credit,
+                })
+            }
+        }
+    }
 }
 
 impl Default for CsvBankParser {
@@ -358,20 +1026,16 @@ impl Default for CsvBankParser {
 
 impl BankStatementParser for CsvBankParser {
     fn parse(&self, input: &str) -> Result<Vec<NormalizedBankTransaction>, BankIngestError> {
-        let mut reader = csv::ReaderBuilder::new()
-            .trim(csv::Trim::All)
-            .from_reader(input.as_bytes());
-        let headers = reader
-            .headers()
-            .map_err(|err| BankIngestError::Csv(err.to_string()))?
-            .clone();
-        let indexes = self.profile.indexes(&headers)?;
+        let mut reader = self.profile.reader(input);
+        let indexes = self.profile.resolve_indexes(&mut reader)?;
+        let width = indexes.width();
         let mut transactions = Vec::new();
         for record in reader.records() {
             let record = record.map_err(|err| BankIngestError::Csv(err.to_string()))?;
             if record.iter().all(|field| field.trim().is_empty()) {
                 continue;
             }
+            ensure_width(&record, width)?;
             let transaction = self.build_transaction(&record, &indexes)?;
             transactions.push(transaction);
         }
@@ -382,6 +1046,16 @@ impl BankStatementParser for CsvBankParser {
 #[derive(Debug, Clone, Default)]
 pub struct OfxParserProfile {
     pub amount_minor_factor: i64,
+    pub rounding: RoundingMode,
+    /// UTC offset of the statement's timezone. When unset, dates land on the
+    /// calendar day of the offset carried by each OFX timestamp.
+    pub statement_utc_offset_minutes: Option<i32>,
+    /// Fields hashed into computed checksums; empty uses
+    /// `DEFAULT_CHECKSUM_FIELDS`.
+    pub checksum_fields: Vec<ChecksumField>,
+    /// Copy the sign-on `<ORG>` (or `<FID>` when unnamed) onto each
+    /// transaction's `institution`.
+    pub stamp_institution: bool,
 }
 
 impl Default for OfxBankParser {
@@ -389,6 +1063,10 @@ impl Default for OfxBankParser {
         Self {
             profile: OfxParserProfile {
                 amount_minor_factor: default_amount_factor(),
+                rounding: RoundingMode::default(),
+                statement_utc_offset_minutes: None,
+                checksum_fields: default_checksum_fields(),
+                stamp_institution: false,
             },
         }
     }
@@ -422,11 +1100,15 @@ impl OfxBankParser {
             .get("TRNAMT")
             .ok_or_else(|| BankIngestError::Invalid("OFX missing TRNAMT".into()))?
             .trim();
-        let amount_minor = parse_amount(amount_raw, self.profile.amount_minor_factor)?;
+        let amount_minor = parse_amount(
+            amount_raw,
+            self.profile.amount_minor_factor,
+            self.profile.rounding,
+        )?;
         let date_raw = fields
             .get("DTPOSTED")
             .ok_or_else(|| BankIngestError::Invalid("OFX missing DTPOSTED".into()))?;
-        let date = parse_ofx_date(date_raw)?;
+        let date = parse_ofx_date(date_raw, self.profile.statement_utc_offset_minutes)?;
         let description = fields
             .get("NAME")
             .or_else(|| fields.get("MEMO"))
@@ -458,28 +1140,41 @@ impl OfxBankParser {
             source_reference,
             source_checksum: None,
             is_void,
+            checksum_computed: false,
             duplicate_metadata: DuplicateMetadata::default(),
             currency_validation,
+            institution: None,
         };
-        transaction.ensure_checksum();
+        transaction.ensure_checksum(&self.profile.checksum_fields);
         Ok(transaction)
     }
 }
 
 impl BankStatementParser for OfxBankParser {
     fn parse(&self, input: &str) -> Result<Vec<NormalizedBankTransaction>, BankIngestError> {
+        self.parse_statement(input)
+            .map(|statement| statement.transactions)
+    }
+
+    fn parse_statement(&self, input: &str) -> Result<ParsedStatement, BankIngestError> {
         let mut account_id = String::new();
         let mut currency = String::new();
         let mut current: HashMap<String, String> = HashMap::new();
         let mut transactions = Vec::new();
         let mut in_transaction = false;
-
-        for line in input.lines() {
+        let mut in_ledger_balance = false;
+        let mut closing_balance_minor = None;
+        let mut as_of = None;
+        let mut institution = None;
+        let mut institution_id = None;
+        let mut server_time = None;
+
+        for line in ofx_segments(input) {
             let trimmed = line.trim();
             if trimmed.is_empty() {
                 continue;
             }
-            if trimmed.eq_ignore_ascii_case("<STMTTRN>") {
+            if is_transaction_tag(trimmed, false) {
                 if in_transaction && !current.is_empty() && !account_id.is_empty() {
                     let tx = self.build_transaction(&current, &account_id, &currency)?;
                     transactions.push(tx);
@@ -488,7 +1183,7 @@ impl BankStatementParser for OfxBankParser {
                 in_transaction = true;
                 continue;
             }
-            if trimmed.eq_ignore_ascii_case("</STMTTRN>") {
+            if is_transaction_tag(trimmed, true) {
                 if in_transaction && !current.is_empty() && !account_id.is_empty() {
                     let tx = self.build_transaction(&current, &account_id, &currency)?;
                     transactions.push(tx);
@@ -497,6 +1192,45 @@ impl BankStatementParser for OfxBankParser {
                 in_transaction = false;
                 continue;
             }
+            if trimmed.eq_ignore_ascii_case("<LEDGERBAL>") {
+                in_ledger_balance = true;
+                continue;
+            }
+            if trimmed.eq_ignore_ascii_case("</LEDGERBAL>")
+                || trimmed.eq_ignore_ascii_case("<AVAILBAL>")
+            {
+                in_ledger_balance = false;
+                continue;
+            }
+            if in_ledger_balance {
+                if let Some(value) = extract_tag_value(trimmed, "BALAMT") {
+                    closing_balance_minor = Some(parse_amount(
+                        value,
+                        self.profile.amount_minor_factor,
+                        self.profile.rounding,
+                    )?);
+                } else if let Some(value) = extract_tag_value(trimmed, "DTASOF") {
+                    as_of = Some(parse_ofx_date(
+                        value,
+                        self.profile.statement_utc_offset_minutes,
+                    )?);
+                }
+                continue;
+            }
+            if !in_transaction {
+                if let Some(value) = extract_tag_value(trimmed, "DTSERVER") {
+                    server_time = Some(parse_ofx_datetime(value)?);
+                    continue;
+                }
+                if let Some(value) = extract_tag_value(trimmed, "ORG") {
+                    institution = Some(value.to_owned()).filter(|value| !value.is_empty());
+                    continue;
+                }
+                if let Some(value) = extract_tag_value(trimmed, "FID") {
+                    institution_id = Some(value.to_owned()).filter(|value| !value.is_empty());
+                    continue;
+                }
+            }
             if let Some(value) = extract_tag_value(trimmed, "ACCTID") {
                 account_id = value.to_owned();
                 continue;
@@ -523,16 +1257,122 @@ impl BankStatementParser for OfxBankParser {
 
         if transactions.is_empty() {
             return Err(BankIngestError::Invalid(
-                "OFX payload did not contain any STMTTRN blocks".into(),
+                "OFX payload did not contain any STMTTRN or CCSTMTTRN blocks".into(),
             ));
         }
 
-        Ok(transactions)
+        if self.profile.stamp_institution
+            && let Some(stamp) = institution.as_ref().or(institution_id.as_ref())
+        {
+            for transaction in &mut transactions {
+                transaction.institution = Some(stamp.clone());
+            }
+        }
+
+        Ok(ParsedStatement {
+            transactions,
+            closing_balance_minor,
+            as_of,
+            institution,
+            institution_id,
+            server_time,
+        })
     }
 }
 
 pub trait BankStatementParser {
     fn parse(&self, input: &str) -> Result<Vec<NormalizedBankTransaction>, BankIngestError>;
+
+    /// Parses transactions along with the statement closing balance and
+    /// header. Formats without that data report `None` for those fields.
+    fn parse_statement(&self, input: &str) -> Result<ParsedStatement, BankIngestError> {
+        Ok(ParsedStatement {
+            transactions: self.parse(input)?,
+            closing_balance_minor: None,
+            as_of: None,
+            institution: None,
+            institution_id: None,
+            server_time: None,
+        })
+    }
+}
+
+/// A rejected NDJSON line, numbered from 1.
+#[derive(Debug, Error)]
+#[error("line {line}: {error}")]
+pub struct NdjsonLineError {
+    pub line: usize,
+    pub error: BankIngestError,
+}
+
+/// Streams already-normalized transactions from newline-delimited JSON. See
+/// [`read_ndjson_transactions`].
+pub struct NdjsonTransactions<R> {
+    lines: Lines<BufReader<R>>,
+    line: usize,
+    failed: bool,
+}
+
+/// Reads one `NormalizedBankTransaction` JSON object per line, yielding each
+/// as it is parsed so large feeds never sit in memory. Blank lines are
+/// skipped. A line that fails to parse or validate (non-empty ids, ISO-4217
+/// currency) yields an `NdjsonLineError` and reading continues; an I/O error
+/// ends the stream. Missing checksums are computed from
+/// `DEFAULT_CHECKSUM_FIELDS`.
+pub fn read_ndjson_transactions<R: Read>(reader: R) -> NdjsonTransactions<R> {
+    NdjsonTransactions {
+        lines: BufReader::new(reader).lines(),
+        line: 0,
+        failed: false,
+    }
+}
+
+impl<R: Read> Iterator for NdjsonTransactions<R> {
+    type Item = Result<NormalizedBankTransaction, NdjsonLineError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        loop {
+            let raw = self.lines.next()?;
+            self.line += 1;
+            let line = self.line;
+            let raw = match raw {
+                Ok(raw) => raw,
+                Err(err) => {
+                    self.failed = true;
+                    return Some(Err(NdjsonLineError {
+                        line,
+                        error: BankIngestError::Invalid(format!("read failed: {err}")),
+                    }));
+                }
+            };
+            if raw.trim().is_empty() {
+                continue;
+            }
+            return Some(
+                parse_ndjson_transaction(&raw).map_err(|error| NdjsonLineError { line, error }),
+            );
+        }
+    }
+}
+
+fn parse_ndjson_transaction(raw: &str) -> Result<NormalizedBankTransaction, BankIngestError> {
+    let mut transaction: NormalizedBankTransaction =
+        serde_json::from_str(raw).map_err(|err| BankIngestError::Parse(err.to_string()))?;
+    for (name, value) in [
+        ("transaction_id", &transaction.transaction_id),
+        ("account_id", &transaction.account_id),
+    ] {
+        if value.trim().is_empty() {
+            return Err(BankIngestError::Invalid(format!("{name} missing")));
+        }
+    }
+    transaction.currency = transaction.currency.trim().to_uppercase();
+    transaction.currency_validation = validate_currency(&transaction.currency)?;
+    transaction.ensure_checksum(&DEFAULT_CHECKSUM_FIELDS);
+    Ok(transaction)
 }
 
 fn is_truthy(value: &str) -> bool {
@@ -542,7 +1382,7 @@ fn is_truthy(value: &str) -> bool {
     )
 }
 
-fn parse_amount(value: &str, factor: i64) -> Result<i64, BankIngestError> {
+fn parse_amount(value: &str, factor: i64, rounding: RoundingMode) -> Result<i64, BankIngestError> {
     if factor <= 0 {
         return Err(BankIngestError::Invalid(
             "amount_minor_factor must be positive".into(),
@@ -585,8 +1425,14 @@ fn parse_amount(value: &str, factor: i64) -> Result<i64, BankIngestError> {
         let fraction_value = fraction
             .parse::<i64>()
             .map_err(|err| BankIngestError::Parse(format!("invalid fraction {fraction}: {err}")))?;
-        amount = amount
-            .checked_add((fraction_value * factor) / fraction_scale)
+        let scaled = fraction_value
+            .checked_mul(factor)
+            .ok_or_else(|| BankIngestError::Parse("fraction overflow".into()))?;
+        let whole = amount
+            .checked_add(scaled / fraction_scale)
+            .ok_or_else(|| BankIngestError::Parse("fraction overflow".into()))?;
+        amount = rounding
+            .round(whole, scaled % fraction_scale, fraction_scale)
             .ok_or_else(|| BankIngestError::Parse("fraction overflow".into()))?;
     }
     if negative {
@@ -605,6 +1451,33 @@ fn validate_currency(code: &str) -> Result<CurrencyValidation, BankIngestError>
     }
 }
 
+/// Bank (`STMTTRN`) and credit-card (`CCSTMTTRN`) transactions share a layout.
+fn is_transaction_tag(line: &str, closing: bool) -> bool {
+    let Some(name) = line
+        .strip_prefix(if closing { "</" } else { "<" })
+        .and_then(|rest| rest.strip_suffix('>'))
+    else {
+        return false;
+    };
+    name.eq_ignore_ascii_case("STMTTRN") || name.eq_ignore_ascii_case("CCSTMTTRN")
+}
+
+/// Splits OFX SGML into one segment per tag, each running from its `<` up to
+/// the next `<`. Tags packed onto one line and values without closing tags
+/// then look the same as the one-tag-per-line layout.
+fn ofx_segments(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = input.find('<').map_or("", |start| &input[start..]);
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let end = rest[1..].find('<').map_or(rest.len(), |next| next + 1);
+        let (segment, tail) = rest.split_at(end);
+        rest = tail;
+        Some(segment)
+    })
+}
+
 fn extract_tag_value<'a>(line: &'a str, tag: &str) -> Option<&'a str> {
     if !line.starts_with('<') {
         return None;
@@ -630,14 +1503,76 @@ fn split_tag(line: &str) -> Option<(&str, &str)> {
     Some((name, value))
 }
 
-fn parse_ofx_date(raw: &str) -> Result<NaiveDate, BankIngestError> {
+/// Parses `YYYYMMDD[HHMMSS[.XXX]][[±hh[.mm][:TZ]]]`. Timestamps without a
+/// bracketed offset are GMT per the OFX spec; date-only values are returned
+/// as-is.
+fn parse_ofx_date(
+    raw: &str,
+    statement_utc_offset_minutes: Option<i32>,
+) -> Result<NaiveDate, BankIngestError> {
+    let raw = raw.trim();
     let digits: String = raw.chars().take_while(char::is_ascii_digit).collect();
     if digits.len() < 8 {
         return Err(BankIngestError::Invalid(format!("invalid OFX date {raw}")));
     }
-    let date_str = &digits[0..8];
-    NaiveDate::parse_from_str(date_str, "%Y%m%d")
-        .map_err(|err| BankIngestError::Parse(format!("invalid OFX date {raw}: {err}")))
+    if digits.len() < 14 {
+        let date_str = &digits[0..8];
+        return NaiveDate::parse_from_str(date_str, "%Y%m%d")
+            .map_err(|err| BankIngestError::Parse(format!("invalid OFX date {raw}: {err}")));
+    }
+    let local = NaiveDateTime::parse_from_str(&digits[0..14], "%Y%m%d%H%M%S")
+        .map_err(|err| BankIngestError::Parse(format!("invalid OFX date {raw}: {err}")))?;
+    let source_offset_minutes = match raw.find('[') {
+        Some(start) => parse_ofx_offset_minutes(&raw[start + 1..])
+            .ok_or_else(|| BankIngestError::Parse(format!("invalid OFX timezone in {raw}")))?,
+        None => 0,
+    };
+    let target_offset_minutes = statement_utc_offset_minutes.unwrap_or(source_offset_minutes);
+    let shifted =
+        local + Duration::minutes(i64::from(target_offset_minutes - source_offset_minutes));
+    Ok(shifted.date())
+}
+
+/// Parses an OFX timestamp into UTC. Date-only values are taken as midnight
+/// UTC; timestamps honour their bracketed offset like `parse_ofx_date`.
+fn parse_ofx_datetime(raw: &str) -> Result<DateTime<Utc>, BankIngestError> {
+    let raw = raw.trim();
+    let digits: String = raw.chars().take_while(char::is_ascii_digit).collect();
+    if digits.len() < 8 {
+        return Err(BankIngestError::Invalid(format!("invalid OFX date {raw}")));
+    }
+    let local = if digits.len() < 14 {
+        NaiveDate::parse_from_str(&digits[0..8], "%Y%m%d")
+            .map(|date| date.and_time(chrono::NaiveTime::MIN))
+    } else {
+        NaiveDateTime::parse_from_str(&digits[0..14], "%Y%m%d%H%M%S")
+    }
+    .map_err(|err| BankIngestError::Parse(format!("invalid OFX date {raw}: {err}")))?;
+    let offset_minutes = match raw.find('[') {
+        Some(start) => parse_ofx_offset_minutes(&raw[start + 1..])
+            .ok_or_else(|| BankIngestError::Parse(format!("invalid OFX timezone in {raw}")))?,
+        None => 0,
+    };
+    Ok((local - Duration::minutes(i64::from(offset_minutes))).and_utc())
+}
+
+/// Parses the offset portion of an OFX timezone suffix such as `-5:EST]` or
+/// `5.30:IST]` into minutes east of UTC.
+fn parse_ofx_offset_minutes(suffix: &str) -> Option<i32> {
+    let offset = suffix.split([':', ']']).next()?.trim();
+    let (negative, unsigned) = match offset.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, offset.strip_prefix('+').unwrap_or(offset)),
+    };
+    let (hours, minutes) = match unsigned.split_once('.') {
+        Some((hours, minutes)) => (hours.parse::<i32>().ok()?, minutes.parse::<i32>().ok()?),
+        None => (unsigned.parse::<i32>().ok()?, 0),
+    };
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+    let total = hours * 60 + minutes;
+    Some(if negative { -total } else { total })
 }
 
 #[cfg(test)]
@@ -672,6 +1607,59 @@ mod tests {
         assert_eq!(duplicate_count, 2);
     }
 
+    #[test]
+    fn csv_parser_summarizes_import() {
+        let profile: CsvParserProfile =
+            from_str(include_str!("../tests/fixtures/csv/profile.json"))
+                .expect("profile fixture must be valid");
+        let parser = CsvBankParser::new(profile);
+        let input = format!(
+            "{}txn-fee,acct-100,2024-09-30,-2.50,USD,Bank fee,,,false\n",
+            include_str!("../tests/fixtures/csv/sample.csv")
+        );
+        let (transactions, summary) = parser
+            .parse_with_summary(&input)
+            .expect("csv parser should succeed");
+
+        assert_eq!(transactions.len(), 5);
+        let date = |day: u32, month: u32| NaiveDate::from_ymd_opt(2024, month, day).expect("date");
+        assert_eq!(
+            summary,
+            ImportSummary {
+                row_count: 5,
+                totals: BTreeMap::from([(
+                    "USD".to_string(),
+                    CurrencyTotals {
+                        debits_minor: 250,
+                        credits_minor: 18_950,
+                    },
+                )]),
+                void_count: 1,
+                currencies: BTreeSet::from(["EUR".to_string(), "USD".to_string()]),
+                date_range: Some((date(30, 9), date(3, 10))),
+            }
+        );
+        assert_eq!(summary.net_minor("USD"), Some(18_700));
+        assert_eq!(summary.net_minor("EUR"), None);
+        assert_eq!(
+            ImportSummary::from_transactions(&[]).expect("empty batch"),
+            ImportSummary::default()
+        );
+
+        let mut large = transactions[0].clone();
+        large.amount_minor = i64::MAX;
+        let mut other = large.clone();
+        other.currency = "EUR".into();
+        let summary = ImportSummary::from_transactions(&[large.clone(), other])
+            .expect("separate currencies do not overflow");
+        assert_eq!(summary.net_minor("EUR"), Some(i64::MAX));
+        assert_eq!(summary.net_minor("USD"), Some(i64::MAX));
+        assert!(matches!(
+            ImportSummary::from_transactions(&[large.clone(), large]),
+            Err(BankIngestError::Invalid(_))
+        ));
+    }
+
     #[test]
     fn csv_parser_rejects_invalid_currency() {
         let profile: CsvParserProfile =
@@ -693,6 +1681,159 @@ bad-txn,acct-1,2024-10-01,10.00,US,Coffee,,,
         }
     }
 
+    #[test]
+    fn csv_parser_derives_amount_from_debit_credit_columns() {
+        let profile = CsvParserProfile {
+            amount: None,
+            debit_column: Some("debit".into()),
+            credit_column: Some("credit".into()),
+            source_reference: None,
+            source_checksum: None,
+            voided: None,
+            ..CsvParserProfile::default()
+        };
+        let payload = "\
+transaction_id,account_id,posted_date,debit,credit,currency,description
+txn-1,acct-1,2024-10-01,12.50,,USD,Coffee
+txn-2,acct-1,2024-10-02,,200.00,USD,Deposit
+";
+        let transactions = CsvBankParser::new(profile.clone())
+            .parse(payload)
+            .expect("split columns should parse");
+        let amounts: Vec<i64> = transactions.iter().map(|tx| tx.amount_minor).collect();
+        assert_eq!(amounts, vec![-1_250, 20_000]);
+
+        let inverted = CsvBankParser::new(CsvParserProfile {
+            split_sign: SplitAmountSign::DebitPositive,
+            ..profile
+        })
+        .parse(payload)
+        .expect("split columns should parse");
+        assert_eq!(inverted[0].amount_minor, 1_250);
+        assert_eq!(inverted[1].amount_minor, -20_000);
+    }
+
+    #[test]
+    fn rounding_mode_resolves_sub_minor_digits() {
+        let cases = [
+            (RoundingMode::Truncate, "1.005", 100),
+            (RoundingMode::Truncate, "1.004", 100),
+            (RoundingMode::HalfUp, "1.005", 101),
+            (RoundingMode::HalfUp, "1.004", 100),
+            (RoundingMode::HalfEven, "1.005", 100),
+            (RoundingMode::HalfEven, "1.004", 100),
+            (RoundingMode::HalfEven, "1.015", 102),
+            (RoundingMode::HalfUp, "-1.005", -101),
+        ];
+        for (mode, raw, expected) in cases {
+            assert_eq!(
+                parse_amount(raw, 100, mode).expect("amount parses"),
+                expected,
+                "{mode:?} {raw}"
+            );
+        }
+        // Whole-unit currencies: parity is taken from the final amount.
+        for (raw, expected) in [("13.5", 14), ("12.5", 12), ("-13.5", -14), ("0.5", 0)] {
+            assert_eq!(
+                parse_amount(raw, 1, RoundingMode::HalfEven).expect("amount parses"),
+                expected,
+                "HalfEven {raw} with factor 1"
+            );
+        }
+
+        let csv = "\
+transaction_id,account_id,posted_date,amount,currency,description
+txn-1,acct-1,2024-10-01,12.345,USD,Fee
+";
+        let parse = |rounding| {
+            CsvBankParser::new(CsvParserProfile {
+                rounding,
+                source_reference: None,
+                source_checksum: None,
+                voided: None,
+                ..CsvParserProfile::default()
+            })
+            .parse(csv)
+            .expect("csv parses")[0]
+                .amount_minor
+        };
+        assert_eq!(parse(RoundingMode::Truncate), 1_234);
+        assert_eq!(parse(RoundingMode::HalfUp), 1_235);
+        assert_eq!(parse(RoundingMode::HalfEven), 1_234);
+    }
+
+    #[test]
+    fn csv_parser_rejects_amount_and_split_columns() {
+        let profile = CsvParserProfile {
+            debit_column: Some("debit".into()),
+            ..CsvParserProfile::default()
+        };
+        let err = CsvBankParser::new(profile)
+            .parse("transaction_id,account_id,posted_date,amount,debit,currency,description\n")
+            .expect_err("conflicting amount columns should fail");
+        assert!(matches!(err, BankIngestError::Invalid(_)));
+    }
+
+    #[test]
+    fn csv_parser_maps_header_less_files_by_position() {
+        let profile = CsvParserProfile {
+            has_headers: false,
+            positions: Some(CsvColumnPositions {
+                transaction_id: 0,
+                account_id: 1,
+                posted_date: 2,
+                amount: Some(4),
+                currency: 5,
+                description: 3,
+                ..CsvColumnPositions::default()
+            }),
+            ..CsvParserProfile::default()
+        };
+        let payload = "\
+txn-1,acct-1,2024-10-01,Coffee,-12.50,usd
+txn-2,acct-1,2024-10-02,Deposit,200.00,USD
+";
+        let transactions = CsvBankParser::new(profile.clone())
+            .parse(payload)
+            .expect("positional columns should parse");
+        let ids: Vec<&str> = transactions
+            .iter()
+            .map(|tx| tx.transaction_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["txn-1", "txn-2"]);
+        assert_eq!(transactions[0].description, "Coffee");
+        assert_eq!(transactions[0].amount_minor, -1_250);
+        assert_eq!(transactions[0].currency, "USD");
+
+        let too_wide = CsvParserProfile {
+            positions: profile
+                .positions
+                .clone()
+                .map(|positions| CsvColumnPositions {
+                    voided: Some(6),
+                    ..positions
+                }),
+            ..profile.clone()
+        };
+        let err = CsvBankParser::new(too_wide)
+            .parse(payload)
+            .expect_err("positions past the record width should fail");
+        match err {
+            BankIngestError::Invalid(message) => {
+                assert_eq!(message, "column position 6 exceeds record width 6");
+            }
+            other => panic!("unexpected error {other:?}"),
+        }
+
+        let err = CsvBankParser::new(CsvParserProfile {
+            positions: None,
+            ..profile
+        })
+        .parse(payload)
+        .expect_err("header-less profiles need positions");
+        assert!(matches!(err, BankIngestError::Invalid(_)));
+    }
+
     #[test]
     fn dedupe_transactions_reports_metrics() {
         let profile: CsvParserProfile =
@@ -717,6 +1858,202 @@ bad-txn,acct-1,2024-10-01,10.00,US,Coffee,,,
         );
     }
 
+    #[test]
+    fn dedupe_selector_prefers_complete_records() {
+        let tx = |id: &str, reference: &str, checksum: Option<&str>, is_void: bool| {
+            let mut transaction = NormalizedBankTransaction {
+                transaction_id: id.into(),
+                account_id: "acct-1".into(),
+                posted_date: NaiveDate::from_ymd_opt(2024, 3, 1).expect("valid date"),
+                amount_minor: -1_250,
+                currency: "USD".into(),
+                description: "Coffee".into(),
+                source_reference: Some(reference.into()),
+                source_checksum: checksum.map(ToString::to_string),
+                is_void,
+                checksum_computed: false,
+                duplicate_metadata: DuplicateMetadata::default(),
+                currency_validation: CurrencyValidation::default(),
+                institution: None,
+            };
+            // Parsers fill in missing checksums, so model their output.
+            transaction.ensure_checksum(&[]);
+            transaction
+        };
+        let batch = vec![
+            tx("a-voided", "ref-a", Some("sum-a"), true),
+            tx("b-only", "ref-b", None, false),
+            tx("a-bare", "ref-a", None, false),
+            tx("a-checksum", "ref-a", Some("sum-a2"), false),
+        ];
+        let ids = |outcome: &DedupeOutcome| {
+            outcome
+                .transactions
+                .iter()
+                .map(|tx| tx.transaction_id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let first = dedupe_transactions_with(batch.clone(), DedupePrimarySelector::FirstSeen);
+        assert_eq!(ids(&first), vec!["a-voided", "b-only"]);
+        assert_eq!(first, dedupe_transactions(batch.clone()));
+
+        let complete = dedupe_transactions_with(batch, DedupePrimarySelector::PreferComplete);
+        assert_eq!(ids(&complete), vec!["b-only", "a-checksum"]);
+        assert_eq!(complete.metrics.kept, 2);
+        assert_eq!(complete.metrics.dropped, 2);
+        let primary = &complete.transactions[1];
+        assert_eq!(primary.duplicate_metadata.total_occurrences, 3);
+        assert_eq!(
+            primary.duplicate_metadata.discarded_ids,
+            vec![String::from("a-voided"), String::from("a-bare")]
+        );
+    }
+
+    #[test]
+    fn stateful_deduper_flags_reimported_statements() {
+        let profile: CsvParserProfile =
+            from_str(include_str!("../tests/fixtures/csv/profile.json"))
+                .expect("profile fixture must be valid");
+        let parser = CsvBankParser::new(profile);
+        let parse = || {
+            parser
+                .parse(include_str!("../tests/fixtures/csv/sample.csv"))
+                .expect("csv parser should succeed")
+        };
+
+        let mut deduper = StatefulDeduper::new();
+        let first = deduper.ingest(parse());
+        assert_eq!(first.transactions.len(), 3);
+        assert!(first.previously_ingested.is_empty());
+        assert_eq!(deduper.seen_count(), 3);
+
+        let second = deduper.ingest(parse());
+        assert!(second.transactions.is_empty());
+        assert_eq!(second.metrics.kept, 0);
+        assert_eq!(second.metrics.dropped, 4);
+        let prior_ids: Vec<Vec<String>> = second
+            .previously_ingested
+            .iter()
+            .map(|tx| tx.duplicate_metadata.discarded_ids.clone())
+            .collect();
+        let expected: Vec<Vec<String>> = first
+            .transactions
+            .iter()
+            .map(|tx| vec![tx.transaction_id.clone()])
+            .collect();
+        assert_eq!(prior_ids, expected);
+        assert_eq!(deduper.seen_count(), 3);
+    }
+
+    #[test]
+    fn checksum_fields_follow_profile() {
+        let payload = "\
+transaction_id,account_id,posted_date,amount,currency,description
+txn-1,acct-1,2024-10-01,10.00,USD,Coffee
+txn-1,acct-1,2024-10-01,10.00,USD,Lunch
+";
+        let base = CsvParserProfile {
+            source_reference: None,
+            source_checksum: None,
+            voided: None,
+            ..CsvParserProfile::default()
+        };
+        let default_fields = CsvBankParser::new(base.clone())
+            .parse(payload)
+            .expect("csv parser should succeed");
+        assert_eq!(
+            default_fields[0].source_checksum,
+            default_fields[1].source_checksum
+        );
+
+        let mut with_description = base;
+        with_description
+            .checksum_fields
+            .push(ChecksumField::Description);
+        let described = CsvBankParser::new(with_description)
+            .parse(payload)
+            .expect("csv parser should succeed");
+        assert_ne!(described[0].source_checksum, described[1].source_checksum);
+        assert_ne!(
+            described[0].source_checksum,
+            default_fields[0].source_checksum
+        );
+    }
+
+    #[test]
+    fn amount_factor_inference_flags_contradictions() {
+        let decimals = "\
+transaction_id,account_id,posted_date,amount,currency,description
+txn-1,acct-1,2024-10-01,10.5,USD,Coffee
+txn-2,acct-1,2024-10-02,-1200.25,USD,Rent
+";
+        let whole = "\
+transaction_id,account_id,posted_date,amount,currency,description
+txn-1,acct-1,2024-10-01,1500,JPY,Lunch
+";
+        let profile = CsvParserProfile::default();
+        let inferred = profile
+            .infer_amount_factor(decimals)
+            .expect("sample parses")
+            .expect("sample has amounts");
+        assert_eq!(inferred.max_decimal_places, 2);
+        assert_eq!(inferred.suggested_factor, 100);
+        assert_eq!(inferred.warning, None);
+
+        let inferred = profile
+            .infer_amount_factor(whole)
+            .expect("sample parses")
+            .expect("sample has amounts");
+        assert_eq!(inferred.suggested_factor, 1);
+        assert_eq!(inferred.warning, None);
+
+        let unit_factor = CsvParserProfile {
+            amount_minor_factor: 1,
+            ..CsvParserProfile::default()
+        };
+        let inferred = unit_factor
+            .infer_amount_factor(decimals)
+            .expect("sample parses")
+            .expect("sample has amounts");
+        assert!(
+            inferred
+                .warning
+                .is_some_and(|warning| warning.contains("truncated"))
+        );
+        assert_eq!(
+            unit_factor
+                .infer_amount_factor(whole)
+                .expect("sample parses")
+                .and_then(|inferred| inferred.warning),
+            None
+        );
+
+        let precise = "\
+transaction_id,account_id,posted_date,amount,currency,description
+txn-1,acct-1,2024-10-01,0.123456789,BTC,Sats
+";
+        let inferred = profile
+            .infer_amount_factor(precise)
+            .expect("sample parses")
+            .expect("sample has amounts");
+        assert_eq!(inferred.max_decimal_places, 6);
+        assert_eq!(inferred.suggested_factor, 1_000_000);
+        assert!(
+            inferred
+                .warning
+                .is_some_and(|warning| warning.contains("6 decimal place(s)"))
+        );
+
+        let header_only = "transaction_id,account_id,posted_date,amount,currency,description\n";
+        assert_eq!(
+            profile
+                .infer_amount_factor(header_only)
+                .expect("sample parses"),
+            None
+        );
+    }
+
     #[test]
     fn ofx_parser_extracts_transactions() {
         let parser = OfxBankParser::default();
@@ -728,4 +2065,261 @@ bad-txn,acct-1,2024-10-01,10.00,US,Coffee,,,
         assert_eq!(transactions[0].currency, "USD");
         assert_eq!(transactions[1].currency, "EUR");
     }
+
+    #[test]
+    fn ofx_parser_reads_credit_card_blocks() {
+        let transactions = OfxBankParser::default()
+            .parse(include_str!("../tests/fixtures/ofx/mixed.ofx"))
+            .expect("mixed ofx should parse");
+        let ids = transactions
+            .iter()
+            .map(|tx| (tx.transaction_id.as_str(), tx.account_id.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                ("BANK-1", "acct-usa-001"),
+                ("CARD-1", "card-4111"),
+                ("CARD-2", "card-4111"),
+            ]
+        );
+        assert_eq!(transactions[2].amount_minor, 1_875);
+
+        let err = OfxBankParser::default()
+            .parse("<OFX>\n<CCACCTFROM>\n<ACCTID>card-4111\n</CCACCTFROM>\n</OFX>")
+            .expect_err("no transaction blocks");
+        assert!(matches!(err, BankIngestError::Invalid(message) if message.contains("CCSTMTTRN")));
+    }
+
+    #[test]
+    fn ofx_dates_apply_timezone_offsets() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).expect("valid date");
+        assert_eq!(
+            parse_ofx_date("20241001", None).expect("date only"),
+            date(2024, 10, 1)
+        );
+        assert_eq!(
+            parse_ofx_date("20241001233000[-5:EST]", None).expect("local offset"),
+            date(2024, 10, 1)
+        );
+        // 23:30 EST is 04:30 the next day in UTC.
+        assert_eq!(
+            parse_ofx_date("20241001233000.000[-5:EST]", Some(0)).expect("utc statement"),
+            date(2024, 10, 2)
+        );
+        // Untagged timestamps are GMT; 02:00 GMT is the previous evening in EST.
+        assert_eq!(
+            parse_ofx_date("20241002020000", Some(-300)).expect("gmt timestamp"),
+            date(2024, 10, 1)
+        );
+        assert_eq!(
+            parse_ofx_date("20241001200000[5.30:IST]", Some(0)).expect("half-hour offset"),
+            date(2024, 10, 1)
+        );
+        assert_eq!(
+            parse_ofx_date("20241001200000[+5.30:IST]", Some(600)).expect("half-hour offset"),
+            date(2024, 10, 2)
+        );
+        assert!(parse_ofx_date("20241001200000[EST]", None).is_err());
+    }
+
+    #[test]
+    fn ofx_parser_reads_ledger_balance() {
+        let parser = OfxBankParser::default();
+        let statement = parser
+            .parse_statement(include_str!("../tests/fixtures/ofx/sample.ofx"))
+            .expect("ofx statement should parse");
+        assert_eq!(statement.transactions.len(), 2);
+        assert_eq!(statement.closing_balance_minor, Some(109_450));
+        assert_eq!(
+            statement.as_of,
+            Some(NaiveDate::from_ymd_opt(2024, 10, 16).expect("valid date"))
+        );
+    }
+
+    #[test]
+    fn ofx_parser_handles_tags_packed_on_one_line() {
+        let compact = "OFXHEADER:100<OFX><BANKMSGSRSV1><STMTTRNRS><STMTRS><CURDEF>USD\
+<BANKACCTFROM><ACCTID>acct-9</BANKACCTFROM><BANKTRANLIST>\
+<STMTTRN><TRNTYPE>DEBIT<DTPOSTED>20241001<TRNAMT>-12.50<FITID>fit-1<NAME>Coffee</STMTTRN>\
+<STMTTRN><TRNTYPE>CREDIT<DTPOSTED>20241002<TRNAMT>100.00</TRNAMT><FITID>fit-2</FITID>\
+<NAME>Deposit</NAME></STMTTRN></BANKTRANLIST><LEDGERBAL><BALAMT>87.50<DTASOF>20241002\
+</LEDGERBAL></STMTRS></STMTTRNRS></BANKMSGSRSV1></OFX>";
+        let statement = OfxBankParser::default()
+            .parse_statement(compact)
+            .expect("compact ofx should parse");
+        let parsed: Vec<(&str, &str, i64, &str)> = statement
+            .transactions
+            .iter()
+            .map(|tx| {
+                (
+                    tx.transaction_id.as_str(),
+                    tx.account_id.as_str(),
+                    tx.amount_minor,
+                    tx.description.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                ("fit-1", "acct-9", -1_250, "Coffee"),
+                ("fit-2", "acct-9", 10_000, "Deposit"),
+            ]
+        );
+        assert_eq!(statement.closing_balance_minor, Some(8_750));
+
+        for garbage in [
+            "",
+            "<",
+            "<<>>",
+            "<STMTTRN><",
+            "<STMTTRN><TRNAMT>",
+            "</STMTTRN><>x<",
+        ] {
+            assert!(OfxBankParser::default().parse_statement(garbage).is_err());
+        }
+    }
+
+    #[test]
+    fn ofx_parser_reads_sign_on_header() {
+        let input = include_str!("../tests/fixtures/ofx/sample.ofx");
+        let statement = OfxBankParser::default()
+            .parse_statement(input)
+            .expect("ofx statement should parse");
+        assert_eq!(
+            statement.institution.as_deref(),
+            Some("Codex Community Bank")
+        );
+        assert_eq!(statement.institution_id.as_deref(), Some("10898"));
+        assert_eq!(
+            statement.server_time,
+            Some(
+                NaiveDate::from_ymd_opt(2024, 10, 17)
+                    .expect("valid date")
+                    .and_hms_opt(0, 0, 0)
+                    .expect("valid time")
+                    .and_utc()
+            )
+        );
+        assert!(
+            statement
+                .transactions
+                .iter()
+                .all(|tx| tx.institution.is_none())
+        );
+
+        let stamped = OfxBankParser::new(OfxParserProfile {
+            stamp_institution: true,
+            ..OfxBankParser::default().profile
+        })
+        .parse_statement(input)
+        .expect("ofx statement should parse");
+        assert!(
+            stamped
+                .transactions
+                .iter()
+                .all(|tx| tx.institution.as_deref() == Some("Codex Community Bank"))
+        );
+
+        assert_eq!(
+            parse_ofx_datetime("20241017093000[-5:EST]").expect("timestamp parses"),
+            NaiveDate::from_ymd_opt(2024, 10, 17)
+                .expect("valid date")
+                .and_hms_opt(14, 30, 0)
+                .expect("valid time")
+                .and_utc()
+        );
+    }
+
+    #[test]
+    fn statement_balance_ties_out_within_tolerance() {
+        let statement = OfxBankParser::default()
+            .parse_statement(include_str!("../tests/fixtures/ofx/sample.ofx"))
+            .expect("ofx statement should parse");
+        let ingested = statement.ingested_total_minor();
+        assert_eq!(ingested, 9_450);
+
+        let reconciliation = reconcile_statement_balance(&statement, 100_000, ingested, 0)
+            .expect("balance should tie out");
+        assert_eq!(
+            reconciliation,
+            BalanceReconciliation {
+                expected: 109_450,
+                actual: 109_450,
+                difference_minor: 0,
+            }
+        );
+
+        let dropped = ingested - statement.transactions[1].amount_minor;
+        let err = reconcile_statement_balance(&statement, 100_000, dropped, 100)
+            .expect_err("missing transaction should be detected");
+        assert!(matches!(err, BankIngestError::Invalid(message) if message.contains("-20000")));
+
+        let close = reconcile_statement_balance(&statement, 100_000, ingested - 5, 10)
+            .expect("small differences are tolerated");
+        assert_eq!(close.difference_minor, -5);
+
+        let err = reconcile_statement_balance(&statement, i64::MIN, 0, 0)
+            .expect_err("difference overflows");
+        assert!(matches!(err, BankIngestError::Parse(_)), "{err:?}");
+        let err = reconcile_statement_balance(&statement, 100_000, ingested, i64::MIN)
+            .expect_err("tolerance overflows");
+        assert!(matches!(err, BankIngestError::Invalid(_)), "{err:?}");
+    }
+
+    #[test]
+    fn csv_parse_statement_has_no_balance() {
+        let profile: CsvParserProfile =
+            from_str(include_str!("../tests/fixtures/csv/profile.json"))
+                .expect("profile fixture must be valid");
+        let statement = CsvBankParser::new(profile)
+            .parse_statement(include_str!("../tests/fixtures/csv/sample.csv"))
+            .expect("csv statement should parse");
+        assert_eq!(statement.transactions.len(), 4);
+        assert_eq!(statement.closing_balance_minor, None);
+        assert_eq!(statement.as_of, None);
+    }
+
+    #[test]
+    fn ndjson_reports_bad_lines_and_keeps_reading() {
+        let input = concat!(
+            r#"{"transaction_id":"t1","account_id":"acc","posted_date":"2024-03-01","amount_minor":1250,"currency":"usd","description":"Coffee"}"#,
+            "\n\n",
+            r#"{"transaction_id":"t2","account_id":"acc","posted_date":"2024-03-02","amount_minor":-900,"currency":"US","description":"Fee"}"#,
+            "\nnot json\n",
+            r#"{"transaction_id":" ","account_id":"acc","posted_date":"2024-03-03","amount_minor":10,"currency":"USD","description":"Blank id"}"#,
+            "\n",
+            r#"{"transaction_id":"t4","account_id":"acc","posted_date":"2024-03-04","amount_minor":500,"currency":"EUR","description":"Refund","source_checksum":"abc"}"#,
+            "\n",
+        );
+
+        let (transactions, errors): (Vec<_>, Vec<_>) =
+            read_ndjson_transactions(input.as_bytes()).partition(Result::is_ok);
+        let transactions: Vec<NormalizedBankTransaction> =
+            transactions.into_iter().filter_map(Result::ok).collect();
+        let errors: Vec<NdjsonLineError> = errors.into_iter().filter_map(Result::err).collect();
+
+        assert_eq!(
+            transactions
+                .iter()
+                .map(|tx| tx.transaction_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["t1", "t4"]
+        );
+        assert_eq!(transactions[0].currency, "USD");
+        assert!(transactions[0].source_checksum.is_some());
+        assert_eq!(transactions[1].source_checksum.as_deref(), Some("abc"));
+
+        assert_eq!(
+            errors.iter().map(|err| err.line).collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+        assert!(matches!(errors[0].error, BankIngestError::Invalid(_)));
+        assert!(matches!(errors[1].error, BankIngestError::Parse(_)));
+        assert_eq!(
+            errors[2].to_string(),
+            "line 5: invalid payload: transaction_id missing"
+        );
+    }
 }
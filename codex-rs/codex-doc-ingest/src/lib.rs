@@ -37,6 +37,28 @@ pub enum IngestError {
     Internal(String),
 }
 
+impl IngestError {
+    /// Stable machine-readable code such as `ingest.validation`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "ingest.not_found",
+            Self::Validation(_) => "ingest.validation",
+            Self::Upstream(_) => "ingest.upstream",
+            Self::Internal(_) => "ingest.internal",
+        }
+    }
+
+    /// Status `ApiError` responds with.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            Self::NotFound(_) => 404,
+            Self::Validation(_) => 400,
+            Self::Upstream(_) => 502,
+            Self::Internal(_) => 500,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct UploadRequestPayload {
     pub firm_id: FirmId,
@@ -165,14 +187,11 @@ pub struct ApiError(IngestError);
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> axum::response::Response {
-        let status = match self.0 {
-            IngestError::Validation(_) => StatusCode::BAD_REQUEST,
-            IngestError::NotFound(_) => StatusCode::NOT_FOUND,
-            IngestError::Upstream(_) => StatusCode::BAD_GATEWAY,
-            IngestError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
-        };
+        let status =
+            StatusCode::from_u16(self.0.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
         let body = serde_json::json!({
             "error": self.0.to_string(),
+            "code": self.0.code(),
         });
         (status, Json(body)).into_response()
     }
@@ -328,4 +347,17 @@ mod tests {
         assert!(response.upload_url.contains("firm-987"));
         assert_eq!(response.fields["token"], "mock-token");
     }
+    #[test]
+    fn api_error_uses_error_status_mapping() {
+        let error = IngestError::Upstream("signer timed out".into());
+        assert_eq!(error.code(), "ingest.upstream");
+        let response = ApiError(error).into_response();
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(
+            ApiError(IngestError::Validation("bad".into()))
+                .into_response()
+                .status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
 }
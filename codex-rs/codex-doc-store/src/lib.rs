@@ -1,5 +1,6 @@
 #![deny(clippy::print_stdout, clippy::print_stderr)]
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -31,6 +32,30 @@ pub enum DocStoreError {
     Internal(String),
 }
 
+impl DocStoreError {
+    /// Stable machine-readable code such as `doc_store.not_found`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "doc_store.not_found",
+            Self::Conflict(_) => "doc_store.conflict",
+            Self::Validation(_) => "doc_store.validation",
+            Self::Encryption(_) => "doc_store.encryption",
+            Self::Internal(_) => "doc_store.internal",
+        }
+    }
+
+    /// HTTP status for API error bodies; encryption failures are server errors.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            Self::NotFound(_) => 404,
+            Self::Conflict(_) => 409,
+            Self::Validation(_) => 400,
+            Self::Encryption(_) => 500,
+            Self::Internal(_) => 500,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EncryptionEnvelope {
     pub key_id: String,
@@ -48,13 +73,17 @@ pub enum EncryptionAlgorithm {
 pub struct EncryptionContext {
     pub firm_id: FirmId,
     pub document_id: DocumentId,
+    /// Pins the wrapping key, e.g. the successor of a rotated KMS key. `None`
+    /// lets the encryptor pick its current key.
+    pub key_id: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PutObjectRequest {
     pub metadata: DocumentMetadata,
     pub payload: Vec<u8>,
-    pub retention: RetentionPolicy,
+    /// Omit to derive the policy from the metadata's registered retention class.
+    pub retention: Option<RetentionPolicy>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -126,18 +155,191 @@ pub struct RetentionPolicy {
     pub legal_hold: bool,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RetentionDefaults {
+    retention_days: u32,
+    legal_hold: bool,
+}
+
+/// Canonical retention settings per class name, so a document's
+/// `retention_class` and its `RetentionPolicy` cannot drift apart.
 #[derive(Debug, Clone, Default)]
+pub struct RetentionClassRegistry {
+    classes: HashMap<String, RetentionDefaults>,
+}
+
+impl RetentionClassRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_class(
+        mut self,
+        class: impl Into<String>,
+        retention_days: u32,
+        legal_hold: bool,
+    ) -> Self {
+        self.classes.insert(
+            class.into().trim().to_string(),
+            RetentionDefaults {
+                retention_days,
+                legal_hold,
+            },
+        );
+        self
+    }
+
+    /// The default policy for `class`, if registered.
+    pub fn policy_for(&self, class: &str) -> Option<RetentionPolicy> {
+        self.classes
+            .get(class.trim())
+            .map(|defaults| RetentionPolicy {
+                class: class.trim().to_string(),
+                retention_days: defaults.retention_days,
+                legal_hold: defaults.legal_hold,
+            })
+    }
+
+    /// Resolves the policy for a document of `class`. A supplied policy must
+    /// name the same class and, for registered classes, keep the registered
+    /// retention period; it may add a legal hold but not drop a default one.
+    /// Without a supplied policy the class must be registered.
+    pub fn resolve(
+        &self,
+        class: &str,
+        requested: Option<RetentionPolicy>,
+    ) -> DocStoreResult<RetentionPolicy> {
+        let class = class.trim();
+        let registered = self.policy_for(class);
+        let Some(requested) = requested else {
+            return registered.ok_or_else(|| {
+                DocStoreError::Validation(format!(
+                    "no retention policy supplied and retention class {class} is not registered"
+                ))
+            });
+        };
+        if requested.class.trim() != class {
+            return Err(DocStoreError::Validation(format!(
+                "retention policy class {} does not match document retention class {class}",
+                requested.class
+            )));
+        }
+        if let Some(registered) = registered {
+            if requested.retention_days != registered.retention_days {
+                return Err(DocStoreError::Validation(format!(
+                    "retention class {class} requires {} retention days, got {}",
+                    registered.retention_days, requested.retention_days
+                )));
+            }
+            if registered.legal_hold && !requested.legal_hold {
+                return Err(DocStoreError::Validation(format!(
+                    "retention class {class} requires a legal hold"
+                )));
+            }
+        }
+        Ok(RetentionPolicy {
+            class: class.to_string(),
+            ..requested
+        })
+    }
+}
+
+/// In-place metadata edits that leave the payload and version untouched.
+/// `None` fields are left as they are.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetadataPatch {
+    /// Replaces the tag list wholesale.
+    pub tags: Option<Vec<String>>,
+    /// Appended after `tags` is applied; duplicates are dropped by `normalize`.
+    pub add_tags: Vec<String>,
+    pub remove_tags: Vec<String>,
+    /// `Some(None)` detaches the document from its company.
+    pub company_id: Option<Option<CompanyId>>,
+    pub retention_class: Option<String>,
+}
+
+impl MetadataPatch {
+    fn apply(self, mut metadata: DocumentMetadata) -> DocStoreResult<DocumentMetadata> {
+        if let Some(tags) = self.tags {
+            metadata.tags = tags;
+        }
+        metadata.tags.extend(self.add_tags);
+        if !self.remove_tags.is_empty() {
+            metadata.tags.retain(|tag| {
+                !self
+                    .remove_tags
+                    .iter()
+                    .any(|removed| removed.trim().eq_ignore_ascii_case(tag.trim()))
+            });
+        }
+        if let Some(company_id) = self.company_id {
+            if company_id
+                .as_ref()
+                .is_some_and(|company_id| company_id.trim().is_empty())
+            {
+                return Err(DocStoreError::Validation(
+                    "company id cannot be empty".into(),
+                ));
+            }
+            metadata.company_id = company_id.map(|company_id| company_id.trim().to_string());
+        }
+        if let Some(retention_class) = self.retention_class {
+            if retention_class.trim().is_empty() {
+                return Err(DocStoreError::Validation(
+                    "retention class cannot be empty".into(),
+                ));
+            }
+            metadata.retention_class = retention_class;
+        }
+        metadata.normalize()
+    }
+}
+
+/// Object count and summed `content_length` for a set of documents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UsageTotals {
+    pub object_count: u64,
+    pub content_length: u64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, metadata: &DocumentMetadata) {
+        self.object_count += 1;
+        self.content_length += metadata.content_length;
+    }
+}
+
+/// Storage consumed by one firm, for billing and quota checks. Documents
+/// without a company count only towards `total` and `unassigned`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageUsage {
+    pub firm_id: FirmId,
+    pub total: UsageTotals,
+    pub by_company: BTreeMap<CompanyId, UsageTotals>,
+    pub unassigned: UsageTotals,
+}
+
+/// Listing filter; always scoped to a single firm.
+#[derive(Debug, Clone)]
 pub struct MetadataQuery {
-    pub firm_id: Option<FirmId>,
+    pub firm_id: FirmId,
     pub company_id: Option<CompanyId>,
     pub tags: Vec<String>,
 }
 
 impl MetadataQuery {
+    pub fn for_firm(firm_id: impl Into<FirmId>) -> Self {
+        Self {
+            firm_id: firm_id.into(),
+            company_id: None,
+            tags: Vec::new(),
+        }
+    }
+
     pub fn matches(&self, metadata: &DocumentMetadata) -> bool {
-        if let Some(firm_id) = &self.firm_id
-            && metadata.firm_id != *firm_id
-        {
+        if metadata.firm_id != self.firm_id {
             return false;
         }
         if let Some(company_id) = &self.company_id
@@ -163,11 +365,32 @@ impl MetadataQuery {
 pub trait DocumentStore: Send + Sync {
     async fn put_object(&self, request: PutObjectRequest) -> DocStoreResult<DocumentMetadata>;
 
-    async fn get_object(&self, document_id: &DocumentId) -> DocStoreResult<StoredObject>;
+    /// Documents owned by another firm are reported as `NotFound` so callers
+    /// cannot probe for ids outside their tenant.
+    async fn get_object(
+        &self,
+        firm_id: &FirmId,
+        document_id: &DocumentId,
+    ) -> DocStoreResult<StoredObject>;
 
-    async fn delete_object(&self, document_id: &DocumentId) -> DocStoreResult<()>;
+    /// Scoped like `get_object`.
+    async fn delete_object(&self, firm_id: &FirmId, document_id: &DocumentId)
+    -> DocStoreResult<()>;
+
+    /// Edits tags, company or retention class without rewriting the payload or
+    /// bumping the version. Scoped like `get_object`.
+    async fn update_metadata(
+        &self,
+        firm_id: &FirmId,
+        document_id: &DocumentId,
+        patch: MetadataPatch,
+    ) -> DocStoreResult<DocumentMetadata>;
 
     async fn list_metadata(&self, query: MetadataQuery) -> DocStoreResult<Vec<DocumentMetadata>>;
+
+    /// Aggregates stored metadata for `firm_id`. Backends with an index
+    /// should answer this with a grouped query rather than a listing.
+    async fn usage(&self, firm_id: &FirmId) -> DocStoreResult<StorageUsage>;
 }
 
 #[async_trait]
@@ -178,6 +401,13 @@ pub trait EnvelopeEncryptor: Send + Sync {
     ) -> DocStoreResult<(EncryptionEnvelope, Vec<u8>)>;
 
     async fn unwrap_key(&self, envelope: &EncryptionEnvelope) -> DocStoreResult<Vec<u8>>;
+
+    /// Wraps an existing data key under the key selected by `context`.
+    async fn rewrap_key(
+        &self,
+        data_key: &[u8],
+        context: EncryptionContext,
+    ) -> DocStoreResult<EncryptionEnvelope>;
 }
 
 #[async_trait]
@@ -196,6 +426,8 @@ pub struct InMemoryDocumentStore {
     state: Arc<RwLock<InMemoryState>>,
     encryptor: Arc<dyn EnvelopeEncryptor>,
     scheduler: Arc<dyn RetentionScheduler>,
+    retention_classes: RetentionClassRegistry,
+    trust_declared_length: bool,
 }
 
 #[derive(Default)]
@@ -213,16 +445,99 @@ impl InMemoryDocumentStore {
             state: Arc::new(RwLock::new(InMemoryState::default())),
             encryptor,
             scheduler,
+            retention_classes: RetentionClassRegistry::default(),
+            trust_declared_length: false,
+        }
+    }
+
+    #[must_use]
+    pub fn with_retention_classes(mut self, registry: RetentionClassRegistry) -> Self {
+        self.retention_classes = registry;
+        self
+    }
+
+    /// Skips the check that `metadata.content_length` equals the payload
+    /// size, for deployments whose trusted ingestion path streams uploads and
+    /// stores only part of each object.
+    #[must_use]
+    pub fn with_trusted_declared_length(mut self) -> Self {
+        self.trust_declared_length = true;
+        self
+    }
+
+    /// Re-wraps a document's data key under the key selected by
+    /// `new_context`, replacing its envelope. The payload is left as is.
+    /// Documents not owned by `new_context.firm_id` are reported as
+    /// `NotFound`.
+    pub async fn rewrap(
+        &self,
+        document_id: &DocumentId,
+        new_context: EncryptionContext,
+    ) -> DocStoreResult<EncryptionEnvelope> {
+        let current = self
+            .state
+            .read()
+            .await
+            .objects
+            .get(document_id)
+            .filter(|stored| stored.metadata.firm_id == new_context.firm_id)
+            .cloned()
+            .ok_or_else(|| DocStoreError::NotFound(format!("document {document_id}")))?;
+        if new_context.document_id != current.metadata.document_id {
+            return Err(DocStoreError::Validation(format!(
+                "encryption context does not belong to document {document_id}"
+            )));
         }
+        let data_key = self.encryptor.unwrap_key(&current.envelope).await?;
+        let envelope = self.encryptor.rewrap_key(&data_key, new_context).await?;
+        drop(data_key);
+
+        let mut guard = self.state.write().await;
+        let stored = guard
+            .objects
+            .get_mut(document_id)
+            .ok_or_else(|| DocStoreError::NotFound(format!("document {document_id}")))?;
+        if stored.metadata.version != current.metadata.version
+            || stored.envelope != current.envelope
+        {
+            return Err(DocStoreError::Conflict(format!(
+                "document {document_id} changed while its key was being rewrapped"
+            )));
+        }
+        stored.envelope = envelope.clone();
+        Ok(envelope)
     }
 
+    /// Documents whose envelope still references `key_id`, for auditing
+    /// retired keys.
+    pub async fn documents_using_key(&self, key_id: &str) -> Vec<DocumentId> {
+        let guard = self.state.read().await;
+        let mut documents = guard
+            .objects
+            .values()
+            .filter(|stored| stored.envelope.key_id == key_id)
+            .map(|stored| stored.metadata.document_id.clone())
+            .collect::<Vec<_>>();
+        documents.sort();
+        documents
+    }
+
+    /// A new version must come from the firm that owns the document; other
+    /// firms see `NotFound`, as with `get_object`.
     fn ensure_new_version(
         state: &InMemoryState,
         metadata: &DocumentMetadata,
     ) -> DocStoreResult<()> {
-        if let Some(existing) = state.objects.get(&metadata.document_id)
-            && metadata.version <= existing.metadata.version
-        {
+        let Some(existing) = state.objects.get(&metadata.document_id) else {
+            return Ok(());
+        };
+        if existing.metadata.firm_id != metadata.firm_id {
+            return Err(DocStoreError::NotFound(format!(
+                "document {}",
+                metadata.document_id
+            )));
+        }
+        if metadata.version <= existing.metadata.version {
             return Err(DocStoreError::Conflict(format!(
                 "document {} already has version {}",
                 metadata.document_id, existing.metadata.version
@@ -236,11 +551,23 @@ impl InMemoryDocumentStore {
 impl DocumentStore for InMemoryDocumentStore {
     async fn put_object(&self, request: PutObjectRequest) -> DocStoreResult<DocumentMetadata> {
         let normalized = request.metadata.clone().normalize()?;
+        if !self.trust_declared_length && normalized.content_length != request.payload.len() as u64
+        {
+            return Err(DocStoreError::Validation(format!(
+                "declared content length {} does not match payload size {}",
+                normalized.content_length,
+                request.payload.len()
+            )));
+        }
+        let retention = self
+            .retention_classes
+            .resolve(&normalized.retention_class, request.retention)?;
         let (envelope, data_key) = self
             .encryptor
             .wrap_key(EncryptionContext {
                 firm_id: normalized.firm_id.clone(),
                 document_id: normalized.document_id.clone(),
+                key_id: None,
             })
             .await?;
 
@@ -259,7 +586,7 @@ impl DocumentStore for InMemoryDocumentStore {
         drop(guard);
 
         self.scheduler
-            .register(&stored.metadata, &request.retention)
+            .register(&stored.metadata, &retention)
             .await?;
 
         // Drop the derived data key to make sure tests assert on behavior rather than leaks.
@@ -268,24 +595,72 @@ impl DocumentStore for InMemoryDocumentStore {
         Ok(stored.metadata)
     }
 
-    async fn get_object(&self, document_id: &DocumentId) -> DocStoreResult<StoredObject> {
+    async fn get_object(
+        &self,
+        firm_id: &FirmId,
+        document_id: &DocumentId,
+    ) -> DocStoreResult<StoredObject> {
         let guard = self.state.read().await;
         guard
             .objects
             .get(document_id)
+            .filter(|stored| stored.metadata.firm_id == *firm_id)
             .cloned()
             .ok_or_else(|| DocStoreError::NotFound(format!("document {document_id}")))
     }
 
-    async fn delete_object(&self, document_id: &DocumentId) -> DocStoreResult<()> {
+    async fn delete_object(
+        &self,
+        firm_id: &FirmId,
+        document_id: &DocumentId,
+    ) -> DocStoreResult<()> {
         let mut guard = self.state.write().await;
-        let removed = guard.objects.remove(document_id);
-        let removed =
-            removed.ok_or_else(|| DocStoreError::NotFound(format!("document {document_id}")))?;
+        let owned = guard
+            .objects
+            .get(document_id)
+            .is_some_and(|stored| stored.metadata.firm_id == *firm_id);
+        let removed = owned
+            .then(|| guard.objects.remove(document_id))
+            .flatten()
+            .ok_or_else(|| DocStoreError::NotFound(format!("document {document_id}")))?;
         drop(guard);
         self.scheduler.cancel(&removed.metadata).await
     }
 
+    /// A retention class change re-schedules retention from the registry, so
+    /// the new class must be registered.
+    async fn update_metadata(
+        &self,
+        firm_id: &FirmId,
+        document_id: &DocumentId,
+        patch: MetadataPatch,
+    ) -> DocStoreResult<DocumentMetadata> {
+        let mut guard = self.state.write().await;
+        let stored = guard
+            .objects
+            .get_mut(document_id)
+            .filter(|stored| stored.metadata.firm_id == *firm_id)
+            .ok_or_else(|| DocStoreError::NotFound(format!("document {document_id}")))?;
+        let previous = stored.metadata.clone();
+        let updated = patch.apply(previous.clone())?;
+        let retention = if updated.retention_class == previous.retention_class {
+            None
+        } else {
+            Some(
+                self.retention_classes
+                    .resolve(&updated.retention_class, None)?,
+            )
+        };
+        stored.metadata = updated.clone();
+        drop(guard);
+
+        if let Some(retention) = retention {
+            self.scheduler.cancel(&previous).await?;
+            self.scheduler.register(&updated, &retention).await?;
+        }
+        Ok(updated)
+    }
+
     async fn list_metadata(&self, query: MetadataQuery) -> DocStoreResult<Vec<DocumentMetadata>> {
         let guard = self.state.read().await;
         let mut results = guard
@@ -297,6 +672,30 @@ impl DocumentStore for InMemoryDocumentStore {
         results.sort_by(|left, right| left.uploaded_at.cmp(&right.uploaded_at));
         Ok(results)
     }
+
+    async fn usage(&self, firm_id: &FirmId) -> DocStoreResult<StorageUsage> {
+        let guard = self.state.read().await;
+        let mut usage = StorageUsage {
+            firm_id: firm_id.clone(),
+            ..StorageUsage::default()
+        };
+        for stored in guard.objects.values() {
+            let metadata = &stored.metadata;
+            if metadata.firm_id != *firm_id {
+                continue;
+            }
+            usage.total.add(metadata);
+            match &metadata.company_id {
+                Some(company_id) => usage
+                    .by_company
+                    .entry(company_id.clone())
+                    .or_default()
+                    .add(metadata),
+                None => usage.unassigned.add(metadata),
+            }
+        }
+        Ok(usage)
+    }
 }
 
 pub struct NoopRetentionScheduler {
@@ -374,17 +773,27 @@ impl EnvelopeEncryptor for MockEnvelopeEncryptor {
         context: EncryptionContext,
     ) -> DocStoreResult<(EncryptionEnvelope, Vec<u8>)> {
         let key = vec![0u8; 32];
-        let envelope = EncryptionEnvelope {
-            key_id: format!("mock-kms:{}", context.firm_id),
-            algorithm: EncryptionAlgorithm::AwsKmsEnvelope,
-            wrapped_key: key.clone(),
-        };
+        let envelope = self.rewrap_key(&key, context).await?;
         Ok((envelope, key))
     }
 
     async fn unwrap_key(&self, envelope: &EncryptionEnvelope) -> DocStoreResult<Vec<u8>> {
         Ok(envelope.wrapped_key.clone())
     }
+
+    async fn rewrap_key(
+        &self,
+        data_key: &[u8],
+        context: EncryptionContext,
+    ) -> DocStoreResult<EncryptionEnvelope> {
+        Ok(EncryptionEnvelope {
+            key_id: context
+                .key_id
+                .unwrap_or_else(|| format!("mock-kms:{}", context.firm_id)),
+            algorithm: EncryptionAlgorithm::AwsKmsEnvelope,
+            wrapped_key: data_key.to_vec(),
+        })
+    }
 }
 
 pub fn generate_document_id() -> DocumentId {
@@ -403,7 +812,7 @@ mod tests {
             company_id: Some("company-456".into()),
             version: 1,
             content_type: "application/pdf".into(),
-            content_length: 1024,
+            content_length: 8,
             checksum: "abc123".into(),
             uploaded_at: Utc::now(),
             uploaded_by: "user@example.com".into(),
@@ -412,6 +821,11 @@ mod tests {
         }
     }
 
+    /// Matches the `content_length` declared by `sample_metadata`.
+    fn sample_payload() -> Vec<u8> {
+        vec![42; 8]
+    }
+
     fn sample_policy() -> RetentionPolicy {
         RetentionPolicy {
             class: "finance.7y".into(),
@@ -429,7 +843,7 @@ mod tests {
         let request = PutObjectRequest {
             metadata: metadata.clone(),
             payload: vec![42; 8],
-            retention: sample_policy(),
+            retention: Some(sample_policy()),
         };
 
         let expected = metadata
@@ -441,7 +855,7 @@ mod tests {
         assert_eq!(stored.document_id, metadata.document_id);
 
         let fetched = store
-            .get_object(&metadata.document_id)
+            .get_object(&metadata.firm_id, &metadata.document_id)
             .await
             .expect("fetch object");
         assert_eq!(fetched.metadata, expected);
@@ -464,8 +878,8 @@ mod tests {
         store
             .put_object(PutObjectRequest {
                 metadata: metadata.clone(),
-                payload: vec![],
-                retention: sample_policy(),
+                payload: sample_payload(),
+                retention: Some(sample_policy()),
             })
             .await
             .expect("first version");
@@ -474,8 +888,8 @@ mod tests {
         let err = store
             .put_object(PutObjectRequest {
                 metadata,
-                payload: vec![],
-                retention: sample_policy(),
+                payload: sample_payload(),
+                retention: Some(sample_policy()),
             })
             .await
             .unwrap_err();
@@ -496,8 +910,8 @@ mod tests {
         store
             .put_object(PutObjectRequest {
                 metadata: meta_a.clone(),
-                payload: vec![],
-                retention: sample_policy(),
+                payload: sample_payload(),
+                retention: Some(sample_policy()),
             })
             .await
             .expect("store a");
@@ -509,15 +923,15 @@ mod tests {
         store
             .put_object(PutObjectRequest {
                 metadata: meta_b.clone(),
-                payload: vec![],
-                retention: sample_policy(),
+                payload: sample_payload(),
+                retention: Some(sample_policy()),
             })
             .await
             .expect("store b");
 
         let results = store
             .list_metadata(MetadataQuery {
-                firm_id: Some(meta_a.firm_id.clone()),
+                firm_id: meta_a.firm_id.clone(),
                 company_id: meta_b.company_id.clone(),
                 tags: vec!["statement".into()],
             })
@@ -528,6 +942,153 @@ mod tests {
         assert_eq!(results[0].document_id, meta_b.document_id);
     }
 
+    #[tokio::test]
+    async fn other_firms_cannot_read_or_delete_documents() {
+        let scheduler = Arc::new(NoopRetentionScheduler::new());
+        let store = InMemoryDocumentStore::new(Arc::new(MockEnvelopeEncryptor), scheduler.clone());
+        let stored = store
+            .put_object(PutObjectRequest {
+                metadata: sample_metadata(),
+                payload: vec![3; 8],
+                retention: Some(sample_policy()),
+            })
+            .await
+            .expect("store");
+        let intruder: FirmId = "firm-other".into();
+
+        let err = store
+            .get_object(&intruder, &stored.document_id)
+            .await
+            .expect_err("foreign read");
+        assert!(matches!(err, DocStoreError::NotFound(_)));
+        let err = store
+            .delete_object(&intruder, &stored.document_id)
+            .await
+            .expect_err("foreign delete");
+        assert!(matches!(err, DocStoreError::NotFound(_)));
+        assert!(
+            store
+                .list_metadata(MetadataQuery::for_firm(intruder.clone()))
+                .await
+                .expect("list")
+                .is_empty()
+        );
+        let err = store
+            .put_object(PutObjectRequest {
+                metadata: DocumentMetadata {
+                    firm_id: intruder,
+                    version: stored.version + 1,
+                    ..stored.clone()
+                },
+                payload: vec![0; 8],
+                retention: Some(sample_policy()),
+            })
+            .await
+            .expect_err("foreign overwrite");
+        assert!(matches!(err, DocStoreError::NotFound(_)));
+
+        let fetched = store
+            .get_object(&stored.firm_id, &stored.document_id)
+            .await
+            .expect("owner read");
+        assert_eq!(fetched.payload, vec![3; 8]);
+        assert_eq!(scheduler.calls().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn usage_sums_content_length_per_company() {
+        let store = InMemoryDocumentStore::new(
+            Arc::new(MockEnvelopeEncryptor),
+            Arc::new(NoopRetentionScheduler::new()),
+        );
+        let put = |company_id: Option<&str>, firm_id: &str, content_length| {
+            let mut metadata = sample_metadata();
+            metadata.firm_id = firm_id.into();
+            metadata.company_id = company_id.map(ToString::to_string);
+            metadata.content_length = content_length;
+            PutObjectRequest {
+                metadata,
+                payload: vec![0; content_length as usize],
+                retention: Some(sample_policy()),
+            }
+        };
+        for request in [
+            put(Some("company-a"), "firm-123", 100),
+            put(Some("company-a"), "firm-123", 50),
+            put(Some("company-b"), "firm-123", 25),
+            put(None, "firm-123", 5),
+            put(Some("company-a"), "firm-other", 1_000),
+        ] {
+            store.put_object(request).await.expect("store");
+        }
+
+        let usage = store.usage(&"firm-123".to_string()).await.expect("usage");
+        assert_eq!(
+            usage.total,
+            UsageTotals {
+                object_count: 4,
+                content_length: 180,
+            }
+        );
+        assert_eq!(
+            usage.by_company.get("company-a"),
+            Some(&UsageTotals {
+                object_count: 2,
+                content_length: 150,
+            })
+        );
+        assert_eq!(usage.by_company.len(), 2);
+        assert_eq!(usage.unassigned.content_length, 5);
+        assert_eq!(
+            store
+                .usage(&"firm-none".to_string())
+                .await
+                .expect("empty usage")
+                .total,
+            UsageTotals::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn put_object_checks_declared_length_against_payload() {
+        let store = InMemoryDocumentStore::new(
+            Arc::new(MockEnvelopeEncryptor),
+            Arc::new(NoopRetentionScheduler::new()),
+        );
+        let request = |payload: Vec<u8>| PutObjectRequest {
+            metadata: sample_metadata(),
+            payload,
+            retention: Some(sample_policy()),
+        };
+
+        let stored = store
+            .put_object(request(vec![7; 8]))
+            .await
+            .expect("matching length");
+        assert_eq!(stored.content_length, 8);
+
+        let err = store
+            .put_object(request(vec![7; 3]))
+            .await
+            .expect_err("mismatched length");
+        match err {
+            DocStoreError::Validation(message) => {
+                assert!(message.contains("does not match payload size 3"));
+            }
+            other => panic!("unexpected error {other:?}"),
+        }
+
+        let streamed = InMemoryDocumentStore::new(
+            Arc::new(MockEnvelopeEncryptor),
+            Arc::new(NoopRetentionScheduler::new()),
+        )
+        .with_trusted_declared_length()
+        .put_object(request(vec![7; 3]))
+        .await
+        .expect("declared length trusted");
+        assert_eq!(streamed.content_length, 8);
+    }
+
     #[tokio::test]
     async fn delete_cancels_retention() {
         let scheduler = Arc::new(NoopRetentionScheduler::new());
@@ -536,14 +1097,14 @@ mod tests {
         store
             .put_object(PutObjectRequest {
                 metadata: metadata.clone(),
-                payload: vec![],
-                retention: sample_policy(),
+                payload: sample_payload(),
+                retention: Some(sample_policy()),
             })
             .await
             .expect("store");
 
         store
-            .delete_object(&metadata.document_id)
+            .delete_object(&metadata.firm_id, &metadata.document_id)
             .await
             .expect("delete");
 
@@ -551,4 +1112,290 @@ mod tests {
         assert_eq!(calls.len(), 2);
         assert!(matches!(calls[1].action, RetentionAction::Cancel));
     }
+
+    fn registry_store() -> InMemoryDocumentStore {
+        InMemoryDocumentStore::new(
+            Arc::new(MockEnvelopeEncryptor),
+            Arc::new(NoopRetentionScheduler::new()),
+        )
+        .with_retention_classes(
+            RetentionClassRegistry::new()
+                .with_class("finance.7y", 365 * 7, false)
+                .with_class("litigation", 365 * 10, true),
+        )
+    }
+
+    #[tokio::test]
+    async fn registered_class_derives_policy_when_omitted() {
+        let scheduler = Arc::new(NoopRetentionScheduler::new());
+        let store = InMemoryDocumentStore::new(Arc::new(MockEnvelopeEncryptor), scheduler.clone())
+            .with_retention_classes(RetentionClassRegistry::new().with_class(
+                "finance.7y",
+                365 * 7,
+                false,
+            ));
+        store
+            .put_object(PutObjectRequest {
+                metadata: sample_metadata(),
+                payload: sample_payload(),
+                retention: None,
+            })
+            .await
+            .expect("derived retention");
+
+        let calls = scheduler.calls().await;
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0]._class, "finance.7y");
+    }
+
+    #[tokio::test]
+    async fn rejects_policy_that_disagrees_with_class() {
+        let store = registry_store();
+
+        let mut short = sample_policy();
+        short.retention_days = 30;
+        let err = store
+            .put_object(PutObjectRequest {
+                metadata: sample_metadata(),
+                payload: sample_payload(),
+                retention: Some(short),
+            })
+            .await
+            .expect_err("30-day policy on a 7-year class");
+        assert!(matches!(err, DocStoreError::Validation(_)));
+
+        let mut other_class = sample_policy();
+        other_class.class = "scratch".into();
+        let err = store
+            .put_object(PutObjectRequest {
+                metadata: sample_metadata(),
+                payload: sample_payload(),
+                retention: Some(other_class),
+            })
+            .await
+            .expect_err("class mismatch");
+        assert!(matches!(err, DocStoreError::Validation(_)));
+
+        let mut held = sample_metadata();
+        held.retention_class = "litigation".into();
+        let err = store
+            .put_object(PutObjectRequest {
+                metadata: held,
+                payload: sample_payload(),
+                retention: Some(RetentionPolicy {
+                    class: "litigation".into(),
+                    retention_days: 365 * 10,
+                    legal_hold: false,
+                }),
+            })
+            .await
+            .expect_err("dropping the default legal hold");
+        assert!(matches!(err, DocStoreError::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn unregistered_class_requires_explicit_policy() {
+        let store = registry_store();
+        let mut metadata = sample_metadata();
+        metadata.retention_class = "scratch".into();
+
+        let err = store
+            .put_object(PutObjectRequest {
+                metadata: metadata.clone(),
+                payload: sample_payload(),
+                retention: None,
+            })
+            .await
+            .expect_err("nothing to derive from");
+        assert!(matches!(err, DocStoreError::Validation(_)));
+
+        store
+            .put_object(PutObjectRequest {
+                metadata,
+                payload: sample_payload(),
+                retention: Some(RetentionPolicy {
+                    class: "scratch".into(),
+                    retention_days: 30,
+                    legal_hold: false,
+                }),
+            })
+            .await
+            .expect("explicit policy for unregistered class");
+    }
+
+    #[tokio::test]
+    async fn update_metadata_patches_in_place() {
+        let scheduler = Arc::new(NoopRetentionScheduler::new());
+        let store = InMemoryDocumentStore::new(Arc::new(MockEnvelopeEncryptor), scheduler.clone())
+            .with_retention_classes(RetentionClassRegistry::new().with_class(
+                "finance.10y",
+                365 * 10,
+                false,
+            ));
+        let stored = store
+            .put_object(PutObjectRequest {
+                metadata: sample_metadata(),
+                payload: vec![7; 8],
+                retention: Some(sample_policy()),
+            })
+            .await
+            .expect("store");
+
+        let updated = store
+            .update_metadata(
+                &stored.firm_id,
+                &stored.document_id,
+                MetadataPatch {
+                    add_tags: vec![" migrated ".into(), "RECEIPT".into()],
+                    remove_tags: vec!["receipt".into()],
+                    company_id: Some(None),
+                    retention_class: Some("finance.10y".into()),
+                    ..MetadataPatch::default()
+                },
+            )
+            .await
+            .expect("patch");
+
+        assert_eq!(updated.version, stored.version);
+        assert_eq!(updated.company_id, None);
+        assert_eq!(updated.retention_class, "finance.10y");
+        assert!(updated.tags.iter().any(|tag| tag == "migrated"));
+        assert!(
+            !updated
+                .tags
+                .iter()
+                .any(|tag| tag.eq_ignore_ascii_case("receipt"))
+        );
+
+        let fetched = store
+            .get_object(&stored.firm_id, &stored.document_id)
+            .await
+            .expect("get");
+        assert_eq!(fetched.metadata, updated);
+        assert_eq!(fetched.payload, vec![7; 8]);
+
+        let calls = scheduler.calls().await;
+        assert_eq!(calls.len(), 3);
+        assert!(matches!(calls[1].action, RetentionAction::Cancel));
+        assert_eq!(calls[2]._class, "finance.10y");
+    }
+
+    #[tokio::test]
+    async fn update_metadata_rejects_missing_and_empty_fields() {
+        let store = InMemoryDocumentStore::new(
+            Arc::new(MockEnvelopeEncryptor),
+            Arc::new(NoopRetentionScheduler::new()),
+        );
+        let err = store
+            .update_metadata(
+                &"firm-123".to_string(),
+                &"missing".to_string(),
+                MetadataPatch::default(),
+            )
+            .await
+            .expect_err("absent document");
+        assert!(matches!(err, DocStoreError::NotFound(_)));
+
+        let stored = store
+            .put_object(PutObjectRequest {
+                metadata: sample_metadata(),
+                payload: sample_payload(),
+                retention: Some(sample_policy()),
+            })
+            .await
+            .expect("store");
+        for patch in [
+            MetadataPatch {
+                retention_class: Some("  ".into()),
+                ..MetadataPatch::default()
+            },
+            MetadataPatch {
+                company_id: Some(Some(String::new())),
+                ..MetadataPatch::default()
+            },
+        ] {
+            let err = store
+                .update_metadata(&stored.firm_id, &stored.document_id, patch)
+                .await
+                .expect_err("empty required field");
+            assert!(matches!(err, DocStoreError::Validation(_)));
+        }
+        let err = store
+            .update_metadata(
+                &"firm-other".to_string(),
+                &stored.document_id,
+                MetadataPatch {
+                    add_tags: vec!["hijacked".into()],
+                    ..MetadataPatch::default()
+                },
+            )
+            .await
+            .expect_err("foreign firm patch");
+        assert!(matches!(err, DocStoreError::NotFound(_)));
+        let fetched = store
+            .get_object(&stored.firm_id, &stored.document_id)
+            .await
+            .expect("get");
+        assert_eq!(fetched.metadata, stored);
+    }
+
+    #[tokio::test]
+    async fn rewrap_moves_documents_off_retired_key() {
+        let store = InMemoryDocumentStore::new(
+            Arc::new(MockEnvelopeEncryptor),
+            Arc::new(NoopRetentionScheduler::new()),
+        );
+        let stored = store
+            .put_object(PutObjectRequest {
+                metadata: sample_metadata(),
+                payload: vec![9; 8],
+                retention: Some(sample_policy()),
+            })
+            .await
+            .expect("store");
+        assert_eq!(
+            store.documents_using_key("mock-kms:firm-123").await,
+            vec![stored.document_id.clone()]
+        );
+
+        let envelope = store
+            .rewrap(
+                &stored.document_id,
+                EncryptionContext {
+                    firm_id: stored.firm_id.clone(),
+                    document_id: stored.document_id.clone(),
+                    key_id: Some("mock-kms:firm-123:v2".into()),
+                },
+            )
+            .await
+            .expect("rewrap");
+        assert_eq!(envelope.key_id, "mock-kms:firm-123:v2");
+
+        let fetched = store
+            .get_object(&stored.firm_id, &stored.document_id)
+            .await
+            .expect("get");
+        assert_eq!(fetched.envelope, envelope);
+        assert_eq!(fetched.payload, vec![9; 8]);
+        assert_eq!(fetched.metadata, stored);
+        assert!(
+            store
+                .documents_using_key("mock-kms:firm-123")
+                .await
+                .is_empty()
+        );
+
+        let err = store
+            .rewrap(
+                &stored.document_id,
+                EncryptionContext {
+                    firm_id: "firm-other".into(),
+                    document_id: stored.document_id.clone(),
+                    key_id: None,
+                },
+            )
+            .await
+            .expect_err("foreign firm context");
+        assert!(matches!(err, DocStoreError::NotFound(_)));
+    }
 }
@@ -3,10 +3,18 @@
 use std::time::SystemTime;
 
 use async_trait::async_trait;
+use chrono::Datelike;
+use chrono::NaiveDate;
+use codex_doc_store::DocumentId;
+use codex_doc_store::FirmId;
+use codex_doc_store::ObjectVersion;
 
 mod memory;
+mod rates;
 
 pub use memory::InMemoryLedgerService;
+pub use rates::InMemoryRateStore;
+pub use rates::RateProvider;
 
 pub type CompanyId = String;
 pub type AccountId = String;
@@ -28,6 +36,28 @@ pub enum LedgerError {
     Internal(String),
 }
 
+impl LedgerError {
+    /// Stable machine-readable code such as `ledger.validation`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "ledger.not_found",
+            Self::Rejected(_) => "ledger.rejected",
+            Self::Validation(_) => "ledger.validation",
+            Self::Internal(_) => "ledger.internal",
+        }
+    }
+
+    /// HTTP status for API error bodies; `Rejected` (e.g. a closed period) maps to 409.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            Self::NotFound(_) => 404,
+            Self::Rejected(_) => 409,
+            Self::Validation(_) => 400,
+            Self::Internal(_) => 500,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Company {
     pub id: CompanyId,
@@ -43,6 +73,49 @@ pub struct FiscalCalendar {
     pub opening_month: u8,
 }
 
+impl FiscalCalendar {
+    /// Resolves the fiscal period containing `date`.
+    ///
+    /// Fiscal years are labelled by the calendar year in which they open.
+    /// Period counts that divide twelve map to whole months; other counts
+    /// (such as 13-period calendars) split the year into equal day ranges of
+    /// `days / periods_per_year`, with the final period absorbing the
+    /// remainder. Out-of-range settings are clamped to 1..=12 months and at
+    /// least one period.
+    #[must_use]
+    pub fn period_for(&self, date: NaiveDate) -> PeriodRef {
+        let opening_month = u32::from(self.opening_month.clamp(1, 12));
+        let periods = u32::from(self.periods_per_year.max(1));
+        let fiscal_year = if date.month() >= opening_month {
+            date.year()
+        } else {
+            date.year() - 1
+        };
+
+        let period = if 12 % periods == 0 {
+            let month_offset = (date.month() + 12 - opening_month) % 12;
+            month_offset / (12 / periods) + 1
+        } else {
+            let start = fiscal_year_start(fiscal_year, opening_month);
+            let next_start = fiscal_year_start(fiscal_year + 1, opening_month);
+            let days_in_year = (next_start - start).num_days();
+            let day_index = (date - start).num_days();
+            let period_length = (days_in_year / i64::from(periods)).max(1);
+            let index = u32::try_from(day_index / period_length).unwrap_or(0);
+            index.min(periods - 1) + 1
+        };
+
+        PeriodRef {
+            fiscal_year,
+            period: u8::try_from(period).unwrap_or(u8::MAX),
+        }
+    }
+}
+
+fn fiscal_year_start(year: i32, opening_month: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, opening_month, 1).unwrap_or(NaiveDate::MIN)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Account {
     pub id: AccountId,
@@ -73,6 +146,23 @@ pub enum AccountType {
     OffBalance,
 }
 
+impl AccountType {
+    /// Infers the account type from the leading digit of a conventional chart
+    /// code: 1xxx assets, 2xxx liabilities, 3xxx equity, 4xxx revenue and
+    /// 5xxx-8xxx expenses. Other codes have no conventional type.
+    #[must_use]
+    pub fn from_code(code: &str) -> Option<AccountType> {
+        match code.trim().chars().next()? {
+            '1' => Some(AccountType::Asset),
+            '2' => Some(AccountType::Liability),
+            '3' => Some(AccountType::Equity),
+            '4' => Some(AccountType::Revenue),
+            '5'..='8' => Some(AccountType::Expense),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CurrencyMode {
     FunctionalOnly,
@@ -149,6 +239,24 @@ pub struct JournalEntry {
     pub memo: Option<String>,
     pub reverses_entry_id: Option<JournalEntryId>,
     pub reversed_by_entry_id: Option<JournalEntryId>,
+    /// Functional amount matched so far by partial reconciliations.
+    pub reconciled_amount_minor: i64,
+    /// Source documents backing the entry, e.g. the invoice it records.
+    pub attachments: Vec<DocumentRef>,
+}
+
+/// A document-store object attached to a journal entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentRef {
+    pub document_id: DocumentId,
+    pub firm_id: FirmId,
+    pub version: ObjectVersion,
+}
+
+/// Maps ledger companies to the firm whose document store holds their
+/// attachments.
+pub trait CompanyFirmResolver: Send + Sync {
+    fn firm_for_company(&self, company_id: &CompanyId) -> Option<FirmId>;
 }
 
 impl JournalEntry {
@@ -163,6 +271,15 @@ impl JournalEntry {
         debits == credits
     }
 
+    /// Total of the debit lines in the functional currency.
+    pub fn total_minor(&self) -> i64 {
+        self.lines
+            .iter()
+            .filter(|line| line.side == PostingSide::Debit)
+            .map(|line| line.functional_amount_minor)
+            .sum()
+    }
+
     pub fn validate(&self) -> LedgerResult<()> {
         if !self.is_balanced() {
             return Err(LedgerError::Validation("Journal entry must balance".into()));
@@ -206,6 +323,7 @@ impl JournalEntry {
                 self.reconciliation_status = ReconciliationStatus::Reconciled {
                     session_id: session_id.into(),
                 };
+                self.reconciled_amount_minor = self.total_minor();
                 Ok(())
             }
             ReconciliationStatus::Pending {
@@ -220,6 +338,55 @@ impl JournalEntry {
         }
     }
 
+    /// Applies `amount_minor` from a partial match. The entry stays pending
+    /// under `session_id` until the applied total covers `total_minor`, then
+    /// becomes reconciled.
+    pub fn mark_partially_reconciled(
+        &mut self,
+        session_id: &str,
+        amount_minor: i64,
+    ) -> LedgerResult<()> {
+        if amount_minor <= 0 {
+            return Err(LedgerError::Validation(
+                "partial reconciliation amount must be positive".into(),
+            ));
+        }
+        if matches!(
+            self.reconciliation_status,
+            ReconciliationStatus::Reconciled { .. } | ReconciliationStatus::WriteOff { .. }
+        ) {
+            return Err(LedgerError::Validation(
+                "entry is already reconciled or written off".into(),
+            ));
+        }
+        let total = self.total_minor();
+        let applied = self
+            .reconciled_amount_minor
+            .checked_add(amount_minor)
+            .ok_or_else(|| {
+                LedgerError::Validation(format!(
+                    "partial reconciliation of {amount_minor} overflows the applied amount"
+                ))
+            })?;
+        if applied > total {
+            return Err(LedgerError::Validation(format!(
+                "partial reconciliation of {amount_minor} exceeds remaining balance {}",
+                total.saturating_sub(self.reconciled_amount_minor)
+            )));
+        }
+        self.reconciled_amount_minor = applied;
+        self.reconciliation_status = if applied == total {
+            ReconciliationStatus::Reconciled {
+                session_id: session_id.into(),
+            }
+        } else {
+            ReconciliationStatus::Pending {
+                session_id: session_id.into(),
+            }
+        };
+        Ok(())
+    }
+
     pub fn mark_write_off(&mut self, approval_reference: impl Into<String>) -> LedgerResult<()> {
         let approval_reference = approval_reference.into();
         if approval_reference.trim().is_empty() {
@@ -241,6 +408,7 @@ impl JournalEntry {
 
     pub fn clear_reconciliation(&mut self) {
         self.reconciliation_status = ReconciliationStatus::Unreconciled;
+        self.reconciled_amount_minor = 0;
     }
 }
 
@@ -296,9 +464,8 @@ impl JournalLine {
                 if rate.base != self.currency || rate.quote != self.functional_currency {
                     return false;
                 }
-                let expected = (self.amount_minor as f64) * rate.rate;
-                let rounded = expected.round() as i64;
-                (rounded - self.functional_amount_minor).abs() <= 2 && rate.source.is_some()
+                (rate.convert_minor(self.amount_minor) - self.functional_amount_minor).abs() <= 2
+                    && rate.source.is_some()
             }
             None => false,
         }
@@ -326,6 +493,31 @@ pub struct CurrencyRate {
     pub observed_at: SystemTime,
 }
 
+impl CurrencyRate {
+    /// Converts base-currency minor units into quote-currency minor units,
+    /// rounding half to even at the quote precision.
+    #[must_use]
+    pub fn convert_minor(&self, amount_minor: i64) -> i64 {
+        let scale = precision_scale(self.quote.precision, self.base.precision);
+        ((amount_minor as f64) * self.rate * scale).round_ties_even() as i64
+    }
+
+    /// Inverse of `convert_minor`, rounding half to even at the base
+    /// precision. Non-positive rates convert to zero.
+    #[must_use]
+    pub fn convert_back(&self, amount_minor: i64) -> i64 {
+        if self.rate <= 0.0 {
+            return 0;
+        }
+        let scale = precision_scale(self.base.precision, self.quote.precision);
+        ((amount_minor as f64) / self.rate * scale).round_ties_even() as i64
+    }
+}
+
+fn precision_scale(to: u8, from: u8) -> f64 {
+    10_f64.powi(i32::from(to) - i32::from(from))
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TaxCode {
     pub code: String,
@@ -406,11 +598,42 @@ pub struct SeedChartRequest {
     pub tenant: TenantContext,
 }
 
+/// A chart account whose supplied type disagrees with the type implied by
+/// its code range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChartTypeWarning {
+    pub code: String,
+    pub supplied: AccountType,
+    pub inferred: AccountType,
+}
+
+impl SeedChartRequest {
+    /// Returns non-fatal warnings for accounts whose `account_type`
+    /// contradicts `AccountType::from_code`.
+    #[must_use]
+    pub fn validate(&self) -> Vec<ChartTypeWarning> {
+        self.accounts
+            .iter()
+            .filter_map(|account| {
+                let inferred = AccountType::from_code(&account.code)?;
+                (inferred != account.account_type).then(|| ChartTypeWarning {
+                    code: account.code.clone(),
+                    supplied: account.account_type,
+                    inferred,
+                })
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct PostEntryRequest {
     pub entry: JournalEntry,
     pub tenant: TenantContext,
     pub mode: PostingMode,
+    /// Client-supplied key; re-posting with a key already committed for the
+    /// same company returns the original entry instead of posting again.
+    pub idempotency_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -419,6 +642,24 @@ pub enum PostingMode {
     Commit,
 }
 
+/// Net functional-currency balance of one account; debits are positive and
+/// credits negative.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountBalance {
+    pub account_id: AccountId,
+    pub currency: Currency,
+    pub balance_minor: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostEntryOutcome {
+    pub entry: JournalEntry,
+    /// For `PostingMode::DryRun`, each affected account's balance as it would
+    /// stand after the entry, in the order the accounts first appear on its
+    /// lines. Empty when committing.
+    pub projected_balances: Vec<AccountBalance>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ReverseEntryRequest {
     pub entry_id: JournalEntryId,
@@ -426,12 +667,22 @@ pub struct ReverseEntryRequest {
     pub tenant: TenantContext,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkReconciledRequest {
+    pub entry_id: JournalEntryId,
+    pub session_id: String,
+    pub tenant: TenantContext,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LockPeriodRequest {
     pub journal_id: JournalId,
     pub period: PeriodRef,
     pub action: PeriodAction,
     pub approval_reference: Option<String>,
+    /// Rejects a `Close` while any posted entry in the period is still
+    /// `Unreconciled` or `Pending`. Other actions ignore it.
+    pub require_reconciled: bool,
     pub tenant: TenantContext,
 }
 
@@ -458,13 +709,57 @@ pub struct AuditTrailFilter {
     pub tenant: TenantContext,
 }
 
+/// Filter for `LedgerService::list_entries`; unset fields match any entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntryQuery {
+    pub status: Option<EntryStatus>,
+    pub reconciliation_status: Option<ReconciliationStatus>,
+    pub journal_id: Option<JournalId>,
+}
+
+impl EntryQuery {
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        if let Some(status) = self.status
+            && entry.status != status
+        {
+            return false;
+        }
+        if let Some(reconciliation_status) = &self.reconciliation_status
+            && entry.reconciliation_status != *reconciliation_status
+        {
+            return false;
+        }
+        if let Some(journal_id) = &self.journal_id
+            && entry.journal_id != *journal_id
+        {
+            return false;
+        }
+        true
+    }
+}
+
 #[async_trait]
 pub trait LedgerService: Send + Sync {
     async fn create_company(&self, request: CreateCompanyRequest) -> LedgerResult<Company>;
     async fn upsert_account(&self, request: UpsertAccountRequest) -> LedgerResult<Account>;
+    /// Sets `is_active = false` on an account whose committed balance is
+    /// zero; accounts still carrying a balance are `Rejected`. Upserting the
+    /// account as active reactivates it.
+    async fn deactivate_account(
+        &self,
+        company_id: &CompanyId,
+        account_id: &AccountId,
+        tenant: TenantContext,
+    ) -> LedgerResult<Account>;
     async fn seed_chart(&self, request: SeedChartRequest) -> LedgerResult<Vec<Account>>;
-    async fn post_entry(&self, request: PostEntryRequest) -> LedgerResult<JournalEntry>;
+    async fn post_entry(&self, request: PostEntryRequest) -> LedgerResult<PostEntryOutcome>;
     async fn reverse_entry(&self, request: ReverseEntryRequest) -> LedgerResult<JournalEntry>;
+    /// Marks a committed entry reconciled under `request.session_id`,
+    /// passing through `Pending` when it is still unreconciled.
+    async fn mark_entry_reconciled(
+        &self,
+        request: MarkReconciledRequest,
+    ) -> LedgerResult<JournalEntry>;
     async fn lock_period(&self, request: LockPeriodRequest) -> LedgerResult<Journal>;
     async fn ensure_period(&self, request: EnsurePeriodRequest) -> LedgerResult<Journal>;
     async fn revalue_currency(
@@ -472,6 +767,22 @@ pub trait LedgerService: Send + Sync {
         request: CurrencyRevaluationRequest,
     ) -> LedgerResult<Vec<JournalEntry>>;
     async fn list_audit_trail(&self, filter: AuditTrailFilter) -> LedgerResult<Vec<AuditEvent>>;
+    /// Lists the company's committed entries matching `filter`, oldest
+    /// posting first.
+    async fn list_entries(
+        &self,
+        company_id: &CompanyId,
+        filter: EntryQuery,
+    ) -> LedgerResult<Vec<JournalEntry>>;
+    /// Committed entries whose `memo` or line memos contain every
+    /// whitespace-separated token of `query`, ignoring case, most recently
+    /// posted first and capped at `limit`.
+    async fn search_entries(
+        &self,
+        company_id: &CompanyId,
+        query: &str,
+        limit: usize,
+    ) -> LedgerResult<Vec<JournalEntry>>;
 }
 
 #[cfg(test)]
@@ -503,6 +814,8 @@ mod tests {
             memo: None,
             reverses_entry_id: None,
             reversed_by_entry_id: None,
+            reconciled_amount_minor: 0,
+            attachments: Vec::new(),
             lines: vec![
                 JournalLine {
                     id: "ln-1".into(),
@@ -546,6 +859,8 @@ mod tests {
             memo: None,
             reverses_entry_id: None,
             reversed_by_entry_id: None,
+            reconciled_amount_minor: 0,
+            attachments: Vec::new(),
             lines: vec![
                 JournalLine {
                     id: "ln-1".into(),
@@ -623,6 +938,54 @@ mod tests {
         assert!(postable.allows_posting());
     }
 
+    #[test]
+    fn account_type_inferred_from_code_range() {
+        assert_eq!(AccountType::from_code("1100"), Some(AccountType::Asset));
+        assert_eq!(AccountType::from_code("2000"), Some(AccountType::Liability));
+        assert_eq!(AccountType::from_code("3100"), Some(AccountType::Equity));
+        assert_eq!(AccountType::from_code("4000"), Some(AccountType::Revenue));
+        assert_eq!(AccountType::from_code("6500"), Some(AccountType::Expense));
+        assert_eq!(AccountType::from_code("9000"), None);
+        assert_eq!(AccountType::from_code("CASH"), None);
+        assert_eq!(AccountType::from_code(""), None);
+    }
+
+    #[test]
+    fn seed_chart_validation_warns_on_type_mismatch() {
+        let account = |code: &str, account_type| ChartAccount {
+            code: code.into(),
+            name: format!("Account {code}"),
+            account_type,
+            parent_code: None,
+            currency_mode: CurrencyMode::FunctionalOnly,
+            tax_code: None,
+            is_summary: false,
+        };
+        let request = SeedChartRequest {
+            company_id: "comp-1".into(),
+            accounts: vec![
+                account("1000", AccountType::Asset),
+                account("4000", AccountType::Expense),
+                account("9100", AccountType::OffBalance),
+            ],
+            tenant: TenantContext {
+                tenant_id: "comp-1".into(),
+                user_id: "user-1".into(),
+                roles: vec![Role::Admin],
+                locale: None,
+            },
+        };
+
+        assert_eq!(
+            request.validate(),
+            vec![ChartTypeWarning {
+                code: "4000".into(),
+                supplied: AccountType::Expense,
+                inferred: AccountType::Revenue,
+            }]
+        );
+    }
+
     #[test]
     fn currency_provenance_requires_rate_metadata() {
         let mut line = JournalLine {
@@ -651,6 +1014,90 @@ mod tests {
         assert!(line.has_currency_provenance());
     }
 
+    #[test]
+    fn currency_rate_converts_with_bankers_rounding() {
+        let rate = |base: Currency, quote: Currency, rate: f64| CurrencyRate {
+            base,
+            quote,
+            rate,
+            source: Some("ECB".into()),
+            observed_at: SystemTime::now(),
+        };
+        let eur_usd = rate(eur(), usd(), 1.07);
+        assert_eq!(eur_usd.convert_minor(10_000), 10_700);
+        assert_eq!(eur_usd.convert_back(10_700), 10_000);
+        assert_eq!(eur_usd.convert_minor(-10_000), -10_700);
+
+        let half = rate(eur(), usd(), 0.5);
+        assert_eq!(half.convert_minor(5), 2);
+        assert_eq!(half.convert_minor(15), 8);
+
+        let jpy = Currency {
+            code: "JPY".into(),
+            precision: 0,
+        };
+        let jpy_usd = rate(jpy, usd(), 0.0067);
+        assert_eq!(jpy_usd.convert_minor(10_000), 6_700);
+        assert_eq!(jpy_usd.convert_back(6_700), 10_000);
+
+        assert_eq!(rate(eur(), usd(), 0.0).convert_back(10_700), 0);
+    }
+
+    #[test]
+    fn fiscal_calendar_resolves_periods() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).expect("valid date");
+        let calendar_year = FiscalCalendar {
+            periods_per_year: 12,
+            opening_month: 1,
+        };
+        assert_eq!(
+            calendar_year.period_for(date(2025, 3, 15)),
+            PeriodRef {
+                fiscal_year: 2025,
+                period: 3
+            }
+        );
+
+        let july_start = FiscalCalendar {
+            periods_per_year: 12,
+            opening_month: 7,
+        };
+        assert_eq!(
+            july_start.period_for(date(2025, 6, 30)),
+            PeriodRef {
+                fiscal_year: 2024,
+                period: 12
+            }
+        );
+        assert_eq!(
+            july_start.period_for(date(2025, 7, 1)),
+            PeriodRef {
+                fiscal_year: 2025,
+                period: 1
+            }
+        );
+
+        let quarterly = FiscalCalendar {
+            periods_per_year: 4,
+            opening_month: 4,
+        };
+        assert_eq!(quarterly.period_for(date(2026, 1, 10)).period, 4);
+
+        let thirteen = FiscalCalendar {
+            periods_per_year: 13,
+            opening_month: 1,
+        };
+        assert_eq!(thirteen.period_for(date(2025, 1, 28)).period, 1);
+        assert_eq!(thirteen.period_for(date(2025, 1, 29)).period, 2);
+        assert_eq!(
+            thirteen.period_for(date(2025, 12, 31)),
+            PeriodRef {
+                fiscal_year: 2025,
+                period: 13
+            }
+        );
+    }
+
     #[test]
     fn reconciliation_status_transitions_enforced() {
         let mut entry = JournalEntry {
@@ -662,6 +1109,8 @@ mod tests {
             memo: None,
             reverses_entry_id: None,
             reversed_by_entry_id: None,
+            reconciled_amount_minor: 0,
+            attachments: Vec::new(),
             lines: vec![
                 JournalLine {
                     id: "ln-1".into(),
@@ -718,5 +1167,34 @@ mod tests {
         ));
         assert!(entry.mark_reconciliation_pending("sess-2").is_err());
         assert!(entry.mark_reconciled("sess-2").is_err());
+
+        entry.clear_reconciliation();
+        entry
+            .mark_partially_reconciled("sess-3", 4_000)
+            .expect("first partial match");
+        assert_eq!(entry.reconciled_amount_minor, 4_000);
+        assert!(matches!(
+            entry.reconciliation_status,
+            ReconciliationStatus::Pending { ref session_id } if session_id == "sess-3"
+        ));
+        assert!(matches!(
+            entry.mark_partially_reconciled("sess-4", 6_001),
+            Err(LedgerError::Validation(_))
+        ));
+        assert_eq!(entry.reconciled_amount_minor, 4_000);
+        assert!(matches!(
+            entry.mark_partially_reconciled("sess-4", i64::MAX),
+            Err(LedgerError::Validation(_))
+        ));
+        assert_eq!(entry.reconciled_amount_minor, 4_000);
+        entry
+            .mark_partially_reconciled("sess-4", 6_000)
+            .expect("final partial match");
+        assert_eq!(entry.reconciled_amount_minor, 10_000);
+        assert!(matches!(
+            entry.reconciliation_status,
+            ReconciliationStatus::Reconciled { ref session_id } if session_id == "sess-4"
+        ));
+        assert!(entry.mark_partially_reconciled("sess-4", 1).is_err());
     }
 }
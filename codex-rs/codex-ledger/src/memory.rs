@@ -1,19 +1,28 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::SystemTime;
 
 use async_trait::async_trait;
+use chrono::DateTime;
+use chrono::Utc;
+use codex_doc_store::DocStoreError;
+use codex_doc_store::DocumentStore;
 use tokio::sync::Mutex;
 
 use crate::Account;
+use crate::AccountBalance;
 use crate::AccountId;
 use crate::AuditEvent;
 use crate::AuditTrailFilter;
 use crate::Company;
+use crate::CompanyFirmResolver;
 use crate::CompanyId;
 use crate::CreateCompanyRequest;
 use crate::CurrencyRevaluationRequest;
+use crate::DocumentRef;
 use crate::EnsurePeriodRequest;
 use crate::EntryOrigin;
+use crate::EntryQuery;
 use crate::EntryStatus;
 use crate::Journal;
 use crate::JournalEntry;
@@ -25,16 +34,21 @@ use crate::LedgerResult;
 use crate::LedgerService;
 use crate::LedgerType;
 use crate::LockPeriodRequest;
+use crate::MarkReconciledRequest;
 use crate::PeriodAction;
 use crate::PeriodLockInfo;
 use crate::PeriodRef;
 use crate::PeriodState;
+use crate::PostEntryOutcome;
 use crate::PostEntryRequest;
 use crate::PostingMode;
 use crate::PostingSide;
+use crate::RateProvider;
 use crate::ReconciliationStatus;
 use crate::ReverseEntryRequest;
+use crate::Role;
 use crate::SeedChartRequest;
+use crate::TenantContext;
 use crate::UpsertAccountRequest;
 
 /// In-memory `LedgerService` used by tests to validate the async contract.
@@ -45,6 +59,8 @@ use crate::UpsertAccountRequest;
 #[derive(Default)]
 pub struct InMemoryLedgerService {
     state: Mutex<State>,
+    rate_provider: Option<Arc<dyn RateProvider>>,
+    document_store: Option<(Arc<dyn DocumentStore>, Arc<dyn CompanyFirmResolver>)>,
 }
 
 #[derive(Default)]
@@ -57,6 +73,12 @@ struct State {
     periods: HashMap<(CompanyId, JournalId, i32, u8), PeriodState>,
     entries: HashMap<JournalEntryId, JournalEntry>,
     entry_companies: HashMap<JournalEntryId, CompanyId>,
+    entry_posted_at: HashMap<JournalEntryId, SystemTime>,
+    /// Order in which entries were posted; unlike `entry_posted_at` it never
+    /// ties or runs backwards.
+    entry_posting_seq: HashMap<JournalEntryId, u64>,
+    posting_seq: u64,
+    idempotency_keys: HashMap<CompanyId, HashMap<String, JournalEntryId>>,
     audit_events: Vec<AuditEvent>,
     audit_seq: u64,
 }
@@ -94,9 +116,7 @@ fn validate_entry(entry: &JournalEntry) -> LedgerResult<()> {
             ));
         }
 
-        let expected = (line.amount_minor as f64) * rate.rate;
-        let rounded = expected.round() as i64;
-        if (rounded - line.functional_amount_minor).abs() > 2 {
+        if (rate.convert_minor(line.amount_minor) - line.functional_amount_minor).abs() > 2 {
             return Err(LedgerError::Validation(
                 "Currency amounts must include provenance".into(),
             ));
@@ -111,6 +131,130 @@ impl InMemoryLedgerService {
         Self::default()
     }
 
+    /// Cross-currency lines posted without an `exchange_rate` are stamped with
+    /// the provider's latest rate as of posting. The supplied functional
+    /// amount must still agree with that rate.
+    #[must_use]
+    pub fn with_rate_provider(mut self, rate_provider: Arc<dyn RateProvider>) -> Self {
+        self.rate_provider = Some(rate_provider);
+        self
+    }
+
+    /// Posted entries must reference attachments that the posting company
+    /// owns in its firm's store, at a version the store has issued. The firm
+    /// comes from `firms`, never from the caller's `DocumentRef`. Without a
+    /// store attachments are unchecked.
+    #[must_use]
+    pub fn with_document_store(
+        mut self,
+        document_store: Arc<dyn DocumentStore>,
+        firms: Arc<dyn CompanyFirmResolver>,
+    ) -> Self {
+        self.document_store = Some((document_store, firms));
+        self
+    }
+
+    async fn validate_attachments(
+        &self,
+        company_id: &CompanyId,
+        attachments: &[DocumentRef],
+    ) -> LedgerResult<()> {
+        let Some((store, firms)) = &self.document_store else {
+            return Ok(());
+        };
+        if attachments.is_empty() {
+            return Ok(());
+        }
+        let firm_id = firms.firm_for_company(company_id).ok_or_else(|| {
+            LedgerError::Validation(format!("company {company_id} has no firm for attachments"))
+        })?;
+        for attachment in attachments {
+            if attachment.firm_id != firm_id {
+                return Err(LedgerError::Validation(format!(
+                    "attachment {} names firm {}, but company {company_id} belongs to firm {firm_id}",
+                    attachment.document_id, attachment.firm_id
+                )));
+            }
+            let stored = match store.get_object(&firm_id, &attachment.document_id).await {
+                Ok(stored) => stored,
+                Err(DocStoreError::NotFound(_)) => {
+                    return Err(LedgerError::Validation(format!(
+                        "attachment {} not found for firm {firm_id}",
+                        attachment.document_id
+                    )));
+                }
+                Err(err) => return Err(LedgerError::Internal(format!("document store: {err}"))),
+            };
+            if stored.metadata.company_id.as_ref() != Some(company_id) {
+                return Err(LedgerError::Validation(format!(
+                    "attachment {} does not belong to company {company_id}",
+                    attachment.document_id
+                )));
+            }
+            if attachment.version == 0 || attachment.version > stored.metadata.version {
+                return Err(LedgerError::Validation(format!(
+                    "attachment {} has no version {}",
+                    attachment.document_id, attachment.version
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn stamp_exchange_rates(&self, entry: &mut JournalEntry) {
+        let Some(provider) = &self.rate_provider else {
+            return;
+        };
+        let as_of = SystemTime::now();
+        for line in &mut entry.lines {
+            if line.currency != line.functional_currency && line.exchange_rate.is_none() {
+                line.exchange_rate =
+                    provider.rate(&line.currency, &line.functional_currency, as_of);
+            }
+        }
+    }
+
+    fn signed_functional_amount(line: &JournalLine) -> i64 {
+        match line.side {
+            PostingSide::Debit => line.functional_amount_minor,
+            PostingSide::Credit => -line.functional_amount_minor,
+        }
+    }
+
+    /// Net functional balance of `account_id` over committed entries.
+    fn committed_balance(state: &State, account_id: &AccountId) -> i64 {
+        state
+            .entries
+            .values()
+            .filter(|posted| matches!(posted.status, EntryStatus::Posted | EntryStatus::Reversed))
+            .flat_map(|posted| &posted.lines)
+            .filter(|posted_line| posted_line.account_id == *account_id)
+            .map(Self::signed_functional_amount)
+            .sum()
+    }
+
+    /// Balances of the entry's accounts over committed entries, with the
+    /// entry's own lines applied on top.
+    fn projected_balances(state: &State, entry: &JournalEntry) -> Vec<AccountBalance> {
+        let mut balances: Vec<AccountBalance> = Vec::new();
+        for line in &entry.lines {
+            if let Some(balance) = balances
+                .iter_mut()
+                .find(|balance| balance.account_id == line.account_id)
+            {
+                balance.balance_minor += Self::signed_functional_amount(line);
+                continue;
+            }
+            balances.push(AccountBalance {
+                account_id: line.account_id.clone(),
+                currency: line.functional_currency.clone(),
+                balance_minor: Self::committed_balance(state, &line.account_id)
+                    + Self::signed_functional_amount(line),
+            });
+        }
+        balances
+    }
+
     fn next_company_id(state: &mut State) -> CompanyId {
         state.company_seq += 1;
         format!("co-{}", state.company_seq)
@@ -124,6 +268,12 @@ impl InMemoryLedgerService {
         }
     }
 
+    /// Service accounts act on behalf of whichever company owns the data;
+    /// every other tenant may only touch its own company.
+    fn tenant_acts_for(tenant: &TenantContext, company_id: &CompanyId) -> bool {
+        tenant.roles.contains(&Role::ServiceAccount) || &tenant.tenant_id == company_id
+    }
+
     fn journal_key(company_id: &CompanyId, journal_id: &JournalId) -> (CompanyId, JournalId) {
         (company_id.clone(), journal_id.clone())
     }
@@ -141,6 +291,47 @@ impl InMemoryLedgerService {
         )
     }
 
+    /// Counts posted entries in `period` that are still `Unreconciled` or
+    /// `Pending`, placing each entry by its posting date in the company's
+    /// fiscal calendar.
+    fn unreconciled_in_period(
+        state: &State,
+        company_id: &CompanyId,
+        journal_id: &JournalId,
+        period: &PeriodRef,
+    ) -> usize {
+        let Some(company) = state.companies.get(company_id) else {
+            return 0;
+        };
+        state
+            .entries
+            .values()
+            .filter(|entry| state.entry_companies.get(&entry.id) == Some(company_id))
+            .filter(|entry| &entry.journal_id == journal_id && entry.status == EntryStatus::Posted)
+            .filter(|entry| {
+                matches!(
+                    entry.reconciliation_status,
+                    ReconciliationStatus::Unreconciled | ReconciliationStatus::Pending { .. }
+                )
+            })
+            .filter(|entry| {
+                state
+                    .entry_posted_at
+                    .get(&entry.id)
+                    .is_some_and(|posted_at| {
+                        let date = DateTime::<Utc>::from(*posted_at).date_naive();
+                        &company.fiscal_calendar.period_for(date) == period
+                    })
+            })
+            .count()
+    }
+
+    fn record_posting_seq(state: &mut State, entry_id: &JournalEntryId) {
+        state.posting_seq += 1;
+        let seq = state.posting_seq;
+        state.entry_posting_seq.insert(entry_id.clone(), seq);
+    }
+
     fn make_account_id(company_id: &CompanyId, code: &str) -> AccountId {
         format!("acc-{company_id}-{code}")
     }
@@ -211,12 +402,17 @@ impl LedgerService for InMemoryLedgerService {
                 return Err(LedgerError::Validation("duplicate account code".into()));
             }
 
-            return Err(LedgerError::Validation(
-                "account code already exists".into(),
-            ));
-        }
-
-        if state.accounts.contains_key(&account.id) {
+            // Only reactivating a deactivated account may reuse its code.
+            let reactivates = state
+                .accounts
+                .get(existing_id)
+                .is_some_and(|existing| !existing.is_active && account.is_active);
+            if !reactivates {
+                return Err(LedgerError::Validation(
+                    "account code already exists".into(),
+                ));
+            }
+        } else if state.accounts.contains_key(&account.id) {
             return Err(LedgerError::Validation(
                 "account identifier already exists".into(),
             ));
@@ -252,7 +448,38 @@ impl LedgerService for InMemoryLedgerService {
         Ok(account)
     }
 
-    async fn post_entry(&self, request: PostEntryRequest) -> LedgerResult<JournalEntry> {
+    async fn deactivate_account(
+        &self,
+        company_id: &CompanyId,
+        account_id: &AccountId,
+        tenant: TenantContext,
+    ) -> LedgerResult<Account> {
+        let mut state = self.state.lock().await;
+        Self::ensure_company_exists(&state, company_id)?;
+        let mut account = state
+            .accounts
+            .get(account_id)
+            .filter(|account| account.company_id == *company_id)
+            .cloned()
+            .ok_or_else(|| LedgerError::NotFound(format!("account {account_id}")))?;
+
+        if Self::committed_balance(&state, account_id) != 0 {
+            return Err(LedgerError::Rejected("account has nonzero balance".into()));
+        }
+
+        account.is_active = false;
+        state.accounts.insert(account_id.clone(), account.clone());
+        Self::record_audit_event(
+            &mut state,
+            company_id.clone(),
+            account_id.clone(),
+            Some(tenant.user_id),
+            format!("Deactivated account {}", account.code),
+        );
+        Ok(account)
+    }
+
+    async fn post_entry(&self, request: PostEntryRequest) -> LedgerResult<PostEntryOutcome> {
         let mut state = self.state.lock().await;
         let mut entry = request.entry;
 
@@ -270,12 +497,20 @@ impl LedgerService for InMemoryLedgerService {
                 .ok_or_else(|| LedgerError::NotFound(format!("account {}", line.account_id)))?;
 
             let account_company = account.company_id.clone();
+            if !Self::tenant_acts_for(&request.tenant, &account_company) {
+                return Err(LedgerError::Validation(format!(
+                    "account {} belongs to company {account_company}, not tenant {}",
+                    line.account_id, request.tenant.tenant_id
+                )));
+            }
             match &mut company_id {
                 Some(existing) => {
                     if existing != &account_company {
-                        return Err(LedgerError::Validation(
-                            "all journal entry lines must belong to the same company".into(),
-                        ));
+                        return Err(LedgerError::Validation(format!(
+                            "account {} belongs to company {account_company}, not {existing}; \
+                             all journal entry lines must belong to the same company",
+                            line.account_id
+                        )));
                     }
                 }
                 None => {
@@ -284,19 +519,39 @@ impl LedgerService for InMemoryLedgerService {
             }
 
             if !account.allows_posting() {
-                return Err(LedgerError::Validation(
-                    "cannot post to summary or inactive account".into(),
-                ));
+                return Err(LedgerError::Validation(format!(
+                    "cannot post to summary or inactive account {} ({})",
+                    line.account_id, account.code
+                )));
             }
         }
 
         let company_id = company_id.ok_or_else(|| {
             LedgerError::Validation("journal entry must contain at least one line".into())
         })?;
+        self.validate_attachments(&company_id, &entry.attachments)
+            .await?;
+        if let Some(previous) = request
+            .idempotency_key
+            .as_ref()
+            .and_then(|key| state.idempotency_keys.get(&company_id)?.get(key))
+            .and_then(|entry_id| state.entries.get(entry_id))
+        {
+            return Ok(PostEntryOutcome {
+                entry: previous.clone(),
+                projected_balances: Vec::new(),
+            });
+        }
         let journal = state
             .journals
             .get_mut(&Self::journal_key(&company_id, &entry.journal_id))
             .ok_or_else(|| LedgerError::NotFound(format!("journal {}", entry.journal_id)))?;
+        if !Self::tenant_acts_for(&request.tenant, &journal.company_id) {
+            return Err(LedgerError::Validation(format!(
+                "journal {} belongs to company {}, not tenant {}",
+                entry.journal_id, journal.company_id, request.tenant.tenant_id
+            )));
+        }
 
         match journal.period_state {
             PeriodState::Open => {}
@@ -310,10 +565,12 @@ impl LedgerService for InMemoryLedgerService {
             }
         }
 
+        self.stamp_exchange_rates(&mut entry);
         validate_entry(&entry)?;
         entry.reverses_entry_id = None;
         entry.reversed_by_entry_id = None;
         entry.reconciliation_status = ReconciliationStatus::Unreconciled;
+        entry.reconciled_amount_minor = 0;
 
         match request.mode {
             PostingMode::DryRun => {
@@ -321,7 +578,11 @@ impl LedgerService for InMemoryLedgerService {
                 preview.status = EntryStatus::Proposed;
                 preview.reverses_entry_id = None;
                 preview.reversed_by_entry_id = None;
-                Ok(preview)
+                let projected_balances = Self::projected_balances(&state, &preview);
+                Ok(PostEntryOutcome {
+                    entry: preview,
+                    projected_balances,
+                })
             }
             PostingMode::Commit => {
                 entry.status = EntryStatus::Posted;
@@ -329,6 +590,17 @@ impl LedgerService for InMemoryLedgerService {
                 state
                     .entry_companies
                     .insert(entry.id.clone(), company_id.clone());
+                state
+                    .entry_posted_at
+                    .insert(entry.id.clone(), SystemTime::now());
+                Self::record_posting_seq(&mut state, &entry.id);
+                if let Some(key) = request.idempotency_key {
+                    state
+                        .idempotency_keys
+                        .entry(company_id.clone())
+                        .or_default()
+                        .insert(key, entry.id.clone());
+                }
                 Self::record_audit_event(
                     &mut state,
                     company_id,
@@ -336,7 +608,10 @@ impl LedgerService for InMemoryLedgerService {
                     Some(request.tenant.user_id.clone()),
                     format!("Posted entry {}", entry.journal_id),
                 );
-                Ok(entry)
+                Ok(PostEntryOutcome {
+                    entry,
+                    projected_balances: Vec::new(),
+                })
             }
         }
     }
@@ -409,6 +684,8 @@ impl LedgerService for InMemoryLedgerService {
                 memo: Some(reversal_memo.clone()),
                 reverses_entry_id: Some(original_id.clone()),
                 reversed_by_entry_id: None,
+                reconciled_amount_minor: 0,
+                attachments: Vec::new(),
             };
 
             entry.reversed_by_entry_id = Some(new_entry_id.clone());
@@ -422,6 +699,10 @@ impl LedgerService for InMemoryLedgerService {
         state
             .entry_companies
             .insert(new_entry_id.clone(), company_id.clone());
+        state
+            .entry_posted_at
+            .insert(new_entry_id.clone(), SystemTime::now());
+        Self::record_posting_seq(&mut state, &new_entry_id);
 
         Self::record_audit_event(
             &mut state,
@@ -441,6 +722,51 @@ impl LedgerService for InMemoryLedgerService {
         Ok(reversing_entry)
     }
 
+    async fn mark_entry_reconciled(
+        &self,
+        request: MarkReconciledRequest,
+    ) -> LedgerResult<JournalEntry> {
+        let MarkReconciledRequest {
+            entry_id,
+            session_id,
+            tenant,
+        } = request;
+
+        let mut state = self.state.lock().await;
+        let mut entry = state
+            .entries
+            .get(&entry_id)
+            .cloned()
+            .ok_or_else(|| LedgerError::NotFound(format!("entry {entry_id}")))?;
+        let company_id = state
+            .entry_companies
+            .get(&entry_id)
+            .cloned()
+            .ok_or_else(|| {
+                LedgerError::Internal(format!("missing company mapping for entry {entry_id}"))
+            })?;
+        if !Self::tenant_acts_for(&tenant, &company_id) {
+            return Err(LedgerError::Validation(format!(
+                "entry {entry_id} belongs to company {company_id}, not tenant {}",
+                tenant.tenant_id
+            )));
+        }
+
+        if entry.reconciliation_status == ReconciliationStatus::Unreconciled {
+            entry.mark_reconciliation_pending(session_id.clone())?;
+        }
+        entry.mark_reconciled(&session_id)?;
+        state.entries.insert(entry_id.clone(), entry.clone());
+        Self::record_audit_event(
+            &mut state,
+            company_id,
+            entry_id,
+            Some(tenant.user_id),
+            format!("Reconciled under session {session_id}"),
+        );
+        Ok(entry)
+    }
+
     async fn seed_chart(&self, request: SeedChartRequest) -> LedgerResult<Vec<Account>> {
         let mut state = self.state.lock().await;
         Self::ensure_company_exists(&state, &request.company_id)?;
@@ -525,6 +851,19 @@ impl LedgerService for InMemoryLedgerService {
 
     async fn lock_period(&self, request: LockPeriodRequest) -> LedgerResult<Journal> {
         let mut state = self.state.lock().await;
+        if request.require_reconciled && request.action == PeriodAction::Close {
+            let unreconciled = Self::unreconciled_in_period(
+                &state,
+                &request.tenant.tenant_id,
+                &request.journal_id,
+                &request.period,
+            );
+            if unreconciled > 0 {
+                return Err(LedgerError::Rejected(format!(
+                    "period has {unreconciled} unreconciled entries"
+                )));
+            }
+        }
         let journal_key = Self::journal_key(&request.tenant.tenant_id, &request.journal_id);
         let journal_entry = state
             .journals
@@ -626,6 +965,84 @@ impl LedgerService for InMemoryLedgerService {
 
         Ok(events)
     }
+
+    async fn list_entries(
+        &self,
+        company_id: &CompanyId,
+        filter: EntryQuery,
+    ) -> LedgerResult<Vec<JournalEntry>> {
+        let state = self.state.lock().await;
+        Self::ensure_company_exists(&state, company_id)?;
+
+        let mut entries: Vec<(u64, JournalEntry)> = state
+            .entries
+            .values()
+            .filter(|entry| state.entry_companies.get(&entry.id) == Some(company_id))
+            .filter(|entry| filter.matches(entry))
+            .map(|entry| {
+                let seq = state
+                    .entry_posting_seq
+                    .get(&entry.id)
+                    .copied()
+                    .unwrap_or_default();
+                (seq, entry.clone())
+            })
+            .collect();
+        entries.sort_by(|(a_seq, a), (b_seq, b)| a_seq.cmp(b_seq).then_with(|| a.id.cmp(&b.id)));
+
+        Ok(entries.into_iter().map(|(_, entry)| entry).collect())
+    }
+
+    async fn search_entries(
+        &self,
+        company_id: &CompanyId,
+        query: &str,
+        limit: usize,
+    ) -> LedgerResult<Vec<JournalEntry>> {
+        let tokens: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        if tokens.is_empty() {
+            return Err(LedgerError::Validation(
+                "search query must contain at least one term".into(),
+            ));
+        }
+        let state = self.state.lock().await;
+        Self::ensure_company_exists(&state, company_id)?;
+
+        let mut entries: Vec<(u64, JournalEntry)> = state
+            .entries
+            .values()
+            .filter(|entry| state.entry_companies.get(&entry.id) == Some(company_id))
+            .filter(|entry| memos_contain_all(entry, &tokens))
+            .map(|entry| {
+                let seq = state
+                    .entry_posting_seq
+                    .get(&entry.id)
+                    .copied()
+                    .unwrap_or_default();
+                (seq, entry.clone())
+            })
+            .collect();
+        entries.sort_by(|(a_seq, a), (b_seq, b)| b_seq.cmp(a_seq).then_with(|| a.id.cmp(&b.id)));
+
+        Ok(entries
+            .into_iter()
+            .take(limit)
+            .map(|(_, entry)| entry)
+            .collect())
+    }
+}
+
+/// Whether every lowercase token appears in the entry memo or a line memo.
+fn memos_contain_all(entry: &JournalEntry, tokens: &[String]) -> bool {
+    let memos: Vec<String> = entry
+        .memo
+        .iter()
+        .chain(entry.lines.iter().filter_map(|line| line.memo.as_ref()))
+        .map(|memo| memo.to_lowercase())
+        .collect();
+    tokens
+        .iter()
+        .all(|token| memos.iter().any(|memo| memo.contains(token.as_str())))
 }
 
 #[cfg(test)]
@@ -635,9 +1052,10 @@ mod tests {
     use crate::ChartAccount;
     use crate::Currency;
     use crate::CurrencyMode;
+    use crate::CurrencyRate;
     use crate::FiscalCalendar;
+    use crate::InMemoryRateStore;
     use crate::PeriodRef;
-    use crate::Role;
     use crate::TenantContext;
 
     fn usd() -> Currency {
@@ -738,6 +1156,7 @@ mod tests {
                 period: period.clone(),
                 action: PeriodAction::SoftClose,
                 approval_reference: None,
+                require_reconciled: false,
                 tenant: tenant(&company.id),
             })
             .await
@@ -757,6 +1176,7 @@ mod tests {
                 period: period.clone(),
                 action: PeriodAction::ReopenFull,
                 approval_reference: Some("APR-1".into()),
+                require_reconciled: false,
                 tenant: tenant(&company.id),
             })
             .await
@@ -780,4 +1200,731 @@ mod tests {
         assert_eq!(ensured.period_state, PeriodState::Open);
         assert_eq!(ensured.lock_history.len(), 2);
     }
+
+    fn line(id: &str, account_id: &str, side: PostingSide) -> JournalLine {
+        JournalLine {
+            id: id.into(),
+            account_id: account_id.into(),
+            side,
+            amount_minor: 5_000,
+            currency: usd(),
+            functional_amount_minor: 5_000,
+            functional_currency: usd(),
+            exchange_rate: None,
+            tax_code: None,
+            memo: None,
+        }
+    }
+
+    fn entry(id: &str, cash: &str, revenue: &str) -> JournalEntry {
+        JournalEntry {
+            id: id.into(),
+            journal_id: "jnl-gl".into(),
+            status: EntryStatus::Draft,
+            reconciliation_status: ReconciliationStatus::Unreconciled,
+            lines: vec![
+                line(&format!("{id}-1"), cash, PostingSide::Debit),
+                line(&format!("{id}-2"), revenue, PostingSide::Credit),
+            ],
+            origin: EntryOrigin::Manual,
+            memo: None,
+            reverses_entry_id: None,
+            reversed_by_entry_id: None,
+            reconciled_amount_minor: 0,
+            attachments: Vec::new(),
+        }
+    }
+
+    async fn seed_cash_and_revenue(
+        service: &InMemoryLedgerService,
+        company: &Company,
+    ) -> (AccountId, AccountId) {
+        let mut accounts = service
+            .seed_chart(SeedChartRequest {
+                company_id: company.id.clone(),
+                tenant: tenant(&company.id),
+                accounts: vec![
+                    ChartAccount {
+                        code: "1100".into(),
+                        name: "Cash".into(),
+                        account_type: AccountType::Asset,
+                        parent_code: None,
+                        currency_mode: CurrencyMode::FunctionalOnly,
+                        tax_code: None,
+                        is_summary: false,
+                    },
+                    ChartAccount {
+                        code: "4000".into(),
+                        name: "Revenue".into(),
+                        account_type: AccountType::Revenue,
+                        parent_code: None,
+                        currency_mode: CurrencyMode::FunctionalOnly,
+                        tax_code: None,
+                        is_summary: false,
+                    },
+                ],
+            })
+            .await
+            .expect("seed chart");
+        let revenue = accounts.remove(1).id;
+        let cash = accounts.remove(0).id;
+        (cash, revenue)
+    }
+
+    #[tokio::test]
+    async fn search_entries_matches_all_terms_across_memos() {
+        let service = InMemoryLedgerService::new();
+        let company = seed_company(&service).await;
+        let (cash, revenue) = seed_cash_and_revenue(&service, &company).await;
+
+        let memos = [
+            ("je-rent", Some("Q1 rent adjustment"), None),
+            (
+                "je-rent-late",
+                Some("Rent"),
+                Some("q1 ADJUSTMENT for parking"),
+            ),
+            ("je-payroll", Some("Q1 payroll"), None),
+        ];
+        for (id, memo, line_memo) in memos {
+            let mut posted = entry(id, &cash, &revenue);
+            posted.memo = memo.map(str::to_string);
+            posted.lines[0].memo = line_memo.map(str::to_string);
+            service
+                .post_entry(PostEntryRequest {
+                    entry: posted,
+                    tenant: tenant(&company.id),
+                    mode: PostingMode::Commit,
+                    idempotency_key: None,
+                })
+                .await
+                .expect("post entry");
+        }
+
+        let found = service
+            .search_entries(&company.id, "  rent Q1 adjustment ", 10)
+            .await
+            .expect("search entries");
+        let ids: Vec<&str> = found.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["je-rent-late", "je-rent"]);
+
+        let limited = service
+            .search_entries(&company.id, "q1", 1)
+            .await
+            .expect("search entries");
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].id, "je-payroll");
+
+        let err = service
+            .search_entries(&company.id, "   ", 10)
+            .await
+            .expect_err("empty query");
+        assert!(matches!(err, LedgerError::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn list_entries_filters_and_orders_by_posting() {
+        let service = InMemoryLedgerService::new();
+        let company = seed_company(&service).await;
+        let (cash, revenue) = seed_cash_and_revenue(&service, &company).await;
+
+        for id in ["je-b", "je-a"] {
+            service
+                .post_entry(PostEntryRequest {
+                    entry: entry(id, &cash, &revenue),
+                    tenant: tenant(&company.id),
+                    mode: PostingMode::Commit,
+                    idempotency_key: None,
+                })
+                .await
+                .expect("post entry");
+        }
+        service
+            .post_entry(PostEntryRequest {
+                entry: entry("je-dry", &cash, &revenue),
+                tenant: tenant(&company.id),
+                mode: PostingMode::DryRun,
+                idempotency_key: None,
+            })
+            .await
+            .expect("dry run");
+
+        let unreconciled = service
+            .list_entries(
+                &company.id,
+                EntryQuery {
+                    status: Some(EntryStatus::Posted),
+                    reconciliation_status: Some(ReconciliationStatus::Unreconciled),
+                    journal_id: Some("jnl-gl".into()),
+                },
+            )
+            .await
+            .expect("list entries");
+        let ids: Vec<&str> = unreconciled.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["je-b", "je-a"]);
+
+        let reconciled = service
+            .list_entries(
+                &company.id,
+                EntryQuery {
+                    reconciliation_status: Some(ReconciliationStatus::Reconciled {
+                        session_id: "sess-1".into(),
+                    }),
+                    ..EntryQuery::default()
+                },
+            )
+            .await
+            .expect("list entries");
+        assert!(reconciled.is_empty());
+
+        let other_journal = service
+            .list_entries(
+                &company.id,
+                EntryQuery {
+                    journal_id: Some("jnl-ap".into()),
+                    ..EntryQuery::default()
+                },
+            )
+            .await
+            .expect("list entries");
+        assert!(other_journal.is_empty());
+
+        let err = service
+            .list_entries(&"co-missing".to_string(), EntryQuery::default())
+            .await
+            .expect_err("unknown company");
+        assert!(matches!(err, LedgerError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn dry_run_projects_balances_without_posting() {
+        let service = InMemoryLedgerService::new();
+        let company = seed_company(&service).await;
+        let (cash, revenue) = seed_cash_and_revenue(&service, &company).await;
+        let post = |id: &str, mode| PostEntryRequest {
+            entry: entry(id, &cash, &revenue),
+            tenant: tenant(&company.id),
+            mode,
+            idempotency_key: None,
+        };
+        let committed = service
+            .post_entry(post("je-1", PostingMode::Commit))
+            .await
+            .expect("commit");
+        assert!(committed.projected_balances.is_empty());
+
+        let preview = service
+            .post_entry(post("je-2", PostingMode::DryRun))
+            .await
+            .expect("dry run");
+        assert_eq!(preview.entry.status, EntryStatus::Proposed);
+        assert_eq!(
+            preview.projected_balances,
+            vec![
+                AccountBalance {
+                    account_id: cash.clone(),
+                    currency: usd(),
+                    balance_minor: 10_000,
+                },
+                AccountBalance {
+                    account_id: revenue.clone(),
+                    currency: usd(),
+                    balance_minor: -10_000,
+                },
+            ]
+        );
+
+        let posted = service
+            .list_entries(&company.id, EntryQuery::default())
+            .await
+            .expect("list entries");
+        assert_eq!(posted.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn post_entry_stamps_exchange_rate_from_provider() {
+        let eur = Currency {
+            code: "EUR".into(),
+            precision: 2,
+        };
+        let rates = Arc::new(InMemoryRateStore::new());
+        rates
+            .insert(CurrencyRate {
+                base: eur.clone(),
+                quote: usd(),
+                rate: 1.07,
+                source: Some("ECB".into()),
+                observed_at: SystemTime::now() - std::time::Duration::from_secs(86_400),
+            })
+            .expect("rate stored");
+        let euro_entry = |id: &str, cash: &str, revenue: &str| {
+            let mut entry = entry(id, cash, revenue);
+            for line in &mut entry.lines {
+                line.amount_minor = 10_000;
+                line.currency = eur.clone();
+                line.functional_amount_minor = 10_700;
+            }
+            entry
+        };
+
+        let unstamped = InMemoryLedgerService::new();
+        let company = seed_company(&unstamped).await;
+        let (cash, revenue) = seed_cash_and_revenue(&unstamped, &company).await;
+        let err = unstamped
+            .post_entry(PostEntryRequest {
+                entry: euro_entry("je-fx", &cash, &revenue),
+                tenant: tenant(&company.id),
+                mode: PostingMode::Commit,
+                idempotency_key: None,
+            })
+            .await
+            .expect_err("no rate provenance");
+        assert!(matches!(err, LedgerError::Validation(_)));
+
+        let service = InMemoryLedgerService::new().with_rate_provider(rates);
+        let company = seed_company(&service).await;
+        let (cash, revenue) = seed_cash_and_revenue(&service, &company).await;
+        let posted = service
+            .post_entry(PostEntryRequest {
+                entry: euro_entry("je-fx", &cash, &revenue),
+                tenant: tenant(&company.id),
+                mode: PostingMode::Commit,
+                idempotency_key: None,
+            })
+            .await
+            .expect("stamped entry posts")
+            .entry;
+        for line in &posted.lines {
+            let rate = line.exchange_rate.as_ref().expect("rate stamped");
+            assert_eq!(rate.source.as_deref(), Some("ECB"));
+            assert_eq!(rate.base, eur);
+        }
+    }
+
+    #[tokio::test]
+    async fn post_entry_replays_idempotency_key() {
+        let service = InMemoryLedgerService::new();
+        let company = seed_company(&service).await;
+        let (cash, revenue) = seed_cash_and_revenue(&service, &company).await;
+        let request = |id: &str, key: &str| PostEntryRequest {
+            entry: entry(id, &cash, &revenue),
+            tenant: tenant(&company.id),
+            mode: PostingMode::Commit,
+            idempotency_key: Some(key.into()),
+        };
+
+        let first = service
+            .post_entry(request("je-1", "retry-1"))
+            .await
+            .expect("first post")
+            .entry;
+        let replay = service
+            .post_entry(request("je-1-retry", "retry-1"))
+            .await
+            .expect("replayed post")
+            .entry;
+        assert_eq!(replay, first);
+
+        let other = service
+            .post_entry(request("je-2", "retry-2"))
+            .await
+            .expect("distinct key")
+            .entry;
+        assert_eq!(other.id, "je-2");
+
+        let posted = service
+            .list_entries(&company.id, EntryQuery::default())
+            .await
+            .expect("list entries");
+        assert_eq!(posted.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn post_entry_rejects_accounts_from_other_companies() {
+        let service = InMemoryLedgerService::new();
+        let company_a = seed_company(&service).await;
+        let company_b = seed_company(&service).await;
+        let (cash_a, _) = seed_cash_and_revenue(&service, &company_a).await;
+        let (_, revenue_b) = seed_cash_and_revenue(&service, &company_b).await;
+
+        let err = service
+            .post_entry(PostEntryRequest {
+                entry: entry("je-cross", &cash_a, &revenue_b),
+                tenant: tenant(&company_a.id),
+                mode: PostingMode::Commit,
+                idempotency_key: None,
+            })
+            .await
+            .expect_err("cross-company entry");
+        match err {
+            LedgerError::Validation(message) => assert!(
+                message.contains(&revenue_b),
+                "message should name the account: {message}"
+            ),
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn post_entry_rejects_accounts_outside_the_tenant_company() {
+        let service = InMemoryLedgerService::new();
+        let company_a = seed_company(&service).await;
+        let company_b = seed_company(&service).await;
+        let (cash_b, revenue_b) = seed_cash_and_revenue(&service, &company_b).await;
+
+        let err = service
+            .post_entry(PostEntryRequest {
+                entry: entry("je-foreign", &cash_b, &revenue_b),
+                tenant: tenant(&company_a.id),
+                mode: PostingMode::Commit,
+                idempotency_key: None,
+            })
+            .await
+            .expect_err("entry for another company");
+        match err {
+            LedgerError::Validation(message) => assert!(
+                message.contains(&cash_b) && message.contains(&company_a.id),
+                "message should name the account and tenant: {message}"
+            ),
+            other => panic!("unexpected error: {other:?}"),
+        }
+        let posted = service
+            .list_entries(&company_b.id, EntryQuery::default())
+            .await
+            .expect("list entries");
+        assert!(posted.is_empty());
+    }
+
+    struct SingleFirm;
+
+    impl CompanyFirmResolver for SingleFirm {
+        fn firm_for_company(&self, _company_id: &CompanyId) -> Option<codex_doc_store::FirmId> {
+            Some("firm-1".into())
+        }
+    }
+
+    async fn store_document(
+        documents: &codex_doc_store::InMemoryDocumentStore,
+        document_id: &str,
+        company_id: &str,
+    ) {
+        documents
+            .put_object(codex_doc_store::PutObjectRequest {
+                metadata: codex_doc_store::DocumentMetadata {
+                    document_id: document_id.into(),
+                    firm_id: "firm-1".into(),
+                    company_id: Some(company_id.into()),
+                    version: 1,
+                    content_type: "application/pdf".into(),
+                    content_length: 4,
+                    checksum: "abc123".into(),
+                    uploaded_at: chrono::Utc::now(),
+                    uploaded_by: "clerk".into(),
+                    tags: Vec::new(),
+                    retention_class: "finance.7y".into(),
+                },
+                payload: vec![1; 4],
+                retention: Some(codex_doc_store::RetentionPolicy {
+                    class: "finance.7y".into(),
+                    retention_days: 365 * 7,
+                    legal_hold: false,
+                }),
+            })
+            .await
+            .expect("store document");
+    }
+
+    #[tokio::test]
+    async fn post_entry_validates_attachments_against_document_store() {
+        let documents = Arc::new(codex_doc_store::InMemoryDocumentStore::new(
+            Arc::new(codex_doc_store::MockEnvelopeEncryptor),
+            Arc::new(codex_doc_store::NoopRetentionScheduler::new()),
+        ));
+        let service = InMemoryLedgerService::new()
+            .with_document_store(documents.clone(), Arc::new(SingleFirm));
+        let company = seed_company(&service).await;
+        store_document(&documents, "doc-invoice", &company.id).await;
+        store_document(&documents, "doc-other-company", "co-other").await;
+        let (cash, revenue) = seed_cash_and_revenue(&service, &company).await;
+        let attached = |id: &str, document_id: &str, firm_id: &str, version| {
+            let mut entry = entry(id, &cash, &revenue);
+            entry.attachments = vec![DocumentRef {
+                document_id: document_id.into(),
+                firm_id: firm_id.into(),
+                version,
+            }];
+            PostEntryRequest {
+                entry,
+                tenant: tenant(&company.id),
+                mode: PostingMode::Commit,
+                idempotency_key: None,
+            }
+        };
+
+        let posted = service
+            .post_entry(attached("je-invoice", "doc-invoice", "firm-1", 1))
+            .await
+            .expect("attachment exists");
+        assert_eq!(posted.entry.attachments[0].document_id, "doc-invoice");
+
+        for (id, document_id, firm_id, version) in [
+            ("je-missing", "doc-missing", "firm-1", 1),
+            ("je-other-firm", "doc-invoice", "firm-2", 1),
+            ("je-future", "doc-invoice", "firm-1", 2),
+            ("je-other-company", "doc-other-company", "firm-1", 1),
+        ] {
+            let err = service
+                .post_entry(attached(id, document_id, firm_id, version))
+                .await
+                .expect_err("invalid attachment");
+            assert!(matches!(err, LedgerError::Validation(_)), "{id}: {err:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn mark_entry_reconciled_moves_unreconciled_entries_through_pending() {
+        let service = InMemoryLedgerService::new();
+        let company = seed_company(&service).await;
+        let (cash, revenue) = seed_cash_and_revenue(&service, &company).await;
+        service
+            .post_entry(PostEntryRequest {
+                entry: entry("je-rec", &cash, &revenue),
+                tenant: tenant(&company.id),
+                mode: PostingMode::Commit,
+                idempotency_key: None,
+            })
+            .await
+            .expect("entry posts");
+        let mark = |entry_id: &str, session_id: &str| MarkReconciledRequest {
+            entry_id: entry_id.into(),
+            session_id: session_id.into(),
+            tenant: tenant(&company.id),
+        };
+
+        let reconciled = service
+            .mark_entry_reconciled(mark("je-rec", "sess-1"))
+            .await
+            .expect("entry reconciles");
+        assert_eq!(
+            reconciled.reconciliation_status,
+            ReconciliationStatus::Reconciled {
+                session_id: "sess-1".into()
+            }
+        );
+        assert_eq!(reconciled.reconciled_amount_minor, reconciled.total_minor());
+
+        let err = service
+            .mark_entry_reconciled(mark("je-missing", "sess-1"))
+            .await
+            .expect_err("unknown entry");
+        assert!(matches!(err, LedgerError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn mark_entry_reconciled_rejects_entries_of_another_company() {
+        let service = InMemoryLedgerService::new();
+        let company_a = seed_company(&service).await;
+        let company_b = seed_company(&service).await;
+        let (cash, revenue) = seed_cash_and_revenue(&service, &company_b).await;
+        service
+            .post_entry(PostEntryRequest {
+                entry: entry("je-b", &cash, &revenue),
+                tenant: tenant(&company_b.id),
+                mode: PostingMode::Commit,
+                idempotency_key: None,
+            })
+            .await
+            .expect("entry posts");
+
+        let err = service
+            .mark_entry_reconciled(MarkReconciledRequest {
+                entry_id: "je-b".into(),
+                session_id: "sess-1".into(),
+                tenant: tenant(&company_a.id),
+            })
+            .await
+            .expect_err("entry belongs to company B");
+        assert!(matches!(err, LedgerError::Validation(_)), "{err:?}");
+        let entries = service
+            .list_entries(&company_b.id, EntryQuery::default())
+            .await
+            .expect("list entries");
+        assert_eq!(
+            entries[0].reconciliation_status,
+            ReconciliationStatus::Unreconciled
+        );
+    }
+
+    #[tokio::test]
+    async fn close_can_require_reconciled_entries() {
+        let service = InMemoryLedgerService::new();
+        let company = seed_company(&service).await;
+        let (cash, revenue) = seed_cash_and_revenue(&service, &company).await;
+        for id in ["je-1", "je-2"] {
+            service
+                .post_entry(PostEntryRequest {
+                    entry: entry(id, &cash, &revenue),
+                    tenant: tenant(&company.id),
+                    mode: PostingMode::Commit,
+                    idempotency_key: None,
+                })
+                .await
+                .expect("post entry");
+        }
+        let period = company.fiscal_calendar.period_for(Utc::now().date_naive());
+        let lock = |action: PeriodAction| LockPeriodRequest {
+            journal_id: "jnl-gl".into(),
+            period: period.clone(),
+            action,
+            approval_reference: None,
+            require_reconciled: true,
+            tenant: tenant(&company.id),
+        };
+
+        let soft = service
+            .lock_period(lock(PeriodAction::SoftClose))
+            .await
+            .expect("soft-close ignores reconciliation");
+        assert_eq!(soft.period_state, PeriodState::SoftClosed);
+
+        service
+            .mark_entry_reconciled(MarkReconciledRequest {
+                entry_id: "je-1".into(),
+                session_id: "sess-1".into(),
+                tenant: tenant(&company.id),
+            })
+            .await
+            .expect("entry reconciles");
+        let err = service
+            .lock_period(lock(PeriodAction::Close))
+            .await
+            .expect_err("je-2 is unreconciled");
+        match err {
+            LedgerError::Rejected(message) => {
+                assert_eq!(message, "period has 1 unreconciled entries");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        let other_period = LockPeriodRequest {
+            period: PeriodRef {
+                fiscal_year: period.fiscal_year - 1,
+                period: period.period,
+            },
+            ..lock(PeriodAction::Close)
+        };
+        let closed = service
+            .lock_period(other_period)
+            .await
+            .expect("no entries posted in the prior year");
+        assert_eq!(closed.period_state, PeriodState::Closed);
+    }
+
+    #[tokio::test]
+    async fn deactivate_account_requires_zero_balance() {
+        let service = InMemoryLedgerService::new();
+        let company = seed_company(&service).await;
+        let (cash, revenue) = seed_cash_and_revenue(&service, &company).await;
+        service
+            .post_entry(PostEntryRequest {
+                entry: entry("je-1", &cash, &revenue),
+                tenant: tenant(&company.id),
+                mode: PostingMode::Commit,
+                idempotency_key: None,
+            })
+            .await
+            .expect("post entry");
+
+        let err = service
+            .deactivate_account(&company.id, &cash, tenant(&company.id))
+            .await
+            .expect_err("cash carries a balance");
+        match err {
+            LedgerError::Rejected(message) => assert_eq!(message, "account has nonzero balance"),
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        service
+            .reverse_entry(ReverseEntryRequest {
+                entry_id: "je-1".into(),
+                reason: "posted in error".into(),
+                tenant: tenant(&company.id),
+            })
+            .await
+            .expect("reverse entry");
+        let deactivated = service
+            .deactivate_account(&company.id, &cash, tenant(&company.id))
+            .await
+            .expect("zero balance deactivates");
+        assert!(!deactivated.is_active);
+        let err = service
+            .post_entry(PostEntryRequest {
+                entry: entry("je-2", &cash, &revenue),
+                tenant: tenant(&company.id),
+                mode: PostingMode::Commit,
+                idempotency_key: None,
+            })
+            .await
+            .expect_err("inactive account refuses postings");
+        assert!(matches!(err, LedgerError::Validation(_)));
+
+        let reactivated = service
+            .upsert_account(UpsertAccountRequest {
+                account: Account {
+                    is_active: true,
+                    ..deactivated
+                },
+                tenant: tenant(&company.id),
+            })
+            .await
+            .expect("upsert reactivates");
+        assert!(reactivated.allows_posting());
+
+        let err = service
+            .deactivate_account(&company.id, &"acc-missing".to_string(), tenant(&company.id))
+            .await
+            .expect_err("unknown account");
+        assert!(matches!(err, LedgerError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn post_entry_rejects_summary_accounts() {
+        let service = InMemoryLedgerService::new();
+        let company = seed_company(&service).await;
+        let (cash, _) = seed_cash_and_revenue(&service, &company).await;
+        let summary = service
+            .seed_chart(SeedChartRequest {
+                company_id: company.id.clone(),
+                tenant: tenant(&company.id),
+                accounts: vec![ChartAccount {
+                    code: "4999".into(),
+                    name: "Revenue rollup".into(),
+                    account_type: AccountType::Revenue,
+                    parent_code: None,
+                    currency_mode: CurrencyMode::FunctionalOnly,
+                    tax_code: None,
+                    is_summary: true,
+                }],
+            })
+            .await
+            .expect("seed summary account")
+            .remove(0);
+
+        let err = service
+            .post_entry(PostEntryRequest {
+                entry: entry("je-summary", &cash, &summary.id),
+                tenant: tenant(&company.id),
+                mode: PostingMode::Commit,
+                idempotency_key: None,
+            })
+            .await
+            .expect_err("summary account");
+        match err {
+            LedgerError::Validation(message) => assert!(
+                message.contains(&summary.id),
+                "message should name the account: {message}"
+            ),
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
 }
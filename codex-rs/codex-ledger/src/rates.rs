@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+use crate::Currency;
+use crate::CurrencyRate;
+use crate::LedgerError;
+use crate::LedgerResult;
+
+/// Source of FX rates for stamping and checking currency provenance.
+pub trait RateProvider: Send + Sync {
+    /// The most recent `base`→`quote` rate observed at or before `as_of`.
+    fn rate(&self, base: &Currency, quote: &Currency, as_of: SystemTime) -> Option<CurrencyRate>;
+}
+
+/// In-memory rate history keyed by currency pair.
+#[derive(Default)]
+pub struct InMemoryRateStore {
+    rates: RwLock<HashMap<(String, String), Vec<CurrencyRate>>>,
+}
+
+impl InMemoryRateStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a rate. Rates must name their `source` and be positive.
+    pub fn insert(&self, rate: CurrencyRate) -> LedgerResult<()> {
+        if rate
+            .source
+            .as_deref()
+            .is_none_or(|source| source.trim().is_empty())
+        {
+            return Err(LedgerError::Validation(format!(
+                "rate {}/{} must carry a source",
+                rate.base.code, rate.quote.code
+            )));
+        }
+        if !rate.rate.is_finite() || rate.rate <= 0.0 {
+            return Err(LedgerError::Validation(format!(
+                "rate {}/{} must be positive",
+                rate.base.code, rate.quote.code
+            )));
+        }
+        let mut rates = self
+            .rates
+            .write()
+            .map_err(|_| LedgerError::Internal("rate store poisoned".into()))?;
+        let history = rates
+            .entry((rate.base.code.clone(), rate.quote.code.clone()))
+            .or_default();
+        let position = history.partition_point(|existing| existing.observed_at <= rate.observed_at);
+        history.insert(position, rate);
+        Ok(())
+    }
+}
+
+impl RateProvider for InMemoryRateStore {
+    fn rate(&self, base: &Currency, quote: &Currency, as_of: SystemTime) -> Option<CurrencyRate> {
+        let rates = self.rates.read().ok()?;
+        let history = rates.get(&(base.code.clone(), quote.code.clone()))?;
+        let observed = history.partition_point(|rate| rate.observed_at <= as_of);
+        observed
+            .checked_sub(1)
+            .and_then(|index| history.get(index))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn currency(code: &str) -> Currency {
+        Currency {
+            code: code.into(),
+            precision: 2,
+        }
+    }
+
+    fn rate(value: f64, source: Option<&str>, observed_at: SystemTime) -> CurrencyRate {
+        CurrencyRate {
+            base: currency("EUR"),
+            quote: currency("USD"),
+            rate: value,
+            source: source.map(ToString::to_string),
+            observed_at,
+        }
+    }
+
+    #[test]
+    fn returns_latest_rate_on_or_before_as_of() {
+        let day = |n: u64| SystemTime::UNIX_EPOCH + Duration::from_secs(n * 86_400);
+        let store = InMemoryRateStore::new();
+        store
+            .insert(rate(1.07, Some("ECB"), day(3)))
+            .expect("insert day 3");
+        store
+            .insert(rate(1.05, Some("ECB"), day(1)))
+            .expect("insert day 1");
+
+        let (eur, usd) = (currency("EUR"), currency("USD"));
+        assert_eq!(store.rate(&eur, &usd, day(0)), None);
+        let on_day_two = store.rate(&eur, &usd, day(2)).expect("rate for day 2");
+        assert_eq!(on_day_two.rate, 1.05);
+        assert_eq!(on_day_two.source.as_deref(), Some("ECB"));
+        let on_day_three = store.rate(&eur, &usd, day(3)).expect("rate for day 3");
+        assert_eq!(on_day_three.rate, 1.07);
+        assert_eq!(store.rate(&usd, &eur, day(3)), None);
+
+        let err = store
+            .insert(rate(1.06, None, day(2)))
+            .expect_err("source is required");
+        assert!(matches!(err, LedgerError::Validation(_)));
+    }
+}
@@ -144,9 +144,12 @@ fn build_post_entry(
             memo: Some(memo.into()),
             reverses_entry_id: None,
             reversed_by_entry_id: None,
+            reconciled_amount_minor: 0,
+            attachments: Vec::new(),
         },
         tenant,
         mode: PostingMode::Commit,
+        idempotency_key: None,
     }
 }
 
@@ -187,9 +190,12 @@ fn journal_entry(tenant: TenantContext) -> PostEntryRequest {
             memo: Some("Payment received".into()),
             reverses_entry_id: None,
             reversed_by_entry_id: None,
+            reconciled_amount_minor: 0,
+            attachments: Vec::new(),
         },
         tenant,
         mode: PostingMode::Commit,
+        idempotency_key: None,
     }
 }
 
@@ -230,9 +236,12 @@ fn unbalanced_entry(tenant: TenantContext) -> PostEntryRequest {
             memo: Some("Out-of-balance".into()),
             reverses_entry_id: None,
             reversed_by_entry_id: None,
+            reconciled_amount_minor: 0,
+            attachments: Vec::new(),
         },
         tenant,
         mode: PostingMode::Commit,
+        idempotency_key: None,
     }
 }
 
@@ -279,9 +288,12 @@ fn fx_entry(tenant: TenantContext) -> PostEntryRequest {
             memo: Some("FX payment".into()),
             reverses_entry_id: None,
             reversed_by_entry_id: None,
+            reconciled_amount_minor: 0,
+            attachments: Vec::new(),
         },
         tenant,
         mode: PostingMode::Commit,
+        idempotency_key: None,
     }
 }
 
@@ -322,9 +334,12 @@ fn fx_entry_missing_rate(tenant: TenantContext) -> PostEntryRequest {
             memo: Some("FX without provenance".into()),
             reverses_entry_id: None,
             reversed_by_entry_id: None,
+            reconciled_amount_minor: 0,
+            attachments: Vec::new(),
         },
         tenant,
         mode: PostingMode::Commit,
+        idempotency_key: None,
     }
 }
 
@@ -337,6 +352,7 @@ fn lock_request(company_id: &CompanyId, action: PeriodAction) -> LockPeriodReque
         },
         action,
         approval_reference: None,
+        require_reconciled: false,
         tenant: tenant_context_for_company(company_id, "user-1"),
     }
 }
@@ -448,7 +464,8 @@ async fn posting_requires_balance_and_fx_provenance() {
     let posted = service
         .post_entry(journal_entry(tenant.clone()))
         .await
-        .expect("balanced entry should post");
+        .expect("balanced entry should post")
+        .entry;
     assert_eq!(posted.status, EntryStatus::Posted);
 
     // Unbalanced entry must be rejected.
@@ -462,7 +479,8 @@ async fn posting_requires_balance_and_fx_provenance() {
     let fx_posted = service
         .post_entry(fx_entry(tenant.clone()))
         .await
-        .expect("FX entry with rate metadata should post");
+        .expect("FX entry with rate metadata should post")
+        .entry;
     assert!(
         fx_posted
             .lines
@@ -563,7 +581,8 @@ async fn period_lock_and_reversal_flow() {
     let posted = service
         .post_entry(journal_entry(tenant.clone()))
         .await
-        .expect("entry should post after reopening");
+        .expect("entry should post after reopening")
+        .entry;
     assert_eq!(posted.status, EntryStatus::Posted);
     assert_eq!(posted.reverses_entry_id, None);
     assert_eq!(posted.reversed_by_entry_id, None);
@@ -745,11 +764,13 @@ async fn multi_company_journal_isolation() {
     let posted_a = service
         .post_entry(entry_a)
         .await
-        .expect("company A should post successfully");
+        .expect("company A should post successfully")
+        .entry;
     let posted_b = service
         .post_entry(entry_b)
         .await
-        .expect("company B should post successfully");
+        .expect("company B should post successfully")
+        .entry;
 
     assert_eq!(posted_a.status, EntryStatus::Posted);
     assert_eq!(posted_b.status, EntryStatus::Posted);
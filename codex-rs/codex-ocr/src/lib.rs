@@ -1,14 +1,19 @@
 #![deny(clippy::print_stdout, clippy::print_stderr)]
 
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Context;
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
 use thiserror::Error;
 use tokio::sync::RwLock;
+use tracing::warn;
 
 pub type FirmId = String;
 pub type DocumentId = String;
@@ -65,6 +70,18 @@ pub struct ClassificationResult {
     pub synopsis: Option<String>,
 }
 
+/// Where a token sits on the page. Coordinates are in the provider's page
+/// units with the origin at the top-left corner; `page` is 1-based.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenBox {
+    pub text: String,
+    pub page: u32,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OcrDocument {
     pub document_id: DocumentId,
@@ -72,6 +89,25 @@ pub struct OcrDocument {
     pub tokens: Vec<String>,
     pub confidence: f32,
     pub classifications: Vec<ClassificationResult>,
+    /// Token positions for layout-aware extraction. Providers without layout
+    /// data leave this empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub token_boxes: Vec<TokenBox>,
+}
+
+impl OcrDocument {
+    /// Token boxes on `page`, in reading order (top to bottom, then left to
+    /// right).
+    #[must_use]
+    pub fn boxes_on_page(&self, page: u32) -> Vec<&TokenBox> {
+        let mut boxes = self
+            .token_boxes
+            .iter()
+            .filter(|token| token.page == page)
+            .collect::<Vec<_>>();
+        boxes.sort_by(|left, right| left.y.total_cmp(&right.y).then(left.x.total_cmp(&right.x)));
+        boxes
+    }
 }
 
 #[async_trait]
@@ -79,6 +115,11 @@ pub trait OcrProvider: Send + Sync {
     async fn extract(&self, request: &OcrRequest) -> OcrResult<OcrDocument>;
 }
 
+/// Classifies extracted documents.
+///
+/// Implementations should scale each result's `confidence` by the document's
+/// extraction `confidence`, so a barely legible scan cannot classify with more
+/// certainty than the OCR pass that produced its text.
 #[async_trait]
 pub trait DocumentClassifier: Send + Sync {
     async fn classify(&self, document: &OcrDocument) -> OcrResult<Vec<ClassificationResult>>;
@@ -88,6 +129,7 @@ pub trait DocumentClassifier: Send + Sync {
 pub struct OcrService {
     provider: Arc<dyn OcrProvider>,
     classifier: Arc<dyn DocumentClassifier>,
+    allowed_kinds: HashSet<DocumentKind>,
 }
 
 impl OcrService {
@@ -96,18 +138,177 @@ impl OcrService {
         Self {
             provider,
             classifier,
+            allowed_kinds: HashSet::new(),
         }
     }
 
+    /// Restricts `process` to documents whose primary (highest-confidence)
+    /// classification is one of `kinds`. An empty set allows every kind.
+    #[must_use]
+    pub fn with_allowed_kinds(mut self, kinds: impl IntoIterator<Item = DocumentKind>) -> Self {
+        self.allowed_kinds = kinds.into_iter().collect();
+        self
+    }
+
     pub async fn process(&self, request: OcrRequest) -> OcrResult<OcrDocument> {
         request.validate()?;
         let mut document = self.provider.extract(&request).await?;
         let classifications = self.classifier.classify(&document).await?;
+        if !self.allowed_kinds.is_empty()
+            && let Some(primary) = primary_classification(&classifications)
+            && !self.allowed_kinds.contains(&primary.kind)
+        {
+            return Err(OcrError::Classification(format!(
+                "document {} classified as {:?}, which this service does not accept",
+                document.document_id, primary.kind
+            )));
+        }
         document.classifications = classifications;
         Ok(document)
     }
 }
 
+fn primary_classification(
+    classifications: &[ClassificationResult],
+) -> Option<&ClassificationResult> {
+    classifications
+        .iter()
+        .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+}
+
+/// Retries transient `OcrError::Provider` failures from the wrapped provider
+/// with exponential backoff. Other errors are returned immediately.
+pub struct RetryingOcrProvider {
+    inner: Arc<dyn OcrProvider>,
+    max_attempts: u32,
+    base_delay: Duration,
+}
+
+impl RetryingOcrProvider {
+    pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+    pub const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(200);
+
+    #[must_use]
+    pub fn new(inner: Arc<dyn OcrProvider>) -> Self {
+        Self {
+            inner,
+            max_attempts: Self::DEFAULT_MAX_ATTEMPTS,
+            base_delay: Self::DEFAULT_BASE_DELAY,
+        }
+    }
+
+    /// Total attempts including the first call; values below 1 are raised to 1.
+    #[must_use]
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Delay before the first retry; each later retry doubles it.
+    #[must_use]
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    fn backoff(&self, retry: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2_u32.saturating_pow(retry.saturating_sub(1)))
+    }
+}
+
+#[async_trait]
+impl OcrProvider for RetryingOcrProvider {
+    async fn extract(&self, request: &OcrRequest) -> OcrResult<OcrDocument> {
+        let mut attempt = 1;
+        loop {
+            match self.inner.extract(request).await {
+                Err(OcrError::Provider(_)) if attempt < self.max_attempts => {
+                    tokio::time::sleep(self.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Storage for [`CachingOcrProvider`] results, keyed by
+/// [`CachingOcrProvider::cache_key`].
+#[async_trait]
+pub trait OcrCache: Send + Sync {
+    async fn get(&self, key: &str) -> OcrResult<Option<OcrDocument>>;
+    async fn put(&self, key: &str, document: OcrDocument) -> OcrResult<()>;
+}
+
+#[derive(Default)]
+pub struct InMemoryOcrCache {
+    documents: RwLock<HashMap<String, OcrDocument>>,
+}
+
+#[async_trait]
+impl OcrCache for InMemoryOcrCache {
+    async fn get(&self, key: &str) -> OcrResult<Option<OcrDocument>> {
+        Ok(self.documents.read().await.get(key).cloned())
+    }
+
+    async fn put(&self, key: &str, document: OcrDocument) -> OcrResult<()> {
+        self.documents
+            .write()
+            .await
+            .insert(key.to_string(), document);
+        Ok(())
+    }
+}
+
+/// Serves repeat extractions of the same bytes from an [`OcrCache`] instead
+/// of calling the wrapped provider. Hits are returned under the requesting
+/// `document_id`. Failed lookups are returned as-is, while a failed write is
+/// logged and the fresh extraction still returned; failed extractions are
+/// not cached.
+pub struct CachingOcrProvider {
+    inner: Arc<dyn OcrProvider>,
+    cache: Arc<dyn OcrCache>,
+}
+
+impl CachingOcrProvider {
+    #[must_use]
+    pub fn new(inner: Arc<dyn OcrProvider>, cache: Arc<dyn OcrCache>) -> Self {
+        Self { inner, cache }
+    }
+
+    /// `<mime type>:<SHA-256 of payload>`, with the mime type lowercased so
+    /// the same bytes under a different type get their own entry.
+    #[must_use]
+    pub fn cache_key(request: &OcrRequest) -> String {
+        let digest = Sha256::digest(&request.payload);
+        format!(
+            "{}:{digest:x}",
+            request.mime_type.trim().to_ascii_lowercase()
+        )
+    }
+}
+
+#[async_trait]
+impl OcrProvider for CachingOcrProvider {
+    async fn extract(&self, request: &OcrRequest) -> OcrResult<OcrDocument> {
+        let key = Self::cache_key(request);
+        if let Some(mut cached) = self.cache.get(&key).await? {
+            cached.document_id = request.document_id.clone();
+            return Ok(cached);
+        }
+        let document = self.inner.extract(request).await?;
+        if let Err(err) = self.cache.put(&key, document.clone()).await {
+            warn!(
+                document_id = %request.document_id,
+                error = %err,
+                "failed to cache ocr extraction; returning it uncached"
+            );
+        }
+        Ok(document)
+    }
+}
+
 #[derive(Default)]
 pub struct MockOcrProvider {
     documents: RwLock<HashMap<DocumentId, OcrDocument>>,
@@ -171,7 +372,9 @@ impl DocumentClassifier for KeywordClassifier {
                 })
                 .count();
             if hits > 0 {
-                let confidence = (hits as f32 / keywords.len() as f32).clamp(0.0, 1.0);
+                let keyword_confidence = hits as f32 / keywords.len() as f32;
+                let confidence =
+                    (keyword_confidence * document.confidence.clamp(0.0, 1.0)).clamp(0.0, 1.0);
                 results.push(ClassificationResult {
                     kind: kind.clone(),
                     confidence,
@@ -194,6 +397,8 @@ impl DocumentClassifier for KeywordClassifier {
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
+    use std::sync::atomic::AtomicU32;
+    use std::sync::atomic::Ordering;
 
     fn sample_document(document_id: &str, text: &str) -> OcrDocument {
         OcrDocument {
@@ -205,9 +410,58 @@ mod tests {
                 .collect(),
             confidence: 0.92,
             classifications: Vec::new(),
+            token_boxes: Vec::new(),
+        }
+    }
+
+    fn token_box(text: &str, page: u32, x: f32, y: f32) -> TokenBox {
+        TokenBox {
+            text: text.into(),
+            page,
+            x,
+            y,
+            width: 40.0,
+            height: 12.0,
         }
     }
 
+    #[tokio::test]
+    async fn token_boxes_flow_through_service_and_serde() {
+        let document = OcrDocument {
+            token_boxes: vec![
+                token_box("120.00", 1, 300.0, 700.0),
+                token_box("Total", 1, 200.0, 700.0),
+                token_box("Invoice", 1, 50.0, 40.0),
+                token_box("Terms", 2, 50.0, 40.0),
+            ],
+            ..sample_document("doc-1", "Invoice Total 120.00 Terms")
+        };
+        let provider = MockOcrProvider::default()
+            .with_document(document.clone())
+            .await;
+        let service = OcrService::new(Arc::new(provider), Arc::new(KeywordClassifier::default()));
+        let result = service.process(ocr_request()).await.expect("OCR succeeds");
+        assert_eq!(result.token_boxes, document.token_boxes);
+
+        let first_page = result
+            .boxes_on_page(1)
+            .into_iter()
+            .map(|token| token.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(first_page, vec!["Invoice", "Total", "120.00"]);
+
+        let mut encoded = serde_json::to_value(&result).expect("encode document");
+        let decoded: OcrDocument =
+            serde_json::from_value(encoded.clone()).expect("decode document");
+        assert_eq!(decoded, result);
+        encoded
+            .as_object_mut()
+            .expect("document object")
+            .remove("token_boxes");
+        let legacy: OcrDocument = serde_json::from_value(encoded).expect("decode legacy");
+        assert!(legacy.token_boxes.is_empty());
+    }
+
     #[tokio::test]
     async fn service_applies_classifier_rules() {
         let provider = MockOcrProvider::default()
@@ -237,6 +491,237 @@ mod tests {
         assert!(result.classifications[0].confidence > 0.4);
     }
 
+    #[tokio::test]
+    async fn low_extraction_confidence_dampens_keyword_match() {
+        let classifier =
+            KeywordClassifier::default().with_rule(DocumentKind::Receipt, vec!["receipt".into()]);
+        let legible = sample_document("doc-1", "Receipt for coffee");
+        let blurry = OcrDocument {
+            confidence: 0.3,
+            ..sample_document("doc-2", "Receipt for coffee")
+        };
+
+        let legible_result = classifier.classify(&legible).await.expect("classify");
+        let blurry_result = classifier.classify(&blurry).await.expect("classify");
+
+        assert_eq!(legible_result[0].kind, DocumentKind::Receipt);
+        assert!((legible_result[0].confidence - 0.92).abs() < f32::EPSILON);
+        assert_eq!(blurry_result[0].kind, DocumentKind::Receipt);
+        assert!((blurry_result[0].confidence - 0.3).abs() < f32::EPSILON);
+    }
+
+    struct FlakyProvider {
+        failures_remaining: AtomicU32,
+        calls: AtomicU32,
+        error: fn() -> OcrError,
+    }
+
+    impl FlakyProvider {
+        fn new(failures: u32, error: fn() -> OcrError) -> Self {
+            Self {
+                failures_remaining: failures.into(),
+                calls: 0.into(),
+                error,
+            }
+        }
+
+        fn calls(&self) -> u32 {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl OcrProvider for FlakyProvider {
+        async fn extract(&self, request: &OcrRequest) -> OcrResult<OcrDocument> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self
+                .failures_remaining
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| {
+                    left.checked_sub(1)
+                })
+                .is_ok()
+            {
+                return Err((self.error)());
+            }
+            Ok(sample_document(&request.document_id, "Invoice total"))
+        }
+    }
+
+    fn ocr_request() -> OcrRequest {
+        OcrRequest {
+            firm_id: "firm-1".into(),
+            document_id: "doc-1".into(),
+            mime_type: "application/pdf".into(),
+            payload: vec![1],
+        }
+    }
+
+    #[tokio::test]
+    async fn retrying_provider_recovers_from_transient_failures() {
+        let flaky = Arc::new(FlakyProvider::new(2, || {
+            OcrError::Provider("timeout".into())
+        }));
+        let provider = RetryingOcrProvider::new(flaky.clone())
+            .with_max_attempts(3)
+            .with_base_delay(Duration::from_millis(1));
+
+        let document = provider
+            .extract(&ocr_request())
+            .await
+            .expect("third attempt succeeds");
+        assert_eq!(document.document_id, "doc-1");
+        assert_eq!(flaky.calls(), 3);
+    }
+
+    #[tokio::test]
+    async fn retrying_provider_gives_up_after_max_attempts() {
+        let flaky = Arc::new(FlakyProvider::new(5, || {
+            OcrError::Provider("timeout".into())
+        }));
+        let provider = RetryingOcrProvider::new(flaky.clone())
+            .with_max_attempts(2)
+            .with_base_delay(Duration::from_millis(1));
+
+        let err = provider
+            .extract(&ocr_request())
+            .await
+            .expect_err("attempts exhausted");
+        assert!(matches!(err, OcrError::Provider(_)));
+        assert_eq!(flaky.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn retrying_provider_passes_through_permanent_errors() {
+        let flaky = Arc::new(FlakyProvider::new(1, || {
+            OcrError::UnsupportedMime("image/tiff".into())
+        }));
+        let provider =
+            RetryingOcrProvider::new(flaky.clone()).with_base_delay(Duration::from_millis(1));
+
+        let err = provider
+            .extract(&ocr_request())
+            .await
+            .expect_err("unsupported mime is not retried");
+        assert!(matches!(err, OcrError::UnsupportedMime(_)));
+        assert_eq!(flaky.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn caching_provider_reuses_results_for_identical_bytes() {
+        let counting = Arc::new(FlakyProvider::new(0, || {
+            OcrError::Provider("unused".into())
+        }));
+        let provider =
+            CachingOcrProvider::new(counting.clone(), Arc::new(InMemoryOcrCache::default()));
+
+        let first = provider
+            .extract(&ocr_request())
+            .await
+            .expect("first extraction");
+        let copy = provider
+            .extract(&OcrRequest {
+                document_id: "doc-2".into(),
+                mime_type: "Application/PDF".into(),
+                ..ocr_request()
+            })
+            .await
+            .expect("cached extraction");
+        assert_eq!(counting.calls(), 1);
+        assert_eq!(copy.document_id, "doc-2");
+        assert_eq!(copy.text, first.text);
+
+        provider
+            .extract(&OcrRequest {
+                mime_type: "image/png".into(),
+                ..ocr_request()
+            })
+            .await
+            .expect("same bytes, other mime type");
+        provider
+            .extract(&OcrRequest {
+                payload: vec![2],
+                ..ocr_request()
+            })
+            .await
+            .expect("other bytes");
+        assert_eq!(counting.calls(), 3);
+        assert_ne!(
+            CachingOcrProvider::cache_key(&ocr_request()),
+            CachingOcrProvider::cache_key(&OcrRequest {
+                mime_type: "image/png".into(),
+                ..ocr_request()
+            })
+        );
+    }
+
+    /// Reads miss and writes fail, like a cache whose backend is down.
+    struct UnwritableOcrCache;
+
+    #[async_trait]
+    impl OcrCache for UnwritableOcrCache {
+        async fn get(&self, _key: &str) -> OcrResult<Option<OcrDocument>> {
+            Ok(None)
+        }
+
+        async fn put(&self, _key: &str, _document: OcrDocument) -> OcrResult<()> {
+            Err(OcrError::Internal("cache unavailable".into()))
+        }
+    }
+
+    #[tokio::test]
+    async fn caching_provider_returns_extractions_it_cannot_cache() {
+        let counting = Arc::new(FlakyProvider::new(0, || {
+            OcrError::Provider("unused".into())
+        }));
+        let provider = CachingOcrProvider::new(counting.clone(), Arc::new(UnwritableOcrCache));
+
+        let document = provider
+            .extract(&ocr_request())
+            .await
+            .expect("extraction survives a failed cache write");
+        assert_eq!(document.document_id, ocr_request().document_id);
+        provider
+            .extract(&ocr_request())
+            .await
+            .expect("second extraction");
+        assert_eq!(counting.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn allow_list_rejects_other_document_kinds() {
+        let provider = MockOcrProvider::default()
+            .with_document(sample_document("doc-1", "Payroll run for October"))
+            .await
+            .with_document(sample_document("doc-2", "Invoice #55"))
+            .await;
+        let classifier = KeywordClassifier::default()
+            .with_rule(DocumentKind::Invoice, vec!["invoice".into()])
+            .with_rule(DocumentKind::Payroll, vec!["payroll".into()]);
+        let service = OcrService::new(Arc::new(provider), Arc::new(classifier))
+            .with_allowed_kinds([DocumentKind::Invoice, DocumentKind::Receipt]);
+
+        let err = service
+            .process(OcrRequest {
+                document_id: "doc-1".into(),
+                ..ocr_request()
+            })
+            .await
+            .expect_err("payroll is not allowed");
+        match err {
+            OcrError::Classification(message) => assert!(message.contains("Payroll")),
+            other => panic!("unexpected error {other:?}"),
+        }
+
+        let invoice = service
+            .process(OcrRequest {
+                document_id: "doc-2".into(),
+                ..ocr_request()
+            })
+            .await
+            .expect("invoice is allowed");
+        assert_eq!(invoice.classifications[0].kind, DocumentKind::Invoice);
+    }
+
     #[tokio::test]
     async fn missing_document_yields_error() {
         let service = OcrService::new(
@@ -3,17 +3,28 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use async_trait::async_trait;
 use chrono::DateTime;
+use chrono::Datelike;
+use chrono::NaiveTime;
 use chrono::Utc;
+use chrono::Weekday;
 use serde::Deserialize;
 use serde::Serialize;
 use thiserror::Error;
 use tokio::sync::RwLock;
+use tracing::Instrument;
+use tracing::field;
+use tracing::info;
+use tracing::info_span;
+use tracing::warn;
 use uuid::Uuid;
 
 pub type CompanyId = String;
+pub type FirmId = String;
 pub type ProposalId = String;
 
 pub type PolicyResult<T> = Result<T, PolicyError>;
@@ -26,6 +37,24 @@ pub enum PolicyError {
     Storage(String),
 }
 
+impl PolicyError {
+    /// Stable machine-readable code such as `policy.validation`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Validation(_) => "policy.validation",
+            Self::Storage(_) => "policy.storage",
+        }
+    }
+
+    /// HTTP status for API error bodies.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            Self::Validation(_) => 400,
+            Self::Storage(_) => 500,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PolicyRuleSet {
     pub auto_post_enabled: bool,
@@ -39,6 +68,14 @@ pub struct PolicyRuleSet {
     pub blocked_vendors: HashSet<String>,
     #[serde(default, skip_serializing_if = "HashSet::is_empty")]
     pub blocked_accounts: HashSet<String>,
+    /// Proposals submitted outside these hours need approval whatever their
+    /// amount.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_approval_outside_hours: Option<BusinessHours>,
+    /// Raise `SelfApprovalBlocked` when a proposal needing approval could
+    /// only be approved by its own submitter.
+    #[serde(default)]
+    pub prevent_self_approval: bool,
 }
 
 impl Default for PolicyRuleSet {
@@ -51,12 +88,208 @@ impl Default for PolicyRuleSet {
             approval_required_accounts: HashSet::new(),
             blocked_vendors: HashSet::new(),
             blocked_accounts: HashSet::new(),
+            require_approval_outside_hours: None,
+            prevent_self_approval: false,
+        }
+    }
+}
+
+/// A firm's working week. `opens_at`/`closes_at` are local times at a fixed
+/// `utc_offset_minutes`, and the window `[opens_at, closes_at)` applies on
+/// each listed weekday. Windows spanning midnight are not supported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BusinessHours {
+    pub opens_at: NaiveTime,
+    pub closes_at: NaiveTime,
+    pub weekdays: Vec<Weekday>,
+    #[serde(default)]
+    pub utc_offset_minutes: i32,
+}
+
+impl BusinessHours {
+    /// Monday to Friday, 09:00 to 17:00 at the given offset.
+    pub fn weekdays_nine_to_five(utc_offset_minutes: i32) -> Self {
+        Self {
+            opens_at: NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default(),
+            closes_at: NaiveTime::from_hms_opt(17, 0, 0).unwrap_or_default(),
+            weekdays: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            utc_offset_minutes,
+        }
+    }
+
+    pub fn is_open_at(&self, at: DateTime<Utc>) -> bool {
+        let local = at.naive_utc() + chrono::Duration::minutes(i64::from(self.utc_offset_minutes));
+        let time = local.time();
+        self.weekdays.contains(&local.weekday()) && time >= self.opens_at && time < self.closes_at
+    }
+}
+
+/// Canonicalizes vendor identifiers before they are compared against rule
+/// sets.
+pub trait VendorNormalizer: Send + Sync {
+    fn normalize(&self, vendor_id: &str) -> String;
+}
+
+/// Lowercases and collapses whitespace, then resolves explicit aliases such
+/// as `AMZN` -> `amazon`.
+#[derive(Debug, Clone, Default)]
+pub struct DefaultVendorNormalizer {
+    aliases: HashMap<String, String>,
+}
+
+impl DefaultVendorNormalizer {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_aliases(aliases: HashMap<String, String>) -> Self {
+        let aliases = aliases
+            .into_iter()
+            .map(|(alias, canonical)| (collapse_vendor(&alias), collapse_vendor(&canonical)))
+            .collect();
+        Self { aliases }
+    }
+}
+
+impl VendorNormalizer for DefaultVendorNormalizer {
+    fn normalize(&self, vendor_id: &str) -> String {
+        let collapsed = collapse_vendor(vendor_id);
+        match self.aliases.get(&collapsed) {
+            Some(canonical) => canonical.clone(),
+            None => collapsed,
+        }
+    }
+}
+
+fn collapse_vendor(vendor_id: &str) -> String {
+    vendor_id
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Field-by-field overrides layered over a baseline `PolicyRuleSet`. `Some`
+/// replaces the baseline value, `None` inherits it, and a non-empty set
+/// replaces the baseline set while an empty one inherits it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PolicyRuleOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_post_enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_post_limit_minor: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence_floor: Option<Option<f32>>,
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub approval_required_vendors: HashSet<String>,
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub approval_required_accounts: HashSet<String>,
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub blocked_vendors: HashSet<String>,
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub blocked_accounts: HashSet<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_approval_outside_hours: Option<Option<BusinessHours>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prevent_self_approval: Option<bool>,
+}
+
+impl PolicyRuleOverrides {
+    /// Overrides for every field of `rules` that differs from
+    /// `PolicyRuleSet::default()`, plus all of its sets.
+    pub fn changed_from_default(rules: &PolicyRuleSet) -> Self {
+        fn changed<T: Clone + PartialEq>(own: &T, unset: &T) -> Option<T> {
+            (own != unset).then(|| own.clone())
+        }
+        let unset = PolicyRuleSet::default();
+        Self {
+            auto_post_enabled: changed(&rules.auto_post_enabled, &unset.auto_post_enabled),
+            auto_post_limit_minor: changed(
+                &rules.auto_post_limit_minor,
+                &unset.auto_post_limit_minor,
+            ),
+            confidence_floor: changed(&rules.confidence_floor, &unset.confidence_floor),
+            approval_required_vendors: rules.approval_required_vendors.clone(),
+            approval_required_accounts: rules.approval_required_accounts.clone(),
+            blocked_vendors: rules.blocked_vendors.clone(),
+            blocked_accounts: rules.blocked_accounts.clone(),
+            require_approval_outside_hours: changed(
+                &rules.require_approval_outside_hours,
+                &unset.require_approval_outside_hours,
+            ),
+            prevent_self_approval: changed(
+                &rules.prevent_self_approval,
+                &unset.prevent_self_approval,
+            ),
+        }
+    }
+
+    #[must_use]
+    pub fn merge_over(&self, base: &PolicyRuleSet) -> PolicyRuleSet {
+        fn set_or(own: &HashSet<String>, base: &HashSet<String>) -> HashSet<String> {
+            if own.is_empty() {
+                base.clone()
+            } else {
+                own.clone()
+            }
+        }
+        PolicyRuleSet {
+            auto_post_enabled: self.auto_post_enabled.unwrap_or(base.auto_post_enabled),
+            auto_post_limit_minor: self
+                .auto_post_limit_minor
+                .unwrap_or(base.auto_post_limit_minor),
+            confidence_floor: self.confidence_floor.unwrap_or(base.confidence_floor),
+            approval_required_vendors: set_or(
+                &self.approval_required_vendors,
+                &base.approval_required_vendors,
+            ),
+            approval_required_accounts: set_or(
+                &self.approval_required_accounts,
+                &base.approval_required_accounts,
+            ),
+            blocked_vendors: set_or(&self.blocked_vendors, &base.blocked_vendors),
+            blocked_accounts: set_or(&self.blocked_accounts, &base.blocked_accounts),
+            require_approval_outside_hours: self
+                .require_approval_outside_hours
+                .clone()
+                .unwrap_or_else(|| base.require_approval_outside_hours.clone()),
+            prevent_self_approval: self
+                .prevent_self_approval
+                .unwrap_or(base.prevent_self_approval),
         }
     }
 }
 
 impl PolicyRuleSet {
+    /// Layers `self` over `base` field by field: fields that differ from
+    /// `PolicyRuleSet::default()` (including non-empty vendor/account sets)
+    /// win, and fields left at their default inherit `base`. Use
+    /// `PolicyRuleOverrides` to pin a field to its default value, e.g. to
+    /// turn auto-posting off over a base that enables it.
+    #[must_use]
+    pub fn merge_over(&self, base: &PolicyRuleSet) -> PolicyRuleSet {
+        PolicyRuleOverrides::changed_from_default(self).merge_over(base)
+    }
+
     pub fn evaluate(&self, proposal: &PostingProposal) -> EvaluationOutcome {
+        self.evaluate_with(proposal, &DefaultVendorNormalizer::default())
+    }
+
+    /// Evaluates with vendor ids (on both the proposal and the rule sets)
+    /// canonicalized by `normalizer`. Account codes are matched exactly.
+    pub fn evaluate_with(
+        &self,
+        proposal: &PostingProposal,
+        normalizer: &dyn VendorNormalizer,
+    ) -> EvaluationOutcome {
         let mut approval = Vec::new();
         let mut rejects = Vec::new();
 
@@ -83,17 +316,31 @@ impl PolicyRuleSet {
         }
 
         if let Some(vendor) = &proposal.vendor_id {
-            if self.blocked_vendors.contains(vendor) {
+            let normalized = normalizer.normalize(vendor);
+            let listed = |vendors: &HashSet<String>| {
+                vendors
+                    .iter()
+                    .any(|candidate| normalizer.normalize(candidate) == normalized)
+            };
+            if listed(&self.blocked_vendors) {
                 rejects.push(PolicyTrigger::VendorBlocked {
                     vendor_id: vendor.clone(),
                 });
-            } else if self.approval_required_vendors.contains(vendor) {
+            } else if listed(&self.approval_required_vendors) {
                 approval.push(PolicyTrigger::VendorRequiresApproval {
                     vendor_id: vendor.clone(),
                 });
             }
         }
 
+        if let Some(hours) = &self.require_approval_outside_hours
+            && !hours.is_open_at(proposal.submitted_at)
+        {
+            approval.push(PolicyTrigger::OutsideBusinessHours {
+                submitted_at: proposal.submitted_at,
+            });
+        }
+
         for account in &proposal.account_codes {
             if self.blocked_accounts.contains(account) {
                 rejects.push(PolicyTrigger::AccountBlocked {
@@ -106,6 +353,14 @@ impl PolicyRuleSet {
             }
         }
 
+        if !approval.is_empty()
+            && let Some(submitted_by) = self.self_approval_only(proposal)
+        {
+            approval.push(PolicyTrigger::SelfApprovalBlocked {
+                submitted_by: submitted_by.to_string(),
+            });
+        }
+
         let decision = if !rejects.is_empty() {
             PolicyDecision::Reject
         } else if !approval.is_empty() {
@@ -119,6 +374,189 @@ impl PolicyRuleSet {
 
         EvaluationOutcome { decision, triggers }
     }
+
+    pub fn explain(&self, proposal: &PostingProposal) -> PolicyExplanation {
+        self.explain_with(proposal, &DefaultVendorNormalizer::default())
+    }
+
+    /// Records every check `evaluate_with` performs together with the values
+    /// it compared, whether or not the check affects the decision. Vendor and
+    /// account checks only appear when the proposal carries those fields.
+    pub fn explain_with(
+        &self,
+        proposal: &PostingProposal,
+        normalizer: &dyn VendorNormalizer,
+    ) -> PolicyExplanation {
+        let mut checks = vec![
+            PolicyCheckResult {
+                check: PolicyCheck::AutoPostEnabled {
+                    enabled: self.auto_post_enabled,
+                },
+                passed: self.auto_post_enabled,
+            },
+            PolicyCheckResult {
+                check: PolicyCheck::AmountWithinLimit {
+                    limit_minor: self.auto_post_limit_minor,
+                    actual_minor: proposal.total_minor,
+                },
+                passed: proposal.total_minor.abs() <= self.auto_post_limit_minor,
+            },
+        ];
+
+        if let Some(floor) = self.confidence_floor {
+            checks.push(PolicyCheckResult {
+                check: PolicyCheck::ConfidenceMeetsFloor {
+                    required: floor,
+                    observed: proposal.confidence,
+                },
+                passed: proposal
+                    .confidence
+                    .is_some_and(|observed| observed + f32::EPSILON >= floor),
+            });
+        }
+
+        if let Some(vendor) = &proposal.vendor_id {
+            let normalized = normalizer.normalize(vendor);
+            let listed = |vendors: &HashSet<String>| {
+                vendors
+                    .iter()
+                    .any(|candidate| normalizer.normalize(candidate) == normalized)
+            };
+            checks.push(PolicyCheckResult {
+                check: PolicyCheck::VendorNotBlocked {
+                    vendor_id: vendor.clone(),
+                },
+                passed: !listed(&self.blocked_vendors),
+            });
+            checks.push(PolicyCheckResult {
+                check: PolicyCheck::VendorApprovalNotRequired {
+                    vendor_id: vendor.clone(),
+                },
+                passed: !listed(&self.approval_required_vendors),
+            });
+        }
+
+        if let Some(hours) = &self.require_approval_outside_hours {
+            checks.push(PolicyCheckResult {
+                check: PolicyCheck::WithinBusinessHours {
+                    submitted_at: proposal.submitted_at,
+                },
+                passed: hours.is_open_at(proposal.submitted_at),
+            });
+        }
+
+        for account in &proposal.account_codes {
+            checks.push(PolicyCheckResult {
+                check: PolicyCheck::AccountNotBlocked {
+                    account_code: account.clone(),
+                },
+                passed: !self.blocked_accounts.contains(account),
+            });
+            checks.push(PolicyCheckResult {
+                check: PolicyCheck::AccountApprovalNotRequired {
+                    account_code: account.clone(),
+                },
+                passed: !self.approval_required_accounts.contains(account),
+            });
+        }
+
+        if self.prevent_self_approval
+            && let Some(submitted_by) = &proposal.submitted_by
+            && !proposal.eligible_approvers.is_empty()
+        {
+            checks.push(PolicyCheckResult {
+                check: PolicyCheck::SecondApproverAvailable {
+                    submitted_by: submitted_by.clone(),
+                },
+                passed: self.self_approval_only(proposal).is_none(),
+            });
+        }
+
+        PolicyExplanation { checks }
+    }
+
+    /// The submitter, when `prevent_self_approval` is set and they are the
+    /// proposal's only eligible approver. An empty approver list means anyone
+    /// may approve, like a `StageAccess::Anyone` stage in codex-approvals, so
+    /// it never counts as self-approval.
+    fn self_approval_only<'a>(&self, proposal: &'a PostingProposal) -> Option<&'a str> {
+        let submitted_by = proposal.submitted_by.as_deref()?;
+        let only_submitter = self.prevent_self_approval
+            && !proposal.eligible_approvers.is_empty()
+            && proposal
+                .eligible_approvers
+                .iter()
+                .all(|approver| approver == submitted_by);
+        only_submitter.then_some(submitted_by)
+    }
+}
+
+/// A single rule comparison, carrying the values that were compared.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyCheck {
+    AutoPostEnabled {
+        enabled: bool,
+    },
+    AmountWithinLimit {
+        limit_minor: i64,
+        actual_minor: i64,
+    },
+    ConfidenceMeetsFloor {
+        required: f32,
+        observed: Option<f32>,
+    },
+    VendorNotBlocked {
+        vendor_id: String,
+    },
+    VendorApprovalNotRequired {
+        vendor_id: String,
+    },
+    AccountNotBlocked {
+        account_code: String,
+    },
+    AccountApprovalNotRequired {
+        account_code: String,
+    },
+    WithinBusinessHours {
+        submitted_at: DateTime<Utc>,
+    },
+    SecondApproverAvailable {
+        submitted_by: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyCheckResult {
+    pub check: PolicyCheck,
+    pub passed: bool,
+}
+
+/// Pass/fail evidence for each rule check applied to a proposal.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PolicyExplanation {
+    pub checks: Vec<PolicyCheckResult>,
+}
+
+impl PolicyExplanation {
+    pub fn passed(&self) -> impl Iterator<Item = &PolicyCheck> {
+        self.checks
+            .iter()
+            .filter(|result| result.passed)
+            .map(|result| &result.check)
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &PolicyCheck> {
+        self.checks
+            .iter()
+            .filter(|result| !result.passed)
+            .map(|result| &result.check)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplainedEvaluation {
+    pub outcome: EvaluationOutcome,
+    pub explanation: PolicyExplanation,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -151,16 +589,41 @@ pub enum PolicyDecision {
     Reject,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
 pub enum PolicyTrigger {
     AutoPostDisabled,
-    AmountExceedsLimit { limit_minor: i64, actual_minor: i64 },
-    ConfidenceBelowFloor { required: f32, observed: f32 },
-    ConfidenceMissing { required: f32 },
-    VendorRequiresApproval { vendor_id: String },
-    AccountRequiresApproval { account_code: String },
-    VendorBlocked { vendor_id: String },
-    AccountBlocked { account_code: String },
+    AmountExceedsLimit {
+        limit_minor: i64,
+        actual_minor: i64,
+    },
+    ConfidenceBelowFloor {
+        required: f32,
+        observed: f32,
+    },
+    ConfidenceMissing {
+        required: f32,
+    },
+    VendorRequiresApproval {
+        vendor_id: String,
+    },
+    AccountRequiresApproval {
+        account_code: String,
+    },
+    VendorBlocked {
+        vendor_id: String,
+    },
+    AccountBlocked {
+        account_code: String,
+    },
+    OutsideBusinessHours {
+        submitted_at: DateTime<Utc>,
+    },
+    /// The submitter is the only eligible approver; a second person must be
+    /// added before the approval can proceed.
+    SelfApprovalBlocked {
+        submitted_by: String,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -178,6 +641,16 @@ pub struct PolicyEvaluationEvent {
     pub auto_post_limit_minor: i64,
     pub confidence_floor: Option<f32>,
     pub evaluated_at: DateTime<Utc>,
+    /// Set when a reviewer overrode a prior decision rather than the engine
+    /// evaluating a proposal.
+    pub is_override: bool,
+    /// Decision the reviewer overrode; `decision` holds the override.
+    pub overridden_decision: Option<PolicyDecision>,
+    pub justification: Option<String>,
+    /// Set when the engine ran in shadow mode: `decision` is what it would
+    /// have decided, while the caller was told `NeedsApproval` unless it was
+    /// a rejection.
+    pub shadow: bool,
 }
 
 #[async_trait]
@@ -228,6 +701,9 @@ pub struct PostingProposal {
     pub account_codes: Vec<String>,
     pub confidence: Option<f32>,
     pub submitted_at: DateTime<Utc>,
+    pub submitted_by: Option<String>,
+    /// Users who may approve this proposal; empty means unrestricted.
+    pub eligible_approvers: Vec<String>,
 }
 
 impl PostingProposal {
@@ -241,6 +717,8 @@ impl PostingProposal {
             account_codes: Vec::new(),
             confidence: None,
             submitted_at: Utc::now(),
+            submitted_by: None,
+            eligible_approvers: Vec::new(),
         }
     }
 }
@@ -256,11 +734,19 @@ pub trait PolicyStore: Send + Sync {
     async fn put_rule_set(&self, company_id: CompanyId, rules: PolicyRuleSet) -> PolicyResult<()>;
     async fn get_rule_set(&self, company_id: &CompanyId) -> PolicyResult<Option<PolicyRuleSet>>;
     async fn list_rule_sets(&self) -> PolicyResult<HashMap<CompanyId, PolicyRuleSet>>;
+    /// Counter bumped by every `put_rule_set` for `company_id`, letting
+    /// `CachingPolicyEngine` drop outcomes computed against older rules.
+    /// `None` means the store does not track versions, which disables
+    /// caching for it.
+    async fn rule_set_version(&self, _company_id: &CompanyId) -> PolicyResult<Option<u64>> {
+        Ok(None)
+    }
 }
 
 #[derive(Default)]
 pub struct InMemoryPolicyStore {
     rules: RwLock<HashMap<CompanyId, PolicyRuleSet>>,
+    versions: RwLock<HashMap<CompanyId, u64>>,
 }
 
 impl InMemoryPolicyStore {
@@ -268,12 +754,22 @@ impl InMemoryPolicyStore {
     pub fn new() -> Self {
         Self::default()
     }
+
+    async fn bump_version(&self, company_id: &CompanyId) {
+        *self
+            .versions
+            .write()
+            .await
+            .entry(company_id.clone())
+            .or_default() += 1;
+    }
 }
 
 #[async_trait]
 impl PolicyStore for InMemoryPolicyStore {
     async fn put_rule_set(&self, company_id: CompanyId, rules: PolicyRuleSet) -> PolicyResult<()> {
         let mut guard = self.rules.write().await;
+        self.bump_version(&company_id).await;
         guard.insert(company_id, rules);
         Ok(())
     }
@@ -287,6 +783,11 @@ impl PolicyStore for InMemoryPolicyStore {
         let guard = self.rules.read().await;
         Ok(guard.clone())
     }
+
+    async fn rule_set_version(&self, company_id: &CompanyId) -> PolicyResult<Option<u64>> {
+        let guard = self.versions.read().await;
+        Ok(Some(guard.get(company_id).copied().unwrap_or_default()))
+    }
 }
 
 #[async_trait]
@@ -297,6 +798,7 @@ impl PolicyRulePersistence for InMemoryPolicyStore {
         rules: &PolicyRuleSet,
     ) -> PolicyResult<()> {
         let mut guard = self.rules.write().await;
+        self.bump_version(company_id).await;
         guard.insert(company_id.clone(), rules.clone());
         Ok(())
     }
@@ -381,6 +883,12 @@ where
         }
         Ok(output)
     }
+
+    /// Versions come from the local cache, so they only track writes made
+    /// through this store instance.
+    async fn rule_set_version(&self, company_id: &CompanyId) -> PolicyResult<Option<u64>> {
+        PolicyStore::rule_set_version(&*self.cache, company_id).await
+    }
 }
 
 #[cfg(feature = "postgres-store")]
@@ -445,6 +953,11 @@ pub struct PolicyEngine {
     store: Arc<dyn PolicyStore>,
     default_rules: PolicyRuleSet,
     event_sink: Arc<dyn PolicyEventSink>,
+    vendor_normalizer: Arc<dyn VendorNormalizer>,
+    firm_defaults: HashMap<FirmId, PolicyRuleSet>,
+    company_firms: HashMap<CompanyId, FirmId>,
+    company_overrides: HashMap<CompanyId, PolicyRuleOverrides>,
+    shadow_mode: bool,
 }
 
 impl PolicyEngine {
@@ -453,6 +966,11 @@ impl PolicyEngine {
             store,
             default_rules: PolicyRuleSet::default(),
             event_sink: Arc::new(NoopPolicyEventSink),
+            vendor_normalizer: Arc::new(DefaultVendorNormalizer::default()),
+            firm_defaults: HashMap::new(),
+            company_firms: HashMap::new(),
+            company_overrides: HashMap::new(),
+            shadow_mode: false,
         }
     }
 
@@ -461,6 +979,11 @@ impl PolicyEngine {
             store,
             default_rules,
             event_sink: Arc::new(NoopPolicyEventSink),
+            vendor_normalizer: Arc::new(DefaultVendorNormalizer::default()),
+            firm_defaults: HashMap::new(),
+            company_firms: HashMap::new(),
+            company_overrides: HashMap::new(),
+            shadow_mode: false,
         }
     }
 
@@ -472,6 +995,11 @@ impl PolicyEngine {
             store,
             default_rules: PolicyRuleSet::default(),
             event_sink,
+            vendor_normalizer: Arc::new(DefaultVendorNormalizer::default()),
+            firm_defaults: HashMap::new(),
+            company_firms: HashMap::new(),
+            company_overrides: HashMap::new(),
+            shadow_mode: false,
         }
     }
 
@@ -484,14 +1012,134 @@ impl PolicyEngine {
             store,
             default_rules,
             event_sink,
+            vendor_normalizer: Arc::new(DefaultVendorNormalizer::default()),
+            firm_defaults: HashMap::new(),
+            company_firms: HashMap::new(),
+            company_overrides: HashMap::new(),
+            shadow_mode: false,
         }
     }
 
+    #[must_use]
+    pub fn with_vendor_normalizer(mut self, vendor_normalizer: Arc<dyn VendorNormalizer>) -> Self {
+        self.vendor_normalizer = vendor_normalizer;
+        self
+    }
+
+    /// Firm-wide baseline layered over the engine's default rules for every
+    /// company mapped to `firm_id` via [`Self::with_company_firm`].
+    #[must_use]
+    pub fn with_firm_default(mut self, firm_id: impl Into<FirmId>, rules: PolicyRuleSet) -> Self {
+        self.firm_defaults.insert(firm_id.into(), rules);
+        self
+    }
+
+    /// Evaluates and records the real decision (tagged `shadow`) but returns
+    /// `NeedsApproval` instead of `AutoPost`, so firms can trial auto-posting
+    /// rules while every postable proposal still goes to a human. Rejections
+    /// stand.
+    #[must_use]
+    pub fn with_shadow_mode(mut self, shadow_mode: bool) -> Self {
+        self.shadow_mode = shadow_mode;
+        self
+    }
+
+    #[must_use]
+    pub fn with_company_firm(
+        mut self,
+        company_id: impl Into<CompanyId>,
+        firm_id: impl Into<FirmId>,
+    ) -> Self {
+        self.company_firms.insert(company_id.into(), firm_id.into());
+        self
+    }
+
+    /// Explicit overrides applied after the company's stored rules, for
+    /// settings a stored `PolicyRuleSet` cannot express such as
+    /// `auto_post_enabled: Some(false)` under a firm that enables it.
+    #[must_use]
+    pub fn with_company_overrides(
+        mut self,
+        company_id: impl Into<CompanyId>,
+        overrides: PolicyRuleOverrides,
+    ) -> Self {
+        self.company_overrides.insert(company_id.into(), overrides);
+        self
+    }
+
     pub async fn evaluate(
         &self,
         context: PolicyContext,
         proposal: PostingProposal,
     ) -> PolicyResult<EvaluationOutcome> {
+        let (_, outcome) = self.evaluate_rules(context, &proposal).await?;
+        Ok(outcome)
+    }
+
+    /// Evaluates like `evaluate` and also returns the per-check evidence
+    /// behind the decision.
+    pub async fn evaluate_explained(
+        &self,
+        context: PolicyContext,
+        proposal: PostingProposal,
+    ) -> PolicyResult<ExplainedEvaluation> {
+        let (rules, outcome) = self.evaluate_rules(context, &proposal).await?;
+        let explanation = rules.explain_with(&proposal, self.vendor_normalizer.as_ref());
+        Ok(ExplainedEvaluation {
+            outcome,
+            explanation,
+        })
+    }
+
+    /// Evaluates a batch of proposals for one company against a single
+    /// rule-set fetch, recording one event per proposal. Every proposal must
+    /// belong to the context's company.
+    pub async fn evaluate_many(
+        &self,
+        context: PolicyContext,
+        proposals: Vec<PostingProposal>,
+    ) -> PolicyResult<Vec<EvaluationOutcome>> {
+        if let Some(first) = proposals.first()
+            && proposals
+                .iter()
+                .any(|proposal| proposal.company_id != first.company_id)
+        {
+            return Err(PolicyError::Validation(
+                "batch proposals span multiple companies".into(),
+            ));
+        }
+        for proposal in &proposals {
+            Self::validate_proposal(&context, proposal)?;
+        }
+        if proposals.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rules = self.rules_for(&context.company_id).await?;
+        let mut outcomes = Vec::with_capacity(proposals.len());
+        for proposal in &proposals {
+            outcomes.push(
+                self.evaluate_against(&context.actor, &rules, proposal)
+                    .await,
+            );
+        }
+        Ok(outcomes)
+    }
+
+    async fn evaluate_rules(
+        &self,
+        context: PolicyContext,
+        proposal: &PostingProposal,
+    ) -> PolicyResult<(PolicyRuleSet, EvaluationOutcome)> {
+        Self::validate_proposal(&context, proposal)?;
+        let rules = self.rules_for(&proposal.company_id).await?;
+        let outcome = self
+            .evaluate_against(&context.actor, &rules, proposal)
+            .await;
+        Ok((rules, outcome))
+    }
+
+    fn validate_proposal(context: &PolicyContext, proposal: &PostingProposal) -> PolicyResult<()> {
         if context.company_id != proposal.company_id {
             return Err(PolicyError::Validation(
                 "proposal company does not match policy context".into(),
@@ -503,17 +1151,59 @@ impl PolicyEngine {
                 "proposal currency cannot be empty".into(),
             ));
         }
+        Ok(())
+    }
 
-        let rules = match self.store.get_rule_set(&proposal.company_id).await? {
-            Some(rules) => rules,
-            None => self.default_rules.clone(),
+    /// Resolves company overrides → stored company rules → firm default →
+    /// engine default. Stored rules inherit only from a configured firm
+    /// default; without one they are used as stored, so a field a company
+    /// saved at its default value is never replaced by the engine default.
+    async fn rules_for(&self, company_id: &CompanyId) -> PolicyResult<PolicyRuleSet> {
+        let firm = self
+            .company_firms
+            .get(company_id)
+            .and_then(|firm_id| self.firm_defaults.get(firm_id));
+        let stored = self.store.get_rule_set(company_id).await?;
+        let mut rules = match (stored, firm) {
+            (Some(stored), Some(firm)) => stored.merge_over(&firm.merge_over(&self.default_rules)),
+            (Some(stored), None) => stored,
+            (None, Some(firm)) => firm.merge_over(&self.default_rules),
+            (None, None) => self.default_rules.clone(),
         };
+        if let Some(overrides) = self.company_overrides.get(company_id) {
+            rules = overrides.merge_over(&rules);
+        }
+        Ok(rules)
+    }
 
-        let outcome = rules.evaluate(&proposal);
+    async fn evaluate_against(
+        &self,
+        actor: &str,
+        rules: &PolicyRuleSet,
+        proposal: &PostingProposal,
+    ) -> EvaluationOutcome {
+        let span = info_span!(
+            "policy_evaluate",
+            company_id = %proposal.company_id,
+            proposal_id = %proposal.id,
+            decision = field::Empty,
+            trigger_count = field::Empty,
+        );
+        let outcome = rules.evaluate_with(proposal, self.vendor_normalizer.as_ref());
+        span.record("decision", field::debug(&outcome.decision));
+        span.record("trigger_count", outcome.triggers.len());
+        span.in_scope(|| match outcome.decision {
+            PolicyDecision::Reject => {
+                warn!(triggers = ?outcome.triggers, "policy rejected proposal")
+            }
+            PolicyDecision::AutoPost | PolicyDecision::NeedsApproval => {
+                info!(triggers = ?outcome.triggers, "policy evaluated proposal")
+            }
+        });
         let event = PolicyEvaluationEvent {
             company_id: proposal.company_id.clone(),
             proposal_id: proposal.id.clone(),
-            actor: context.actor,
+            actor: actor.to_string(),
             decision: outcome.decision.clone(),
             triggers: outcome.triggers.clone(),
             total_minor: proposal.total_minor,
@@ -524,39 +1214,236 @@ impl PolicyEngine {
             auto_post_limit_minor: rules.auto_post_limit_minor,
             confidence_floor: rules.confidence_floor,
             evaluated_at: Utc::now(),
+            is_override: false,
+            overridden_decision: None,
+            justification: None,
+            shadow: self.shadow_mode,
+        };
+        self.event_sink.record(event).instrument(span).await;
+        if self.shadow_mode && outcome.decision == PolicyDecision::AutoPost {
+            return EvaluationOutcome {
+                decision: PolicyDecision::NeedsApproval,
+                ..outcome
+            };
+        }
+        outcome
+    }
+
+    /// Records a reviewer overruling the engine's decision for a proposal,
+    /// e.g. manually posting something flagged `NeedsApproval`. The event
+    /// carries the proposal's amount, vendor, and accounts, and the triggers
+    /// the current rules raise for it, so it stands on its own as evidence.
+    pub async fn record_override(
+        &self,
+        context: PolicyContext,
+        proposal: &PostingProposal,
+        original: PolicyDecision,
+        override_to: PolicyDecision,
+        justification: String,
+    ) -> PolicyResult<()> {
+        if justification.trim().is_empty() {
+            return Err(PolicyError::Validation(
+                "override justification cannot be empty".into(),
+            ));
+        }
+        if original == override_to {
+            return Err(PolicyError::Validation(
+                "override must change the policy decision".into(),
+            ));
+        }
+        Self::validate_proposal(&context, proposal)?;
+
+        let rules = self.rules_for(&context.company_id).await?;
+        let triggers = rules
+            .evaluate_with(proposal, self.vendor_normalizer.as_ref())
+            .triggers;
+        let event = PolicyEvaluationEvent {
+            company_id: context.company_id,
+            proposal_id: proposal.id.clone(),
+            actor: context.actor,
+            decision: override_to,
+            triggers,
+            total_minor: proposal.total_minor,
+            currency: proposal.currency.clone(),
+            vendor_id: proposal.vendor_id.clone(),
+            account_codes: proposal.account_codes.clone(),
+            confidence: proposal.confidence,
+            auto_post_limit_minor: rules.auto_post_limit_minor,
+            confidence_floor: rules.confidence_floor,
+            evaluated_at: Utc::now(),
+            is_override: true,
+            overridden_decision: Some(original),
+            justification: Some(justification),
+            shadow: false,
         };
         self.event_sink.record(event).await;
-        Ok(outcome)
+        Ok(())
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use pretty_assertions::assert_eq;
+/// The proposal fields `PolicyRuleSet::evaluate_with` reads. The proposal id
+/// is left out so resubmissions of the same posting share an entry, and
+/// `submitted_at` only counts through the business-hours check, so it splits
+/// entries only when the rules require approval outside hours.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ProposalFingerprint {
+    total_minor: i64,
+    currency: String,
+    vendor_id: Option<String>,
+    account_codes: Vec<String>,
+    confidence_bits: Option<u32>,
+    within_business_hours: Option<bool>,
+    submitted_by: Option<String>,
+    eligible_approvers: Vec<String>,
+}
 
-    fn make_rules() -> PolicyRuleSet {
-        PolicyRuleSet {
-            auto_post_enabled: true,
-            auto_post_limit_minor: 100_000,
-            confidence_floor: Some(0.75),
-            approval_required_vendors: HashSet::new(),
-            approval_required_accounts: HashSet::new(),
-            blocked_vendors: HashSet::new(),
-            blocked_accounts: HashSet::new(),
+impl ProposalFingerprint {
+    fn of(proposal: &PostingProposal, hours: Option<&BusinessHours>) -> Self {
+        Self {
+            total_minor: proposal.total_minor,
+            currency: proposal.currency.clone(),
+            vendor_id: proposal.vendor_id.clone(),
+            account_codes: proposal.account_codes.clone(),
+            confidence_bits: proposal.confidence.map(f32::to_bits),
+            within_business_hours: hours.map(|hours| hours.is_open_at(proposal.submitted_at)),
+            submitted_by: proposal.submitted_by.clone(),
+            eligible_approvers: proposal.eligible_approvers.clone(),
         }
     }
+}
 
-    fn base_proposal(total_minor: i64) -> PostingProposal {
-        let mut proposal = PostingProposal::new("comp-1".into(), total_minor);
-        proposal.currency = "USD".into();
-        proposal.confidence = Some(0.9);
-        proposal.account_codes = vec!["6000".into()];
-        proposal
-    }
+struct CompanyOutcomes {
+    rule_set_version: u64,
+    /// `require_approval_outside_hours` of the rules at `rule_set_version`.
+    business_hours: Option<BusinessHours>,
+    outcomes: HashMap<ProposalFingerprint, (EvaluationOutcome, Instant)>,
+}
 
-    #[tokio::test]
-    async fn evaluate_auto_posts_when_within_limits() {
+/// Wraps a `PolicyEngine` and reuses `EvaluationOutcome`s for identical
+/// proposals evaluated within `ttl`. Entries are keyed by company, proposal
+/// fingerprint, and the store's `rule_set_version`, so `put_rule_set` drops
+/// the company's cached outcomes. Hits skip the rule-set read and record no
+/// `PolicyEvaluationEvent`; misses evaluate and record as usual. Stores that
+/// report no version are never cached.
+pub struct CachingPolicyEngine {
+    engine: PolicyEngine,
+    ttl: Duration,
+    cache: RwLock<HashMap<CompanyId, CompanyOutcomes>>,
+}
+
+impl CachingPolicyEngine {
+    pub fn new(engine: PolicyEngine, ttl: Duration) -> Self {
+        Self {
+            engine,
+            ttl,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn engine(&self) -> &PolicyEngine {
+        &self.engine
+    }
+
+    pub async fn evaluate(
+        &self,
+        context: PolicyContext,
+        proposal: PostingProposal,
+    ) -> PolicyResult<EvaluationOutcome> {
+        PolicyEngine::validate_proposal(&context, &proposal)?;
+        let Some(version) = self
+            .engine
+            .store
+            .rule_set_version(&proposal.company_id)
+            .await?
+        else {
+            return self.engine.evaluate(context, proposal).await;
+        };
+        let company_id = proposal.company_id.clone();
+        if let Some(outcome) = self.cached(&company_id, version, &proposal).await {
+            return Ok(outcome);
+        }
+
+        let (rules, outcome) = self.engine.evaluate_rules(context, &proposal).await?;
+        let business_hours = rules.require_approval_outside_hours;
+        let fingerprint = ProposalFingerprint::of(&proposal, business_hours.as_ref());
+        let mut cache = self.cache.write().await;
+        let company = cache.entry(company_id).or_insert_with(|| CompanyOutcomes {
+            rule_set_version: version,
+            business_hours: business_hours.clone(),
+            outcomes: HashMap::new(),
+        });
+        if company.rule_set_version != version || company.business_hours != business_hours {
+            company.rule_set_version = version;
+            company.business_hours = business_hours;
+            company.outcomes.clear();
+        }
+        let ttl = self.ttl;
+        company
+            .outcomes
+            .retain(|_, (_, cached_at)| cached_at.elapsed() < ttl);
+        company
+            .outcomes
+            .insert(fingerprint, (outcome.clone(), Instant::now()));
+        Ok(outcome)
+    }
+
+    /// A cached outcome for `proposal`, with any `OutsideBusinessHours`
+    /// trigger restamped to this submission.
+    async fn cached(
+        &self,
+        company_id: &CompanyId,
+        version: u64,
+        proposal: &PostingProposal,
+    ) -> Option<EvaluationOutcome> {
+        let cache = self.cache.read().await;
+        let company = cache.get(company_id)?;
+        if company.rule_set_version != version {
+            return None;
+        }
+        let fingerprint = ProposalFingerprint::of(proposal, company.business_hours.as_ref());
+        let (outcome, cached_at) = company.outcomes.get(&fingerprint)?;
+        if cached_at.elapsed() >= self.ttl {
+            return None;
+        }
+        let mut outcome = outcome.clone();
+        for trigger in &mut outcome.triggers {
+            if let PolicyTrigger::OutsideBusinessHours { submitted_at } = trigger {
+                *submitted_at = proposal.submitted_at;
+            }
+        }
+        Some(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn make_rules() -> PolicyRuleSet {
+        PolicyRuleSet {
+            auto_post_enabled: true,
+            auto_post_limit_minor: 100_000,
+            confidence_floor: Some(0.75),
+            approval_required_vendors: HashSet::new(),
+            approval_required_accounts: HashSet::new(),
+            blocked_vendors: HashSet::new(),
+            blocked_accounts: HashSet::new(),
+            require_approval_outside_hours: None,
+            prevent_self_approval: false,
+        }
+    }
+
+    fn base_proposal(total_minor: i64) -> PostingProposal {
+        let mut proposal = PostingProposal::new("comp-1".into(), total_minor);
+        proposal.currency = "USD".into();
+        proposal.confidence = Some(0.9);
+        proposal.account_codes = vec!["6000".into()];
+        proposal
+    }
+
+    #[tokio::test]
+    async fn evaluate_auto_posts_when_within_limits() {
         let store: Arc<dyn PolicyStore> = Arc::new(InMemoryPolicyStore::new());
         store
             .put_rule_set("comp-1".into(), make_rules())
@@ -584,6 +1471,387 @@ mod tests {
         );
     }
 
+    #[test]
+    fn explain_records_passing_checks_for_auto_post() {
+        let rules = make_rules();
+        let mut proposal = base_proposal(20_000);
+        proposal.vendor_id = Some("vendor-1".into());
+
+        let explanation = rules.explain(&proposal);
+        assert_eq!(rules.evaluate(&proposal).decision, PolicyDecision::AutoPost);
+        assert_eq!(explanation.failed().count(), 0);
+        assert_eq!(
+            explanation.checks[1],
+            PolicyCheckResult {
+                check: PolicyCheck::AmountWithinLimit {
+                    limit_minor: 100_000,
+                    actual_minor: 20_000,
+                },
+                passed: true,
+            }
+        );
+        assert_eq!(
+            explanation.checks[2],
+            PolicyCheckResult {
+                check: PolicyCheck::ConfidenceMeetsFloor {
+                    required: 0.75,
+                    observed: Some(0.9),
+                },
+                passed: true,
+            }
+        );
+        assert_eq!(explanation.checks.len(), 7);
+    }
+
+    #[tokio::test]
+    async fn evaluate_explained_reports_failed_checks() {
+        let store: Arc<dyn PolicyStore> = Arc::new(InMemoryPolicyStore::new());
+        store
+            .put_rule_set("comp-1".into(), make_rules())
+            .await
+            .expect("store rules");
+        let engine = PolicyEngine::new(store);
+
+        let explained = engine
+            .evaluate_explained(
+                PolicyContext {
+                    company_id: "comp-1".into(),
+                    actor: "tester".into(),
+                },
+                base_proposal(150_000),
+            )
+            .await
+            .expect("evaluation succeeds");
+        assert_eq!(explained.outcome.decision, PolicyDecision::NeedsApproval);
+        let failed: Vec<&PolicyCheck> = explained.explanation.failed().collect();
+        assert_eq!(
+            failed,
+            vec![&PolicyCheck::AmountWithinLimit {
+                limit_minor: 100_000,
+                actual_minor: 150_000,
+            }]
+        );
+    }
+
+    #[derive(Default)]
+    struct CountingPolicyStore {
+        inner: InMemoryPolicyStore,
+        reads: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl PolicyStore for CountingPolicyStore {
+        async fn put_rule_set(
+            &self,
+            company_id: CompanyId,
+            rules: PolicyRuleSet,
+        ) -> PolicyResult<()> {
+            self.inner.put_rule_set(company_id, rules).await
+        }
+
+        async fn get_rule_set(
+            &self,
+            company_id: &CompanyId,
+        ) -> PolicyResult<Option<PolicyRuleSet>> {
+            self.reads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.inner.get_rule_set(company_id).await
+        }
+
+        async fn list_rule_sets(&self) -> PolicyResult<HashMap<CompanyId, PolicyRuleSet>> {
+            self.inner.list_rule_sets().await
+        }
+
+        async fn rule_set_version(&self, company_id: &CompanyId) -> PolicyResult<Option<u64>> {
+            self.inner.rule_set_version(company_id).await
+        }
+    }
+
+    #[tokio::test]
+    async fn evaluate_many_fetches_rules_once_per_batch() {
+        let store = Arc::new(CountingPolicyStore::default());
+        store
+            .put_rule_set("comp-1".into(), make_rules())
+            .await
+            .expect("store rules");
+        let sink = Arc::new(InMemoryPolicyEventSink::new());
+        let engine =
+            PolicyEngine::with_components(store.clone(), PolicyRuleSet::default(), sink.clone());
+        let context = PolicyContext {
+            company_id: "comp-1".into(),
+            actor: "batcher".into(),
+        };
+
+        let outcomes = engine
+            .evaluate_many(
+                context.clone(),
+                vec![
+                    base_proposal(20_000),
+                    base_proposal(150_000),
+                    base_proposal(5_000),
+                ],
+            )
+            .await
+            .expect("batch evaluates");
+        let decisions: Vec<PolicyDecision> = outcomes
+            .into_iter()
+            .map(|outcome| outcome.decision)
+            .collect();
+        assert_eq!(
+            decisions,
+            vec![
+                PolicyDecision::AutoPost,
+                PolicyDecision::NeedsApproval,
+                PolicyDecision::AutoPost,
+            ]
+        );
+        assert_eq!(store.reads.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(sink.events().await.len(), 3);
+
+        let mut foreign = base_proposal(1_000);
+        foreign.company_id = "comp-2".into();
+        let err = engine
+            .evaluate_many(context, vec![base_proposal(1_000), foreign])
+            .await
+            .expect_err("mixed companies are rejected");
+        assert!(matches!(err, PolicyError::Validation(_)));
+        assert_eq!(store.reads.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(sink.events().await.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn caching_engine_reuses_outcomes_until_rules_change() {
+        let store = Arc::new(CountingPolicyStore::default());
+        store
+            .put_rule_set("comp-1".into(), make_rules())
+            .await
+            .expect("store rules");
+        let sink = Arc::new(InMemoryPolicyEventSink::new());
+        let engine =
+            PolicyEngine::with_components(store.clone(), PolicyRuleSet::default(), sink.clone());
+        let caching = CachingPolicyEngine::new(engine, Duration::from_secs(60));
+        let context = PolicyContext {
+            company_id: "comp-1".into(),
+            actor: "cacher".into(),
+        };
+        let reads = || store.reads.load(std::sync::atomic::Ordering::SeqCst);
+
+        let proposal = base_proposal(150_000);
+        let first = caching
+            .evaluate(context.clone(), proposal.clone())
+            .await
+            .expect("first evaluation");
+        assert_eq!(first.decision, PolicyDecision::NeedsApproval);
+        let mut resubmitted = proposal.clone();
+        resubmitted.id = "proposal-2".into();
+        let second = caching
+            .evaluate(context.clone(), resubmitted)
+            .await
+            .expect("cached evaluation");
+        assert_eq!(second, first);
+        assert_eq!(reads(), 1);
+        assert_eq!(sink.events().await.len(), 1);
+
+        store
+            .put_rule_set(
+                "comp-1".into(),
+                PolicyRuleSet {
+                    auto_post_limit_minor: 200_000,
+                    ..make_rules()
+                },
+            )
+            .await
+            .expect("replace rules");
+        let after_update = caching
+            .evaluate(context.clone(), proposal.clone())
+            .await
+            .expect("re-evaluated");
+        assert_eq!(after_update.decision, PolicyDecision::AutoPost);
+        assert_eq!(reads(), 2);
+        assert_eq!(sink.events().await.len(), 2);
+
+        let uncached = CachingPolicyEngine::new(
+            PolicyEngine::with_components(store.clone(), PolicyRuleSet::default(), sink.clone()),
+            Duration::ZERO,
+        );
+        for _ in 0..2 {
+            uncached
+                .evaluate(context.clone(), proposal.clone())
+                .await
+                .expect("zero ttl evaluation");
+        }
+        assert_eq!(reads(), 4);
+        assert_eq!(sink.events().await.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn caching_engine_keys_submission_time_by_business_hours() {
+        let at = |rfc3339: &str| {
+            DateTime::parse_from_rfc3339(rfc3339)
+                .expect("timestamp")
+                .with_timezone(&Utc)
+        };
+        let store = Arc::new(CountingPolicyStore::default());
+        store
+            .put_rule_set("comp-1".into(), make_rules())
+            .await
+            .expect("store rules");
+        let sink = Arc::new(InMemoryPolicyEventSink::new());
+        let engine =
+            PolicyEngine::with_components(store.clone(), PolicyRuleSet::default(), sink.clone());
+        let caching = CachingPolicyEngine::new(engine, Duration::from_secs(60));
+        let context = PolicyContext {
+            company_id: "comp-1".into(),
+            actor: "cacher".into(),
+        };
+        let reads = || store.reads.load(std::sync::atomic::Ordering::SeqCst);
+        let submitted = |timestamp: &str| {
+            let mut proposal = base_proposal(5_000);
+            proposal.submitted_at = at(timestamp);
+            proposal
+        };
+
+        // Without an hours rule the timestamp does not split entries.
+        caching
+            .evaluate(context.clone(), submitted("2025-03-05T14:00:00Z"))
+            .await
+            .expect("first evaluation");
+        caching
+            .evaluate(context.clone(), submitted("2025-03-05T23:30:00Z"))
+            .await
+            .expect("cached evaluation");
+        assert_eq!(reads(), 1);
+
+        store
+            .put_rule_set(
+                "comp-1".into(),
+                PolicyRuleSet {
+                    // UTC-5: 14:00Z and 15:00Z are open, 23:30Z and 23:45Z closed.
+                    require_approval_outside_hours: Some(BusinessHours::weekdays_nine_to_five(
+                        -300,
+                    )),
+                    ..make_rules()
+                },
+            )
+            .await
+            .expect("add hours rule");
+        let open = caching
+            .evaluate(context.clone(), submitted("2025-03-05T14:00:00Z"))
+            .await
+            .expect("open evaluation");
+        assert_eq!(open.decision, PolicyDecision::AutoPost);
+        let closed = caching
+            .evaluate(context.clone(), submitted("2025-03-05T23:30:00Z"))
+            .await
+            .expect("closed evaluation");
+        assert_eq!(closed.decision, PolicyDecision::NeedsApproval);
+        assert_eq!(reads(), 3);
+
+        let later_open = caching
+            .evaluate(context.clone(), submitted("2025-03-05T15:00:00Z"))
+            .await
+            .expect("cached open evaluation");
+        assert_eq!(later_open, open);
+        let later_closed = caching
+            .evaluate(context.clone(), submitted("2025-03-05T23:45:00Z"))
+            .await
+            .expect("cached closed evaluation");
+        assert_eq!(later_closed.decision, PolicyDecision::NeedsApproval);
+        assert_eq!(
+            later_closed.triggers,
+            vec![PolicyTrigger::OutsideBusinessHours {
+                submitted_at: at("2025-03-05T23:45:00Z"),
+            }]
+        );
+        assert_eq!(reads(), 3);
+        assert_eq!(sink.events().await.len(), 3);
+    }
+
+    #[test]
+    fn evaluate_requires_approval_outside_business_hours() {
+        let rules = PolicyRuleSet {
+            // UTC-5: 14:00Z is 09:00 local, 22:30Z is 17:30 local.
+            require_approval_outside_hours: Some(BusinessHours::weekdays_nine_to_five(-300)),
+            ..make_rules()
+        };
+        let at = |rfc3339: &str| {
+            DateTime::parse_from_rfc3339(rfc3339)
+                .expect("timestamp")
+                .with_timezone(&Utc)
+        };
+        let mut proposal = base_proposal(1_000);
+
+        // Wednesday at opening time.
+        proposal.submitted_at = at("2025-03-05T14:00:00Z");
+        assert_eq!(rules.evaluate(&proposal).decision, PolicyDecision::AutoPost);
+
+        for late in ["2025-03-05T22:30:00Z", "2025-03-08T15:00:00Z"] {
+            proposal.submitted_at = at(late);
+            assert_eq!(
+                rules.evaluate(&proposal),
+                EvaluationOutcome {
+                    decision: PolicyDecision::NeedsApproval,
+                    triggers: vec![PolicyTrigger::OutsideBusinessHours {
+                        submitted_at: proposal.submitted_at,
+                    }],
+                },
+                "{late} should be outside business hours"
+            );
+            assert!(
+                rules
+                    .explain(&proposal)
+                    .failed()
+                    .any(|check| matches!(check, PolicyCheck::WithinBusinessHours { .. }))
+            );
+        }
+    }
+
+    #[test]
+    fn evaluate_blocks_self_approval_when_submitter_is_only_approver() {
+        let rules = PolicyRuleSet {
+            prevent_self_approval: true,
+            ..make_rules()
+        };
+        let mut proposal = base_proposal(250_000);
+        proposal.submitted_by = Some("user-1".into());
+        proposal.eligible_approvers = vec!["user-1".into()];
+
+        let outcome = rules.evaluate(&proposal);
+        assert_eq!(outcome.decision, PolicyDecision::NeedsApproval);
+        assert_eq!(
+            outcome.triggers.last(),
+            Some(&PolicyTrigger::SelfApprovalBlocked {
+                submitted_by: "user-1".into(),
+            })
+        );
+        assert!(
+            rules
+                .explain(&proposal)
+                .failed()
+                .any(|check| matches!(check, PolicyCheck::SecondApproverAvailable { .. }))
+        );
+
+        let blocked = |proposal: &PostingProposal, rules: &PolicyRuleSet| {
+            rules
+                .evaluate(proposal)
+                .triggers
+                .iter()
+                .any(|trigger| matches!(trigger, PolicyTrigger::SelfApprovalBlocked { .. }))
+        };
+        let mut second = proposal.clone();
+        second.eligible_approvers.push("user-2".into());
+        assert!(!blocked(&second, &rules));
+        let mut unrestricted = proposal.clone();
+        unrestricted.eligible_approvers.clear();
+        assert!(!blocked(&unrestricted, &rules));
+        let mut auto_post = proposal.clone();
+        auto_post.total_minor = 1_000;
+        assert_eq!(
+            rules.evaluate(&auto_post).decision,
+            PolicyDecision::AutoPost
+        );
+        assert!(!blocked(&proposal, &make_rules()));
+    }
+
     #[tokio::test]
     async fn evaluate_requires_approval_when_over_limit() {
         let store: Arc<dyn PolicyStore> = Arc::new(InMemoryPolicyStore::new());
@@ -684,6 +1952,157 @@ mod tests {
         );
     }
 
+    #[test]
+    fn merge_over_inherits_unset_fields() {
+        let firm = PolicyRuleSet {
+            auto_post_enabled: true,
+            auto_post_limit_minor: 250_000,
+            blocked_vendors: HashSet::from(["shell co".to_string()]),
+            ..PolicyRuleSet::default()
+        };
+        let company = PolicyRuleSet {
+            auto_post_limit_minor: 10_000,
+            confidence_floor: Some(0.9),
+            blocked_vendors: HashSet::from(["front llc".to_string()]),
+            ..PolicyRuleSet::default()
+        };
+
+        assert_eq!(
+            company.merge_over(&firm),
+            PolicyRuleSet {
+                auto_post_enabled: true,
+                auto_post_limit_minor: 10_000,
+                confidence_floor: Some(0.9),
+                blocked_vendors: HashSet::from(["front llc".to_string()]),
+                ..PolicyRuleSet::default()
+            }
+        );
+        assert_eq!(PolicyRuleSet::default().merge_over(&firm), firm);
+    }
+
+    #[test]
+    fn overrides_can_pin_stricter_defaults_over_a_base() {
+        let firm = PolicyRuleSet {
+            auto_post_enabled: true,
+            prevent_self_approval: true,
+            approval_required_accounts: HashSet::from(["6100".to_string()]),
+            ..PolicyRuleSet::default()
+        };
+        let overrides = PolicyRuleOverrides {
+            auto_post_enabled: Some(false),
+            confidence_floor: Some(None),
+            approval_required_accounts: HashSet::from(["1000".to_string()]),
+            ..PolicyRuleOverrides::default()
+        };
+
+        let merged = overrides.merge_over(&firm);
+
+        assert!(!merged.auto_post_enabled);
+        assert_eq!(merged.confidence_floor, None);
+        assert!(merged.prevent_self_approval);
+        assert_eq!(
+            merged.approval_required_accounts,
+            HashSet::from(["1000".to_string()])
+        );
+        assert_eq!(
+            PolicyRuleOverrides::default()
+                .merge_over(&firm)
+                .approval_required_accounts,
+            firm.approval_required_accounts
+        );
+    }
+
+    #[tokio::test]
+    async fn stored_rules_without_firm_default_ignore_engine_default() {
+        let store: Arc<dyn PolicyStore> = Arc::new(InMemoryPolicyStore::new());
+        store
+            .put_rule_set(
+                "comp-1".into(),
+                PolicyRuleSet {
+                    auto_post_enabled: false,
+                    ..make_rules()
+                },
+            )
+            .await
+            .expect("store rules");
+        let engine = PolicyEngine::with_default(store, make_rules());
+
+        let outcome = engine
+            .evaluate(
+                PolicyContext {
+                    company_id: "comp-1".into(),
+                    actor: "user-1".into(),
+                },
+                base_proposal(10_000),
+            )
+            .await
+            .expect("evaluation should succeed");
+
+        assert_eq!(outcome.decision, PolicyDecision::NeedsApproval);
+        assert_eq!(outcome.triggers, vec![PolicyTrigger::AutoPostDisabled]);
+    }
+
+    #[tokio::test]
+    async fn engine_resolves_company_then_firm_then_system_rules() {
+        let store: Arc<dyn PolicyStore> = Arc::new(InMemoryPolicyStore::new());
+        store
+            .put_rule_set(
+                "comp-1".into(),
+                PolicyRuleSet {
+                    auto_post_limit_minor: 5_000,
+                    ..PolicyRuleSet::default()
+                },
+            )
+            .await
+            .expect("store rules");
+        let engine = PolicyEngine::new(store)
+            .with_firm_default(
+                "firm-1",
+                PolicyRuleSet {
+                    auto_post_enabled: true,
+                    ..PolicyRuleSet::default()
+                },
+            )
+            .with_company_firm("comp-1", "firm-1")
+            .with_company_firm("comp-2", "firm-1")
+            .with_company_firm("comp-4", "firm-1")
+            .with_company_overrides(
+                "comp-4",
+                PolicyRuleOverrides {
+                    auto_post_enabled: Some(false),
+                    ..PolicyRuleOverrides::default()
+                },
+            );
+        let evaluate = |company_id: &str, total_minor: i64| {
+            let mut proposal = base_proposal(total_minor);
+            proposal.company_id = company_id.into();
+            let context = PolicyContext {
+                company_id: company_id.into(),
+                actor: "user-1".into(),
+            };
+            let engine = engine.clone();
+            async move { engine.evaluate(context, proposal).await }
+        };
+
+        let company_override = evaluate("comp-1", 10_000).await.expect("comp-1");
+        assert_eq!(
+            company_override.triggers,
+            vec![PolicyTrigger::AmountExceedsLimit {
+                limit_minor: 5_000,
+                actual_minor: 10_000
+            }]
+        );
+        let firm_inherited = evaluate("comp-2", 10_000).await.expect("comp-2");
+        assert_eq!(firm_inherited.decision, PolicyDecision::AutoPost);
+        let system_default = evaluate("comp-3", 10_000).await.expect("comp-3");
+        assert_eq!(
+            system_default.triggers,
+            vec![PolicyTrigger::AutoPostDisabled]
+        );
+        let stricter = evaluate("comp-4", 10_000).await.expect("comp-4");
+        assert_eq!(stricter.triggers, vec![PolicyTrigger::AutoPostDisabled]);
+    }
+
     #[tokio::test]
     async fn evaluate_uses_default_rules_when_missing() {
         let store: Arc<dyn PolicyStore> = Arc::new(InMemoryPolicyStore::new());
@@ -765,6 +2184,50 @@ mod tests {
         assert!(event.evaluated_at >= proposal.submitted_at);
     }
 
+    #[tokio::test]
+    async fn shadow_mode_records_would_be_decision_but_requires_approval() {
+        let store: Arc<dyn PolicyStore> = Arc::new(InMemoryPolicyStore::new());
+        let mut rules = make_rules();
+        rules.blocked_vendors.insert("acme inc".into());
+        store
+            .put_rule_set("comp-1".into(), rules)
+            .await
+            .expect("rules save");
+        let sink = Arc::new(InMemoryPolicyEventSink::new());
+        let event_sink: Arc<dyn PolicyEventSink> = sink.clone();
+        let engine = PolicyEngine::with_components(store, PolicyRuleSet::default(), event_sink)
+            .with_shadow_mode(true);
+        let context = || PolicyContext {
+            company_id: "comp-1".into(),
+            actor: "user-1".into(),
+        };
+
+        let outcome = engine
+            .evaluate(context(), base_proposal(42_000))
+            .await
+            .expect("evaluation should succeed");
+        assert_eq!(outcome.decision, PolicyDecision::NeedsApproval);
+        let batch = engine
+            .evaluate_many(context(), vec![base_proposal(42_000)])
+            .await
+            .expect("batch evaluation should succeed");
+        assert_eq!(batch[0].decision, PolicyDecision::NeedsApproval);
+
+        let mut blocked = base_proposal(42_000);
+        blocked.vendor_id = Some("Acme Inc".into());
+        let rejected = engine
+            .evaluate(context(), blocked)
+            .await
+            .expect("evaluation should succeed");
+        assert_eq!(rejected.decision, PolicyDecision::Reject);
+
+        let events = sink.events().await;
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|event| event.shadow));
+        assert_eq!(events[0].decision, PolicyDecision::AutoPost);
+        assert_eq!(events[2].decision, PolicyDecision::Reject);
+    }
+
     #[tokio::test]
     async fn durable_store_populates_cache_from_persistence() {
         let persistence = Arc::new(InMemoryPolicyStore::new());
@@ -875,4 +2338,141 @@ mod tests {
             assert_eq!(outcome, expected);
         }
     }
+
+    #[tokio::test]
+    async fn record_override_emits_override_event() {
+        let store: Arc<dyn PolicyStore> = Arc::new(InMemoryPolicyStore::new());
+        store
+            .put_rule_set("comp-1".into(), make_rules())
+            .await
+            .expect("store rules");
+        let sink = Arc::new(InMemoryPolicyEventSink::new());
+        let engine = PolicyEngine::with_event_sink(store, sink.clone());
+        let context = PolicyContext {
+            company_id: "comp-1".into(),
+            actor: "reviewer-1".into(),
+        };
+        let mut proposal = base_proposal(150_000);
+        proposal.vendor_id = Some("vendor-9".into());
+
+        let err = engine
+            .record_override(
+                context.clone(),
+                &proposal,
+                PolicyDecision::NeedsApproval,
+                PolicyDecision::AutoPost,
+                "  ".into(),
+            )
+            .await
+            .expect_err("justification is required");
+        assert!(matches!(err, PolicyError::Validation(_)));
+        let err = engine
+            .record_override(
+                PolicyContext {
+                    company_id: "comp-2".into(),
+                    actor: "reviewer-1".into(),
+                },
+                &proposal,
+                PolicyDecision::NeedsApproval,
+                PolicyDecision::AutoPost,
+                "Vendor verified by controller".into(),
+            )
+            .await
+            .expect_err("context must match the proposal company");
+        assert!(matches!(err, PolicyError::Validation(_)));
+
+        engine
+            .record_override(
+                context,
+                &proposal,
+                PolicyDecision::NeedsApproval,
+                PolicyDecision::AutoPost,
+                "Vendor verified by controller".into(),
+            )
+            .await
+            .expect("override recorded");
+
+        let events = sink.events().await;
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert!(event.is_override);
+        assert_eq!(event.decision, PolicyDecision::AutoPost);
+        assert_eq!(
+            event.overridden_decision,
+            Some(PolicyDecision::NeedsApproval)
+        );
+        assert_eq!(
+            event.justification.as_deref(),
+            Some("Vendor verified by controller")
+        );
+        assert_eq!(event.actor, "reviewer-1");
+        assert_eq!(event.proposal_id, proposal.id);
+        assert_eq!(event.total_minor, 150_000);
+        assert_eq!(event.currency, "USD");
+        assert_eq!(event.vendor_id.as_deref(), Some("vendor-9"));
+        assert_eq!(event.account_codes, vec!["6000".to_string()]);
+        assert_eq!(event.confidence, Some(0.9));
+        assert_eq!(
+            event.triggers,
+            vec![PolicyTrigger::AmountExceedsLimit {
+                limit_minor: 100_000,
+                actual_minor: 150_000,
+            }]
+        );
+    }
+
+    #[test]
+    fn vendor_matching_ignores_case_and_whitespace() {
+        let mut rules = make_rules();
+        rules.blocked_vendors.insert("acme inc".into());
+        rules
+            .approval_required_vendors
+            .insert("Globex  Corp".into());
+
+        let mut blocked = base_proposal(10_000);
+        blocked.vendor_id = Some("  Acme   Inc ".into());
+        let outcome = rules.evaluate(&blocked);
+        assert_eq!(outcome.decision, PolicyDecision::Reject);
+        assert_eq!(
+            outcome.triggers,
+            vec![PolicyTrigger::VendorBlocked {
+                vendor_id: "  Acme   Inc ".into(),
+            }]
+        );
+
+        let mut flagged = base_proposal(10_000);
+        flagged.vendor_id = Some("globex corp".into());
+        assert_eq!(
+            rules.evaluate(&flagged).decision,
+            PolicyDecision::NeedsApproval
+        );
+    }
+
+    #[tokio::test]
+    async fn engine_resolves_vendor_aliases() {
+        let store: Arc<dyn PolicyStore> = Arc::new(InMemoryPolicyStore::new());
+        let mut rules = make_rules();
+        rules.blocked_vendors.insert("Amazon".into());
+        store
+            .put_rule_set("comp-1".into(), rules)
+            .await
+            .expect("rules save");
+        let aliases = HashMap::from([("AMZN".to_string(), "amazon".to_string())]);
+        let engine = PolicyEngine::new(store)
+            .with_vendor_normalizer(Arc::new(DefaultVendorNormalizer::with_aliases(aliases)));
+
+        let mut proposal = base_proposal(10_000);
+        proposal.vendor_id = Some("amzn".into());
+        let outcome = engine
+            .evaluate(
+                PolicyContext {
+                    company_id: "comp-1".into(),
+                    actor: "user-1".into(),
+                },
+                proposal,
+            )
+            .await
+            .expect("evaluation should succeed");
+        assert_eq!(outcome.decision, PolicyDecision::Reject);
+    }
 }
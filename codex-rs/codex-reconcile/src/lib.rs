@@ -2,16 +2,34 @@
 
 use std::collections::BTreeSet;
 use std::collections::HashMap;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::RwLock;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 
+use async_trait::async_trait;
 use chrono::DateTime;
 use chrono::Utc;
+use regex_lite::Regex;
 use serde::Deserialize;
 use serde::Serialize;
 use thiserror::Error;
+use tracing::warn;
 use uuid::Uuid;
 
+#[cfg(feature = "webhook-notifier")]
+mod webhook;
+
+#[cfg(feature = "webhook-notifier")]
+pub use webhook::WebhookReconciliationNotifier;
+
 pub type SessionId = String;
 pub type CandidateId = String;
 pub type ReconcileResult<T> = Result<T, ReconcileError>;
@@ -20,12 +38,58 @@ pub type ReconcileResult<T> = Result<T, ReconcileError>;
 pub enum ReconcileError {
     #[error("session {0} not found")]
     SessionNotFound(SessionId),
+    #[error("session {0} already exists")]
+    SessionAlreadyExists(SessionId),
     #[error("candidate {0} not found")]
     CandidateNotFound(CandidateId),
+    #[error("candidate {0} already exists")]
+    CandidateAlreadyExists(CandidateId),
     #[error("invalid transition: {0}")]
     InvalidTransition(String),
+    #[error("invalid scoring configuration: {0}")]
+    InvalidConfiguration(String),
+    #[error("invalid match proposal: {0}")]
+    InvalidProposal(String),
     #[error("storage error: {0}")]
     Storage(String),
+    #[error("candidate score {score:.3} is below the floor {floor:.3}")]
+    BelowScoreFloor { score: f32, floor: f32 },
+    #[error("notification failed: {0}")]
+    Notification(String),
+}
+
+impl ReconcileError {
+    /// Stable machine-readable code such as `reconcile.invalid_transition`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::SessionNotFound(_) => "reconcile.session_not_found",
+            Self::SessionAlreadyExists(_) => "reconcile.session_already_exists",
+            Self::CandidateNotFound(_) => "reconcile.candidate_not_found",
+            Self::CandidateAlreadyExists(_) => "reconcile.candidate_already_exists",
+            Self::InvalidTransition(_) => "reconcile.invalid_transition",
+            Self::InvalidConfiguration(_) => "reconcile.invalid_configuration",
+            Self::InvalidProposal(_) => "reconcile.invalid_proposal",
+            Self::Storage(_) => "reconcile.storage",
+            Self::BelowScoreFloor { .. } => "reconcile.below_score_floor",
+            Self::Notification(_) => "reconcile.notification",
+        }
+    }
+
+    /// HTTP status for API error bodies; notifier failures map to 502.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            Self::SessionNotFound(_) => 404,
+            Self::SessionAlreadyExists(_) => 409,
+            Self::CandidateNotFound(_) => 404,
+            Self::CandidateAlreadyExists(_) => 409,
+            Self::InvalidTransition(_) => 409,
+            Self::InvalidConfiguration(_) => 400,
+            Self::InvalidProposal(_) => 400,
+            Self::Storage(_) => 500,
+            Self::BelowScoreFloor { .. } => 422,
+            Self::Notification(_) => 502,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -40,6 +104,162 @@ pub struct MatchCandidate {
     pub group_id: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub write_off_reason: Option<String>,
+    /// Amount delta between the bank line and the journal entry, in minor units.
+    #[serde(default)]
+    pub residual_minor: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write_off_approval_reference: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_breakdown: Option<ScoreExplanation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejection_reason: Option<String>,
+    /// Scoring inputs retained from the original `MatchProposal` so the
+    /// candidate can be re-scored; `residual_minor` holds the amount delta.
+    #[serde(default)]
+    pub date_delta_days: i64,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub transaction_description: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub journal_description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency_precision: Option<u8>,
+    /// Descriptions as the scoring strategy compared them, for display. Set
+    /// only when the strategy preprocesses descriptions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalized_transaction_description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalized_journal_description: Option<String>,
+    /// Currencies of the two sides and the rate applied between them, kept
+    /// from the proposal; see [`MatchProposal::with_fx_rate`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_currency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub journal_currency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fx_rate: Option<f64>,
+    /// Set when the sides are in different currencies and no rate was
+    /// applied, so `residual_minor` mixes currencies and the amount
+    /// component scored 0.
+    #[serde(default)]
+    pub needs_fx_review: bool,
+}
+
+impl MatchCandidate {
+    /// Rebuilds the proposal this candidate was scored from.
+    #[must_use]
+    pub fn to_proposal(&self) -> MatchProposal {
+        MatchProposal {
+            transaction_id: self.transaction_id.clone(),
+            journal_entry_id: self.journal_entry_id.clone(),
+            amount_delta_minor: self.residual_minor,
+            date_delta_days: self.date_delta_days,
+            transaction_description: self.transaction_description.clone(),
+            journal_description: self.journal_description.clone(),
+            group_id: self.group_id.clone(),
+            currency: self.currency.clone(),
+            currency_precision: self.currency_precision,
+            transaction_currency: self.transaction_currency.clone(),
+            journal_currency: self.journal_currency.clone(),
+            fx_rate: self.fx_rate,
+            proposed_candidate_id: Some(self.id.clone()),
+            ..MatchProposal::default()
+        }
+    }
+
+    /// The deltas behind this candidate's score for a "why does this
+    /// match?" view. Description similarity comes from the stored score
+    /// breakdown when there is one, and is otherwise recomputed with the
+    /// [`WhitespaceTokenizer`].
+    #[must_use]
+    pub fn comparison(&self) -> CandidateComparison {
+        let description_similarity = self
+            .score_breakdown
+            .as_ref()
+            .and_then(|breakdown| {
+                breakdown
+                    .components
+                    .iter()
+                    .find(|component| component.kind == ScoreComponentKind::Description)
+            })
+            .map(|component| component.normalized)
+            .unwrap_or_else(|| {
+                description_similarity(
+                    &WhitespaceTokenizer,
+                    &self.transaction_description,
+                    &self.journal_description,
+                )
+            });
+        CandidateComparison {
+            amount_delta_minor: self.residual_minor,
+            date_delta_days: self.date_delta_days,
+            description_similarity,
+            transaction_description: self.transaction_description.clone(),
+            journal_description: self.journal_description.clone(),
+            currency: self.currency.clone(),
+            currency_precision: self.currency_precision,
+        }
+    }
+}
+
+/// Side-by-side deltas between a bank line and its proposed journal entry;
+/// see [`MatchCandidate::comparison`]. `Display` renders a one-line summary
+/// such as `amount Δ 1.50 USD, date Δ 2 days, description 67% similar`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CandidateComparison {
+    pub amount_delta_minor: i64,
+    pub date_delta_days: i64,
+    /// Token overlap of the two descriptions, `0.0..=1.0`.
+    pub description_similarity: f32,
+    pub transaction_description: String,
+    pub journal_description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency_precision: Option<u8>,
+}
+
+impl CandidateComparison {
+    /// The amount delta in major units at the currency's precision
+    /// (two decimals when unknown), e.g. `-1.50`.
+    #[must_use]
+    pub fn formatted_amount_delta(&self) -> String {
+        // i64 magnitudes have at most 19 digits, so larger precisions only
+        // add leading zeros.
+        let precision = self
+            .currency_precision
+            .map_or(REFERENCE_CURRENCY_PRECISION as u32, u32::from)
+            .min(19);
+        let sign = if self.amount_delta_minor < 0 { "-" } else { "" };
+        let magnitude = self.amount_delta_minor.unsigned_abs();
+        if precision == 0 {
+            return format!("{sign}{magnitude}");
+        }
+        let scale = 10_u64.pow(precision);
+        format!(
+            "{sign}{}.{:0width$}",
+            magnitude / scale,
+            magnitude % scale,
+            width = precision as usize
+        )
+    }
+}
+
+impl std::fmt::Display for CandidateComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "amount Δ {}", self.formatted_amount_delta())?;
+        if let Some(currency) = &self.currency {
+            write!(f, " {currency}")?;
+        }
+        let days = self.date_delta_days;
+        let unit = if days.abs() == 1 { "day" } else { "days" };
+        write!(
+            f,
+            ", date Δ {days} {unit}, description {:.0}% similar",
+            self.description_similarity * 100.0
+        )
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -58,6 +278,41 @@ pub enum SessionStatus {
     Closed,
 }
 
+/// Candidate counts by status for a single session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Coverage {
+    pub pending: usize,
+    pub accepted: usize,
+    pub partially_accepted: usize,
+    pub rejected: usize,
+    pub written_off: usize,
+}
+
+impl Coverage {
+    /// Candidates that have been resolved by acceptance or write-off.
+    #[must_use]
+    pub fn matched(&self) -> usize {
+        self.accepted + self.partially_accepted + self.written_off
+    }
+
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.matched() + self.pending + self.rejected
+    }
+
+    /// Share of matched candidates out of matched plus pending; rejected
+    /// candidates are excluded because they no longer need review.
+    #[must_use]
+    pub fn ratio(&self) -> f32 {
+        let denominator = self.matched() + self.pending;
+        if denominator == 0 {
+            0.0
+        } else {
+            self.matched() as f32 / denominator as f32
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ReconciliationSession {
     pub id: SessionId,
@@ -65,9 +320,78 @@ pub struct ReconciliationSession {
     pub status: SessionStatus,
     pub opened_at: DateTime<Utc>,
     pub candidates: Vec<MatchCandidate>,
+    /// Reviewer transitions `undo_last` can revert, oldest first.
+    /// Cleared whenever the session is reopened or auto-closed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub undo_stack: Vec<UndoEntry>,
+}
+
+/// How many transitions `undo_last` can walk back per session.
+const MAX_UNDO_DEPTH: usize = 10;
+
+/// State a reviewer transition replaced: the session status, the prior
+/// versions of the candidates it changed and the ids of any it added.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UndoEntry {
+    pub action: ReconciliationAuditAction,
+    pub previous_status: SessionStatus,
+    pub previous_candidates: Vec<MatchCandidate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added_candidate_ids: Vec<CandidateId>,
 }
 
 impl ReconciliationSession {
+    #[must_use]
+    pub fn coverage(&self) -> Coverage {
+        let mut coverage = Coverage::default();
+        for candidate in &self.candidates {
+            match candidate.status {
+                CandidateStatus::Pending => coverage.pending += 1,
+                CandidateStatus::Accepted => coverage.accepted += 1,
+                CandidateStatus::PartiallyAccepted => coverage.partially_accepted += 1,
+                CandidateStatus::Rejected => coverage.rejected += 1,
+                CandidateStatus::WrittenOff => coverage.written_off += 1,
+            }
+        }
+        coverage
+    }
+
+    /// The highest-scoring pending candidate; ties go to the earliest proposal.
+    #[must_use]
+    pub fn best_candidate(&self) -> Option<&MatchCandidate> {
+        self.candidates
+            .iter()
+            .filter(|candidate| candidate.status == CandidateStatus::Pending)
+            .min_by(|left, right| {
+                right
+                    .score
+                    .total_cmp(&left.score)
+                    .then(left.proposed_at.cmp(&right.proposed_at))
+            })
+    }
+
+    /// Candidate counts per equal-width score bucket over `0.0..=1.0`, as
+    /// `(lower_bound, count)` pairs. A score of `1.0` lands in the last
+    /// bucket; `buckets == 0` yields an empty histogram.
+    #[must_use]
+    pub fn score_histogram(&self, buckets: usize) -> Vec<(f32, usize)> {
+        if buckets == 0 {
+            return Vec::new();
+        }
+        let width = 1.0 / buckets as f32;
+        let mut counts = vec![0; buckets];
+        for candidate in &self.candidates {
+            let score = candidate.score.clamp(0.0, 1.0);
+            let index = ((score * buckets as f32) as usize).min(buckets - 1);
+            counts[index] += 1;
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(index, count)| (index as f32 * width, count))
+            .collect()
+    }
+
     fn ensure_mutable(&self) -> ReconcileResult<()> {
         if matches!(self.status, SessionStatus::Closed) {
             Err(ReconcileError::InvalidTransition(format!(
@@ -79,8 +403,76 @@ impl ReconciliationSession {
         }
     }
 
+    fn record_undo(
+        &mut self,
+        action: ReconciliationAuditAction,
+        previous_status: SessionStatus,
+        previous_candidates: Vec<MatchCandidate>,
+    ) {
+        let added_candidate_ids = self
+            .candidates
+            .iter()
+            .filter(|candidate| {
+                !previous_candidates
+                    .iter()
+                    .any(|previous| previous.id == candidate.id)
+            })
+            .map(|candidate| candidate.id.clone())
+            .collect::<Vec<_>>();
+        let changed = previous_candidates
+            .into_iter()
+            .filter(|previous| {
+                self.candidates
+                    .iter()
+                    .find(|candidate| candidate.id == previous.id)
+                    .is_some_and(|candidate| candidate != previous)
+            })
+            .collect::<Vec<_>>();
+        if changed.is_empty() && added_candidate_ids.is_empty() && previous_status == self.status {
+            return;
+        }
+        self.undo_stack.push(UndoEntry {
+            action,
+            previous_status,
+            previous_candidates: changed,
+            added_candidate_ids,
+        });
+        if self.undo_stack.len() > MAX_UNDO_DEPTH {
+            self.undo_stack.remove(0);
+        }
+    }
+
+    fn undo_last(&mut self) -> ReconcileResult<UndoEntry> {
+        let entry = self.undo_stack.pop().ok_or_else(|| {
+            ReconcileError::InvalidTransition(format!(
+                "session {} has no action to undo since it was last opened",
+                self.id
+            ))
+        })?;
+        self.candidates
+            .retain(|candidate| !entry.added_candidate_ids.contains(&candidate.id));
+        for previous in &entry.previous_candidates {
+            if let Some(candidate) = self
+                .candidates
+                .iter_mut()
+                .find(|candidate| candidate.id == previous.id)
+            {
+                *candidate = previous.clone();
+            }
+        }
+        self.status = entry.previous_status;
+        Ok(entry)
+    }
+
     fn add_candidate(&mut self, candidate: MatchCandidate) -> ReconcileResult<()> {
         self.ensure_mutable()?;
+        if self
+            .candidates
+            .iter()
+            .any(|existing| existing.id == candidate.id)
+        {
+            return Err(ReconcileError::CandidateAlreadyExists(candidate.id));
+        }
         self.candidates.push(candidate);
         Ok(())
     }
@@ -100,6 +492,7 @@ impl ReconciliationSession {
                 }
                 candidate.status = CandidateStatus::Accepted;
                 candidate.write_off_reason = None;
+                candidate.write_off_approval_reference = None;
                 accepted = Some(candidate.clone());
             } else if matches!(
                 candidate.status,
@@ -114,7 +507,38 @@ impl ReconciliationSession {
         Ok(accepted)
     }
 
-    fn reject(&mut self, candidate_id: &CandidateId) -> ReconcileResult<MatchCandidate> {
+    /// Adds an already-accepted candidate without closing the session or
+    /// touching other candidates. Neither its transaction nor its journal
+    /// entry may already be matched by an accepted candidate.
+    fn add_exact_match(&mut self, candidate: MatchCandidate) -> ReconcileResult<()> {
+        self.ensure_mutable()?;
+        for existing in self
+            .candidates
+            .iter()
+            .filter(|existing| existing.status == CandidateStatus::Accepted)
+        {
+            if existing.transaction_id == candidate.transaction_id {
+                return Err(ReconcileError::InvalidTransition(format!(
+                    "transaction {} is already matched by candidate {}",
+                    candidate.transaction_id, existing.id
+                )));
+            }
+            if existing.journal_entry_id == candidate.journal_entry_id {
+                return Err(ReconcileError::InvalidTransition(format!(
+                    "journal entry {} is already matched by candidate {}",
+                    candidate.journal_entry_id, existing.id
+                )));
+            }
+        }
+        self.candidates.push(candidate);
+        Ok(())
+    }
+
+    fn reject(
+        &mut self,
+        candidate_id: &CandidateId,
+        reason: Option<String>,
+    ) -> ReconcileResult<MatchCandidate> {
         self.ensure_mutable()?;
         let candidate = self
             .candidates
@@ -127,6 +551,7 @@ impl ReconciliationSession {
             )));
         }
         candidate.status = CandidateStatus::Rejected;
+        candidate.rejection_reason = reason;
         Ok(candidate.clone())
     }
 
@@ -173,6 +598,8 @@ impl ReconciliationSession {
         &mut self,
         candidate_id: &CandidateId,
         reason: String,
+        approval_reference: Option<String>,
+        max_write_off_minor: Option<i64>,
     ) -> ReconcileResult<MatchCandidate> {
         self.ensure_mutable()?;
         let candidate = self
@@ -191,12 +618,46 @@ impl ReconciliationSession {
                 candidate.status
             )));
         }
+        let approval_reference = approval_reference.filter(|value| !value.trim().is_empty());
+        if let Some(limit) = max_write_off_minor
+            && candidate.residual_minor.abs() > limit
+            && approval_reference.is_none()
+        {
+            return Err(ReconcileError::InvalidTransition(format!(
+                "write-off of {} exceeds threshold {limit} and requires an approval reference",
+                candidate.residual_minor.abs()
+            )));
+        }
         candidate.status = CandidateStatus::WrittenOff;
         candidate.write_off_reason = Some(reason);
+        candidate.write_off_approval_reference = approval_reference;
         self.status = SessionStatus::PendingPartial;
         Ok(candidate.clone())
     }
 
+    /// Re-scores pending candidates and returns `(id, old, new)` for each one
+    /// whose score changed.
+    fn rescore(
+        &mut self,
+        scoring: &dyn ScoringStrategy,
+    ) -> ReconcileResult<Vec<(CandidateId, f32, f32)>> {
+        self.ensure_mutable()?;
+        let mut changes = Vec::new();
+        for candidate in &mut self.candidates {
+            if candidate.status != CandidateStatus::Pending {
+                continue;
+            }
+            let explanation = scoring.explain(&candidate.to_proposal());
+            let previous = candidate.score;
+            candidate.score = explanation.total;
+            candidate.score_breakdown = (!explanation.components.is_empty()).then_some(explanation);
+            if (candidate.score - previous).abs() > f32::EPSILON {
+                changes.push((candidate.id.clone(), previous, candidate.score));
+            }
+        }
+        Ok(changes)
+    }
+
     fn reopen(&mut self) -> ReconcileResult<()> {
         if matches!(self.status, SessionStatus::Open) {
             return Ok(());
@@ -204,153 +665,957 @@ impl ReconciliationSession {
         for candidate in &mut self.candidates {
             candidate.status = CandidateStatus::Pending;
             candidate.write_off_reason = None;
+            candidate.write_off_approval_reference = None;
+            candidate.rejection_reason = None;
         }
         self.status = SessionStatus::Open;
+        self.undo_stack.clear();
         Ok(())
     }
+
+    /// Returns written-off candidates to `Pending` and leaves every other
+    /// candidate untouched. Returns how many were reopened.
+    fn reopen_write_offs(&mut self) -> usize {
+        let mut reopened = 0;
+        for candidate in &mut self.candidates {
+            if candidate.status == CandidateStatus::WrittenOff {
+                candidate.status = CandidateStatus::Pending;
+                candidate.write_off_reason = None;
+                candidate.write_off_approval_reference = None;
+                reopened += 1;
+            }
+        }
+        if reopened > 0 {
+            self.undo_stack.clear();
+            self.status = if self
+                .candidates
+                .iter()
+                .any(|candidate| candidate.status != CandidateStatus::Pending)
+            {
+                SessionStatus::PendingPartial
+            } else {
+                SessionStatus::Open
+            };
+        }
+        reopened
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct MatchProposal {
     pub transaction_id: String,
     pub journal_entry_id: String,
     pub amount_delta_minor: i64,
     pub date_delta_days: i64,
+    #[serde(default)]
     pub transaction_description: String,
+    #[serde(default)]
     pub journal_description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub group_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Minor-unit precision of `currency`; scales the amount tolerance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency_precision: Option<u8>,
+    /// Currency and amount of the bank line and of the journal entry. When the
+    /// currencies differ, `amount_delta_minor` only means something once
+    /// [`MatchProposal::with_fx_rate`] has converted the bank amount.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_currency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub journal_currency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_amount_minor: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub journal_amount_minor: Option<i64>,
+    /// `transaction_currency`→`journal_currency` rate applied by
+    /// `with_fx_rate`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fx_rate: Option<f64>,
+    /// Caller-chosen candidate id so an external matcher can re-submit
+    /// idempotently; `add_candidate` generates a UUID when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proposed_candidate_id: Option<CandidateId>,
 }
 
-pub trait ScoringStrategy: Send + Sync {
-    fn score(&self, proposal: &MatchProposal) -> f32;
-}
-
-#[derive(Debug, Clone)]
-pub struct WeightedScoringStrategy {
-    amount_weight: f32,
-    date_weight: f32,
-    description_weight: f32,
-    amount_tolerance_minor: i64,
-    date_tolerance_days: i64,
+/// Exchange rate for [`MatchProposal::with_fx_rate`]: one unit of `base` buys
+/// `rate` units of `quote`. The precisions are each currency's minor-unit
+/// digits.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FxRate {
+    pub base: String,
+    pub base_precision: u8,
+    pub quote: String,
+    pub quote_precision: u8,
+    pub rate: f64,
 }
 
-impl WeightedScoringStrategy {
-    pub fn new(
-        amount_weight: f32,
-        date_weight: f32,
-        description_weight: f32,
-        amount_tolerance_minor: i64,
-        date_tolerance_days: i64,
-    ) -> Self {
-        Self {
-            amount_weight,
-            date_weight,
-            description_weight,
-            amount_tolerance_minor: amount_tolerance_minor.max(1),
-            date_tolerance_days: date_tolerance_days.max(1),
+impl MatchProposal {
+    /// Whether the bank line and the journal entry are in different
+    /// currencies.
+    #[must_use]
+    pub fn is_cross_currency(&self) -> bool {
+        match (&self.transaction_currency, &self.journal_currency) {
+            (Some(transaction), Some(journal)) => !transaction.eq_ignore_ascii_case(journal),
+            _ => false,
         }
     }
 
-    fn normalize_amount(&self, delta: i64) -> f32 {
-        let ratio = (delta.abs() as f32) / (self.amount_tolerance_minor as f32);
-        (1.0 - ratio).clamp(0.0, 1.0)
+    /// Cross-currency proposals no rate has been applied to. Their amount
+    /// component scores 0 and the resulting candidate is flagged
+    /// `needs_fx_review`.
+    #[must_use]
+    pub fn needs_fx_review(&self) -> bool {
+        self.is_cross_currency() && self.fx_rate.is_none()
     }
 
-    fn normalize_date(&self, delta: i64) -> f32 {
-        let ratio = (delta.abs() as f32) / (self.date_tolerance_days as f32);
-        (1.0 - ratio).clamp(0.0, 1.0)
+    /// Recomputes `amount_delta_minor` as the converted bank amount minus the
+    /// journal amount, in the journal currency, which also becomes
+    /// `currency`/`currency_precision`. `rate` may be quoted in either
+    /// direction between the two currencies and must be positive.
+    pub fn with_fx_rate(mut self, rate: &FxRate) -> ReconcileResult<Self> {
+        let (Some(transaction_currency), Some(journal_currency)) =
+            (&self.transaction_currency, &self.journal_currency)
+        else {
+            return Err(ReconcileError::InvalidProposal(
+                "fx conversion needs both currencies".into(),
+            ));
+        };
+        let (Some(transaction_amount), Some(journal_amount)) =
+            (self.transaction_amount_minor, self.journal_amount_minor)
+        else {
+            return Err(ReconcileError::InvalidProposal(
+                "fx conversion needs both amounts".into(),
+            ));
+        };
+        if !(rate.rate.is_finite() && rate.rate > 0.0) {
+            return Err(ReconcileError::InvalidProposal(format!(
+                "rate {}/{} must be positive, got {}",
+                rate.base, rate.quote, rate.rate
+            )));
+        }
+        let scale = |to: u8, from: u8| 10_f64.powi(i32::from(to) - i32::from(from));
+        let amount = transaction_amount as f64;
+        let (converted, journal, journal_precision, applied_rate) =
+            if rate.base.eq_ignore_ascii_case(transaction_currency)
+                && rate.quote.eq_ignore_ascii_case(journal_currency)
+            {
+                (
+                    amount * rate.rate * scale(rate.quote_precision, rate.base_precision),
+                    &rate.quote,
+                    rate.quote_precision,
+                    rate.rate,
+                )
+            } else if rate.base.eq_ignore_ascii_case(journal_currency)
+                && rate.quote.eq_ignore_ascii_case(transaction_currency)
+            {
+                (
+                    amount / rate.rate * scale(rate.base_precision, rate.quote_precision),
+                    &rate.base,
+                    rate.base_precision,
+                    1.0 / rate.rate,
+                )
+            } else {
+                return Err(ReconcileError::InvalidProposal(format!(
+                    "rate {}/{} does not convert {transaction_currency} to {journal_currency}",
+                    rate.base, rate.quote
+                )));
+            };
+        let converted = converted.round_ties_even();
+        if !(converted.is_finite() && converted.abs() < i64::MAX as f64) {
+            return Err(ReconcileError::InvalidProposal(format!(
+                "converted amount {converted} is out of range"
+            )));
+        }
+        let converted = converted as i64;
+        self.amount_delta_minor = converted.checked_sub(journal_amount).ok_or_else(|| {
+            ReconcileError::InvalidProposal(format!(
+                "amount delta {converted} - {journal_amount} overflows"
+            ))
+        })?;
+        self.currency = Some(journal.clone());
+        self.currency_precision = Some(journal_precision);
+        self.fx_rate = Some(applied_rate);
+        Ok(self)
     }
 }
 
-impl Default for WeightedScoringStrategy {
-    fn default() -> Self {
-        Self::new(0.45, 0.35, 0.20, 5_000, 7)
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScoreComponentKind {
+    Amount,
+    Date,
+    Description,
 }
 
-impl ScoringStrategy for WeightedScoringStrategy {
-    fn score(&self, proposal: &MatchProposal) -> f32 {
-        let total_weight = self.amount_weight + self.date_weight + self.description_weight;
-        if total_weight <= f32::EPSILON {
-            return 0.0;
-        }
-        let amount_component = self.normalize_amount(proposal.amount_delta_minor);
-        let date_component = self.normalize_date(proposal.date_delta_days);
-        let description_component = description_similarity(
-            &proposal.transaction_description,
-            &proposal.journal_description,
-        );
-        let weighted = amount_component * self.amount_weight
-            + date_component * self.date_weight
-            + description_component * self.description_weight;
-        (weighted / total_weight).clamp(0.0, 1.0)
-    }
+/// One weighted input to a match score. `contribution` is the share of the
+/// total score this component accounts for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreComponent {
+    pub kind: ScoreComponentKind,
+    pub weight: f32,
+    pub normalized: f32,
+    pub contribution: f32,
 }
 
-#[derive(Debug, Default)]
-pub struct LinearScoringStrategy {
-    inner: WeightedScoringStrategy,
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreExplanation {
+    pub total: f32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<ScoreComponent>,
 }
 
-impl LinearScoringStrategy {
-    pub fn new() -> Self {
-        Self {
-            inner: WeightedScoringStrategy::default(),
+pub trait ScoringStrategy: Send + Sync {
+    fn score(&self, proposal: &MatchProposal) -> f32;
+
+    /// Breaks a score down into its components. Strategies without a
+    /// breakdown report only the total.
+    fn explain(&self, proposal: &MatchProposal) -> ScoreExplanation {
+        ScoreExplanation {
+            total: self.score(proposal),
+            components: Vec::new(),
         }
     }
-}
 
-impl ScoringStrategy for LinearScoringStrategy {
-    fn score(&self, proposal: &MatchProposal) -> f32 {
-        self.inner.score(proposal)
+    /// The description as this strategy compares it, when it preprocesses
+    /// descriptions at all.
+    fn normalize_description(&self, _description: &str) -> Option<String> {
+        None
     }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum ReconciliationAuditAction {
-    SessionCreated,
-    CandidateAdded,
-    CandidateAccepted,
-    CandidateRejected,
-    CandidatePartiallyAccepted,
-    CandidateWrittenOff,
-    SessionReopened,
-}
 
-#[derive(Debug, Clone)]
-pub struct ReconciliationAuditEvent {
-    pub session_id: SessionId,
-    pub candidate_id: Option<CandidateId>,
-    pub action: ReconciliationAuditAction,
-    pub note: Option<String>,
+    /// Scores a many-to-one split where several proposals share one bank line.
+    fn score_group(&self, proposals: &[MatchProposal]) -> f32 {
+        if proposals.is_empty() {
+            return 0.0;
+        }
+        let total: f32 = proposals.iter().map(|proposal| self.score(proposal)).sum();
+        total / proposals.len() as f32
+    }
 }
 
-pub trait ReconciliationAuditHook: Send + Sync {
-    fn record(&self, event: &ReconciliationAuditEvent);
+/// Splits descriptions into comparable tokens for description similarity.
+pub trait Tokenizer: std::fmt::Debug + Send + Sync {
+    fn tokenize(&self, input: &str) -> Vec<String>;
 }
 
-#[derive(Default)]
-pub struct NoopReconciliationAuditHook;
+/// Lowercased whitespace-separated words; punctuation stays attached.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WhitespaceTokenizer;
 
-impl ReconciliationAuditHook for NoopReconciliationAuditHook {
-    fn record(&self, _event: &ReconciliationAuditEvent) {}
+impl Tokenizer for WhitespaceTokenizer {
+    fn tokenize(&self, input: &str) -> Vec<String> {
+        input
+            .split_whitespace()
+            .map(str::to_ascii_lowercase)
+            .collect()
+    }
 }
 
-pub trait ReconciliationStore: Send + Sync {
-    fn create_session(
-        &self,
-        session: ReconciliationSession,
-    ) -> ReconcileResult<ReconciliationSession>;
-    fn save_session(&self, session: &ReconciliationSession) -> ReconcileResult<()>;
-    fn get_session(&self, session_id: &SessionId) -> ReconcileResult<ReconciliationSession>;
-}
+const DEFAULT_STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "at", "by", "for", "from", "in", "of", "on", "the", "to",
+];
 
-#[derive(Default)]
-pub struct InMemoryReconciliationStore {
-    sessions: RwLock<HashMap<SessionId, ReconciliationSession>>,
+/// Splits on every non-alphanumeric character, so bank memos such as
+/// `Inv#1001` yield `inv` and `1001`, and drops stop-words.
+#[derive(Debug, Clone)]
+pub struct AlphanumericTokenizer {
+    stop_words: BTreeSet<String>,
 }
 
-impl InMemoryReconciliationStore {
+impl AlphanumericTokenizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the default English stop-word list.
+    #[must_use]
+    pub fn with_stop_words<I, S>(mut self, stop_words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.stop_words = stop_words
+            .into_iter()
+            .map(|word| word.as_ref().to_lowercase())
+            .collect();
+        self
+    }
+}
+
+impl Default for AlphanumericTokenizer {
+    fn default() -> Self {
+        Self {
+            stop_words: DEFAULT_STOP_WORDS.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+impl Tokenizer for AlphanumericTokenizer {
+    fn tokenize(&self, input: &str) -> Vec<String> {
+        input
+            .split(|ch: char| !ch.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(str::to_lowercase)
+            .filter(|token| !self.stop_words.contains(token))
+            .collect()
+    }
+}
+
+/// Strip rules applied by `DescriptionNormalizer::default()`: card and ACH
+/// prefixes such as `POS DEBIT 1234 ` or `ACH CREDIT `, and a trailing
+/// `CITY ST` location where `ST` is a US state or DC code. Reference numbers
+/// are kept because they are the strongest matching tokens.
+pub const DEFAULT_DESCRIPTION_STRIP_RULES: &[&str] = &[
+    r"(?i)^\s*(pos|ach|atm|checkcard|debit card|wire)(\s+(debit|credit|purchase|withdrawal|deposit|transfer))?(\s+\d{4})?\s+",
+    r"\s+[A-Z]{3,}\s+(AL|AK|AZ|AR|CA|CO|CT|DC|DE|FL|GA|HI|ID|IL|IN|IA|KS|KY|LA|ME|MD|MA|MI|MN|MS|MO|MT|NE|NV|NH|NJ|NM|NY|NC|ND|OH|OK|OR|PA|RI|SC|SD|TN|TX|UT|VT|VA|WA|WV|WI|WY)\s*$",
+];
+
+/// Removes bank noise from descriptions before they are tokenized. Each
+/// rule is a regex whose matches are replaced by a space, applied in order;
+/// whitespace is then collapsed. Location stripping only recognizes
+/// single-word cities in upper case, and cannot tell a state code from a
+/// business suffix that spells one (`CO`).
+#[derive(Debug, Clone)]
+pub struct DescriptionNormalizer {
+    rules: Vec<Regex>,
+}
+
+impl DescriptionNormalizer {
+    /// Compiles `rules`, rejecting invalid patterns.
+    pub fn new<I, S>(rules: I) -> ReconcileResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let rules = rules
+            .into_iter()
+            .map(|rule| {
+                Regex::new(rule.as_ref()).map_err(|err| {
+                    ReconcileError::InvalidConfiguration(format!(
+                        "invalid description rule {}: {err}",
+                        rule.as_ref()
+                    ))
+                })
+            })
+            .collect::<ReconcileResult<Vec<_>>>()?;
+        Ok(Self { rules })
+    }
+
+    #[must_use]
+    pub fn normalize(&self, description: &str) -> String {
+        let stripped = self
+            .rules
+            .iter()
+            .fold(description.to_string(), |current, rule| {
+                rule.replace_all(&current, " ").into_owned()
+            });
+        stripped.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+impl Default for DescriptionNormalizer {
+    fn default() -> Self {
+        Self {
+            rules: DEFAULT_DESCRIPTION_STRIP_RULES
+                .iter()
+                .filter_map(|rule| Regex::new(rule).ok())
+                .collect(),
+        }
+    }
+}
+
+/// Precision the configured amount tolerance is expressed in.
+const REFERENCE_CURRENCY_PRECISION: i32 = 2;
+
+#[derive(Debug, Clone)]
+pub struct WeightedScoringStrategy {
+    amount_weight: f32,
+    date_weight: f32,
+    description_weight: f32,
+    amount_tolerance_minor: i64,
+    date_tolerance_days: i64,
+    max_date_delta_days: Option<i64>,
+    tokenizer: Arc<dyn Tokenizer>,
+    description_normalizer: Option<Arc<DescriptionNormalizer>>,
+}
+
+impl WeightedScoringStrategy {
+    /// Lenient constructor: weights are used as given and divided by their
+    /// sum at score time, so all-zero weights score every proposal as 0.0.
+    /// Tolerances are clamped to at least 1. Prefer [`Self::try_new`] for
+    /// user-supplied configuration.
+    pub fn new(
+        amount_weight: f32,
+        date_weight: f32,
+        description_weight: f32,
+        amount_tolerance_minor: i64,
+        date_tolerance_days: i64,
+    ) -> Self {
+        Self {
+            amount_weight,
+            date_weight,
+            description_weight,
+            amount_tolerance_minor: amount_tolerance_minor.max(1),
+            date_tolerance_days: date_tolerance_days.max(1),
+            max_date_delta_days: None,
+            tokenizer: Arc::new(WhitespaceTokenizer),
+            description_normalizer: None,
+        }
+    }
+
+    /// Validating constructor that rejects negative, non-finite, or all-zero
+    /// weights.
+    pub fn try_new(
+        amount_weight: f32,
+        date_weight: f32,
+        description_weight: f32,
+        amount_tolerance_minor: i64,
+        date_tolerance_days: i64,
+    ) -> ReconcileResult<Self> {
+        for (name, weight) in [
+            ("amount", amount_weight),
+            ("date", date_weight),
+            ("description", description_weight),
+        ] {
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(ReconcileError::InvalidConfiguration(format!(
+                    "{name} weight must be a non-negative number, got {weight}"
+                )));
+            }
+        }
+        if amount_weight + date_weight + description_weight <= f32::EPSILON {
+            return Err(ReconcileError::InvalidConfiguration(
+                "at least one scoring weight must be positive".into(),
+            ));
+        }
+        Ok(Self::new(
+            amount_weight,
+            date_weight,
+            description_weight,
+            amount_tolerance_minor,
+            date_tolerance_days,
+        ))
+    }
+
+    /// Rescales the weights so they sum to 1.0. All-zero weights are left
+    /// unchanged.
+    #[must_use]
+    pub fn with_normalized_weights(mut self) -> Self {
+        let total = self.amount_weight + self.date_weight + self.description_weight;
+        if total > f32::EPSILON {
+            self.amount_weight /= total;
+            self.date_weight /= total;
+            self.description_weight /= total;
+        }
+        self
+    }
+
+    /// Hard cap on the date delta: proposals further apart than
+    /// `max_date_delta_days` score 0.0 whatever their amount and description.
+    #[must_use]
+    pub fn with_max_date_delta_days(mut self, max_date_delta_days: Option<i64>) -> Self {
+        self.max_date_delta_days = max_date_delta_days.map(i64::abs);
+        self
+    }
+
+    /// Tokenizer for the description component; defaults to
+    /// [`WhitespaceTokenizer`].
+    #[must_use]
+    pub fn with_tokenizer(mut self, tokenizer: Arc<dyn Tokenizer>) -> Self {
+        self.tokenizer = tokenizer;
+        self
+    }
+
+    /// Preprocesses both descriptions before the description component is
+    /// computed; unset by default.
+    #[must_use]
+    pub fn with_description_normalizer(mut self, normalizer: Arc<DescriptionNormalizer>) -> Self {
+        self.description_normalizer = Some(normalizer);
+        self
+    }
+
+    fn amount_component(&self, proposal: &MatchProposal) -> f32 {
+        if proposal.needs_fx_review() {
+            return 0.0;
+        }
+        self.normalize_amount(proposal.amount_delta_minor, proposal.currency_precision)
+    }
+
+    fn description_component(&self, proposal: &MatchProposal) -> f32 {
+        match &self.description_normalizer {
+            Some(normalizer) => description_similarity(
+                self.tokenizer.as_ref(),
+                &normalizer.normalize(&proposal.transaction_description),
+                &normalizer.normalize(&proposal.journal_description),
+            ),
+            None => description_similarity(
+                self.tokenizer.as_ref(),
+                &proposal.transaction_description,
+                &proposal.journal_description,
+            ),
+        }
+    }
+
+    fn exceeds_date_cap(&self, delta: i64) -> bool {
+        self.max_date_delta_days
+            .is_some_and(|max| delta.abs() > max)
+    }
+
+    /// The tolerance is defined for a two-decimal currency; other precisions
+    /// rescale it so the same major-unit tolerance applies.
+    fn amount_tolerance(&self, precision: Option<u8>) -> f32 {
+        let tolerance = self.amount_tolerance_minor as f32;
+        match precision {
+            Some(precision) => {
+                let exponent = i32::from(precision) - REFERENCE_CURRENCY_PRECISION;
+                let scaled = if exponent >= 0 {
+                    tolerance * 10_f32.powi(exponent)
+                } else {
+                    tolerance / 10_f32.powi(-exponent)
+                };
+                scaled.max(1.0)
+            }
+            None => tolerance,
+        }
+    }
+
+    fn normalize_amount(&self, delta: i64, precision: Option<u8>) -> f32 {
+        let ratio = (delta.abs() as f32) / self.amount_tolerance(precision);
+        (1.0 - ratio).clamp(0.0, 1.0)
+    }
+
+    fn normalize_date(&self, delta: i64) -> f32 {
+        let ratio = (delta.abs() as f32) / (self.date_tolerance_days as f32);
+        (1.0 - ratio).clamp(0.0, 1.0)
+    }
+
+    fn combine(&self, amount: f32, date: f32, description: f32) -> f32 {
+        let total_weight = self.amount_weight + self.date_weight + self.description_weight;
+        if total_weight <= f32::EPSILON {
+            return 0.0;
+        }
+        let weighted = amount * self.amount_weight
+            + date * self.date_weight
+            + description * self.description_weight;
+        (weighted / total_weight).clamp(0.0, 1.0)
+    }
+}
+
+impl Default for WeightedScoringStrategy {
+    fn default() -> Self {
+        WeightedScoringConfig::default().strategy()
+    }
+}
+
+/// Tokenizer selection for [`WeightedScoringConfig`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TokenizerConfig {
+    #[default]
+    Whitespace,
+    /// `stop_words` replaces the default English list when present.
+    Alphanumeric {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stop_words: Option<Vec<String>>,
+    },
+}
+
+impl TokenizerConfig {
+    fn tokenizer(&self) -> Arc<dyn Tokenizer> {
+        match self {
+            Self::Whitespace => Arc::new(WhitespaceTokenizer),
+            Self::Alphanumeric { stop_words: None } => Arc::new(AlphanumericTokenizer::new()),
+            Self::Alphanumeric {
+                stop_words: Some(stop_words),
+            } => Arc::new(AlphanumericTokenizer::new().with_stop_words(stop_words)),
+        }
+    }
+}
+
+/// Serializable settings for [`WeightedScoringStrategy`], e.g. loaded from a
+/// firm's JSON config at startup. Omitted fields take the default strategy's
+/// values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeightedScoringConfig {
+    pub amount_weight: f32,
+    pub date_weight: f32,
+    pub description_weight: f32,
+    pub amount_tolerance_minor: i64,
+    pub date_tolerance_days: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_date_delta_days: Option<i64>,
+    /// Rescale the weights to sum to 1.0 when building the strategy.
+    pub normalize_weights: bool,
+    pub tokenizer: TokenizerConfig,
+}
+
+impl Default for WeightedScoringConfig {
+    fn default() -> Self {
+        Self {
+            amount_weight: 0.45,
+            date_weight: 0.35,
+            description_weight: 0.20,
+            amount_tolerance_minor: 5_000,
+            date_tolerance_days: 7,
+            max_date_delta_days: None,
+            normalize_weights: false,
+            tokenizer: TokenizerConfig::default(),
+        }
+    }
+}
+
+impl WeightedScoringConfig {
+    /// Builds the strategy, validating weights as
+    /// [`WeightedScoringStrategy::try_new`] does.
+    pub fn build(&self) -> ReconcileResult<WeightedScoringStrategy> {
+        WeightedScoringStrategy::try_new(
+            self.amount_weight,
+            self.date_weight,
+            self.description_weight,
+            self.amount_tolerance_minor,
+            self.date_tolerance_days,
+        )
+        .map(|strategy| self.configure(strategy))
+    }
+
+    fn strategy(&self) -> WeightedScoringStrategy {
+        self.configure(WeightedScoringStrategy::new(
+            self.amount_weight,
+            self.date_weight,
+            self.description_weight,
+            self.amount_tolerance_minor,
+            self.date_tolerance_days,
+        ))
+    }
+
+    fn configure(&self, strategy: WeightedScoringStrategy) -> WeightedScoringStrategy {
+        let strategy = strategy
+            .with_max_date_delta_days(self.max_date_delta_days)
+            .with_tokenizer(self.tokenizer.tokenizer());
+        if self.normalize_weights {
+            strategy.with_normalized_weights()
+        } else {
+            strategy
+        }
+    }
+}
+
+impl ScoringStrategy for WeightedScoringStrategy {
+    fn score(&self, proposal: &MatchProposal) -> f32 {
+        if self.exceeds_date_cap(proposal.date_delta_days) {
+            return 0.0;
+        }
+        let amount_component = self.amount_component(proposal);
+        let date_component = self.normalize_date(proposal.date_delta_days);
+        let description_component = self.description_component(proposal);
+        self.combine(amount_component, date_component, description_component)
+    }
+
+    fn explain(&self, proposal: &MatchProposal) -> ScoreExplanation {
+        let amount_component = self.amount_component(proposal);
+        let date_component = self.normalize_date(proposal.date_delta_days);
+        let description_component = self.description_component(proposal);
+        let total_weight = self.amount_weight + self.date_weight + self.description_weight;
+        let component = |kind, weight: f32, normalized: f32| ScoreComponent {
+            kind,
+            weight,
+            normalized,
+            contribution: if total_weight <= f32::EPSILON {
+                0.0
+            } else {
+                normalized * weight / total_weight
+            },
+        };
+        let total = if self.exceeds_date_cap(proposal.date_delta_days) {
+            0.0
+        } else {
+            self.combine(amount_component, date_component, description_component)
+        };
+        ScoreExplanation {
+            total,
+            components: vec![
+                component(
+                    ScoreComponentKind::Amount,
+                    self.amount_weight,
+                    amount_component,
+                ),
+                component(ScoreComponentKind::Date, self.date_weight, date_component),
+                component(
+                    ScoreComponentKind::Description,
+                    self.description_weight,
+                    description_component,
+                ),
+            ],
+        }
+    }
+
+    fn normalize_description(&self, description: &str) -> Option<String> {
+        self.description_normalizer
+            .as_ref()
+            .map(|normalizer| normalizer.normalize(description))
+    }
+
+    /// Individual parts of a split rarely match the bank amount on their own, so
+    /// the amount component is taken from the summed group delta instead.
+    fn score_group(&self, proposals: &[MatchProposal]) -> f32 {
+        if proposals.is_empty()
+            || proposals
+                .iter()
+                .any(|proposal| self.exceeds_date_cap(proposal.date_delta_days))
+        {
+            return 0.0;
+        }
+        let count = proposals.len() as f32;
+        let summed_delta: i64 = proposals
+            .iter()
+            .map(|proposal| proposal.amount_delta_minor)
+            .sum();
+        let date_component = proposals
+            .iter()
+            .map(|proposal| self.normalize_date(proposal.date_delta_days))
+            .sum::<f32>()
+            / count;
+        let description_component = proposals
+            .iter()
+            .map(|proposal| self.description_component(proposal))
+            .sum::<f32>()
+            / count;
+        let amount_component = if proposals.iter().any(MatchProposal::needs_fx_review) {
+            0.0
+        } else {
+            self.normalize_amount(
+                summed_delta,
+                proposals
+                    .first()
+                    .and_then(|proposal| proposal.currency_precision),
+            )
+        };
+        self.combine(amount_component, date_component, description_component)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct LinearScoringStrategy {
+    inner: WeightedScoringStrategy,
+}
+
+impl LinearScoringStrategy {
+    pub fn new() -> Self {
+        Self {
+            inner: WeightedScoringStrategy::default(),
+        }
+    }
+}
+
+impl ScoringStrategy for LinearScoringStrategy {
+    fn score(&self, proposal: &MatchProposal) -> f32 {
+        self.inner.score(proposal)
+    }
+
+    fn explain(&self, proposal: &MatchProposal) -> ScoreExplanation {
+        self.inner.explain(proposal)
+    }
+
+    fn normalize_description(&self, description: &str) -> Option<String> {
+        self.inner.normalize_description(description)
+    }
+
+    fn score_group(&self, proposals: &[MatchProposal]) -> f32 {
+        self.inner.score_group(proposals)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReconciliationAuditAction {
+    SessionCreated,
+    CandidateAdded,
+    CandidateAccepted,
+    CandidateRejected,
+    CandidatePartiallyAccepted,
+    CandidateWrittenOff,
+    SessionReopened,
+    SessionAutoClosed,
+    CandidatesRescored,
+    WriteOffsReopened,
+    ActionUndone,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReconciliationAuditEvent {
+    pub session_id: SessionId,
+    pub candidate_id: Option<CandidateId>,
+    pub action: ReconciliationAuditAction,
+    pub note: Option<String>,
+}
+
+pub trait ReconciliationAuditHook: Send + Sync {
+    fn record(&self, event: &ReconciliationAuditEvent);
+}
+
+/// Async counterpart of `ReconciliationAuditHook` for integrations. The
+/// service dispatches on the ambient tokio runtime after the transition is
+/// saved; errors are logged and never undo the transition.
+#[async_trait]
+pub trait ReconciliationNotifier: Send + Sync {
+    async fn on_session_closed(&self, session: &ReconciliationSession) -> ReconcileResult<()>;
+
+    async fn on_candidate_accepted(
+        &self,
+        session: &ReconciliationSession,
+        candidate: &MatchCandidate,
+    ) -> ReconcileResult<()>;
+}
+
+enum ReconciliationNotification {
+    SessionClosed(ReconciliationSession),
+    CandidateAccepted {
+        session: ReconciliationSession,
+        candidate: Box<MatchCandidate>,
+    },
+}
+
+#[derive(Default)]
+pub struct NoopReconciliationAuditHook;
+
+impl ReconciliationAuditHook for NoopReconciliationAuditHook {
+    fn record(&self, _event: &ReconciliationAuditEvent) {}
+}
+
+/// An audit event as persisted by `JsonlReconciliationAuditHook`, stamped
+/// with the time it was recorded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReconciliationAuditRecord {
+    pub recorded_at: DateTime<Utc>,
+    pub session_id: SessionId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub candidate_id: Option<CandidateId>,
+    pub action: ReconciliationAuditAction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Appends every audit event as one JSON line to a file so the trail
+/// outlives the process.
+pub struct JsonlReconciliationAuditHook {
+    path: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl JsonlReconciliationAuditHook {
+    pub fn new(path: impl Into<PathBuf>) -> ReconcileResult<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| {
+                ReconcileError::Storage(format!(
+                    "failed to create audit directory {}: {err}",
+                    parent.display()
+                ))
+            })?;
+        }
+        Ok(Self {
+            path,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Like `record`, but reports write failures instead of dropping them.
+    pub fn append(&self, event: &ReconciliationAuditEvent) -> ReconcileResult<()> {
+        let record = ReconciliationAuditRecord {
+            recorded_at: Utc::now(),
+            session_id: event.session_id.clone(),
+            candidate_id: event.candidate_id.clone(),
+            action: event.action.clone(),
+            note: event.note.clone(),
+        };
+        let mut line = serde_json::to_vec(&record).map_err(|err| {
+            ReconcileError::Storage(format!("failed to encode audit record: {err}"))
+        })?;
+        line.push(b'\n');
+        let _guard = self
+            .write_lock
+            .lock()
+            .map_err(|_| ReconcileError::Storage("audit log poisoned".into()))?;
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(&line))
+            .map_err(|err| {
+                ReconcileError::Storage(format!("failed to write {}: {err}", self.path.display()))
+            })
+    }
+
+    /// Reads persisted records in the order they were written, optionally
+    /// limited to one session. A missing file yields no records.
+    pub fn records(
+        &self,
+        session_id: Option<&SessionId>,
+    ) -> ReconcileResult<Vec<ReconciliationAuditRecord>> {
+        let data = match fs::read_to_string(&self.path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => {
+                return Err(ReconcileError::Storage(format!(
+                    "failed to read {}: {err}",
+                    self.path.display()
+                )));
+            }
+        };
+        let mut records = Vec::new();
+        for line in data.lines().filter(|line| !line.trim().is_empty()) {
+            let record: ReconciliationAuditRecord = serde_json::from_str(line).map_err(|err| {
+                ReconcileError::Storage(format!("failed to parse {}: {err}", self.path.display()))
+            })?;
+            if session_id.is_none_or(|id| *id == record.session_id) {
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+}
+
+impl ReconciliationAuditHook for JsonlReconciliationAuditHook {
+    /// Write failures are dropped so auditing never blocks reconciliation;
+    /// call `append` directly to observe them.
+    fn record(&self, event: &ReconciliationAuditEvent) {
+        let _ = self.append(event);
+    }
+}
+
+pub trait ReconciliationStore: Send + Sync {
+    fn create_session(
+        &self,
+        session: ReconciliationSession,
+    ) -> ReconcileResult<ReconciliationSession>;
+    fn save_session(&self, session: &ReconciliationSession) -> ReconcileResult<()>;
+    fn get_session(&self, session_id: &SessionId) -> ReconcileResult<ReconciliationSession>;
+    fn list_sessions(&self) -> ReconcileResult<Vec<ReconciliationSession>>;
+    /// Sessions belonging to `company_id`, in `list_sessions` order. The
+    /// default filters `list_sessions`; stores with a company index should
+    /// override it.
+    fn list_sessions_for_company(
+        &self,
+        company_id: &str,
+    ) -> ReconcileResult<Vec<ReconciliationSession>> {
+        Ok(self
+            .list_sessions()?
+            .into_iter()
+            .filter(|session| session.company_id == company_id)
+            .collect())
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryReconciliationStore {
+    sessions: RwLock<HashMap<SessionId, ReconciliationSession>>,
+}
+
+impl InMemoryReconciliationStore {
     pub fn new() -> Self {
         Self::default()
     }
@@ -398,6 +1663,149 @@ impl ReconciliationStore for InMemoryReconciliationStore {
             .cloned()
             .ok_or_else(|| ReconcileError::SessionNotFound(session_id.clone()))
     }
+
+    fn list_sessions(&self) -> ReconcileResult<Vec<ReconciliationSession>> {
+        let guard = self
+            .sessions
+            .read()
+            .map_err(|_| ReconcileError::Storage("session store poisoned".into()))?;
+        let mut sessions = guard.values().cloned().collect::<Vec<_>>();
+        sessions.sort_by(|a, b| a.opened_at.cmp(&b.opened_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(sessions)
+    }
+}
+
+/// Stores each session as `<session id>.json` under a directory, giving
+/// single-node durability without a database.
+pub struct JsonFileReconciliationStore {
+    root: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl JsonFileReconciliationStore {
+    pub fn new(root: impl Into<PathBuf>) -> ReconcileResult<Self> {
+        let root = root.into();
+        fs::create_dir_all(&root).map_err(|err| {
+            ReconcileError::Storage(format!(
+                "failed to create session directory {}: {err}",
+                root.display()
+            ))
+        })?;
+        Ok(Self {
+            root,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    fn session_path(&self, session_id: &SessionId) -> ReconcileResult<PathBuf> {
+        if session_id.is_empty()
+            || !session_id
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        {
+            return Err(ReconcileError::Storage(format!(
+                "session id {session_id} cannot be used as a file name"
+            )));
+        }
+        Ok(self.root.join(format!("{session_id}.json")))
+    }
+
+    fn with_write<F, T>(&self, f: F) -> ReconcileResult<T>
+    where
+        F: FnOnce() -> ReconcileResult<T>,
+    {
+        let _guard = self
+            .write_lock
+            .lock()
+            .map_err(|_| ReconcileError::Storage("session store poisoned".into()))?;
+        f()
+    }
+
+    fn write_session(&self, path: &Path, session: &ReconciliationSession) -> ReconcileResult<()> {
+        let data = serde_json::to_vec_pretty(session).map_err(|err| {
+            ReconcileError::Storage(format!("failed to encode session {}: {err}", session.id))
+        })?;
+        let staging = path.with_extension("json.tmp");
+        fs::write(&staging, data)
+            .and_then(|()| fs::rename(&staging, path))
+            .map_err(|err| {
+                ReconcileError::Storage(format!("failed to write {}: {err}", path.display()))
+            })
+    }
+}
+
+impl ReconciliationStore for JsonFileReconciliationStore {
+    fn create_session(
+        &self,
+        session: ReconciliationSession,
+    ) -> ReconcileResult<ReconciliationSession> {
+        let path = self.session_path(&session.id)?;
+        self.with_write(|| {
+            if path.exists() {
+                return Err(ReconcileError::SessionAlreadyExists(session.id));
+            }
+            self.write_session(&path, &session)?;
+            Ok(session)
+        })
+    }
+
+    fn save_session(&self, session: &ReconciliationSession) -> ReconcileResult<()> {
+        let path = self.session_path(&session.id)?;
+        self.with_write(|| {
+            if !path.exists() {
+                return Err(ReconcileError::SessionNotFound(session.id.clone()));
+            }
+            self.write_session(&path, session)
+        })
+    }
+
+    fn get_session(&self, session_id: &SessionId) -> ReconcileResult<ReconciliationSession> {
+        let path = self.session_path(session_id)?;
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                return Err(ReconcileError::SessionNotFound(session_id.clone()));
+            }
+            Err(err) => {
+                return Err(ReconcileError::Storage(format!(
+                    "failed to read {}: {err}",
+                    path.display()
+                )));
+            }
+        };
+        serde_json::from_slice(&data).map_err(|err| {
+            ReconcileError::Storage(format!("failed to parse {}: {err}", path.display()))
+        })
+    }
+
+    fn list_sessions(&self) -> ReconcileResult<Vec<ReconciliationSession>> {
+        let entries = fs::read_dir(&self.root).map_err(|err| {
+            ReconcileError::Storage(format!("failed to read {}: {err}", self.root.display()))
+        })?;
+        let mut sessions = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|err| {
+                    ReconcileError::Storage(format!(
+                        "failed to read {}: {err}",
+                        self.root.display()
+                    ))
+                })?
+                .path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let data = fs::read(&path).map_err(|err| {
+                ReconcileError::Storage(format!("failed to read {}: {err}", path.display()))
+            })?;
+            let session: ReconciliationSession = serde_json::from_slice(&data).map_err(|err| {
+                ReconcileError::Storage(format!("failed to parse {}: {err}", path.display()))
+            })?;
+            sessions.push(session);
+        }
+        sessions.sort_by(|a, b| a.opened_at.cmp(&b.opened_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(sessions)
+    }
 }
 
 #[cfg(feature = "postgres-store")]
@@ -441,6 +1849,46 @@ impl ReconciliationStore for PostgresReconciliationStore {
             "postgres reconciliation store not yet implemented".into(),
         ))
     }
+
+    fn list_sessions(&self) -> ReconcileResult<Vec<ReconciliationSession>> {
+        let _ = &self.connection_string;
+        Err(ReconcileError::Storage(
+            "postgres reconciliation store not yet implemented".into(),
+        ))
+    }
+}
+
+/// Self-contained snapshot of one session for external review. Candidates
+/// (with their scores and statuses) travel inside `session`; `audit_events`
+/// is empty unless the service was built `with_audit_trail`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionReport {
+    pub generated_at: DateTime<Utc>,
+    pub session: ReconciliationSession,
+    pub coverage: Coverage,
+    pub audit_events: Vec<ReconciliationAuditRecord>,
+}
+
+/// `(label, min, max)` of the buckets `aging` reports: candidates at least
+/// `min` and under `max` days old; `None` leaves the last bucket open-ended.
+const AGING_BUCKETS: &[(&str, i64, Option<i64>)] = &[
+    ("<1d", 0, Some(1)),
+    ("1-7d", 1, Some(7)),
+    ("7-30d", 7, Some(30)),
+    (">30d", 30, None),
+];
+
+/// Pending candidates whose age since `proposed_at` is at least
+/// `min_age_days` and below `max_age_days` (unbounded when `None`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgingBucket {
+    pub label: String,
+    pub min_age_days: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<i64>,
+    pub count: usize,
+    /// Oldest first.
+    pub candidate_ids: Vec<CandidateId>,
 }
 
 pub trait ReconciliationService: Send + Sync {
@@ -455,32 +1903,87 @@ pub trait ReconciliationService: Send + Sync {
         session_id: &SessionId,
         candidate_id: &CandidateId,
     ) -> ReconcileResult<MatchCandidate>;
+    /// Records a pre-matched pair (e.g. a bank reference naming the invoice)
+    /// as an accepted candidate with score `1.0`, bypassing the scoring
+    /// strategy and score floor. The session stays open for other lines.
+    fn add_exact_match(
+        &self,
+        session_id: &SessionId,
+        transaction_id: &str,
+        journal_entry_id: &str,
+    ) -> ReconcileResult<MatchCandidate>;
     fn reject(
         &self,
         session_id: &SessionId,
         candidate_id: &CandidateId,
     ) -> ReconcileResult<MatchCandidate>;
+    /// Rejects the candidate and records `reason` on it and in the audit note.
+    fn reject_with_reason(
+        &self,
+        session_id: &SessionId,
+        candidate_id: &CandidateId,
+        reason: String,
+    ) -> ReconcileResult<MatchCandidate>;
     fn accept_partial(
         &self,
         session_id: &SessionId,
         group_id: &str,
         candidate_ids: Vec<CandidateId>,
     ) -> ReconcileResult<Vec<MatchCandidate>>;
+    /// Writes off the candidate's residual. Residuals above the service's
+    /// write-off threshold require an `approval_reference`.
     fn write_off(
         &self,
         session_id: &SessionId,
         candidate_id: &CandidateId,
         reason: String,
+        approval_reference: Option<String>,
     ) -> ReconcileResult<MatchCandidate>;
     fn reopen(&self, session_id: &SessionId) -> ReconcileResult<ReconciliationSession>;
+    /// Reverts the most recent accept, reject, partial accept or write-off.
+    /// Errors once the history is exhausted; reopening or auto-closing a
+    /// session clears it.
+    fn undo_last(&self, session_id: &SessionId) -> ReconcileResult<ReconciliationSession>;
+    /// Returns only `WrittenOff` candidates to `Pending`; accepted and
+    /// rejected candidates keep their status.
+    fn reopen_write_offs(&self, session_id: &SessionId) -> ReconcileResult<ReconciliationSession>;
+    /// Recomputes scores for pending candidates with the service's current
+    /// strategy, e.g. after scoring weights change.
+    fn rescore_session(&self, session_id: &SessionId) -> ReconcileResult<ReconciliationSession>;
     fn session(&self, session_id: &SessionId) -> ReconcileResult<ReconciliationSession>;
+    fn coverage(&self, session_id: &SessionId) -> ReconcileResult<Coverage>;
+    /// The pending candidate to present first; see
+    /// [`ReconciliationSession::best_candidate`].
+    fn best_candidate(&self, session_id: &SessionId) -> ReconcileResult<Option<MatchCandidate>>;
+    /// See [`ReconciliationSession::score_histogram`].
+    fn score_histogram(
+        &self,
+        session_id: &SessionId,
+        buckets: usize,
+    ) -> ReconcileResult<Vec<(f32, usize)>>;
+    /// Bundles the session, its coverage, and its persisted audit records.
+    fn export_session(&self, session_id: &SessionId) -> ReconcileResult<SessionReport>;
+    /// Closes `Open`/`PendingPartial` sessions opened before `older_than` and
+    /// returns their ids.
+    fn sweep_stale(&self, older_than: DateTime<Utc>) -> ReconcileResult<Vec<SessionId>>;
+    /// Buckets the pending candidates of the company's non-closed sessions by
+    /// age at `now` (<1d, 1-7d, 7-30d, >30d). Every bucket is returned, empty
+    /// or not.
+    fn aging(&self, company_id: &str, now: DateTime<Utc>) -> ReconcileResult<Vec<AgingBucket>>;
     fn register_audit_hook(&self, hook: Arc<dyn ReconciliationAuditHook>);
+    fn register_notifier(&self, notifier: Arc<dyn ReconciliationNotifier>);
 }
 
 pub struct InMemoryReconciliationService {
     scoring: Arc<dyn ScoringStrategy>,
     store: Arc<dyn ReconciliationStore>,
     audit_hooks: RwLock<Vec<Arc<dyn ReconciliationAuditHook>>>,
+    audit_trail: Option<Arc<JsonlReconciliationAuditHook>>,
+    notifiers: RwLock<Vec<Arc<dyn ReconciliationNotifier>>>,
+    max_write_off_minor: Option<i64>,
+    candidate_score_floor: f32,
+    session_id_prefix: Option<String>,
+    sessions_created: AtomicU64,
 }
 
 impl InMemoryReconciliationService {
@@ -496,9 +1999,59 @@ impl InMemoryReconciliationService {
             scoring,
             store,
             audit_hooks: RwLock::new(Vec::new()),
+            audit_trail: None,
+            notifiers: RwLock::new(Vec::new()),
+            max_write_off_minor: None,
+            candidate_score_floor: 0.0,
+            session_id_prefix: None,
+            sessions_created: AtomicU64::new(0),
+        }
+    }
+
+    /// Caps the residual that may be written off without an approval reference.
+    #[must_use]
+    pub fn with_max_write_off_minor(mut self, max_write_off_minor: Option<i64>) -> Self {
+        self.max_write_off_minor = max_write_off_minor.map(i64::abs);
+        self
+    }
+
+    /// Proposals scoring below `floor` are refused by `add_candidate`. The
+    /// default of `0.0` keeps every candidate.
+    #[must_use]
+    pub fn with_score_floor(mut self, floor: f32) -> Self {
+        self.candidate_score_floor = floor;
+        self
+    }
+
+    /// Issues session ids `<prefix>-1`, `<prefix>-2`, ... in creation order
+    /// instead of random UUIDs, for reproducible demos and fixtures.
+    #[must_use]
+    pub fn with_session_id_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.session_id_prefix = Some(prefix.into());
+        self
+    }
+
+    fn next_session_id(&self) -> SessionId {
+        match &self.session_id_prefix {
+            Some(prefix) => {
+                let sequence = self.sessions_created.fetch_add(1, Ordering::Relaxed) + 1;
+                format!("{prefix}-{sequence}")
+            }
+            None => Uuid::new_v4().to_string(),
         }
     }
 
+    /// Registers `trail` as an audit hook and reads it back in
+    /// `export_session`.
+    #[must_use]
+    pub fn with_audit_trail(mut self, trail: Arc<JsonlReconciliationAuditHook>) -> Self {
+        if let Ok(hooks) = self.audit_hooks.get_mut() {
+            hooks.push(trail.clone());
+        }
+        self.audit_trail = Some(trail);
+        self
+    }
+
     fn emit_audit(&self, event: ReconciliationAuditEvent) {
         if let Ok(hooks) = self.audit_hooks.read() {
             for hook in hooks.iter() {
@@ -507,6 +2060,55 @@ impl InMemoryReconciliationService {
         }
     }
 
+    fn notify(&self, notifications: Vec<ReconciliationNotification>) {
+        let notifiers = match self.notifiers.read() {
+            Ok(notifiers) if !notifiers.is_empty() => notifiers.clone(),
+            _ => return,
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            warn!("no tokio runtime available; dropping reconciliation notifications");
+            return;
+        };
+        runtime.spawn(async move {
+            for notification in &notifications {
+                for notifier in &notifiers {
+                    let result = match notification {
+                        ReconciliationNotification::SessionClosed(session) => {
+                            notifier.on_session_closed(session).await
+                        }
+                        ReconciliationNotification::CandidateAccepted { session, candidate } => {
+                            notifier.on_candidate_accepted(session, candidate).await
+                        }
+                    };
+                    if let Err(err) = result {
+                        warn!(error = %err, "reconciliation notifier failed");
+                    }
+                }
+            }
+        });
+    }
+
+    fn reject_candidate(
+        &self,
+        session_id: &SessionId,
+        candidate_id: &CandidateId,
+        reason: Option<String>,
+    ) -> ReconcileResult<MatchCandidate> {
+        let note = reason.clone();
+        let (_, rejected) = self.modify_undoable(
+            session_id,
+            ReconciliationAuditAction::CandidateRejected,
+            |session| session.reject(candidate_id, reason),
+        )?;
+        self.emit_audit(ReconciliationAuditEvent {
+            session_id: session_id.clone(),
+            candidate_id: Some(candidate_id.clone()),
+            action: ReconciliationAuditAction::CandidateRejected,
+            note,
+        });
+        Ok(rejected)
+    }
+
     fn modify_session<F, T>(
         &self,
         session_id: &SessionId,
@@ -521,6 +2123,26 @@ impl InMemoryReconciliationService {
         Ok((session, result))
     }
 
+    /// Like `modify_session`, recording what the transition replaced so
+    /// `undo_last` can restore it.
+    fn modify_undoable<F, T>(
+        &self,
+        session_id: &SessionId,
+        action: ReconciliationAuditAction,
+        mutator: F,
+    ) -> ReconcileResult<(ReconciliationSession, T)>
+    where
+        F: FnOnce(&mut ReconciliationSession) -> ReconcileResult<T>,
+    {
+        self.modify_session(session_id, |session| {
+            let previous_status = session.status;
+            let previous_candidates = session.candidates.clone();
+            let result = mutator(session)?;
+            session.record_undo(action, previous_status, previous_candidates);
+            Ok(result)
+        })
+    }
+
     fn update_session<F>(
         &self,
         session_id: &SessionId,
@@ -540,11 +2162,12 @@ impl InMemoryReconciliationService {
 impl ReconciliationService for InMemoryReconciliationService {
     fn create_session(&self, company_id: &str) -> ReconcileResult<ReconciliationSession> {
         let session = ReconciliationSession {
-            id: Uuid::new_v4().to_string(),
+            id: self.next_session_id(),
             company_id: company_id.into(),
             status: SessionStatus::Open,
             opened_at: Utc::now(),
             candidates: Vec::new(),
+            undo_stack: Vec::new(),
         };
         let stored = self.store.create_session(session)?;
         self.emit_audit(ReconciliationAuditEvent {
@@ -561,9 +2184,26 @@ impl ReconciliationService for InMemoryReconciliationService {
         session_id: &SessionId,
         proposal: MatchProposal,
     ) -> ReconcileResult<MatchCandidate> {
-        let score = self.scoring.score(&proposal);
+        let explanation = self.scoring.explain(&proposal);
+        let score = explanation.total;
+        if score < self.candidate_score_floor {
+            return Err(ReconcileError::BelowScoreFloor {
+                score,
+                floor: self.candidate_score_floor,
+            });
+        }
+        let score_breakdown = (!explanation.components.is_empty()).then_some(explanation);
+        let normalized_transaction_description = self
+            .scoring
+            .normalize_description(&proposal.transaction_description);
+        let normalized_journal_description = self
+            .scoring
+            .normalize_description(&proposal.journal_description);
+        let needs_fx_review = proposal.needs_fx_review();
         let candidate = MatchCandidate {
-            id: Uuid::new_v4().to_string(),
+            id: proposal
+                .proposed_candidate_id
+                .unwrap_or_else(|| Uuid::new_v4().to_string()),
             transaction_id: proposal.transaction_id,
             journal_entry_id: proposal.journal_entry_id,
             proposed_at: Utc::now(),
@@ -571,6 +2211,21 @@ impl ReconciliationService for InMemoryReconciliationService {
             status: CandidateStatus::Pending,
             group_id: proposal.group_id,
             write_off_reason: None,
+            residual_minor: proposal.amount_delta_minor,
+            write_off_approval_reference: None,
+            score_breakdown,
+            rejection_reason: None,
+            date_delta_days: proposal.date_delta_days,
+            transaction_description: proposal.transaction_description,
+            journal_description: proposal.journal_description,
+            currency: proposal.currency,
+            currency_precision: proposal.currency_precision,
+            normalized_transaction_description,
+            normalized_journal_description,
+            transaction_currency: proposal.transaction_currency,
+            journal_currency: proposal.journal_currency,
+            fx_rate: proposal.fx_rate,
+            needs_fx_review,
         };
         self.update_session(session_id, |session| {
             session.add_candidate(candidate.clone())
@@ -589,31 +2244,91 @@ impl ReconciliationService for InMemoryReconciliationService {
         session_id: &SessionId,
         candidate_id: &CandidateId,
     ) -> ReconcileResult<MatchCandidate> {
-        let (_, accepted) =
-            self.modify_session(session_id, |session| session.accept(candidate_id))?;
+        let (session, accepted) = self.modify_undoable(
+            session_id,
+            ReconciliationAuditAction::CandidateAccepted,
+            |session| session.accept(candidate_id),
+        )?;
         self.emit_audit(ReconciliationAuditEvent {
             session_id: session_id.clone(),
             candidate_id: Some(candidate_id.clone()),
             action: ReconciliationAuditAction::CandidateAccepted,
             note: None,
         });
+        self.notify(vec![
+            ReconciliationNotification::CandidateAccepted {
+                session: session.clone(),
+                candidate: Box::new(accepted.clone()),
+            },
+            ReconciliationNotification::SessionClosed(session),
+        ]);
         Ok(accepted)
     }
 
-    fn reject(
+    fn add_exact_match(
         &self,
         session_id: &SessionId,
-        candidate_id: &CandidateId,
+        transaction_id: &str,
+        journal_entry_id: &str,
     ) -> ReconcileResult<MatchCandidate> {
-        let (_, rejected) =
-            self.modify_session(session_id, |session| session.reject(candidate_id))?;
+        let candidate = MatchCandidate {
+            id: Uuid::new_v4().to_string(),
+            transaction_id: transaction_id.into(),
+            journal_entry_id: journal_entry_id.into(),
+            proposed_at: Utc::now(),
+            score: 1.0,
+            status: CandidateStatus::Accepted,
+            group_id: None,
+            write_off_reason: None,
+            residual_minor: 0,
+            write_off_approval_reference: None,
+            score_breakdown: None,
+            rejection_reason: None,
+            date_delta_days: 0,
+            transaction_description: String::new(),
+            journal_description: String::new(),
+            currency: None,
+            currency_precision: None,
+            normalized_transaction_description: None,
+            normalized_journal_description: None,
+            transaction_currency: None,
+            journal_currency: None,
+            fx_rate: None,
+            needs_fx_review: false,
+        };
+        let (session, ()) = self.modify_undoable(
+            session_id,
+            ReconciliationAuditAction::CandidateAccepted,
+            |session| session.add_exact_match(candidate.clone()),
+        )?;
         self.emit_audit(ReconciliationAuditEvent {
             session_id: session_id.clone(),
-            candidate_id: Some(candidate_id.clone()),
-            action: ReconciliationAuditAction::CandidateRejected,
-            note: None,
+            candidate_id: Some(candidate.id.clone()),
+            action: ReconciliationAuditAction::CandidateAccepted,
+            note: Some("exact reference match".into()),
         });
-        Ok(rejected)
+        self.notify(vec![ReconciliationNotification::CandidateAccepted {
+            session,
+            candidate: Box::new(candidate.clone()),
+        }]);
+        Ok(candidate)
+    }
+
+    fn reject(
+        &self,
+        session_id: &SessionId,
+        candidate_id: &CandidateId,
+    ) -> ReconcileResult<MatchCandidate> {
+        self.reject_candidate(session_id, candidate_id, None)
+    }
+
+    fn reject_with_reason(
+        &self,
+        session_id: &SessionId,
+        candidate_id: &CandidateId,
+        reason: String,
+    ) -> ReconcileResult<MatchCandidate> {
+        self.reject_candidate(session_id, candidate_id, Some(reason))
     }
 
     fn accept_partial(
@@ -622,9 +2337,11 @@ impl ReconciliationService for InMemoryReconciliationService {
         group_id: &str,
         candidate_ids: Vec<CandidateId>,
     ) -> ReconcileResult<Vec<MatchCandidate>> {
-        let (_, updated) = self.modify_session(session_id, |session| {
-            session.partial_accept(group_id, &candidate_ids)
-        })?;
+        let (_, updated) = self.modify_undoable(
+            session_id,
+            ReconciliationAuditAction::CandidatePartiallyAccepted,
+            |session| session.partial_accept(group_id, &candidate_ids),
+        )?;
         self.emit_audit(ReconciliationAuditEvent {
             session_id: session_id.clone(),
             candidate_id: candidate_ids.first().cloned(),
@@ -639,27 +2356,87 @@ impl ReconciliationService for InMemoryReconciliationService {
         session_id: &SessionId,
         candidate_id: &CandidateId,
         reason: String,
+        approval_reference: Option<String>,
     ) -> ReconcileResult<MatchCandidate> {
         let reason_clone = reason.clone();
-        let (_, written_off) = self.modify_session(session_id, |session| {
-            session.write_off(candidate_id, reason_clone.clone())
-        })?;
+        let max_write_off_minor = self.max_write_off_minor;
+        let (_, written_off) = self.modify_undoable(
+            session_id,
+            ReconciliationAuditAction::CandidateWrittenOff,
+            |session| {
+                session.write_off(
+                    candidate_id,
+                    reason_clone,
+                    approval_reference,
+                    max_write_off_minor,
+                )
+            },
+        )?;
+        self.emit_audit(ReconciliationAuditEvent {
+            session_id: session_id.clone(),
+            candidate_id: Some(candidate_id.clone()),
+            action: ReconciliationAuditAction::CandidateWrittenOff,
+            note: Some(reason),
+        });
+        Ok(written_off)
+    }
+
+    fn reopen(&self, session_id: &SessionId) -> ReconcileResult<ReconciliationSession> {
+        let session = self.update_session(session_id, ReconciliationSession::reopen)?;
+        self.emit_audit(ReconciliationAuditEvent {
+            session_id: session_id.clone(),
+            candidate_id: None,
+            action: ReconciliationAuditAction::SessionReopened,
+            note: None,
+        });
+        Ok(session)
+    }
+
+    fn undo_last(&self, session_id: &SessionId) -> ReconcileResult<ReconciliationSession> {
+        let (session, entry) = self.modify_session(session_id, ReconciliationSession::undo_last)?;
         self.emit_audit(ReconciliationAuditEvent {
             session_id: session_id.clone(),
-            candidate_id: Some(candidate_id.clone()),
-            action: ReconciliationAuditAction::CandidateWrittenOff,
-            note: Some(reason),
+            candidate_id: entry
+                .previous_candidates
+                .first()
+                .map(|candidate| candidate.id.clone()),
+            action: ReconciliationAuditAction::ActionUndone,
+            note: Some(format!("undid {:?}", entry.action)),
         });
-        Ok(written_off)
+        Ok(session)
     }
 
-    fn reopen(&self, session_id: &SessionId) -> ReconcileResult<ReconciliationSession> {
-        let session = self.update_session(session_id, ReconciliationSession::reopen)?;
+    fn reopen_write_offs(&self, session_id: &SessionId) -> ReconcileResult<ReconciliationSession> {
+        let (session, reopened) =
+            self.modify_session(session_id, |session| Ok(session.reopen_write_offs()))?;
         self.emit_audit(ReconciliationAuditEvent {
             session_id: session_id.clone(),
             candidate_id: None,
-            action: ReconciliationAuditAction::SessionReopened,
-            note: None,
+            action: ReconciliationAuditAction::WriteOffsReopened,
+            note: Some(format!("reopened {reopened} write-off(s)")),
+        });
+        Ok(session)
+    }
+
+    fn rescore_session(&self, session_id: &SessionId) -> ReconcileResult<ReconciliationSession> {
+        let scoring = Arc::clone(&self.scoring);
+        let (session, changes) =
+            self.modify_session(session_id, |session| session.rescore(scoring.as_ref()))?;
+        let summary = if changes.is_empty() {
+            "no pending candidate scores changed".to_string()
+        } else {
+            let details = changes
+                .iter()
+                .map(|(id, previous, current)| format!("{id}: {previous:.3} -> {current:.3}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("rescored {} candidate(s): {details}", changes.len())
+        };
+        self.emit_audit(ReconciliationAuditEvent {
+            session_id: session_id.clone(),
+            candidate_id: None,
+            action: ReconciliationAuditAction::CandidatesRescored,
+            note: Some(summary),
         });
         Ok(session)
     }
@@ -668,20 +2445,122 @@ impl ReconciliationService for InMemoryReconciliationService {
         self.store.get_session(session_id)
     }
 
+    fn coverage(&self, session_id: &SessionId) -> ReconcileResult<Coverage> {
+        self.store
+            .get_session(session_id)
+            .map(|session| session.coverage())
+    }
+
+    fn best_candidate(&self, session_id: &SessionId) -> ReconcileResult<Option<MatchCandidate>> {
+        self.store
+            .get_session(session_id)
+            .map(|session| session.best_candidate().cloned())
+    }
+
+    fn score_histogram(
+        &self,
+        session_id: &SessionId,
+        buckets: usize,
+    ) -> ReconcileResult<Vec<(f32, usize)>> {
+        self.store
+            .get_session(session_id)
+            .map(|session| session.score_histogram(buckets))
+    }
+
+    fn export_session(&self, session_id: &SessionId) -> ReconcileResult<SessionReport> {
+        let mut session = self.store.get_session(session_id)?;
+        // Undo snapshots are reviewer-internal state, not part of the report.
+        session.undo_stack.clear();
+        let audit_events = match &self.audit_trail {
+            Some(trail) => trail.records(Some(session_id))?,
+            None => Vec::new(),
+        };
+        Ok(SessionReport {
+            generated_at: Utc::now(),
+            coverage: session.coverage(),
+            session,
+            audit_events,
+        })
+    }
+
+    fn sweep_stale(&self, older_than: DateTime<Utc>) -> ReconcileResult<Vec<SessionId>> {
+        let mut closed = Vec::new();
+        let mut notifications = Vec::new();
+        for mut session in self.store.list_sessions()? {
+            if matches!(session.status, SessionStatus::Closed) || session.opened_at >= older_than {
+                continue;
+            }
+            session.status = SessionStatus::Closed;
+            session.undo_stack.clear();
+            self.store.save_session(&session)?;
+            self.emit_audit(ReconciliationAuditEvent {
+                session_id: session.id.clone(),
+                candidate_id: None,
+                action: ReconciliationAuditAction::SessionAutoClosed,
+                note: Some(format!("opened before {}", older_than.to_rfc3339())),
+            });
+            closed.push(session.id.clone());
+            notifications.push(ReconciliationNotification::SessionClosed(session));
+        }
+        self.notify(notifications);
+        Ok(closed)
+    }
+
+    fn aging(&self, company_id: &str, now: DateTime<Utc>) -> ReconcileResult<Vec<AgingBucket>> {
+        let mut pending = self
+            .store
+            .list_sessions_for_company(company_id)?
+            .into_iter()
+            .filter(|session| !matches!(session.status, SessionStatus::Closed))
+            .flat_map(|session| session.candidates)
+            .filter(|candidate| candidate.status == CandidateStatus::Pending)
+            .collect::<Vec<_>>();
+        pending.sort_by(|a, b| {
+            a.proposed_at
+                .cmp(&b.proposed_at)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        Ok(AGING_BUCKETS
+            .iter()
+            .map(|&(label, min_age_days, max_age_days)| {
+                let candidate_ids = pending
+                    .iter()
+                    .filter(|candidate| {
+                        let age = now - candidate.proposed_at;
+                        let above_min =
+                            min_age_days == 0 || age >= chrono::Duration::days(min_age_days);
+                        above_min
+                            && max_age_days.is_none_or(|max| age < chrono::Duration::days(max))
+                    })
+                    .map(|candidate| candidate.id.clone())
+                    .collect::<Vec<_>>();
+                AgingBucket {
+                    label: label.to_string(),
+                    min_age_days,
+                    max_age_days,
+                    count: candidate_ids.len(),
+                    candidate_ids,
+                }
+            })
+            .collect())
+    }
+
     fn register_audit_hook(&self, hook: Arc<dyn ReconciliationAuditHook>) {
         if let Ok(mut hooks) = self.audit_hooks.write() {
             hooks.push(hook);
         }
     }
+
+    fn register_notifier(&self, notifier: Arc<dyn ReconciliationNotifier>) {
+        if let Ok(mut notifiers) = self.notifiers.write() {
+            notifiers.push(notifier);
+        }
+    }
 }
 
-fn description_similarity(left: &str, right: &str) -> f32 {
-    let tokenize = |input: &str| -> BTreeSet<String> {
-        input
-            .split_whitespace()
-            .map(str::to_ascii_lowercase)
-            .collect()
-    };
+fn description_similarity(tokenizer: &dyn Tokenizer, left: &str, right: &str) -> f32 {
+    let tokenize =
+        |input: &str| -> BTreeSet<String> { tokenizer.tokenize(input).into_iter().collect() };
     let left_tokens = tokenize(left);
     let right_tokens = tokenize(right);
     if left_tokens.is_empty() || right_tokens.is_empty() {
@@ -720,9 +2599,23 @@ mod tests {
             transaction_description: transaction_description.into(),
             journal_description: journal_description.into(),
             group_id: group_id.map(ToString::to_string),
+            currency: None,
+            currency_precision: None,
+            proposed_candidate_id: None,
+            ..MatchProposal::default()
         }
     }
 
+    #[test]
+    fn session_id_prefix_issues_sequential_ids() {
+        let service = InMemoryReconciliationService::new(Arc::new(LinearScoringStrategy::new()))
+            .with_session_id_prefix("demo");
+        let first = service.create_session("co-1").expect("first session");
+        let second = service.create_session("co-1").expect("second session");
+        assert_eq!(first.id, "demo-1");
+        assert_eq!(second.id, "demo-2");
+    }
+
     #[test]
     fn create_session_initializes_state() {
         let service = service();
@@ -751,6 +2644,27 @@ mod tests {
         assert_eq!(fetched.candidates.len(), 1);
     }
 
+    #[test]
+    fn add_candidate_uses_proposed_id_and_rejects_duplicates() {
+        let service = service();
+        let session = service.create_session("comp-1").expect("session created");
+        let with_id = || MatchProposal {
+            proposed_candidate_id: Some("engine-42".into()),
+            ..proposal(None, 0, 0, "Coffee", "Coffee")
+        };
+        let candidate = service
+            .add_candidate(&session.id, with_id())
+            .expect("candidate added");
+        assert_eq!(candidate.id, "engine-42");
+
+        let err = service
+            .add_candidate(&session.id, with_id())
+            .expect_err("duplicate id rejected");
+        assert!(matches!(err, ReconcileError::CandidateAlreadyExists(id) if id == "engine-42"));
+        let fetched = service.session(&session.id).expect("session fetch");
+        assert_eq!(fetched.candidates.len(), 1);
+    }
+
     #[test]
     fn accept_candidate_closes_session() {
         let service = service();
@@ -761,17 +2675,590 @@ mod tests {
                 proposal(None, 0, 0, "Invoice #1", "Invoice #1"),
             )
             .expect("candidate added");
-        let accepted = service
-            .accept(&session.id, &candidate.id)
+        let accepted = service
+            .accept(&session.id, &candidate.id)
+            .expect("candidate accepted");
+        assert_eq!(accepted.status, CandidateStatus::Accepted);
+        let updated = service.session(&session.id).expect("session fetch");
+        assert_eq!(updated.status, SessionStatus::Closed);
+    }
+
+    #[test]
+    fn match_proposal_round_trips_through_json() {
+        let original = MatchProposal {
+            currency: Some("JPY".into()),
+            currency_precision: Some(0),
+            ..proposal(Some("grp-1"), 120, 2, "Inv#1001", "Invoice 1001")
+        };
+        let json = serde_json::to_string(&original).expect("encode proposal");
+        assert!(!json.contains("proposed_candidate_id"));
+        let decoded: MatchProposal = serde_json::from_str(&json).expect("decode proposal");
+        assert_eq!(decoded, original);
+
+        let minimal: MatchProposal = serde_json::from_str(
+            r#"{"transaction_id":"txn-1","journal_entry_id":"je-1","amount_delta_minor":0,"date_delta_days":0}"#,
+        )
+        .expect("decode minimal proposal");
+        assert_eq!(minimal, proposal(None, 0, 0, "", ""));
+    }
+
+    #[test]
+    fn weighted_scoring_config_builds_strategy_from_json() {
+        let config: WeightedScoringConfig = serde_json::from_str(
+            r#"{"amount_weight":2.0,"date_weight":1.0,"description_weight":1.0,"max_date_delta_days":10,"normalize_weights":true,"tokenizer":{"kind":"alphanumeric"}}"#,
+        )
+        .expect("decode config");
+        assert_eq!(config.amount_tolerance_minor, 5_000);
+        assert_eq!(config.date_tolerance_days, 7);
+        assert_eq!(
+            config.tokenizer,
+            TokenizerConfig::Alphanumeric { stop_words: None }
+        );
+
+        let strategy = config.build().expect("valid config");
+        let from_code = WeightedScoringStrategy::new(2.0, 1.0, 1.0, 5_000, 7)
+            .with_max_date_delta_days(Some(10))
+            .with_tokenizer(Arc::new(AlphanumericTokenizer::new()))
+            .with_normalized_weights();
+        let sample = proposal(None, 100, 3, "Inv#1001", "inv 1001");
+        assert_eq!(strategy.score(&sample), from_code.score(&sample));
+        assert_eq!(strategy.score(&proposal(None, 0, 11, "a", "a")), 0.0);
+
+        let round_trip: WeightedScoringConfig =
+            serde_json::from_str(&serde_json::to_string(&config).expect("encode config"))
+                .expect("decode round trip");
+        assert_eq!(round_trip, config);
+
+        let err = WeightedScoringConfig {
+            amount_weight: -1.0,
+            ..WeightedScoringConfig::default()
+        }
+        .build()
+        .expect_err("negative weight rejected");
+        assert!(matches!(err, ReconcileError::InvalidConfiguration(_)));
+    }
+
+    #[test]
+    fn comparison_reports_deltas_for_display() {
+        let service =
+            InMemoryReconciliationService::new(Arc::new(WeightedScoringStrategy::default()));
+        let session = service.create_session("comp-1").expect("session created");
+        let candidate = service
+            .add_candidate(
+                &session.id,
+                MatchProposal {
+                    currency: Some("USD".into()),
+                    currency_precision: Some(2),
+                    ..proposal(None, -150, 2, "acme invoice 1001", "acme invoice")
+                },
+            )
+            .expect("candidate added");
+
+        let comparison = candidate.comparison();
+        assert_eq!(comparison.amount_delta_minor, -150);
+        assert_eq!(comparison.date_delta_days, 2);
+        assert!((comparison.description_similarity - 2.0 / 3.0).abs() < 1e-6);
+        assert_eq!(comparison.formatted_amount_delta(), "-1.50");
+        assert_eq!(
+            comparison.to_string(),
+            "amount Δ -1.50 USD, date Δ 2 days, description 67% similar"
+        );
+
+        let unscored = MatchCandidate {
+            score_breakdown: None,
+            currency: None,
+            currency_precision: Some(0),
+            residual_minor: 1_200,
+            date_delta_days: -1,
+            ..candidate
+        };
+        assert_eq!(
+            unscored.comparison().to_string(),
+            "amount Δ 1200, date Δ -1 day, description 67% similar"
+        );
+    }
+
+    #[test]
+    fn score_histogram_buckets_candidate_scores() {
+        let service = service();
+        let session = service.create_session("comp-1").expect("session created");
+        let base = service
+            .add_candidate(&session.id, proposal(None, 0, 0, "Rent", "Rent"))
+            .expect("candidate added");
+        let mut stored = service.session(&session.id).expect("session fetch");
+        stored.candidates = [0.0, 0.1, 0.3, 0.8, 1.0]
+            .into_iter()
+            .enumerate()
+            .map(|(index, score)| MatchCandidate {
+                id: format!("cand-{index}"),
+                score,
+                ..base.clone()
+            })
+            .collect();
+        service.store.save_session(&stored).expect("save session");
+
+        let histogram = service
+            .score_histogram(&session.id, 4)
+            .expect("histogram computed");
+        assert_eq!(histogram, vec![(0.0, 2), (0.25, 1), (0.5, 0), (0.75, 2)]);
+        assert!(
+            service
+                .score_histogram(&session.id, 0)
+                .expect("empty histogram")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn add_exact_match_accepts_without_closing_session() {
+        #[derive(Default)]
+        struct NoteHook {
+            notes: RwLock<Vec<(ReconciliationAuditAction, Option<String>)>>,
+        }
+
+        impl ReconciliationAuditHook for NoteHook {
+            fn record(&self, event: &ReconciliationAuditEvent) {
+                if let Ok(mut guard) = self.notes.write() {
+                    guard.push((event.action.clone(), event.note.clone()));
+                }
+            }
+        }
+
+        let hook = Arc::new(NoteHook::default());
+        let service = service().with_score_floor(2.0);
+        service.register_audit_hook(hook.clone());
+        let session = service.create_session("comp-1").expect("session created");
+        let matched = service
+            .add_exact_match(&session.id, "txn-ref", "je-inv-1001")
+            .expect("exact match recorded");
+        assert_eq!(matched.status, CandidateStatus::Accepted);
+        assert_eq!(matched.score, 1.0);
+        assert_eq!(matched.journal_entry_id, "je-inv-1001");
+
+        let updated = service.session(&session.id).expect("session fetch");
+        assert_eq!(updated.status, SessionStatus::Open);
+        assert_eq!(updated.coverage().accepted, 1);
+
+        let err = service
+            .add_exact_match(&session.id, "txn-ref", "je-inv-1002")
+            .expect_err("transaction already matched");
+        assert!(matches!(err, ReconcileError::InvalidTransition(_)));
+        let err = service
+            .add_exact_match(&session.id, "txn-other", "je-inv-1001")
+            .expect_err("journal entry already matched");
+        assert!(matches!(err, ReconcileError::InvalidTransition(_)));
+
+        let undone = service.undo_last(&session.id).expect("undo exact match");
+        assert!(undone.candidates.is_empty());
+        assert_eq!(undone.status, SessionStatus::Open);
+
+        let notes = hook.notes.read().expect("notes lock");
+        assert!(notes.contains(&(
+            ReconciliationAuditAction::CandidateAccepted,
+            Some("exact reference match".to_string()),
+        )));
+    }
+
+    #[test]
+    fn undo_last_reverts_transitions_until_reopen() {
+        let service = service();
+        let session = service.create_session("comp-1").expect("session created");
+        let first = service
+            .add_candidate(&session.id, proposal(None, 0, 0, "Rent", "Rent"))
+            .expect("first candidate");
+        let second = service
+            .add_candidate(&session.id, proposal(None, 0, 0, "Fees", "Fees"))
+            .expect("second candidate");
+        service
+            .reject_with_reason(&session.id, &first.id, "wrong vendor".into())
+            .expect("reject first");
+        service
+            .accept(&session.id, &second.id)
+            .expect("accept second");
+
+        let undone = service.undo_last(&session.id).expect("undo accept");
+        assert_eq!(undone.status, SessionStatus::Open);
+        assert_eq!(undone.candidates[1], second);
+        assert_eq!(undone.candidates[0].status, CandidateStatus::Rejected);
+        let undone = service.undo_last(&session.id).expect("undo reject");
+        assert_eq!(undone.candidates[0], first);
+        let err = service
+            .undo_last(&session.id)
+            .expect_err("history exhausted");
+        assert!(matches!(err, ReconcileError::InvalidTransition(_)));
+
+        service
+            .accept(&session.id, &first.id)
+            .expect("accept first");
+        service.reopen(&session.id).expect("reopen");
+        let err = service
+            .undo_last(&session.id)
+            .expect_err("reopen clears history");
+        assert!(matches!(err, ReconcileError::InvalidTransition(_)));
+    }
+
+    struct ChannelNotifier {
+        events: tokio::sync::mpsc::UnboundedSender<String>,
+    }
+
+    #[async_trait]
+    impl ReconciliationNotifier for ChannelNotifier {
+        async fn on_session_closed(&self, session: &ReconciliationSession) -> ReconcileResult<()> {
+            let _ = self.events.send(format!("closed:{}", session.id));
+            Ok(())
+        }
+
+        async fn on_candidate_accepted(
+            &self,
+            _session: &ReconciliationSession,
+            candidate: &MatchCandidate,
+        ) -> ReconcileResult<()> {
+            let _ = self.events.send(format!("accepted:{}", candidate.id));
+            Ok(())
+        }
+    }
+
+    struct FailingNotifier;
+
+    #[async_trait]
+    impl ReconciliationNotifier for FailingNotifier {
+        async fn on_session_closed(&self, _session: &ReconciliationSession) -> ReconcileResult<()> {
+            Err(ReconcileError::Notification("endpoint down".into()))
+        }
+
+        async fn on_candidate_accepted(
+            &self,
+            _session: &ReconciliationSession,
+            _candidate: &MatchCandidate,
+        ) -> ReconcileResult<()> {
+            Err(ReconcileError::Notification("endpoint down".into()))
+        }
+    }
+
+    #[tokio::test]
+    async fn notifiers_fire_after_accept_and_failures_do_not_block() {
+        let service = service();
+        let (events, mut received) = tokio::sync::mpsc::unbounded_channel();
+        service.register_notifier(Arc::new(FailingNotifier));
+        service.register_notifier(Arc::new(ChannelNotifier { events }));
+
+        let session = service.create_session("comp-1").expect("session created");
+        let candidate = service
+            .add_candidate(
+                &session.id,
+                proposal(None, 0, 0, "Invoice #6", "Invoice #6"),
+            )
+            .expect("candidate added");
+        service
+            .accept(&session.id, &candidate.id)
+            .expect("accept despite failing notifier");
+
+        let mut delivered = Vec::new();
+        while delivered.len() < 2 {
+            let event = tokio::time::timeout(std::time::Duration::from_secs(5), received.recv())
+                .await
+                .expect("notification delivered")
+                .expect("channel open");
+            delivered.push(event);
+        }
+        assert_eq!(
+            delivered,
+            vec![
+                format!("accepted:{}", candidate.id),
+                format!("closed:{}", session.id),
+            ]
+        );
+        assert_eq!(
+            service.session(&session.id).expect("session fetch").status,
+            SessionStatus::Closed
+        );
+    }
+
+    #[test]
+    fn best_candidate_prefers_highest_score_then_earliest() {
+        let service = service();
+        let session = service.create_session("comp-1").expect("session created");
+        assert_eq!(service.best_candidate(&session.id).expect("best"), None);
+
+        service
+            .add_candidate(&session.id, proposal(None, 4_000, 6, "Fuel", "Rent"))
+            .expect("weak candidate added");
+        let first_exact = service
+            .add_candidate(
+                &session.id,
+                proposal(None, 0, 0, "Invoice #4", "Invoice #4"),
+            )
+            .expect("exact candidate added");
+        let second_exact = service
+            .add_candidate(
+                &session.id,
+                proposal(None, 0, 0, "Invoice #4", "Invoice #4"),
+            )
+            .expect("exact candidate added");
+
+        let best = service
+            .best_candidate(&session.id)
+            .expect("best")
+            .expect("pending candidates");
+        assert_eq!(best.id, first_exact.id);
+
+        service
+            .reject(&session.id, &first_exact.id)
+            .expect("candidate rejected");
+        let best = service
+            .best_candidate(&session.id)
+            .expect("best")
+            .expect("pending candidates");
+        assert_eq!(best.id, second_exact.id);
+    }
+
+    #[test]
+    fn score_floor_keeps_noise_out_of_sessions() {
+        let service = service().with_score_floor(0.5);
+        let session = service.create_session("comp-1").expect("session created");
+        let err = service
+            .add_candidate(&session.id, proposal(None, 90_000, 30, "Fuel", "Rent"))
+            .expect_err("noise candidate refused");
+        assert!(
+            matches!(err, ReconcileError::BelowScoreFloor { .. }),
+            "unexpected error: {err:?}"
+        );
+        assert!(err.to_string().contains("0.500"));
+        service
+            .add_candidate(
+                &session.id,
+                proposal(None, 0, 0, "Invoice #5", "Invoice #5"),
+            )
+            .expect("strong candidate kept");
+        let fetched = service.session(&session.id).expect("session fetch");
+        assert_eq!(fetched.candidates.len(), 1);
+    }
+
+    #[test]
+    fn reject_candidate_keeps_session_open() {
+        let service = service();
+        let session = service.create_session("comp-1").expect("session created");
+        let candidate = service
+            .add_candidate(
+                &session.id,
+                proposal(None, 0, 0, "Invoice #2", "Invoice #3"),
+            )
+            .expect("candidate added");
+        let rejected = service
+            .reject(&session.id, &candidate.id)
+            .expect("candidate rejected");
+        assert_eq!(rejected.status, CandidateStatus::Rejected);
+        let updated = service.session(&session.id).expect("session fetch");
+        assert_eq!(updated.status, SessionStatus::Open);
+    }
+
+    #[test]
+    fn max_date_delta_zeroes_distant_matches() {
+        let distant = proposal(None, 0, 90, "ACME invoice", "ACME invoice");
+        let uncapped = WeightedScoringStrategy::default();
+        assert!(uncapped.score(&distant) > 0.0);
+
+        let capped = WeightedScoringStrategy::default().with_max_date_delta_days(Some(30));
+        assert_eq!(capped.score(&distant), 0.0);
+        assert_eq!(capped.explain(&distant).total, 0.0);
+        assert_eq!(capped.score_group(std::slice::from_ref(&distant)), 0.0);
+
+        let near = proposal(None, 0, 30, "ACME invoice", "ACME invoice");
+        assert_eq!(capped.score(&near), uncapped.score(&near));
+    }
+
+    #[test]
+    fn reopen_write_offs_keeps_other_candidates() {
+        let service = service();
+        let session = service.create_session("comp-1").expect("session created");
+        let accepted = service
+            .add_candidate(&session.id, proposal(None, 0, 0, "Rent", "Rent"))
+            .expect("accepted candidate");
+        let written_off = service
+            .add_candidate(&session.id, proposal(None, -200, 0, "Fees", "Bank fees"))
+            .expect("written-off candidate");
+        service
+            .write_off(&session.id, &written_off.id, "bank fee".into(), None)
+            .expect("write off");
+        service
+            .accept(&session.id, &accepted.id)
+            .expect("candidate accepted");
+
+        let reopened = service
+            .reopen_write_offs(&session.id)
+            .expect("write-offs reopened");
+        assert_eq!(reopened.status, SessionStatus::PendingPartial);
+        let status_of = |id: &CandidateId| {
+            reopened
+                .candidates
+                .iter()
+                .find(|candidate| &candidate.id == id)
+                .map(|candidate| (candidate.status, candidate.write_off_reason.clone()))
+        };
+        assert_eq!(
+            status_of(&accepted.id),
+            Some((CandidateStatus::Accepted, None))
+        );
+        assert_eq!(
+            status_of(&written_off.id),
+            Some((CandidateStatus::Pending, None))
+        );
+    }
+
+    #[test]
+    fn rescore_session_applies_current_strategy_to_pending_candidates() {
+        let store: Arc<dyn ReconciliationStore> = Arc::new(InMemoryReconciliationStore::new());
+        let original = InMemoryReconciliationService::with_store(
+            Arc::new(LinearScoringStrategy::new()),
+            Arc::clone(&store),
+        );
+        let session = original.create_session("comp-1").expect("session created");
+        let pending = original
+            .add_candidate(
+                &session.id,
+                proposal(None, 150, 2, "ACME invoice 42", "ACME invoice"),
+            )
+            .expect("pending candidate");
+        let rejected = original
+            .add_candidate(&session.id, proposal(None, 0, 0, "Coffee", "Coffee"))
+            .expect("rejected candidate");
+        original
+            .reject(&session.id, &rejected.id)
+            .expect("candidate rejected");
+
+        let weighted = WeightedScoringStrategy::new(0.1, 0.1, 0.8, 5_000, 7);
+        let expected = weighted.score(&pending.to_proposal());
+        assert!((expected - pending.score).abs() > f32::EPSILON);
+        let rescoring =
+            InMemoryReconciliationService::with_store(Arc::new(weighted), Arc::clone(&store));
+        let updated = rescoring
+            .rescore_session(&session.id)
+            .expect("session rescored");
+
+        let rescored = updated
+            .candidates
+            .iter()
+            .find(|candidate| candidate.id == pending.id)
+            .expect("pending candidate kept");
+        assert!((rescored.score - expected).abs() < f32::EPSILON);
+        assert_eq!(rescored.date_delta_days, 2);
+        assert_eq!(rescored.transaction_description, "ACME invoice 42");
+        let untouched = updated
+            .candidates
+            .iter()
+            .find(|candidate| candidate.id == rejected.id)
+            .expect("rejected candidate kept");
+        assert!((untouched.score - rejected.score).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn export_session_bundles_candidates_coverage_and_audit_trail() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let trail = Arc::new(
+            JsonlReconciliationAuditHook::new(dir.path().join("reconciliation.jsonl"))
+                .expect("hook created"),
+        );
+        let tracked = service().with_audit_trail(trail);
+        let session = tracked.create_session("comp-1").expect("session");
+        let accepted = tracked
+            .add_candidate(
+                &session.id,
+                proposal(None, 0, 0, "Invoice #1", "Invoice #1"),
+            )
+            .expect("candidate added");
+        tracked
+            .add_candidate(&session.id, proposal(None, 500, 3, "Invoice #2", "Rent"))
+            .expect("candidate added");
+        tracked
+            .accept(&session.id, &accepted.id)
             .expect("candidate accepted");
-        assert_eq!(accepted.status, CandidateStatus::Accepted);
-        let updated = service.session(&session.id).expect("session fetch");
-        assert_eq!(updated.status, SessionStatus::Closed);
+        tracked.create_session("comp-1").expect("other session");
+
+        let report = tracked.export_session(&session.id).expect("report");
+        assert_eq!(report.session.id, session.id);
+        assert_eq!(report.session.candidates.len(), 2);
+        assert_eq!(report.session.status, SessionStatus::Closed);
+        assert_eq!(report.coverage.accepted, 1);
+        assert_eq!(report.coverage.rejected, 1);
+        assert_eq!(
+            report
+                .audit_events
+                .iter()
+                .map(|record| record.action.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                ReconciliationAuditAction::SessionCreated,
+                ReconciliationAuditAction::CandidateAdded,
+                ReconciliationAuditAction::CandidateAdded,
+                ReconciliationAuditAction::CandidateAccepted,
+            ]
+        );
+
+        let json = serde_json::to_value(&report).expect("report serializes");
+        assert_eq!(json["session"]["candidates"][0]["status"], "Accepted");
+        assert_eq!(json["session"]["candidates"][1]["status"], "Rejected");
+        assert_eq!(json["coverage"]["accepted"], 1);
+        assert!(json["session"].get("undo_stack").is_none());
+
+        let untracked = service();
+        let bare = untracked.create_session("comp-1").expect("session");
+        let report = untracked.export_session(&bare.id).expect("report");
+        assert!(report.audit_events.is_empty());
     }
 
     #[test]
-    fn reject_candidate_keeps_session_open() {
+    fn jsonl_audit_hook_persists_records_per_session() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("audit").join("reconciliation.jsonl");
+        let service = service();
+        service.register_audit_hook(Arc::new(
+            JsonlReconciliationAuditHook::new(&path).expect("hook created"),
+        ));
+        let first = service.create_session("comp-1").expect("first session");
+        let candidate = service
+            .add_candidate(&first.id, proposal(None, 0, 0, "Invoice #2", "Invoice #3"))
+            .expect("candidate added");
+        service
+            .reject_with_reason(&first.id, &candidate.id, "wrong payee".into())
+            .expect("candidate rejected");
+        let second = service.create_session("comp-1").expect("second session");
+
+        let reloaded = JsonlReconciliationAuditHook::new(&path).expect("hook reopened");
+        let records = reloaded.records(Some(&first.id)).expect("records read");
+        let actions: Vec<ReconciliationAuditAction> =
+            records.iter().map(|record| record.action.clone()).collect();
+        assert_eq!(
+            actions,
+            vec![
+                ReconciliationAuditAction::SessionCreated,
+                ReconciliationAuditAction::CandidateAdded,
+                ReconciliationAuditAction::CandidateRejected,
+            ]
+        );
+        assert_eq!(records[2].candidate_id.as_ref(), Some(&candidate.id));
+        assert_eq!(records[2].note.as_deref(), Some("wrong payee"));
+        assert_eq!(reloaded.records(None).expect("all records").len(), 4);
+        assert_eq!(reloaded.records(Some(&second.id)).expect("second").len(), 1);
+    }
+
+    #[test]
+    fn reject_with_reason_records_reason_and_audit_note() {
+        #[derive(Default)]
+        struct NoteHook {
+            notes: RwLock<Vec<(ReconciliationAuditAction, Option<String>)>>,
+        }
+
+        impl ReconciliationAuditHook for NoteHook {
+            fn record(&self, event: &ReconciliationAuditEvent) {
+                if let Ok(mut guard) = self.notes.write() {
+                    guard.push((event.action.clone(), event.note.clone()));
+                }
+            }
+        }
+
+        let hook = Arc::new(NoteHook::default());
         let service = service();
+        service.register_audit_hook(hook.clone());
         let session = service.create_session("comp-1").expect("session created");
         let candidate = service
             .add_candidate(
@@ -780,11 +3267,16 @@ mod tests {
             )
             .expect("candidate added");
         let rejected = service
-            .reject(&session.id, &candidate.id)
+            .reject_with_reason(&session.id, &candidate.id, "wrong vendor".into())
             .expect("candidate rejected");
         assert_eq!(rejected.status, CandidateStatus::Rejected);
-        let updated = service.session(&session.id).expect("session fetch");
-        assert_eq!(updated.status, SessionStatus::Open);
+        assert_eq!(rejected.rejection_reason.as_deref(), Some("wrong vendor"));
+
+        let notes = hook.notes.read().expect("notes lock");
+        assert!(notes.contains(&(
+            ReconciliationAuditAction::CandidateRejected,
+            Some("wrong vendor".to_string())
+        )));
     }
 
     #[test]
@@ -828,7 +3320,12 @@ mod tests {
             )
             .expect("candidate added");
         let written_off = service
-            .write_off(&session.id, &candidate.id, "Immateral difference".into())
+            .write_off(
+                &session.id,
+                &candidate.id,
+                "Immateral difference".into(),
+                None,
+            )
             .expect("write off");
         assert_eq!(written_off.status, CandidateStatus::WrittenOff);
         assert_eq!(
@@ -837,6 +3334,99 @@ mod tests {
         );
     }
 
+    #[test]
+    fn write_off_above_threshold_requires_approval_reference() {
+        let service = InMemoryReconciliationService::new(Arc::new(LinearScoringStrategy::new()))
+            .with_max_write_off_minor(Some(500));
+        let session = service.create_session("comp-1").expect("session created");
+        let small = service
+            .add_candidate(
+                &session.id,
+                proposal(None, -200, 0, "Bank fee", "Monthly fee"),
+            )
+            .expect("candidate added");
+        let large = service
+            .add_candidate(
+                &session.id,
+                proposal(None, 2_500, 0, "Vendor ACH", "Vendor invoice"),
+            )
+            .expect("candidate added");
+
+        let written_off = service
+            .write_off(&session.id, &small.id, "Rounding".into(), None)
+            .expect("immaterial write off");
+        assert_eq!(written_off.residual_minor, -200);
+        assert_eq!(written_off.write_off_approval_reference, None);
+
+        let err = service
+            .write_off(&session.id, &large.id, "Variance".into(), Some("  ".into()))
+            .expect_err("approval required");
+        assert!(matches!(err, ReconcileError::InvalidTransition(_)));
+
+        let approved = service
+            .write_off(
+                &session.id,
+                &large.id,
+                "Variance".into(),
+                Some("APR-7".into()),
+            )
+            .expect("approved write off");
+        assert_eq!(approved.status, CandidateStatus::WrittenOff);
+        assert_eq!(
+            approved.write_off_approval_reference.as_deref(),
+            Some("APR-7")
+        );
+    }
+
+    #[test]
+    fn coverage_counts_candidates_by_status() {
+        let service = service();
+        let session = service.create_session("comp-1").expect("session created");
+        let empty = service.coverage(&session.id).expect("coverage");
+        assert_eq!(empty, Coverage::default());
+        assert_eq!(empty.ratio(), 0.0);
+
+        let split_a = service
+            .add_candidate(&session.id, proposal(Some("grp-1"), 0, 0, "ACH", "ACH"))
+            .expect("candidate added");
+        let split_b = service
+            .add_candidate(&session.id, proposal(Some("grp-1"), 0, 0, "ACH", "ACH"))
+            .expect("candidate added");
+        let rejected = service
+            .add_candidate(&session.id, proposal(None, 0, 0, "Rent", "Fuel"))
+            .expect("candidate added");
+        let written_off = service
+            .add_candidate(&session.id, proposal(None, 5, 0, "Fee", "Fee"))
+            .expect("candidate added");
+        service
+            .add_candidate(&session.id, proposal(None, 0, 0, "Card", "Card"))
+            .expect("candidate added");
+        service
+            .accept_partial(&session.id, "grp-1", vec![split_a.id, split_b.id])
+            .expect("partial accept");
+        service
+            .reject(&session.id, &rejected.id)
+            .expect("candidate rejected");
+        service
+            .write_off(&session.id, &written_off.id, "Rounding".into(), None)
+            .expect("write off");
+
+        let coverage = service.coverage(&session.id).expect("coverage");
+        assert_eq!(
+            coverage,
+            Coverage {
+                pending: 1,
+                accepted: 0,
+                partially_accepted: 2,
+                rejected: 1,
+                written_off: 1,
+            }
+        );
+        assert_eq!(coverage.matched(), 3);
+        assert_eq!(coverage.total(), 5);
+        assert_eq!(coverage.ratio(), 0.75);
+    }
+
     #[test]
     fn reopen_resets_candidate_statuses() {
         let service = service();
@@ -857,6 +3447,144 @@ mod tests {
         );
     }
 
+    #[test]
+    fn aging_buckets_pending_candidates_across_open_sessions() {
+        let store = Arc::new(InMemoryReconciliationStore::new());
+        let service = InMemoryReconciliationService::with_store(
+            Arc::new(LinearScoringStrategy::new()),
+            store.clone(),
+        );
+        let now = Utc::now();
+        let with_id = |id: &str| MatchProposal {
+            proposed_candidate_id: Some(id.into()),
+            ..proposal(None, 0, 0, "Rent", "Rent")
+        };
+        let open = service.create_session("comp-1").expect("session created");
+        for id in ["fresh", "week", "month", "ancient", "rejected"] {
+            service
+                .add_candidate(&open.id, with_id(id))
+                .expect("candidate added");
+        }
+        service
+            .reject(&open.id, &"rejected".to_string())
+            .expect("candidate rejected");
+        let mut session = service.session(&open.id).expect("session fetch");
+        for candidate in &mut session.candidates {
+            let days = match candidate.id.as_str() {
+                "week" => 3,
+                "month" => 7,
+                "ancient" => 45,
+                _ => 0,
+            };
+            candidate.proposed_at = now - chrono::Duration::days(days);
+        }
+        store.save_session(&session).expect("backdate candidates");
+
+        let closed = service.create_session("comp-1").expect("session created");
+        service
+            .add_candidate(&closed.id, with_id("closed"))
+            .expect("candidate added");
+        let mut closed_session = service.session(&closed.id).expect("session fetch");
+        closed_session.opened_at = now - chrono::Duration::days(60);
+        store
+            .save_session(&closed_session)
+            .expect("backdate closed session");
+        service
+            .sweep_stale(now - chrono::Duration::days(1))
+            .expect("sweep succeeds");
+        let second = service.create_session("comp-1").expect("session created");
+        service
+            .add_candidate(&second.id, with_id("other-session"))
+            .expect("candidate added");
+        let other = service.create_session("comp-2").expect("session created");
+        service
+            .add_candidate(&other.id, with_id("other-company"))
+            .expect("candidate added");
+
+        let buckets = service.aging("comp-1", now).expect("aging report");
+        let summary = buckets
+            .iter()
+            .map(|bucket| (bucket.label.as_str(), bucket.candidate_ids.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "<1d",
+                    vec!["fresh".to_string(), "other-session".to_string()]
+                ),
+                ("1-7d", vec!["week".to_string()]),
+                ("7-30d", vec!["month".to_string()]),
+                (">30d", vec!["ancient".to_string()]),
+            ]
+        );
+        assert_eq!(buckets[3].max_age_days, None);
+    }
+
+    #[test]
+    fn sweep_stale_closes_old_open_sessions() {
+        let store = Arc::new(InMemoryReconciliationStore::new());
+        let service = InMemoryReconciliationService::with_store(
+            Arc::new(LinearScoringStrategy::new()),
+            store.clone(),
+        );
+        let stale = service.create_session("comp-1").expect("session created");
+        let closed = service.create_session("comp-1").expect("session created");
+        let candidate = service
+            .add_candidate(&closed.id, proposal(None, 0, 0, "Rent", "Rent"))
+            .expect("candidate added");
+        service
+            .accept(&closed.id, &candidate.id)
+            .expect("candidate accepted");
+        let cutoff = Utc::now();
+        let fresh = service.create_session("comp-1").expect("session created");
+        let mut closed_session = service.session(&closed.id).expect("session fetch");
+        closed_session.opened_at = cutoff - chrono::Duration::days(30);
+        store
+            .save_session(&closed_session)
+            .expect("backdate closed session");
+
+        let swept = service.sweep_stale(cutoff).expect("sweep succeeds");
+        assert_eq!(swept, vec![stale.id.clone()]);
+        assert_eq!(
+            service.session(&stale.id).expect("session fetch").status,
+            SessionStatus::Closed
+        );
+        assert_eq!(
+            service.session(&fresh.id).expect("session fetch").status,
+            SessionStatus::Open
+        );
+        assert!(
+            service
+                .sweep_stale(cutoff)
+                .expect("sweep succeeds")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn try_new_rejects_invalid_weights() {
+        for weights in [(0.0, 0.0, 0.0), (-0.1, 0.5, 0.5), (f32::NAN, 0.5, 0.5)] {
+            let err = WeightedScoringStrategy::try_new(weights.0, weights.1, weights.2, 5_000, 7)
+                .expect_err("invalid weights should be rejected");
+            assert!(matches!(err, ReconcileError::InvalidConfiguration(_)));
+        }
+        WeightedScoringStrategy::try_new(0.0, 0.0, 1.0, 5_000, 7)
+            .expect("a single positive weight is valid");
+    }
+
+    #[test]
+    fn normalized_weights_preserve_scores() {
+        let raw = WeightedScoringStrategy::new(3.0, 2.0, 5.0, 5_000, 7);
+        let normalized = raw.clone().with_normalized_weights();
+        let summed =
+            normalized.amount_weight + normalized.date_weight + normalized.description_weight;
+        assert!((summed - 1.0).abs() < 1e-6);
+        assert!((normalized.amount_weight - 0.3).abs() < 1e-6);
+        let sample = proposal(None, 1_000, 2, "Utilities invoice", "Monthly utilities");
+        assert!((raw.score(&sample) - normalized.score(&sample)).abs() < 1e-6);
+    }
+
     #[test]
     fn weighted_strategy_penalizes_amount_delta() {
         let strategy = WeightedScoringStrategy::default();
@@ -884,6 +3612,46 @@ mod tests {
         assert!(reductions >= 1, "expected at least one reduction");
     }
 
+    #[test]
+    fn weighted_strategy_scales_tolerance_by_currency_precision() {
+        let strategy = WeightedScoringStrategy::default();
+        let with_currency = |amount_delta_minor, currency: &str, precision| MatchProposal {
+            currency: Some(currency.into()),
+            currency_precision: Some(precision),
+            ..proposal(None, amount_delta_minor, 0, "Rent", "Rent")
+        };
+
+        let untagged = strategy.score(&proposal(None, 500, 0, "Rent", "Rent"));
+        assert_eq!(strategy.score(&with_currency(500, "USD", 2)), untagged);
+        // 500 yen is a much larger relative delta than 500 cents.
+        assert!(strategy.score(&with_currency(500, "JPY", 0)) < untagged);
+        // 5 yen is equivalent to 500 cents in major units.
+        assert_eq!(strategy.score(&with_currency(5, "JPY", 0)), untagged);
+        // 5000 fils (precision 3) is equivalent to 500 cents.
+        assert_eq!(strategy.score(&with_currency(5_000, "KWD", 3)), untagged);
+    }
+
+    #[test]
+    fn alphanumeric_tokenizer_splits_memo_references() {
+        let tokenizer = AlphanumericTokenizer::new();
+        assert_eq!(
+            tokenizer.tokenize("Payment for Inv#1001 to ACME-Corp"),
+            vec!["payment", "inv", "1001", "acme", "corp"]
+        );
+        assert_eq!(
+            AlphanumericTokenizer::new()
+                .with_stop_words(["Payment"])
+                .tokenize("Payment for Inv#1001"),
+            vec!["for", "inv", "1001"]
+        );
+
+        let memo = proposal(None, 0, 0, "Inv#1001", "Invoice 1001");
+        let whitespace = WeightedScoringStrategy::default();
+        let alphanumeric = WeightedScoringStrategy::default()
+            .with_tokenizer(Arc::new(AlphanumericTokenizer::new()));
+        assert!(alphanumeric.score(&memo) > whitespace.score(&memo));
+    }
+
     #[test]
     fn weighted_strategy_rewards_description_similarity() {
         let strategy = WeightedScoringStrategy::default();
@@ -906,6 +3674,271 @@ mod tests {
         assert!(medium > low);
     }
 
+    #[test]
+    fn cross_currency_proposals_need_a_rate_before_amount_scoring() {
+        let rate = |base: &str, quote: &str, rate: f64| FxRate {
+            base: base.into(),
+            base_precision: 2,
+            quote: quote.into(),
+            quote_precision: 2,
+            rate,
+        };
+        let eur_line = MatchProposal {
+            transaction_currency: Some("EUR".into()),
+            journal_currency: Some("USD".into()),
+            transaction_amount_minor: Some(10_000),
+            journal_amount_minor: Some(10_800),
+            ..proposal(None, -800, 0, "Invoice 7", "Invoice 7")
+        };
+        let strategy = WeightedScoringStrategy::default();
+        let amount = |proposal: &MatchProposal| strategy.explain(proposal).components[0].normalized;
+
+        assert!(eur_line.needs_fx_review());
+        assert_eq!(amount(&eur_line), 0.0);
+
+        let converted = eur_line
+            .clone()
+            .with_fx_rate(&rate("EUR", "USD", 1.08))
+            .expect("rate converts EUR to USD");
+        assert_eq!(converted.amount_delta_minor, 0);
+        assert_eq!(converted.currency.as_deref(), Some("USD"));
+        assert_eq!(converted.fx_rate, Some(1.08));
+        assert!(!converted.needs_fx_review());
+        assert_eq!(amount(&converted), 1.0);
+
+        let inverse = eur_line
+            .clone()
+            .with_fx_rate(&rate("USD", "EUR", 1.25))
+            .expect("inverse rate converts too");
+        assert_eq!(inverse.amount_delta_minor, 8_000 - 10_800);
+        assert_eq!(inverse.fx_rate, Some(0.8));
+        assert!(matches!(
+            eur_line.clone().with_fx_rate(&rate("GBP", "USD", 1.27)),
+            Err(ReconcileError::InvalidProposal(_))
+        ));
+        assert!(matches!(
+            eur_line.clone().with_fx_rate(&rate("USD", "EUR", 0.0)),
+            Err(ReconcileError::InvalidProposal(_))
+        ));
+        let overflowing = MatchProposal {
+            journal_amount_minor: Some(i64::MIN),
+            ..eur_line.clone()
+        };
+        assert!(matches!(
+            overflowing.with_fx_rate(&rate("EUR", "USD", 1.08)),
+            Err(ReconcileError::InvalidProposal(_))
+        ));
+
+        let service = InMemoryReconciliationService::new(Arc::new(strategy.clone()));
+        let session = service.create_session("comp-1").expect("session created");
+        let flagged = service
+            .add_candidate(&session.id, eur_line)
+            .expect("candidate added");
+        assert!(flagged.needs_fx_review);
+        assert!(flagged.to_proposal().needs_fx_review());
+        let reviewed = service
+            .add_candidate(&session.id, converted)
+            .expect("candidate added");
+        assert!(!reviewed.needs_fx_review);
+        assert_eq!(reviewed.transaction_currency.as_deref(), Some("EUR"));
+        assert!(reviewed.score > flagged.score);
+    }
+
+    #[test]
+    fn description_normalizer_strips_bank_noise_before_scoring() {
+        let normalizer = DescriptionNormalizer::default();
+        assert_eq!(
+            normalizer.normalize("POS DEBIT 1234 STARBUCKS SEATTLE WA"),
+            "STARBUCKS"
+        );
+        assert_eq!(
+            normalizer.normalize("ACH CREDIT PAYROLL ACME 1234567"),
+            "PAYROLL ACME 1234567"
+        );
+        assert_eq!(
+            normalizer.normalize("ACH DEBIT ACME GROUP LTD"),
+            "ACME GROUP LTD"
+        );
+        assert!(matches!(
+            DescriptionNormalizer::new(["("]),
+            Err(ReconcileError::InvalidConfiguration(_))
+        ));
+
+        let memo = proposal(
+            None,
+            0,
+            0,
+            "POS DEBIT 1234 STARBUCKS SEATTLE WA",
+            "Starbucks",
+        );
+        let raw = WeightedScoringStrategy::default();
+        let normalized =
+            WeightedScoringStrategy::default().with_description_normalizer(Arc::new(normalizer));
+        assert!(normalized.score(&memo) > raw.score(&memo));
+
+        let normalizing = InMemoryReconciliationService::new(Arc::new(normalized));
+        let session = normalizing
+            .create_session("comp-1")
+            .expect("session created");
+        let candidate = normalizing
+            .add_candidate(&session.id, memo)
+            .expect("candidate added");
+        assert_eq!(
+            candidate.normalized_transaction_description.as_deref(),
+            Some("STARBUCKS")
+        );
+        assert_eq!(
+            candidate.normalized_journal_description.as_deref(),
+            Some("Starbucks")
+        );
+        let plain = service();
+        let session = plain.create_session("comp-1").expect("session created");
+        let candidate = plain
+            .add_candidate(&session.id, proposal(None, 0, 0, "Rent", "Rent"))
+            .expect("candidate added");
+        assert_eq!(candidate.normalized_transaction_description, None);
+    }
+
+    #[test]
+    fn default_group_score_averages_individual_scores() {
+        struct FixedScore;
+
+        impl ScoringStrategy for FixedScore {
+            fn score(&self, proposal: &MatchProposal) -> f32 {
+                if proposal.amount_delta_minor == 0 {
+                    1.0
+                } else {
+                    0.5
+                }
+            }
+        }
+
+        let strategy = FixedScore;
+        let group = [
+            proposal(Some("grp-1"), 0, 0, "Rent", "Rent"),
+            proposal(Some("grp-1"), 10, 0, "Rent", "Rent"),
+        ];
+        assert_eq!(strategy.score_group(&group), 0.75);
+        assert_eq!(strategy.score_group(&[]), 0.0);
+    }
+
+    #[test]
+    fn weighted_group_score_rewards_offsetting_deltas() {
+        let strategy = WeightedScoringStrategy::default();
+        let balanced = [
+            proposal(Some("grp-1"), 6_000, 0, "Payroll", "Payroll"),
+            proposal(Some("grp-1"), -6_000, 0, "Payroll", "Payroll"),
+        ];
+        let unbalanced = [
+            proposal(Some("grp-1"), 6_000, 0, "Payroll", "Payroll"),
+            proposal(Some("grp-1"), 6_000, 0, "Payroll", "Payroll"),
+        ];
+        let balanced_score = strategy.score_group(&balanced);
+        assert!(balanced_score > strategy.score(&balanced[0]));
+        assert!(balanced_score > strategy.score_group(&unbalanced));
+        assert!((balanced_score - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn session_round_trips_through_json() {
+        let service = service();
+        let session = service.create_session("comp-1").expect("session created");
+        let candidate = service
+            .add_candidate(
+                &session.id,
+                proposal(Some("grp-1"), 25, 1, "Bank fee", "Monthly fee"),
+            )
+            .expect("candidate added");
+        service
+            .write_off(&session.id, &candidate.id, "Rounding".into(), None)
+            .expect("write off");
+        let session = service.session(&session.id).expect("session fetch");
+
+        let encoded = serde_json::to_string(&session).expect("encode session");
+        let decoded: ReconciliationSession =
+            serde_json::from_str(&encoded).expect("decode session");
+        assert_eq!(decoded, session);
+    }
+
+    #[test]
+    fn json_file_store_persists_sessions() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let store = Arc::new(JsonFileReconciliationStore::new(dir.path()).expect("store created"));
+        let service = InMemoryReconciliationService::with_store(
+            Arc::new(LinearScoringStrategy::new()),
+            store.clone(),
+        );
+        let session = service.create_session("comp-1").expect("session created");
+        service
+            .add_candidate(&session.id, proposal(None, 0, 0, "Rent", "Rent"))
+            .expect("candidate added");
+
+        let reloaded = JsonFileReconciliationStore::new(dir.path()).expect("store reopened");
+        let fetched = reloaded.get_session(&session.id).expect("session loaded");
+        assert_eq!(fetched.candidates.len(), 1);
+        assert_eq!(
+            fetched,
+            service.session(&session.id).expect("session fetch")
+        );
+
+        let err = store
+            .create_session(fetched.clone())
+            .expect_err("duplicate create should fail");
+        assert!(matches!(err, ReconcileError::SessionAlreadyExists(id) if id == session.id));
+
+        let mut missing = fetched;
+        missing.id = "missing-session".into();
+        let err = store
+            .save_session(&missing)
+            .expect_err("saving a missing session should fail");
+        assert!(matches!(err, ReconcileError::SessionNotFound(_)));
+        let err = store
+            .get_session(&"missing-session".to_string())
+            .expect_err("missing session");
+        assert!(matches!(err, ReconcileError::SessionNotFound(_)));
+    }
+
+    #[test]
+    fn weighted_strategy_explains_components() {
+        let strategy = WeightedScoringStrategy::default();
+        let proposal = proposal(None, 2_500, 0, "Utilities invoice", "Monthly utilities");
+        let explanation = strategy.explain(&proposal);
+        assert_eq!(explanation.total, strategy.score(&proposal));
+        let kinds: Vec<ScoreComponentKind> = explanation
+            .components
+            .iter()
+            .map(|component| component.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ScoreComponentKind::Amount,
+                ScoreComponentKind::Date,
+                ScoreComponentKind::Description,
+            ]
+        );
+        assert_eq!(explanation.components[0].normalized, 0.5);
+        assert_eq!(explanation.components[1].normalized, 1.0);
+        let summed: f32 = explanation
+            .components
+            .iter()
+            .map(|component| component.contribution)
+            .sum();
+        assert!((summed - explanation.total).abs() < 1e-6);
+    }
+
+    #[test]
+    fn add_candidate_stores_score_breakdown() {
+        let service = service();
+        let session = service.create_session("comp-1").expect("session created");
+        let candidate = service
+            .add_candidate(&session.id, proposal(None, 0, 0, "Rent", "Rent"))
+            .expect("candidate added");
+        let breakdown = candidate.score_breakdown.expect("breakdown stored");
+        assert_eq!(breakdown.total, candidate.score);
+        assert_eq!(breakdown.components.len(), 3);
+    }
+
     #[test]
     fn audit_hook_captures_events() {
         #[derive(Default)]
@@ -936,4 +3969,60 @@ mod tests {
         assert!(events.contains(&ReconciliationAuditAction::CandidateAdded));
         assert!(events.contains(&ReconciliationAuditAction::CandidateRejected));
     }
+
+    #[derive(Default)]
+    struct RecordingAuditHook {
+        events: RwLock<Vec<ReconciliationAuditEvent>>,
+    }
+
+    impl RecordingAuditHook {
+        fn last(&self) -> Option<ReconciliationAuditEvent> {
+            self.events.read().expect("events lock").last().cloned()
+        }
+    }
+
+    impl ReconciliationAuditHook for RecordingAuditHook {
+        fn record(&self, event: &ReconciliationAuditEvent) {
+            if let Ok(mut guard) = self.events.write() {
+                guard.push(event.clone());
+            }
+        }
+    }
+
+    #[test]
+    fn undo_last_emits_action_undone_audit_event() {
+        let hook = Arc::new(RecordingAuditHook::default());
+        let service = service();
+        service.register_audit_hook(hook.clone());
+        let session = service.create_session("comp-2").expect("session created");
+        let candidate = service
+            .add_candidate(&session.id, proposal(None, 0, 0, "Audit", "Audit"))
+            .expect("candidate added");
+        service
+            .reject(&session.id, &candidate.id)
+            .expect("candidate rejected");
+        service.undo_last(&session.id).expect("undo reject");
+
+        let event = hook.last().expect("undo recorded");
+        assert_eq!(event.action, ReconciliationAuditAction::ActionUndone);
+        assert_eq!(event.session_id, session.id);
+        assert_eq!(event.candidate_id, Some(candidate.id));
+        assert_eq!(event.note.as_deref(), Some("undid CandidateRejected"));
+    }
+
+    #[test]
+    fn sweep_stale_emits_auto_closed_audit_event() {
+        let hook = Arc::new(RecordingAuditHook::default());
+        let service = service();
+        service.register_audit_hook(hook.clone());
+        let session = service.create_session("comp-2").expect("session created");
+        service
+            .sweep_stale(Utc::now() + chrono::Duration::minutes(1))
+            .expect("sweep succeeds");
+
+        let event = hook.last().expect("sweep recorded");
+        assert_eq!(event.action, ReconciliationAuditAction::SessionAutoClosed);
+        assert_eq!(event.session_id, session.id);
+        assert_eq!(event.candidate_id, None);
+    }
 }
@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::MatchCandidate;
+use crate::ReconcileError;
+use crate::ReconcileResult;
+use crate::ReconciliationNotifier;
+use crate::ReconciliationSession;
+use crate::SessionId;
+
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum WebhookPayload<'a> {
+    SessionClosed {
+        session: &'a ReconciliationSession,
+    },
+    CandidateAccepted {
+        session_id: &'a SessionId,
+        candidate: &'a MatchCandidate,
+    },
+}
+
+/// Posts each notification as JSON (`{"event": "session_closed", ...}`) to a
+/// fixed URL. Non-2xx responses are reported as `ReconcileError::Notification`.
+pub struct WebhookReconciliationNotifier {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookReconciliationNotifier {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_client(reqwest::Client::new(), url)
+    }
+
+    pub fn with_client(client: reqwest::Client, url: impl Into<String>) -> Self {
+        Self {
+            client,
+            url: url.into(),
+        }
+    }
+
+    async fn post(&self, payload: &WebhookPayload<'_>) -> ReconcileResult<()> {
+        self.client
+            .post(&self.url)
+            .json(payload)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map(|_| ())
+            .map_err(|err| ReconcileError::Notification(format!("webhook {}: {err}", self.url)))
+    }
+}
+
+#[async_trait]
+impl ReconciliationNotifier for WebhookReconciliationNotifier {
+    async fn on_session_closed(&self, session: &ReconciliationSession) -> ReconcileResult<()> {
+        self.post(&WebhookPayload::SessionClosed { session }).await
+    }
+
+    async fn on_candidate_accepted(
+        &self,
+        session: &ReconciliationSession,
+        candidate: &MatchCandidate,
+    ) -> ReconcileResult<()> {
+        self.post(&WebhookPayload::CandidateAccepted {
+            session_id: &session.id,
+            candidate,
+        })
+        .await
+    }
+}
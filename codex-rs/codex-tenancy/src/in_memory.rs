@@ -1,6 +1,10 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use chrono::Utc;
+use codex_audit_log::AppendRequest;
+use codex_audit_log::AuditLog;
+use serde_json::json;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
@@ -12,6 +16,9 @@ use crate::CreateFirmRequest;
 use crate::Firm;
 use crate::FirmId;
 use crate::InviteUserRequest;
+use crate::Role;
+use crate::RoleScope;
+use crate::TenancyActor;
 use crate::TenancyError;
 use crate::TenancyResult;
 use crate::TenancyService;
@@ -20,6 +27,7 @@ use crate::UpdateUserRolesRequest;
 use crate::UserAccount;
 use crate::UserId;
 use crate::UserStatus;
+use crate::default_currency_precision;
 use crate::normalize_company_name;
 
 #[derive(Default)]
@@ -31,6 +39,7 @@ struct TenancyState {
 
 pub struct InMemoryTenancyService {
     state: RwLock<TenancyState>,
+    audit_log: Option<Arc<dyn AuditLog>>,
 }
 
 impl InMemoryTenancyService {
@@ -38,9 +47,20 @@ impl InMemoryTenancyService {
     pub fn new() -> Self {
         Self {
             state: RwLock::new(TenancyState::default()),
+            audit_log: None,
         }
     }
 
+    /// Appends a record for every successful mutation. The record is written
+    /// while the state lock is held and before the change is applied, so a
+    /// failed append surfaces as `TenancyError::Internal` and leaves the
+    /// state untouched.
+    #[must_use]
+    pub fn with_audit_log(mut self, audit_log: Arc<dyn AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
     #[must_use]
     pub fn from_companies(companies: Vec<Company>) -> Self {
         Self::from_snapshot(TenancySnapshot {
@@ -72,6 +92,7 @@ impl InMemoryTenancyService {
                 companies,
                 users,
             }),
+            audit_log: None,
         }
     }
 
@@ -88,6 +109,29 @@ impl InMemoryTenancyService {
         }
     }
 
+    async fn record(
+        &self,
+        actor: &TenancyActor,
+        firm_id: &FirmId,
+        entity_id: &str,
+        action: &str,
+    ) -> TenancyResult<()> {
+        let Some(audit_log) = &self.audit_log else {
+            return Ok(());
+        };
+        audit_log
+            .append(AppendRequest {
+                entity_id: entity_id.to_string(),
+                actor: actor.user_id.clone(),
+                action: action.to_string(),
+                metadata: json!({ "firm_id": firm_id }),
+                request_id: None,
+            })
+            .await
+            .map(|_| ())
+            .map_err(|err| TenancyError::Internal(format!("audit log append failed: {err}")))
+    }
+
     fn generate_company_id() -> CompanyId {
         Uuid::new_v4().to_string()
     }
@@ -180,6 +224,55 @@ impl InMemoryTenancyService {
         Ok(user)
     }
 
+    fn has_users(state: &TenancyState, firm_id: &FirmId) -> bool {
+        state.users.values().any(|user| user.firm_id == *firm_id)
+    }
+
+    /// The actor as the service trusts it for a mutation in `firm_id`. While
+    /// the firm has no users at all, asserted roles are taken as-is so the
+    /// firm can be set up; its first invite then creates the actor's own
+    /// partner account (see `invite_user`). Afterwards the actor must be an
+    /// active member of the firm and keeps only the asserted roles its
+    /// account holds firm-wide or, when `company_id` is given, for that
+    /// company.
+    fn resolve_actor(
+        state: &TenancyState,
+        firm_id: &FirmId,
+        company_id: Option<&CompanyId>,
+        actor: &TenancyActor,
+    ) -> TenancyResult<TenancyActor> {
+        if !Self::has_users(state, firm_id) {
+            return Ok(actor.clone());
+        }
+        let is_active_member =
+            |user: &&UserAccount| user.firm_id == *firm_id && user.status.is_active();
+        let account = state
+            .users
+            .get(&actor.user_id)
+            .filter(is_active_member)
+            .ok_or_else(|| {
+                TenancyError::Rejected(format!(
+                    "user {} is not an active member of firm {firm_id}",
+                    actor.user_id
+                ))
+            })?;
+        let roles = actor
+            .roles
+            .iter()
+            .copied()
+            .filter(|role| {
+                account.roles.iter().any(|assignment| {
+                    assignment.role == *role
+                        && match &assignment.scope {
+                            RoleScope::FirmWide => true,
+                            RoleScope::Company(scoped) => company_id == Some(scoped),
+                        }
+                })
+            })
+            .collect();
+        Ok(TenancyActor::new(actor.user_id.clone(), roles))
+    }
+
     fn ensure_company_firm<'a>(
         firm_id: &FirmId,
         company: &'a Company,
@@ -203,7 +296,11 @@ impl Default for InMemoryTenancyService {
 
 #[async_trait::async_trait]
 impl TenancyService for InMemoryTenancyService {
-    async fn create_firm(&self, request: CreateFirmRequest) -> TenancyResult<Firm> {
+    async fn create_firm(
+        &self,
+        request: CreateFirmRequest,
+        actor: &TenancyActor,
+    ) -> TenancyResult<Firm> {
         let normalized = request.normalize()?;
         let mut guard = self.state.write().await;
         Self::ensure_unique_firm_name(&guard, &normalized.name)?;
@@ -213,6 +310,8 @@ impl TenancyService for InMemoryTenancyService {
             metadata: normalized.metadata,
             created_at: Utc::now(),
         };
+        self.record(actor, &firm.id, &firm.id, "firm.created")
+            .await?;
         guard.firms.insert(firm.id.clone(), firm.clone());
         Ok(firm)
     }
@@ -230,10 +329,15 @@ impl TenancyService for InMemoryTenancyService {
         Ok(firm.clone())
     }
 
-    async fn create_company(&self, request: CreateCompanyRequest) -> TenancyResult<Company> {
+    async fn create_company(
+        &self,
+        request: CreateCompanyRequest,
+        actor: &TenancyActor,
+    ) -> TenancyResult<Company> {
         let normalized = request.normalize()?;
         let mut guard = self.state.write().await;
         Self::require_firm(&normalized.firm_id, &guard)?;
+        Self::resolve_actor(&guard, &normalized.firm_id, None, actor)?.require_company_manager()?;
         Self::ensure_unique_name(&guard, &normalized)?;
         let company = Company {
             id: Self::generate_company_id(),
@@ -241,11 +345,16 @@ impl TenancyService for InMemoryTenancyService {
             name: normalized.name.clone(),
             status: CompanyStatus::Active,
             base_currency: normalized.base_currency.clone(),
+            currency_precision: normalized
+                .currency_precision
+                .unwrap_or_else(|| default_currency_precision(&normalized.base_currency)),
             tags: normalized.tags.clone(),
             created_at: Utc::now(),
             archived_at: None,
             metadata: normalized.metadata.clone(),
         };
+        self.record(actor, &company.firm_id, &company.id, "company.created")
+            .await?;
         guard.companies.insert(company.id.clone(), company.clone());
         Ok(company)
     }
@@ -280,11 +389,13 @@ impl TenancyService for InMemoryTenancyService {
         &self,
         firm_id: &FirmId,
         company_id: &CompanyId,
+        actor: &TenancyActor,
     ) -> TenancyResult<Company> {
         let mut guard = self.state.write().await;
+        Self::resolve_actor(&guard, firm_id, Some(company_id), actor)?.require_company_manager()?;
         let company = guard
             .companies
-            .get_mut(company_id)
+            .get(company_id)
             .ok_or_else(|| Self::map_err_not_found(company_id))?;
         Self::ensure_company_firm(firm_id, company, company_id)?;
         if company.status == CompanyStatus::Archived {
@@ -292,20 +403,26 @@ impl TenancyService for InMemoryTenancyService {
                 "company {company_id} is already archived"
             )));
         }
-        company.status = CompanyStatus::Archived;
-        company.archived_at = Some(Utc::now());
-        Ok(company.clone())
+        let mut archived = company.clone();
+        archived.status = CompanyStatus::Archived;
+        archived.archived_at = Some(Utc::now());
+        self.record(actor, firm_id, company_id, "company.archived")
+            .await?;
+        guard.companies.insert(company_id.clone(), archived.clone());
+        Ok(archived)
     }
 
     async fn reactivate_company(
         &self,
         firm_id: &FirmId,
         company_id: &CompanyId,
+        actor: &TenancyActor,
     ) -> TenancyResult<Company> {
         let mut guard = self.state.write().await;
+        Self::resolve_actor(&guard, firm_id, Some(company_id), actor)?.require_company_manager()?;
         let company = guard
             .companies
-            .get_mut(company_id)
+            .get(company_id)
             .ok_or_else(|| Self::map_err_not_found(company_id))?;
         Self::ensure_company_firm(firm_id, company, company_id)?;
         if company.status == CompanyStatus::Active {
@@ -313,26 +430,70 @@ impl TenancyService for InMemoryTenancyService {
                 "company {company_id} is already active"
             )));
         }
-        company.status = CompanyStatus::Active;
-        company.archived_at = None;
-        Ok(company.clone())
+        let mut reactivated = company.clone();
+        reactivated.status = CompanyStatus::Active;
+        reactivated.archived_at = None;
+        self.record(actor, firm_id, company_id, "company.reactivated")
+            .await?;
+        guard
+            .companies
+            .insert(company_id.clone(), reactivated.clone());
+        Ok(reactivated)
     }
 
-    async fn invite_user(&self, request: InviteUserRequest) -> TenancyResult<UserAccount> {
+    async fn invite_user(
+        &self,
+        request: InviteUserRequest,
+        actor: &TenancyActor,
+    ) -> TenancyResult<UserAccount> {
         let normalized = request.normalize()?;
         let mut guard = self.state.write().await;
         Self::require_firm(&normalized.firm_id, &guard)?;
+        Self::resolve_actor(&guard, &normalized.firm_id, None, actor)?.require_user_manager()?;
         Self::ensure_unique_user_email(&guard, &normalized.firm_id, &normalized.email)?;
-        let user = UserAccount {
-            id: Self::generate_user_id(),
-            firm_id: normalized.firm_id.clone(),
-            email: normalized.email.clone(),
-            display_name: normalized.display_name.clone(),
-            roles: normalized.roles.clone(),
-            status: UserStatus::Invited,
-            invited_at: Utc::now(),
-            activated_at: None,
+        let now = Utc::now();
+        // The first user of a firm is the bootstrapping actor's own active
+        // partner account, so claimed roles stop being trusted as soon as
+        // the firm has a member who can vouch for others.
+        let user = if Self::has_users(&guard, &normalized.firm_id) {
+            UserAccount {
+                id: Self::generate_user_id(),
+                firm_id: normalized.firm_id.clone(),
+                email: normalized.email.clone(),
+                display_name: normalized.display_name.clone(),
+                roles: normalized.roles.clone(),
+                status: UserStatus::Invited,
+                invited_at: now,
+                activated_at: None,
+            }
+        } else {
+            if !normalized.roles.iter().any(|assignment| {
+                assignment.role == Role::Partner && assignment.scope.is_firm_wide()
+            }) {
+                return Err(TenancyError::Rejected(format!(
+                    "the first user of firm {} must be a firm-wide partner",
+                    normalized.firm_id
+                )));
+            }
+            if guard.users.contains_key(&actor.user_id) {
+                return Err(TenancyError::Conflict(format!(
+                    "user {} already exists",
+                    actor.user_id
+                )));
+            }
+            UserAccount {
+                id: actor.user_id.clone(),
+                firm_id: normalized.firm_id.clone(),
+                email: normalized.email.clone(),
+                display_name: normalized.display_name.clone(),
+                roles: normalized.roles.clone(),
+                status: UserStatus::Active,
+                invited_at: now,
+                activated_at: Some(now),
+            }
         };
+        self.record(actor, &user.firm_id, &user.id, "user.invited")
+            .await?;
         guard.users.insert(user.id.clone(), user.clone());
         Ok(user)
     }
@@ -359,16 +520,26 @@ impl TenancyService for InMemoryTenancyService {
         Ok(user.clone())
     }
 
-    async fn set_user_roles(&self, request: UpdateUserRolesRequest) -> TenancyResult<UserAccount> {
+    async fn set_user_roles(
+        &self,
+        request: UpdateUserRolesRequest,
+        actor: &TenancyActor,
+    ) -> TenancyResult<UserAccount> {
         let normalized = request.normalize()?;
         let mut guard = self.state.write().await;
+        Self::resolve_actor(&guard, &normalized.firm_id, None, actor)?
+            .require_role_manager(&normalized.user_id)?;
         let user = guard
             .users
-            .get_mut(&normalized.user_id)
+            .get(&normalized.user_id)
             .ok_or_else(|| Self::map_err_user_not_found(&normalized.user_id))?;
         Self::ensure_user_firm(&normalized.firm_id, user, &normalized.user_id)?;
-        user.roles = normalized.roles;
-        Ok(user.clone())
+        let mut updated = user.clone();
+        updated.roles = normalized.roles;
+        self.record(actor, &updated.firm_id, &updated.id, "user.roles_changed")
+            .await?;
+        guard.users.insert(updated.id.clone(), updated.clone());
+        Ok(updated)
     }
 
     async fn update_user_status(
@@ -376,18 +547,24 @@ impl TenancyService for InMemoryTenancyService {
         firm_id: &FirmId,
         user_id: &UserId,
         status: UserStatus,
+        actor: &TenancyActor,
     ) -> TenancyResult<UserAccount> {
         let mut guard = self.state.write().await;
+        Self::resolve_actor(&guard, firm_id, None, actor)?.require_user_manager()?;
         let user = guard
             .users
-            .get_mut(user_id)
+            .get(user_id)
             .ok_or_else(|| Self::map_err_user_not_found(user_id))?;
         Self::ensure_user_firm(firm_id, user, user_id)?;
-        user.status = status;
-        if status.is_active() && user.activated_at.is_none() {
-            user.activated_at = Some(Utc::now());
+        let mut updated = user.clone();
+        updated.status = status;
+        if status.is_active() && updated.activated_at.is_none() {
+            updated.activated_at = Some(Utc::now());
         }
-        Ok(user.clone())
+        self.record(actor, firm_id, user_id, "user.status_changed")
+            .await?;
+        guard.users.insert(user_id.clone(), updated.clone());
+        Ok(updated)
     }
 }
 
@@ -396,43 +573,81 @@ mod tests {
     use super::*;
     use crate::Role;
     use crate::RoleAssignment;
+    use codex_audit_log::AuditLogError;
+    use codex_audit_log::AuditLogFilter;
+    use codex_audit_log::AuditLogResult;
+    use codex_audit_log::AuditRecord;
+    use codex_audit_log::InMemoryAuditLog;
     use pretty_assertions::assert_eq;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering;
+
+    fn actor() -> TenancyActor {
+        TenancyActor::new("user-admin", vec![Role::Partner])
+    }
 
     async fn create_firm(service: &InMemoryTenancyService, name: &str) -> Firm {
         service
-            .create_firm(CreateFirmRequest {
-                name: name.into(),
-                metadata: None,
-            })
+            .create_firm(
+                CreateFirmRequest {
+                    name: name.into(),
+                    metadata: None,
+                },
+                &actor(),
+            )
             .await
             .expect("create firm")
     }
 
     async fn create_company(service: &InMemoryTenancyService, firm: &Firm, name: &str) -> Company {
         service
-            .create_company(CreateCompanyRequest {
-                firm_id: firm.id.clone(),
-                name: name.into(),
-                base_currency: "usd".into(),
-                tags: vec![],
-                metadata: None,
-            })
+            .create_company(
+                CreateCompanyRequest {
+                    firm_id: firm.id.clone(),
+                    name: name.into(),
+                    base_currency: "usd".into(),
+                    currency_precision: None,
+                    tags: vec![],
+                    metadata: None,
+                },
+                &actor(),
+            )
             .await
             .expect("create company")
     }
 
+    /// The firm's first invite, which becomes `actor()`'s own active partner
+    /// account.
+    async fn bootstrap_partner(service: &InMemoryTenancyService, firm: &Firm) -> UserAccount {
+        service
+            .invite_user(
+                InviteUserRequest {
+                    firm_id: firm.id.clone(),
+                    email: "admin@example.com".into(),
+                    display_name: "Admin User".into(),
+                    roles: vec![RoleAssignment::firm(Role::Partner)],
+                },
+                &actor(),
+            )
+            .await
+            .expect("bootstrap partner")
+    }
+
     async fn invite_partner(
         service: &InMemoryTenancyService,
         firm: &Firm,
         email: &str,
     ) -> UserAccount {
         service
-            .invite_user(InviteUserRequest {
-                firm_id: firm.id.clone(),
-                email: email.into(),
-                display_name: "Example User".into(),
-                roles: vec![RoleAssignment::firm(Role::Partner)],
-            })
+            .invite_user(
+                InviteUserRequest {
+                    firm_id: firm.id.clone(),
+                    email: email.into(),
+                    display_name: "Example User".into(),
+                    roles: vec![RoleAssignment::firm(Role::Partner)],
+                },
+                &actor(),
+            )
             .await
             .expect("invite user")
     }
@@ -454,10 +669,13 @@ mod tests {
         let service = InMemoryTenancyService::new();
         let _ = create_firm(&service, "Acme Firm").await;
         let err = service
-            .create_firm(CreateFirmRequest {
-                name: "acme firm".into(),
-                metadata: None,
-            })
+            .create_firm(
+                CreateFirmRequest {
+                    name: "acme firm".into(),
+                    metadata: None,
+                },
+                &actor(),
+            )
             .await
             .unwrap_err();
         assert!(matches!(err, TenancyError::Conflict(_)));
@@ -488,13 +706,17 @@ mod tests {
         let _ = create_company(&service, &firm, "Demo Co").await;
 
         let err = service
-            .create_company(CreateCompanyRequest {
-                firm_id: firm.id.clone(),
-                name: " demo co ".into(),
-                base_currency: "usd".into(),
-                tags: vec![],
-                metadata: None,
-            })
+            .create_company(
+                CreateCompanyRequest {
+                    firm_id: firm.id.clone(),
+                    name: " demo co ".into(),
+                    base_currency: "usd".into(),
+                    currency_precision: None,
+                    tags: vec![],
+                    metadata: None,
+                },
+                &actor(),
+            )
             .await
             .unwrap_err();
 
@@ -508,14 +730,14 @@ mod tests {
         let created = create_company(&service, &firm, "Demo Co").await;
 
         let archived = service
-            .archive_company(&firm.id, &created.id)
+            .archive_company(&firm.id, &created.id, &actor())
             .await
             .expect("archive company");
         assert_eq!(archived.status, CompanyStatus::Archived);
         assert!(archived.archived_at.is_some());
 
         let reactivated = service
-            .reactivate_company(&firm.id, &created.id)
+            .reactivate_company(&firm.id, &created.id, &actor())
             .await
             .expect("reactivate company");
         assert_eq!(reactivated.status, CompanyStatus::Active);
@@ -530,7 +752,7 @@ mod tests {
         let company = create_company(&service, &firm_a, "Demo Co").await;
 
         let err = service
-            .archive_company(&firm_b.id, &company.id)
+            .archive_company(&firm_b.id, &company.id, &actor())
             .await
             .unwrap_err();
         assert!(matches!(err, TenancyError::Rejected(_)));
@@ -540,12 +762,41 @@ mod tests {
     async fn invites_users_and_lists_by_firm() {
         let service = InMemoryTenancyService::new();
         let firm = create_firm(&service, "Demo Firm").await;
+        let admin = bootstrap_partner(&service, &firm).await;
         let user = invite_partner(&service, &firm, "user@example.com").await;
 
+        assert_eq!(admin.id, actor().user_id);
+        assert!(admin.status.is_active());
         assert_eq!(user.status, UserStatus::Invited);
         let users = service.list_users(&firm.id).await.expect("list users");
-        assert_eq!(users.len(), 1);
-        assert_eq!(users[0], user);
+        assert_eq!(users, vec![admin, user]);
+    }
+
+    #[tokio::test]
+    async fn first_user_must_be_a_firm_wide_partner() {
+        let service = InMemoryTenancyService::new();
+        let firm = create_firm(&service, "Demo Firm").await;
+
+        let err = service
+            .invite_user(
+                InviteUserRequest {
+                    firm_id: firm.id.clone(),
+                    email: "staff@example.com".into(),
+                    display_name: "Staff User".into(),
+                    roles: vec![RoleAssignment::firm(Role::Staff)],
+                },
+                &actor(),
+            )
+            .await
+            .expect_err("first user is not a partner");
+        assert!(matches!(err, TenancyError::Rejected(_)));
+        assert!(
+            service
+                .list_users(&firm.id)
+                .await
+                .expect("list users")
+                .is_empty()
+        );
     }
 
     #[tokio::test]
@@ -554,12 +805,15 @@ mod tests {
         let firm = create_firm(&service, "Demo Firm").await;
         let _ = invite_partner(&service, &firm, "user@example.com").await;
         let err = service
-            .invite_user(InviteUserRequest {
-                firm_id: firm.id.clone(),
-                email: "USER@example.com".into(),
-                display_name: "Other User".into(),
-                roles: vec![RoleAssignment::firm(Role::Partner)],
-            })
+            .invite_user(
+                InviteUserRequest {
+                    firm_id: firm.id.clone(),
+                    email: "USER@example.com".into(),
+                    display_name: "Other User".into(),
+                    roles: vec![RoleAssignment::firm(Role::Partner)],
+                },
+                &actor(),
+            )
             .await
             .unwrap_err();
         assert!(matches!(err, TenancyError::Conflict(_)));
@@ -584,14 +838,17 @@ mod tests {
         let user = invite_partner(&service, &firm, "user@example.com").await;
 
         let updated = service
-            .set_user_roles(UpdateUserRolesRequest {
-                firm_id: firm.id.clone(),
-                user_id: user.id.clone(),
-                roles: vec![
-                    RoleAssignment::firm(Role::Senior),
-                    RoleAssignment::company(Role::Staff, company.id.clone()),
-                ],
-            })
+            .set_user_roles(
+                UpdateUserRolesRequest {
+                    firm_id: firm.id.clone(),
+                    user_id: user.id.clone(),
+                    roles: vec![
+                        RoleAssignment::firm(Role::Senior),
+                        RoleAssignment::company(Role::Staff, company.id.clone()),
+                    ],
+                },
+                &actor(),
+            )
             .await
             .expect("set user roles");
 
@@ -603,16 +860,324 @@ mod tests {
     async fn update_user_status_tracks_activation() {
         let service = InMemoryTenancyService::new();
         let firm = create_firm(&service, "Demo Firm").await;
+        bootstrap_partner(&service, &firm).await;
         let user = invite_partner(&service, &firm, "user@example.com").await;
+        assert!(user.activated_at.is_none());
 
         let activated = service
-            .update_user_status(&firm.id, &user.id, UserStatus::Active)
+            .update_user_status(&firm.id, &user.id, UserStatus::Active, &actor())
             .await
             .expect("activate user");
         assert!(activated.status.is_active());
         assert!(activated.activated_at.is_some());
     }
 
+    #[tokio::test]
+    async fn enforces_actor_roles_on_mutations() {
+        let service = InMemoryTenancyService::new();
+        let firm = create_firm(&service, "Demo Firm").await;
+        let company = create_company(&service, &firm, "Demo Co").await;
+        bootstrap_partner(&service, &firm).await;
+        let user = invite_partner(&service, &firm, "user@example.com").await;
+        let mut members = Vec::new();
+        for (email, roles) in [
+            ("staff@example.com", vec![Role::Staff]),
+            ("senior@example.com", vec![Role::Senior]),
+        ] {
+            let member = service
+                .invite_user(
+                    InviteUserRequest {
+                        firm_id: firm.id.clone(),
+                        email: email.into(),
+                        display_name: email.into(),
+                        roles: roles.iter().copied().map(RoleAssignment::firm).collect(),
+                    },
+                    &actor(),
+                )
+                .await
+                .expect("invite member");
+            service
+                .update_user_status(&firm.id, &member.id, UserStatus::Active, &actor())
+                .await
+                .expect("activate member");
+            members.push(TenancyActor::new(member.id, roles));
+        }
+        let [staff, senior] = members.as_slice() else {
+            panic!("two members");
+        };
+
+        let err = service
+            .archive_company(&firm.id, &company.id, staff)
+            .await
+            .expect_err("staff cannot archive");
+        assert!(matches!(err, TenancyError::Rejected(_)));
+        service
+            .archive_company(&firm.id, &company.id, senior)
+            .await
+            .expect("senior can archive");
+
+        let request = UpdateUserRolesRequest {
+            firm_id: firm.id.clone(),
+            user_id: user.id.clone(),
+            roles: vec![RoleAssignment::firm(Role::Staff)],
+        };
+        let err = service
+            .set_user_roles(request.clone(), senior)
+            .await
+            .expect_err("senior cannot change roles");
+        assert!(matches!(err, TenancyError::Rejected(_)));
+        let updated = service
+            .set_user_roles(request, &actor())
+            .await
+            .expect("partner can change roles");
+        assert!(updated.has_role(Role::Staff));
+    }
+
+    #[tokio::test]
+    async fn resolves_actor_roles_from_stored_accounts_once_the_firm_has_users() {
+        let service = InMemoryTenancyService::new();
+        let firm = create_firm(&service, "Demo Firm").await;
+        let company = create_company(&service, &firm, "Demo Co").await;
+        let partner = invite_partner(&service, &firm, "partner@example.com").await;
+        let staff = service
+            .invite_user(
+                InviteUserRequest {
+                    firm_id: firm.id.clone(),
+                    email: "staff@example.com".into(),
+                    display_name: "Staff User".into(),
+                    roles: vec![
+                        RoleAssignment::firm(Role::Staff),
+                        RoleAssignment::company(Role::Senior, company.id.clone()),
+                    ],
+                },
+                &actor(),
+            )
+            .await
+            .expect("invite staff");
+        for user in [&staff, &partner] {
+            service
+                .update_user_status(&firm.id, &user.id, UserStatus::Active, &actor())
+                .await
+                .expect("activate user");
+        }
+
+        let outsider = TenancyActor::new("user-outsider", vec![Role::Partner]);
+        let err = service
+            .archive_company(&firm.id, &company.id, &outsider)
+            .await
+            .expect_err("non-member cannot act once the firm has users");
+        assert!(matches!(err, TenancyError::Rejected(_)));
+
+        let claims_partner = TenancyActor::new(staff.id.clone(), vec![Role::Partner, Role::Senior]);
+        let err = service
+            .set_user_roles(
+                UpdateUserRolesRequest {
+                    firm_id: firm.id.clone(),
+                    user_id: staff.id.clone(),
+                    roles: vec![RoleAssignment::firm(Role::Partner)],
+                },
+                &claims_partner,
+            )
+            .await
+            .expect_err("asserted partner role is not held");
+        assert!(matches!(err, TenancyError::Rejected(_)));
+        let err = service
+            .invite_user(
+                InviteUserRequest {
+                    firm_id: firm.id.clone(),
+                    email: "sneaky@example.com".into(),
+                    display_name: "Sneaky".into(),
+                    roles: vec![RoleAssignment::firm(Role::Partner)],
+                },
+                &claims_partner,
+            )
+            .await
+            .expect_err("staff cannot invite");
+        assert!(matches!(err, TenancyError::Rejected(_)));
+        let err = service
+            .update_user_status(
+                &firm.id,
+                &partner.id,
+                UserStatus::Suspended,
+                &claims_partner,
+            )
+            .await
+            .expect_err("staff cannot suspend users");
+        assert!(matches!(err, TenancyError::Rejected(_)));
+        let err = service
+            .create_company(
+                CreateCompanyRequest {
+                    firm_id: firm.id.clone(),
+                    name: "Other Co".into(),
+                    base_currency: "usd".into(),
+                    currency_precision: None,
+                    tags: vec![],
+                    metadata: None,
+                },
+                &claims_partner,
+            )
+            .await
+            .expect_err("company-scoped senior cannot create companies");
+        assert!(matches!(err, TenancyError::Rejected(_)));
+        service
+            .archive_company(&firm.id, &company.id, &claims_partner)
+            .await
+            .expect("company-scoped senior can archive their company");
+
+        let partner_actor = TenancyActor::new(partner.id.clone(), vec![Role::Partner]);
+        service
+            .reactivate_company(&firm.id, &company.id, &partner_actor)
+            .await
+            .expect("stored partner can reactivate");
+        service
+            .update_user_status(&firm.id, &staff.id, UserStatus::Suspended, &partner_actor)
+            .await
+            .expect("stored partner can suspend");
+    }
+
+    #[tokio::test]
+    async fn firm_with_only_a_suspended_partner_rejects_unknown_actors() {
+        let service = InMemoryTenancyService::new();
+        let firm = create_firm(&service, "Demo Firm").await;
+        let company = create_company(&service, &firm, "Demo Co").await;
+        let partner = bootstrap_partner(&service, &firm).await;
+        service
+            .update_user_status(&firm.id, &partner.id, UserStatus::Suspended, &actor())
+            .await
+            .expect("suspend partner");
+
+        for claimant in [
+            TenancyActor::new("user-outsider", vec![Role::Partner]),
+            actor(),
+        ] {
+            let err = service
+                .archive_company(&firm.id, &company.id, &claimant)
+                .await
+                .expect_err("no active member vouches for the claimant");
+            assert!(matches!(err, TenancyError::Rejected(_)), "{err:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn records_audit_trail_for_mutations() {
+        let audit_log = InMemoryAuditLog::shared();
+        let service = InMemoryTenancyService::new().with_audit_log(audit_log.clone());
+        let firm = create_firm(&service, "Demo Firm").await;
+        let company = create_company(&service, &firm, "Demo Co").await;
+        let user = invite_partner(&service, &firm, "user@example.com").await;
+        service
+            .archive_company(&firm.id, &company.id, &actor())
+            .await
+            .expect("archive company");
+        service
+            .update_user_status(&firm.id, &user.id, UserStatus::Active, &actor())
+            .await
+            .expect("activate user");
+        service
+            .archive_company(&firm.id, &company.id, &actor())
+            .await
+            .expect_err("already archived");
+
+        let records = audit_log
+            .records(AuditLogFilter::default())
+            .await
+            .expect("audit records");
+        let trail: Vec<(&str, &str)> = records
+            .iter()
+            .map(|record| (record.entity_id.as_str(), record.action.as_str()))
+            .collect();
+        assert_eq!(
+            trail,
+            vec![
+                (firm.id.as_str(), "firm.created"),
+                (company.id.as_str(), "company.created"),
+                (user.id.as_str(), "user.invited"),
+                (company.id.as_str(), "company.archived"),
+                (user.id.as_str(), "user.status_changed"),
+            ]
+        );
+        assert!(records.iter().all(|record| record.actor == "user-admin"));
+        assert_eq!(records[1].metadata["firm_id"], firm.id.as_str());
+    }
+
+    /// Delegates to an in-memory log until `failing` is set.
+    struct FlakyAuditLog {
+        inner: InMemoryAuditLog,
+        failing: AtomicBool,
+    }
+
+    #[async_trait::async_trait]
+    impl AuditLog for FlakyAuditLog {
+        async fn append(&self, request: AppendRequest) -> AuditLogResult<AuditRecord> {
+            if self.failing.load(Ordering::SeqCst) {
+                return Err(AuditLogError::Storage("disk full".into()));
+            }
+            self.inner.append(request).await
+        }
+
+        async fn records(&self, filter: AuditLogFilter) -> AuditLogResult<Vec<AuditRecord>> {
+            self.inner.records(filter).await
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_audit_appends_leave_state_unchanged() {
+        let audit_log = Arc::new(FlakyAuditLog {
+            inner: InMemoryAuditLog::default(),
+            failing: AtomicBool::new(false),
+        });
+        let service = InMemoryTenancyService::new().with_audit_log(audit_log.clone());
+        let firm = create_firm(&service, "Demo Firm").await;
+        let company = create_company(&service, &firm, "Demo Co").await;
+        let admin = bootstrap_partner(&service, &firm).await;
+        audit_log.failing.store(true, Ordering::SeqCst);
+
+        let err = service
+            .archive_company(&firm.id, &company.id, &actor())
+            .await
+            .expect_err("append fails");
+        assert!(matches!(err, TenancyError::Internal(_)), "{err:?}");
+        let err = service
+            .create_company(
+                CreateCompanyRequest {
+                    firm_id: firm.id.clone(),
+                    name: "Second Co".into(),
+                    base_currency: "usd".into(),
+                    currency_precision: None,
+                    tags: vec![],
+                    metadata: None,
+                },
+                &actor(),
+            )
+            .await
+            .expect_err("append fails");
+        assert!(matches!(err, TenancyError::Internal(_)), "{err:?}");
+        service
+            .update_user_status(&firm.id, &admin.id, UserStatus::Suspended, &actor())
+            .await
+            .expect_err("append fails");
+
+        assert_eq!(
+            service.list_companies(&firm.id).await.expect("companies"),
+            vec![company.clone()]
+        );
+        assert_eq!(
+            service.get_user(&firm.id, &admin.id).await.expect("user"),
+            admin
+        );
+
+        audit_log.failing.store(false, Ordering::SeqCst);
+        service
+            .archive_company(&firm.id, &company.id, &actor())
+            .await
+            .expect("archive once the log recovers");
+        let records = audit_log
+            .records(AuditLogFilter::default())
+            .await
+            .expect("audit records");
+        assert_eq!(records.len(), 4);
+    }
+
     #[tokio::test]
     async fn round_trips_snapshot() {
         let service = InMemoryTenancyService::new();
@@ -5,6 +5,8 @@ use std::fmt::Display;
 use async_trait::async_trait;
 use chrono::DateTime;
 use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
 use thiserror::Error;
 
 mod in_memory;
@@ -84,6 +86,8 @@ pub struct Company {
     pub name: String,
     pub status: CompanyStatus,
     pub base_currency: String,
+    /// Minor-unit digits of `base_currency`, e.g. 0 for JPY.
+    pub currency_precision: u8,
     pub tags: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub archived_at: Option<DateTime<Utc>>,
@@ -95,10 +99,29 @@ pub struct CreateCompanyRequest {
     pub firm_id: FirmId,
     pub name: String,
     pub base_currency: String,
+    /// Defaults to the ISO-4217 minor units of `base_currency` (see
+    /// `default_currency_precision`) during `normalize`.
+    pub currency_precision: Option<u8>,
     pub tags: Vec<String>,
     pub metadata: Option<String>,
 }
 
+/// Largest minor-unit precision accepted for a company base currency.
+pub const MAX_CURRENCY_PRECISION: u8 = 4;
+
+/// ISO-4217 minor units for `code`: 0 for currencies such as JPY and KRW,
+/// 3 for the dinar family, 2 otherwise.
+#[must_use]
+pub fn default_currency_precision(code: &str) -> u8 {
+    match code.to_ascii_uppercase().as_str() {
+        "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" | "RWF" | "UGX"
+        | "UYI" | "VND" | "VUV" | "XAF" | "XOF" | "XPF" => 0,
+        "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+        "CLF" | "UYW" => 4,
+        _ => 2,
+    }
+}
+
 impl CreateCompanyRequest {
     pub fn normalize(mut self) -> Result<Self, TenancyError> {
         if self.name.trim().is_empty() {
@@ -119,6 +142,15 @@ impl CreateCompanyRequest {
                 "base currency must be a 3-letter ISO code".into(),
             ));
         }
+        let precision = self
+            .currency_precision
+            .unwrap_or_else(|| default_currency_precision(&code));
+        if precision > MAX_CURRENCY_PRECISION {
+            return Err(TenancyError::Validation(format!(
+                "currency precision must be at most {MAX_CURRENCY_PRECISION}"
+            )));
+        }
+        self.currency_precision = Some(precision);
         self.base_currency = code;
 
         let mut seen = std::collections::HashSet::new();
@@ -134,7 +166,8 @@ impl CreateCompanyRequest {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Role {
     Partner,
     Senior,
@@ -303,15 +336,89 @@ impl UserAccount {
     }
 }
 
+/// The user performing a tenancy mutation. Recorded on its audit record and
+/// checked against the role each mutation requires. `roles` are the roles
+/// the caller claims; once a firm has any users, services only honor those
+/// the user's stored account actually holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TenancyActor {
+    pub user_id: UserId,
+    pub roles: Vec<Role>,
+}
+
+impl TenancyActor {
+    #[must_use]
+    pub fn new(user_id: impl Into<UserId>, roles: Vec<Role>) -> Self {
+        Self {
+            user_id: user_id.into(),
+            roles,
+        }
+    }
+
+    #[must_use]
+    pub fn has_role(&self, role: Role) -> bool {
+        self.roles.contains(&role)
+    }
+
+    #[must_use]
+    pub fn can_manage_companies(&self) -> bool {
+        self.roles.iter().any(|role| role.can_manage_companies())
+    }
+
+    /// Creating, archiving, and reactivating companies requires a `Partner`
+    /// or `Senior` role.
+    pub fn require_company_manager(&self) -> TenancyResult<()> {
+        if self.can_manage_companies() {
+            return Ok(());
+        }
+        Err(TenancyError::Rejected(format!(
+            "user {} cannot manage companies",
+            self.user_id
+        )))
+    }
+
+    /// Only a `Partner` may change role assignments, including their own, so
+    /// no one can grant themselves a role they do not hold.
+    pub fn require_role_manager(&self, user_id: &UserId) -> TenancyResult<()> {
+        if self.has_role(Role::Partner) {
+            return Ok(());
+        }
+        Err(TenancyError::Rejected(format!(
+            "user {} cannot change roles for user {user_id}",
+            self.user_id
+        )))
+    }
+
+    /// Inviting users and changing their status requires a `Partner`, since
+    /// an invite carries role assignments and activation makes them usable.
+    pub fn require_user_manager(&self) -> TenancyResult<()> {
+        if self.has_role(Role::Partner) {
+            return Ok(());
+        }
+        Err(TenancyError::Rejected(format!(
+            "user {} cannot manage users",
+            self.user_id
+        )))
+    }
+}
+
 #[async_trait]
 pub trait TenancyService: Send + Sync {
-    async fn create_firm(&self, request: CreateFirmRequest) -> TenancyResult<Firm>;
+    async fn create_firm(
+        &self,
+        request: CreateFirmRequest,
+        actor: &TenancyActor,
+    ) -> TenancyResult<Firm>;
 
     async fn list_firms(&self) -> TenancyResult<Vec<Firm>>;
 
     async fn get_firm(&self, firm_id: &FirmId) -> TenancyResult<Firm>;
 
-    async fn create_company(&self, request: CreateCompanyRequest) -> TenancyResult<Company>;
+    async fn create_company(
+        &self,
+        request: CreateCompanyRequest,
+        actor: &TenancyActor,
+    ) -> TenancyResult<Company>;
 
     async fn list_companies(&self, firm_id: &FirmId) -> TenancyResult<Vec<Company>>;
 
@@ -322,27 +429,38 @@ pub trait TenancyService: Send + Sync {
         &self,
         firm_id: &FirmId,
         company_id: &CompanyId,
+        actor: &TenancyActor,
     ) -> TenancyResult<Company>;
 
     async fn reactivate_company(
         &self,
         firm_id: &FirmId,
         company_id: &CompanyId,
+        actor: &TenancyActor,
     ) -> TenancyResult<Company>;
 
-    async fn invite_user(&self, request: InviteUserRequest) -> TenancyResult<UserAccount>;
+    async fn invite_user(
+        &self,
+        request: InviteUserRequest,
+        actor: &TenancyActor,
+    ) -> TenancyResult<UserAccount>;
 
     async fn list_users(&self, firm_id: &FirmId) -> TenancyResult<Vec<UserAccount>>;
 
     async fn get_user(&self, firm_id: &FirmId, user_id: &UserId) -> TenancyResult<UserAccount>;
 
-    async fn set_user_roles(&self, request: UpdateUserRolesRequest) -> TenancyResult<UserAccount>;
+    async fn set_user_roles(
+        &self,
+        request: UpdateUserRolesRequest,
+        actor: &TenancyActor,
+    ) -> TenancyResult<UserAccount>;
 
     async fn update_user_status(
         &self,
         firm_id: &FirmId,
         user_id: &UserId,
         status: UserStatus,
+        actor: &TenancyActor,
     ) -> TenancyResult<UserAccount>;
 }
 
@@ -366,6 +484,7 @@ mod tests {
             firm_id: "firm-1".into(),
             name: "  Example Firm  ".into(),
             base_currency: " usd ".into(),
+            currency_precision: None,
             tags: vec![
                 "  Retail  ".into(),
                 "retail".into(),
@@ -379,6 +498,7 @@ mod tests {
 
         assert_eq!(result.name, "Example Firm");
         assert_eq!(result.base_currency, "USD");
+        assert_eq!(result.currency_precision, Some(2));
         assert_eq!(
             result.tags,
             vec![String::from("Retail"), String::from("Q1")]
@@ -391,6 +511,7 @@ mod tests {
             firm_id: "firm-1".into(),
             name: "Name".into(),
             base_currency: "US".into(),
+            currency_precision: None,
             tags: vec![],
             metadata: None,
         }
@@ -400,6 +521,34 @@ mod tests {
         assert!(matches!(err, TenancyError::Validation(_)));
     }
 
+    #[test]
+    fn create_request_resolves_currency_precision() {
+        let request = |currency: &str, precision: Option<u8>| CreateCompanyRequest {
+            firm_id: "firm-1".into(),
+            name: "Name".into(),
+            base_currency: currency.into(),
+            currency_precision: precision,
+            tags: vec![],
+            metadata: None,
+        };
+
+        let yen = request("jpy", None)
+            .normalize()
+            .expect("request should normalize");
+        assert_eq!(yen.currency_precision, Some(0));
+        let dinar = request("KWD", None)
+            .normalize()
+            .expect("request should normalize");
+        assert_eq!(dinar.currency_precision, Some(3));
+        let explicit = request("USD", Some(0))
+            .normalize()
+            .expect("request should normalize");
+        assert_eq!(explicit.currency_precision, Some(0));
+
+        let err = request("USD", Some(9)).normalize().unwrap_err();
+        assert!(matches!(err, TenancyError::Validation(_)));
+    }
+
     #[test]
     fn create_firm_request_normalizes() {
         let result = CreateFirmRequest {
@@ -244,6 +244,7 @@ mod tests {
                 memo: Some("Demo entry".to_string()),
                 reverses_entry_id: None,
                 reversed_by_entry_id: None,
+                attachments: Vec::new(),
             },
         }
     }